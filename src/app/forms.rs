@@ -3,31 +3,73 @@
 //! Handles validation of firewall rule form inputs with detailed error reporting.
 //! Supports multi-value fields (ports, IPs) with helper modal editing pattern.
 
-use crate::core::firewall::{PortEntry, Protocol, RejectType};
+use crate::core::firewall::{
+    AddressEntry, ByteUnit, CtState, IcmpType, MatchField, NamedSet, Negation, PerSourceLimit,
+    PortEntry, Protocol, Quota, QuotaMode, RejectType, Schedule, SetType, Weekday,
+};
 use crate::core::rule_constraints::{
-    ip_compatible_with_protocol, protocol_supports_ports, reject_type_valid_for_protocol,
+    action_valid_for_port_forward, chain_supports_port_forward, icmp_type_valid_for_protocol,
+    interfaces_valid_as_list, ip_compatible_with_forward_target, ip_compatible_with_protocol,
+    ip_valid_for_negation, negation_valid_for_port_forward, ports_valid_for_port_forward,
+    protocol_supports_icmp_types, protocol_supports_ports, reject_type_valid_for_protocol,
 };
-use ipnetwork::IpNetwork;
+use std::net::IpAddr;
+
+/// What a rate limit counts in the form: packets, or an amount of bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateMeasure(pub Option<ByteUnit>);
+
+impl RateMeasure {
+    pub const ALL: [RateMeasure; 5] = [
+        RateMeasure(None),
+        RateMeasure(Some(ByteUnit::Bytes)),
+        RateMeasure(Some(ByteUnit::KBytes)),
+        RateMeasure(Some(ByteUnit::MBytes)),
+        RateMeasure(Some(ByteUnit::GBytes)),
+    ];
+}
+
+impl std::fmt::Display for RateMeasure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(unit) => f.write_str(unit.display_name()),
+            None => f.write_str("packets"),
+        }
+    }
+}
 
 /// Form validation errors for individual fields
 #[derive(Debug, Clone, Default)]
 pub struct FormErrors {
     pub port: Option<String>,
+    pub source_port: Option<String>,
+    pub icmp: Option<String>,
     pub source: Option<String>,
     pub interface: Option<String>,
     pub output_interface: Option<String>,
     pub destination: Option<String>,
     pub rate_limit: Option<String>,
+    pub quota: Option<String>,
     pub connection_limit: Option<String>,
+    pub per_source: Option<String>,
     pub reject_type: Option<String>,
+    pub port_forward: Option<String>,
+    pub schedule: Option<String>,
+    pub countries: Option<String>,
+    pub reference: Option<String>,
+    pub expiry: Option<String>,
 }
 
 /// Helper modal types for multi-value field editing
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HelperType {
     Ports,
+    SourcePorts,
+    IcmpTypes,
     SourceAddresses,
     DestinationAddresses,
+    Interfaces,
+    OutputInterfaces,
     Tags,
 }
 
@@ -46,9 +88,25 @@ pub struct RuleFormHelper {
 ///
 /// Supports multi-value fields via helper modals:
 /// - `ports`: Multiple port entries (single or range)
-/// - `sources`: Multiple source IP/CIDR addresses
-/// - `destinations`: Multiple destination IP/CIDR addresses
+/// - `source_ports`: Multiple source port entries (single or range)
+/// - `icmp_types`: ICMP/ICMPv6 types (with optional `icmp_code` for a single type)
+/// - `sources`: Multiple source IP/CIDR addresses or ranges
+/// - `destinations`: Multiple destination IP/CIDR addresses or ranges
+/// - `interfaces`/`output_interfaces`: Interface names, wildcards allowed (e.g., "eth*")
 /// - `tags`: Multiple organizational tags
+/// - `countries`: Source countries (ISO codes, picked from a list)
+///
+/// Ports, sources and destinations can alternatively reference a named set
+/// (`port_set`, `source_set`, `destination_set`), which replaces the literal values.
+///
+/// With `port_forward_enabled`, the rule forwards its external port to
+/// `forward_address`:`forward_port` (DNAT) instead of accepting locally.
+///
+/// With `schedule_enabled`, the rule only matches between `schedule_start` and
+/// `schedule_end` (HH:MM, local time) on the selected `schedule_days`.
+///
+/// `negate` inverts the source, destination, port and interface matches; a
+/// flag on an empty field is dropped when the rule is saved.
 #[derive(Debug, Clone)]
 pub struct RuleForm {
     pub id: Option<uuid::Uuid>,
@@ -57,29 +115,76 @@ pub struct RuleForm {
 
     // Multi-value fields (edited via helper modals)
     pub ports: Vec<PortEntry>,
-    pub sources: Vec<IpNetwork>,
-    pub destinations: Vec<IpNetwork>,
+    pub source_ports: Vec<PortEntry>,
+    pub icmp_types: Vec<IcmpType>,
+    pub ct_states: Vec<CtState>,
+    pub sources: Vec<AddressEntry>,
+    pub destinations: Vec<AddressEntry>,
+    pub interfaces: Vec<String>,
+    pub output_interfaces: Vec<String>,
     pub tags: Vec<String>,
+    pub countries: Vec<String>,
+
+    // Named set references (replace the matching multi-value field)
+    pub port_set: Option<String>,
+    pub source_set: Option<String>,
+    pub destination_set: Option<String>,
+
+    // Fields matched with `!=`
+    pub negate: Negation,
 
     // Single-value fields
-    pub interface: String,
-    pub output_interface: String,
     pub chain: crate::core::firewall::Chain,
     pub action: crate::core::firewall::Action,
     pub reject_type: RejectType,
+    pub icmp_code: String,
 
     // Rate limiting
     pub rate_limit_enabled: bool,
     pub rate_limit_count: String,
     pub rate_limit_unit: crate::core::firewall::TimeUnit,
     pub rate_limit_burst: String,
+    pub rate_limit_over: bool,
+    pub rate_limit_measure: RateMeasure,
+
+    // Byte quota
+    pub quota_enabled: bool,
+    pub quota_amount: String,
+    pub quota_unit: ByteUnit,
+    pub quota_mode: QuotaMode,
 
     // Connection limiting
     pub connection_limit: String,
 
+    // Per-source limit tracking
+    pub per_source_enabled: bool,
+    pub per_source_size: String,
+    pub per_source_timeout: String,
+
     // Per-rule logging
     pub log_enabled: bool,
 
+    // Port forwarding (DNAT)
+    pub port_forward_enabled: bool,
+    pub forward_address: String,
+    pub forward_port: String,
+
+    // Time-of-day / weekday schedule
+    pub schedule_enabled: bool,
+    pub schedule_start: String,
+    pub schedule_end: String,
+    pub schedule_days: Vec<Weekday>,
+
+    // Expiry (local date/time or a duration from now)
+    pub expiry_enabled: bool,
+    pub expiry: String,
+
+    // Metadata (not compiled into nftables). The description is edited through
+    // `State::rule_description`, which mirrors it into this field.
+    pub description: String,
+    pub owner: String,
+    pub reference: String,
+
     // UI state
     pub show_advanced: bool,
 }
@@ -91,20 +196,50 @@ impl Default for RuleForm {
             label: String::new(),
             protocol: Protocol::Tcp,
             ports: Vec::new(),
+            source_ports: Vec::new(),
+            icmp_types: Vec::new(),
+            ct_states: Vec::new(),
             sources: Vec::new(),
             destinations: Vec::new(),
+            interfaces: Vec::new(),
+            output_interfaces: Vec::new(),
             tags: Vec::new(),
-            interface: String::new(),
-            output_interface: String::new(),
+            countries: Vec::new(),
+            port_set: None,
+            source_set: None,
+            destination_set: None,
+            negate: Negation::default(),
             chain: crate::core::firewall::Chain::Input,
             action: crate::core::firewall::Action::Accept,
             reject_type: RejectType::Default,
+            icmp_code: String::new(),
             rate_limit_enabled: false,
             rate_limit_count: String::new(),
             rate_limit_unit: crate::core::firewall::TimeUnit::Second,
             rate_limit_burst: String::new(),
+            rate_limit_over: false,
+            rate_limit_measure: RateMeasure::default(),
+            quota_enabled: false,
+            quota_amount: String::new(),
+            quota_unit: ByteUnit::GBytes,
+            quota_mode: QuotaMode::Until,
             connection_limit: String::new(),
+            per_source_enabled: false,
+            per_source_size: PerSourceLimit::default().size.to_string(),
+            per_source_timeout: PerSourceLimit::default().timeout_secs.to_string(),
             log_enabled: false,
+            port_forward_enabled: false,
+            forward_address: String::new(),
+            forward_port: String::new(),
+            schedule_enabled: false,
+            schedule_start: String::new(),
+            schedule_end: String::new(),
+            schedule_days: Vec::new(),
+            expiry_enabled: false,
+            expiry: String::new(),
+            description: String::new(),
+            owner: String::new(),
+            reference: String::new(),
             show_advanced: false,
         }
     }
@@ -115,21 +250,32 @@ impl RuleForm {
     ///
     /// Multi-value fields (ports, sources, destinations) are validated as Vec
     /// and returned directly since they're already parsed during helper modal input.
+    /// Named set references are checked against `sets` for existence and type.
     ///
     /// Returns Option<FormErrors> - None if validation passed
-    pub fn validate(&self) -> Option<FormErrors> {
+    pub fn validate(&self, sets: &[NamedSet]) -> Option<FormErrors> {
         let mut errors = FormErrors::default();
         let mut has_errors = false;
 
         self.validate_ports(&mut errors, &mut has_errors);
+        self.validate_icmp_types(&mut errors, &mut has_errors);
         self.validate_sources(&mut errors, &mut has_errors);
         self.validate_destinations(&mut errors, &mut has_errors);
+        self.validate_countries(&mut errors, &mut has_errors);
+        self.validate_set_refs(sets, &mut errors, &mut has_errors);
         self.validate_interface(&mut errors, &mut has_errors);
         self.validate_output_interface(&mut errors, &mut has_errors);
         self.validate_interface_chain_compat(&mut errors, &mut has_errors);
+        self.validate_negation(&mut errors, &mut has_errors);
         self.validate_reject_type(&mut errors, &mut has_errors);
         self.validate_rate_limit(&mut errors, &mut has_errors);
+        self.validate_quota(&mut errors, &mut has_errors);
         self.validate_connection_limit(&mut errors, &mut has_errors);
+        self.validate_per_source(&mut errors, &mut has_errors);
+        self.validate_port_forward(sets, &mut errors, &mut has_errors);
+        self.validate_schedule(&mut errors, &mut has_errors);
+        self.validate_expiry(&mut errors, &mut has_errors);
+        self.validate_reference(&mut errors, &mut has_errors);
 
         if has_errors { Some(errors) } else { None }
     }
@@ -142,19 +288,24 @@ impl RuleForm {
 
         // Multi-value ports are already validated when added via helper
         // Just check for any obvious issues
-        for port in &self.ports {
-            match port {
-                PortEntry::Single(p) if *p == 0 => {
-                    errors.port = Some("Port cannot be 0".to_string());
-                    *has_errors = true;
-                    return;
-                }
-                PortEntry::Range { start, end } if start > end => {
-                    errors.port = Some("Port range start must be <= end".to_string());
-                    *has_errors = true;
-                    return;
+        for (ports, error) in [
+            (&self.ports, &mut errors.port),
+            (&self.source_ports, &mut errors.source_port),
+        ] {
+            for port in ports {
+                match port {
+                    PortEntry::Single(0) => {
+                        *error = Some("Port cannot be 0".to_string());
+                        *has_errors = true;
+                        break;
+                    }
+                    PortEntry::Range { start, end } if start > end => {
+                        *error = Some("Port range start must be <= end".to_string());
+                        *has_errors = true;
+                        break;
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
     }
@@ -191,24 +342,125 @@ impl RuleForm {
         }
     }
 
+    /// Validates the country selection, which replaces source addresses
+    fn validate_countries(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if self.countries.is_empty() {
+            return;
+        }
+        let msg = if let Err(msg) = crate::validators::validate_countries(&self.countries) {
+            msg
+        } else if !self.sources.is_empty() || self.source_set.is_some() {
+            "Use either source countries or source addresses"
+        } else if self.port_forward_enabled {
+            "Countries are not available for port forwards"
+        } else {
+            return;
+        };
+        errors.countries = Some(msg.to_string());
+        *has_errors = true;
+    }
+
+    /// Validates named set references.
+    ///
+    /// A set must exist, be of the right kind (address vs port), not be mixed
+    /// with literal values for the same field, and match the protocol family.
+    /// Source and destination sets must share a family since nftables emits
+    /// a single rule for set-based matches.
+    fn validate_set_refs(&self, sets: &[NamedSet], errors: &mut FormErrors, has_errors: &mut bool) {
+        let find = |name: &str| sets.iter().find(|s| s.name == name);
+
+        if let Some(name) = &self.port_set {
+            let msg = match find(name) {
+                None => Some(format!("Set '{name}' does not exist")),
+                Some(set) if set.set_type != SetType::InetService => {
+                    Some(format!("Set '{name}' does not contain ports"))
+                }
+                Some(_) if !protocol_supports_ports(self.protocol) => {
+                    Some("Port sets require TCP or UDP".to_string())
+                }
+                Some(_) if !self.ports.is_empty() => {
+                    Some("Use either a port set or explicit ports".to_string())
+                }
+                Some(_) => None,
+            };
+            if msg.is_some() {
+                errors.port = msg;
+                *has_errors = true;
+            }
+        }
+
+        let mut family = None;
+        for (name, literals, is_source) in [
+            (&self.source_set, &self.sources, true),
+            (&self.destination_set, &self.destinations, false),
+        ] {
+            let Some(name) = name else { continue };
+            let msg = match find(name) {
+                None => Some(format!("Set '{name}' does not exist")),
+                Some(set) if !set.set_type.is_address() => {
+                    Some(format!("Set '{name}' does not contain addresses"))
+                }
+                Some(_) if !literals.is_empty() => {
+                    Some("Use either an address set or explicit addresses".to_string())
+                }
+                Some(set) if !set_compatible_with_protocol(set.set_type, self.protocol) => {
+                    Some(if set.set_type == SetType::Ipv6Addr {
+                        "ICMP (v4) cannot be used with IPv6 addresses".to_string()
+                    } else {
+                        "ICMPv6 cannot be used with IPv4 addresses".to_string()
+                    })
+                }
+                Some(set) if family.is_some_and(|f| f != set.set_type) => {
+                    Some("Source and destination sets must share an address family".to_string())
+                }
+                Some(set) => {
+                    family = Some(set.set_type);
+                    None
+                }
+            };
+            if msg.is_some() {
+                if is_source {
+                    errors.source = msg;
+                } else {
+                    errors.destination = msg;
+                }
+                *has_errors = true;
+            }
+        }
+    }
+
+    fn validate_reference(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if let Err(msg) = crate::validators::validate_reference(self.reference.trim()) {
+            errors.reference = Some(msg.to_string());
+            *has_errors = true;
+        }
+    }
+
     fn validate_interface(&self, errors: &mut FormErrors, has_errors: &mut bool) {
-        if !self.interface.is_empty()
-            && let Err(msg) = crate::validators::validate_interface(&self.interface)
-        {
+        if let Some(msg) = Self::interface_list_error(&self.interfaces) {
             errors.interface = Some(msg.to_string());
             *has_errors = true;
         }
     }
 
     fn validate_output_interface(&self, errors: &mut FormErrors, has_errors: &mut bool) {
-        if !self.output_interface.is_empty()
-            && let Err(msg) = crate::validators::validate_interface(&self.output_interface)
-        {
+        if let Some(msg) = Self::interface_list_error(&self.output_interfaces) {
             errors.output_interface = Some(msg.to_string());
             *has_errors = true;
         }
     }
 
+    /// Returns the first problem with an interface list (names, then wildcards)
+    pub(crate) fn interface_list_error(interfaces: &[String]) -> Option<&'static str> {
+        interfaces
+            .iter()
+            .find_map(|iface| crate::validators::validate_interface(iface).err())
+            .or_else(|| {
+                (!interfaces_valid_as_list(interfaces))
+                    .then_some("A wildcard interface cannot be combined with other interfaces")
+            })
+    }
+
     /// Validates interface/chain compatibility.
     ///
     /// nftables only sets certain meta keys for specific hooks:
@@ -220,19 +472,75 @@ impl RuleForm {
         use crate::core::firewall::Chain;
 
         // Input interface on OUTPUT chain won't match (packets originate locally)
-        if !self.interface.is_empty() && self.chain == Chain::Output {
+        if !self.interfaces.is_empty() && self.chain == Chain::Output {
             errors.interface = Some("Input interface not available for OUTPUT rules".to_string());
             *has_errors = true;
         }
 
         // Output interface on INPUT chain won't match (packets not routed yet)
-        if !self.output_interface.is_empty() && self.chain == Chain::Input {
+        if !self.output_interfaces.is_empty() && self.chain == Chain::Input {
             errors.output_interface =
                 Some("Output interface not available for INPUT rules".to_string());
             *has_errors = true;
         }
     }
 
+    /// Returns `true` if the field has a value a negated match could exclude
+    pub fn has_negation_target(&self, field: MatchField) -> bool {
+        match field {
+            MatchField::Sources => {
+                !self.sources.is_empty() || self.source_set.is_some() || !self.countries.is_empty()
+            }
+            MatchField::Destinations => {
+                !self.destinations.is_empty() || self.destination_set.is_some()
+            }
+            MatchField::Ports => {
+                protocol_supports_ports(self.protocol)
+                    && (!self.ports.is_empty() || self.port_set.is_some())
+            }
+            MatchField::SourcePorts => {
+                protocol_supports_ports(self.protocol) && !self.source_ports.is_empty()
+            }
+            MatchField::Interface => !self.interfaces.is_empty(),
+            MatchField::OutputInterface => !self.output_interfaces.is_empty(),
+        }
+    }
+
+    /// Returns the negation flags to save: only fields with a value stay negated
+    pub fn parsed_negation(&self) -> Negation {
+        let mut negate = Negation::default();
+        for field in MatchField::ALL {
+            negate.set(
+                field,
+                self.negate.get(field) && self.has_negation_target(field),
+            );
+        }
+        negate
+    }
+
+    fn validate_negation(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        const WHOLE_FAMILY: &str = "Cannot exclude a whole address family (/0)";
+        let negate = self.parsed_negation();
+
+        // Use centralized constraints for negated addresses and port forwards
+        if negate.sources && !self.sources.iter().all(ip_valid_for_negation) {
+            errors.source = Some(WHOLE_FAMILY.to_string());
+            *has_errors = true;
+        }
+        if negate.destinations && !self.destinations.iter().all(ip_valid_for_negation) {
+            errors.destination = Some(WHOLE_FAMILY.to_string());
+            *has_errors = true;
+        }
+        if self.port_forward_enabled
+            && MatchField::ALL
+                .into_iter()
+                .any(|field| negate.get(field) && !negation_valid_for_port_forward(field))
+        {
+            errors.port = Some("Negated ports cannot be port-forwarded".to_string());
+            *has_errors = true;
+        }
+    }
+
     fn validate_reject_type(&self, errors: &mut FormErrors, has_errors: &mut bool) {
         // Use centralized constraint for reject type validity
         if !reject_type_valid_for_protocol(self.reject_type, self.protocol) {
@@ -248,7 +556,11 @@ impl RuleForm {
 
         if let Ok(count) = self.rate_limit_count.parse::<u32>() {
             // Ignore warnings (Ok result), only handle errors
-            if let Err(msg) = crate::validators::validate_rate_limit(count, self.rate_limit_unit) {
+            if let Err(msg) = crate::validators::validate_rate_limit(
+                count,
+                self.rate_limit_unit,
+                self.rate_limit_measure.0,
+            ) {
                 errors.rate_limit = Some(msg);
                 *has_errors = true;
             }
@@ -264,6 +576,67 @@ impl RuleForm {
         }
     }
 
+    fn validate_quota(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if !self.quota_enabled {
+            return;
+        }
+
+        if let Ok(amount) = self.quota_amount.trim().parse::<u64>() {
+            // Ignore warnings (Ok result), only handle errors
+            if let Err(msg) = crate::validators::validate_quota(amount, self.quota_unit) {
+                errors.quota = Some(msg);
+                *has_errors = true;
+            }
+        } else {
+            errors.quota = Some("Invalid quota amount".to_string());
+            *has_errors = true;
+        }
+    }
+
+    /// Parses the quota fields. Returns `None` if the quota is disabled or invalid.
+    pub fn parsed_quota(&self) -> Option<Quota> {
+        if !self.quota_enabled {
+            return None;
+        }
+        let amount = self.quota_amount.trim().parse().ok().filter(|&a| a > 0)?;
+        Some(Quota {
+            amount,
+            unit: self.quota_unit,
+            mode: self.quota_mode,
+        })
+    }
+
+    fn validate_icmp_types(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        let msg = if self.icmp_types.is_empty() {
+            (!self.icmp_code.trim().is_empty())
+                .then(|| "ICMP code requires an ICMP type".to_string())
+        } else if !protocol_supports_icmp_types(self.protocol) {
+            Some("ICMP types require the ICMP (v4) or ICMPv6 protocol".to_string())
+        } else if let Some(t) = self
+            .icmp_types
+            .iter()
+            .find(|t| !icmp_type_valid_for_protocol(**t, self.protocol))
+        {
+            Some(if self.protocol == Protocol::Icmpv6 {
+                format!("{} is an IPv4-only ICMP type", t.display_name())
+            } else {
+                format!("{} is an ICMPv6-only type", t.display_name())
+            })
+        } else if self.icmp_code.trim().is_empty() {
+            None
+        } else if self.icmp_types.len() != 1 {
+            Some("ICMP code requires exactly one ICMP type".to_string())
+        } else if self.icmp_code.trim().parse::<u8>().is_err() {
+            Some("ICMP code must be 0-255".to_string())
+        } else {
+            None
+        };
+        if msg.is_some() {
+            errors.icmp = msg;
+            *has_errors = true;
+        }
+    }
+
     fn validate_connection_limit(&self, errors: &mut FormErrors, has_errors: &mut bool) {
         if self.connection_limit.is_empty() {
             return;
@@ -280,4 +653,176 @@ impl RuleForm {
             *has_errors = true;
         }
     }
+
+    fn validate_per_source(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if !self.per_source_enabled {
+            return;
+        }
+
+        let has_limit = self.rate_limit_enabled
+            || self
+                .connection_limit
+                .trim()
+                .parse::<u32>()
+                .is_ok_and(|l| l > 0);
+        let msg = if has_limit {
+            match (
+                self.per_source_size.trim().parse::<u32>(),
+                self.per_source_timeout.trim().parse::<u32>(),
+            ) {
+                (Ok(size), Ok(timeout)) => {
+                    crate::validators::validate_per_source(size, timeout).err()
+                }
+                (Err(_), _) => Some("Invalid number of tracked addresses".to_string()),
+                (_, Err(_)) => Some("Invalid timeout in seconds".to_string()),
+            }
+        } else {
+            Some("Per-source tracking needs a rate limit or connection limit".to_string())
+        };
+        if msg.is_some() {
+            errors.per_source = msg;
+            *has_errors = true;
+        }
+    }
+
+    /// Returns the per-source tracking settings, or `None` if disabled or invalid
+    pub fn parsed_per_source(&self) -> Option<PerSourceLimit> {
+        if !self.per_source_enabled {
+            return None;
+        }
+        Some(PerSourceLimit {
+            size: self.per_source_size.trim().parse().ok()?,
+            timeout_secs: self.per_source_timeout.trim().parse().ok()?,
+        })
+    }
+
+    /// Parses the port-forward target fields (address and optional internal port).
+    ///
+    /// Returns `None` if port forwarding is disabled or the fields don't parse;
+    /// `validate()` reports the details.
+    pub fn parsed_port_forward(&self) -> Option<crate::core::firewall::PortForward> {
+        if !self.port_forward_enabled {
+            return None;
+        }
+        let to_address = self.forward_address.trim().parse().ok()?;
+        let to_port = match self.forward_port.trim() {
+            "" => None,
+            port => Some(port.parse::<u16>().ok().filter(|&p| p > 0)?),
+        };
+        Some(crate::core::firewall::PortForward {
+            to_address,
+            to_port,
+        })
+    }
+
+    /// Parses the schedule fields into a [`Schedule`].
+    ///
+    /// Returns `None` if scheduling is disabled or a time doesn't parse;
+    /// `validate()` reports the details.
+    pub fn parsed_schedule(&self) -> Option<Schedule> {
+        if !self.schedule_enabled {
+            return None;
+        }
+        let start = crate::validators::parse_schedule_time(&self.schedule_start).ok()?;
+        let end = crate::validators::parse_schedule_time(&self.schedule_end).ok()?;
+        Some(Schedule {
+            start,
+            end,
+            days: self.schedule_days.clone(),
+        })
+    }
+
+    fn validate_schedule(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if !self.schedule_enabled {
+            return;
+        }
+        let result = crate::validators::parse_schedule_time(&self.schedule_start)
+            .and_then(|_| crate::validators::parse_schedule_time(&self.schedule_end));
+        if let Err(msg) = result {
+            errors.schedule = Some(msg.to_string());
+            *has_errors = true;
+        }
+    }
+
+    /// Returns the expiry if enabled and valid (durations count from now)
+    pub fn parsed_expiry(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        if !self.expiry_enabled {
+            return None;
+        }
+        crate::validators::parse_expiry(&self.expiry, chrono::Utc::now()).ok()
+    }
+
+    fn validate_expiry(&self, errors: &mut FormErrors, has_errors: &mut bool) {
+        if !self.expiry_enabled {
+            return;
+        }
+        if let Err(msg) = crate::validators::parse_expiry(&self.expiry, chrono::Utc::now()) {
+            errors.expiry = Some(msg.to_string());
+            *has_errors = true;
+        }
+    }
+
+    /// Validates port-forward settings against the centralized constraints.
+    fn validate_port_forward(
+        &self,
+        sets: &[NamedSet],
+        errors: &mut FormErrors,
+        has_errors: &mut bool,
+    ) {
+        if !self.port_forward_enabled {
+            return;
+        }
+
+        let Ok(target) = self.forward_address.trim().parse::<IpAddr>() else {
+            errors.port_forward = Some("Enter the internal host's IP address".to_string());
+            *has_errors = true;
+            return;
+        };
+        let source_family_mismatch = self
+            .sources
+            .iter()
+            .any(|ip| !ip_compatible_with_forward_target(ip, target))
+            || self.source_set.as_ref().is_some_and(|name| {
+                sets.iter().any(|s| {
+                    &s.name == name && (s.set_type == SetType::Ipv6Addr) != target.is_ipv6()
+                })
+            });
+
+        let msg = if let Err(msg) = crate::validators::validate_forward_address(target) {
+            msg
+        } else if !self.forward_port.trim().is_empty()
+            && !self.forward_port.trim().parse::<u16>().is_ok_and(|p| p > 0)
+        {
+            "Internal port must be between 1 and 65535"
+        } else if !protocol_supports_ports(self.protocol) {
+            "Port forwarding requires TCP or UDP"
+        } else if self.port_set.is_some() || !ports_valid_for_port_forward(&self.ports) {
+            "Port forwarding needs exactly one external port or range"
+        } else if self.interfaces.is_empty() {
+            "Port forwarding needs an external interface"
+        } else if !chain_supports_port_forward(self.chain) {
+            "Port forwarding only applies to incoming traffic"
+        } else if !action_valid_for_port_forward(self.action) {
+            "Port forwards always accept matching traffic"
+        } else if !self.destinations.is_empty() || self.destination_set.is_some() {
+            "The internal host replaces destination addresses"
+        } else if !self.output_interfaces.is_empty() {
+            "Output interface is not available for port forwards"
+        } else if source_family_mismatch {
+            "Source addresses must match the internal host's IP family"
+        } else {
+            return;
+        };
+        errors.port_forward = Some(msg.to_string());
+        *has_errors = true;
+    }
+}
+
+/// Returns whether an address set of `set_type` can be used with `protocol`.
+fn set_compatible_with_protocol(set_type: SetType, protocol: Protocol) -> bool {
+    match protocol {
+        Protocol::Icmp => set_type != SetType::Ipv6Addr,
+        Protocol::Icmpv6 => set_type != SetType::Ipv4Addr,
+        _ => true,
+    }
 }
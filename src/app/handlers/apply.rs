@@ -58,14 +58,69 @@ fn interpret_elevated_command_output(output: &std::process::Output) -> Result<()
     }
 }
 
-/// Handles apply button click (starts verification)
+/// Handles apply button click (expands interface subnets and resolves hostnames,
+/// then starts verification)
 pub(crate) fn handle_apply_clicked(state: &mut State) -> Task<Message> {
     if state.is_busy() {
         return Task::none();
     }
 
     state.status = AppStatus::Verifying;
-    let nft_json = state.ruleset.to_nftables_json();
+    // Addresses may have changed since the preview: expand again before verifying
+    if crate::core::subnets::uses_subnets(&state.ruleset) {
+        state.refresh_local_subnets();
+        let warnings = crate::core::subnets::expansion_warnings(
+            &state.ruleset,
+            &state.local_subnets,
+            state.last_applied_subnets.as_deref(),
+        );
+        for warning in &warnings {
+            state.push_banner(warning, BannerSeverity::Warning);
+        }
+        state.update_cached_text();
+    }
+    let hostnames = crate::core::hosts::hostnames(&state.ruleset);
+    if !hostnames.is_empty() {
+        return Task::perform(
+            crate::core::hosts::resolve_all(hostnames),
+            Message::ApplyHostsResolved,
+        );
+    }
+    verify_for_apply(state)
+}
+
+/// Handles background hostname resolution (startup, rule and set edits)
+pub(crate) fn handle_hosts_resolved(
+    state: &mut State,
+    addresses: crate::core::hosts::HostAddresses,
+) {
+    state.host_addresses.extend(addresses);
+    state.update_cached_text();
+}
+
+/// Handles hostname resolution for an apply: warns about names that failed
+/// to resolve or changed since the last apply, then starts verification
+pub(crate) fn handle_apply_hosts_resolved(
+    state: &mut State,
+    addresses: crate::core::hosts::HostAddresses,
+) -> Task<Message> {
+    let hostnames = crate::core::hosts::hostnames(&state.ruleset);
+    let warnings = crate::core::hosts::resolution_warnings(
+        &hostnames,
+        &addresses,
+        state.last_applied_hosts.as_ref(),
+    );
+    for warning in &warnings {
+        state.push_banner(warning, BannerSeverity::Warning);
+    }
+    state.host_addresses = addresses;
+    state.update_cached_text();
+    verify_for_apply(state)
+}
+
+/// Verifies the expanded ruleset before an apply
+fn verify_for_apply(state: &State) -> Task<Message> {
+    let nft_json = state.expanded_ruleset().to_nftables_json();
 
     Task::perform(
         async move {
@@ -138,14 +193,47 @@ pub(crate) fn handle_verify_completed(
 /// Handles proceeding to apply after successful verification
 pub(crate) fn handle_proceed_to_apply(state: &mut State) -> Task<Message> {
     state.status = AppStatus::Applying;
-    let nft_json = state.ruleset.to_nftables_json();
+    // Country sets are generated for the rules active at `now`, so fill the same ones
+    let now = Utc::now();
+    let mut nft_json = state.expanded_ruleset().to_nftables_json_at(now);
+    let attached_blocklists = state.ruleset.blocklists.clone();
+    let country_groups = crate::core::geoip::country_groups(&state.ruleset, now);
+    let geoip_database = state.geoip_database.clone();
     let rule_count = state.ruleset.rules.len();
     let enabled_count = state.ruleset.rules.iter().filter(|r| r.enabled).count();
     let enable_event_log = state.enable_event_log;
 
     Task::perform(
         async move {
-            let result = crate::core::nft_json::apply_with_snapshot(nft_json).await;
+            // Re-inject active bans (in case the table is recreated) and fill the blocklist sets
+            let bans = crate::core::bans::load_bans().await.unwrap_or_else(|e| {
+                warn!("Failed to load bans, applying without them: {e}");
+                Vec::new()
+            });
+            crate::core::bans::append_active_bans(&mut nft_json, &bans, Utc::now());
+            let blocklists = crate::core::blocklists::load_cache()
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to load blocklists, applying without entries: {e}");
+                    Vec::new()
+                });
+            crate::core::blocklists::append_blocklist_elements(
+                &mut nft_json,
+                &attached_blocklists,
+                &blocklists,
+            );
+
+            // Country sets must be filled: empty sets would change what the rules match
+            let result = match crate::core::geoip::append_geoip_elements(
+                &mut nft_json,
+                &country_groups,
+                geoip_database.as_deref(),
+            )
+            .await
+            {
+                Ok(()) => crate::core::nft_json::apply_with_snapshot(nft_json).await,
+                Err(e) => Err(e),
+            };
             let success = result.is_ok();
             let error = result.as_ref().err().map(std::string::ToString::to_string);
             audit::log_apply(
@@ -166,6 +254,9 @@ pub(crate) fn handle_proceed_to_apply(state: &mut State) -> Task<Message> {
 /// Handles apply result (success or failure)
 pub(crate) fn handle_apply_result(state: &mut State, snapshot: serde_json::Value) -> Task<Message> {
     state.last_applied_ruleset = Some(state.ruleset.clone());
+    state.last_applied_hosts = Some(state.host_addresses.clone());
+    state.last_applied_subnets = Some(state.local_subnets.clone());
+    super::clear_rule_counters(state);
 
     let snapshot_task = if let Err(e) = crate::core::nft_json::save_snapshot_to_disk(&snapshot) {
         warn!("Failed to save snapshot to disk: {e}");
@@ -345,7 +436,7 @@ pub(crate) fn handle_save_to_system_clicked(state: &mut State) -> Task<Message>
     }
 
     state.status = AppStatus::Verifying;
-    let nft_json = state.ruleset.to_nftables_json();
+    let nft_json = state.expanded_ruleset().to_nftables_json();
 
     Task::perform(
         async move {
@@ -412,7 +503,7 @@ pub(crate) fn handle_save_to_system_verify_result(
 pub(crate) fn handle_save_to_system_confirmed(state: &mut State) -> Task<Message> {
     state.status = AppStatus::SavingToSystem;
 
-    let text = state.ruleset.to_nft_text();
+    let text = state.expanded_ruleset().to_nft_text();
 
     Task::perform(
         async move {
@@ -443,7 +534,7 @@ pub(crate) fn handle_save_to_system_confirmed(state: &mut State) -> Task<Message
             .map_err(|e| format!("Elevation error: {e}"))?;
 
             // Capture output for better error messages
-            let output = match tokio::time::timeout(Duration::from_secs(120), cmd.output()).await {
+            let output = match tokio::time::timeout(Duration::from_mins(2), cmd.output()).await {
                 Ok(Ok(output)) => output,
                 Ok(Err(e)) => return Err(format!("Failed to execute install: {e}")),
                 Err(_) => return Err("Install timed out (authentication dialog expired?)".into()),
@@ -689,4 +780,46 @@ mod tests {
         let _task = handle_apply_clicked(&mut state);
         assert_eq!(state.status, AppStatus::Verifying);
     }
+
+    #[test]
+    fn test_handle_apply_hosts_resolved_warns() {
+        use crate::core::hosts::HostAddresses;
+
+        let mut state = create_test_state();
+        let mut rule = crate::core::test_helpers::create_test_rule("NAS", Some(445));
+        rule.sources = vec!["nas.local".parse().unwrap(), "backup.lan".parse().unwrap()];
+        state.ruleset.rules.push(rule);
+        state.last_applied_hosts = Some(
+            [(
+                "nas.local".to_string(),
+                vec!["192.168.1.20".parse().unwrap()],
+            )]
+            .into(),
+        );
+        state.status = AppStatus::Verifying;
+
+        let resolved: HostAddresses = [
+            ("backup.lan".to_string(), Vec::new()),
+            (
+                "nas.local".to_string(),
+                vec!["192.168.1.21".parse().unwrap()],
+            ),
+        ]
+        .into();
+        let _task = handle_apply_hosts_resolved(&mut state, resolved);
+
+        let messages: Vec<_> = state.banners.iter().map(|b| b.message.as_str()).collect();
+        assert!(messages.contains(&"backup.lan did not resolve; rules using it are skipped"));
+        assert!(
+            messages.contains(&"nas.local changed since last apply: 192.168.1.20 → 192.168.1.21")
+        );
+        assert!(
+            state
+                .banners
+                .iter()
+                .all(|b| b.severity == BannerSeverity::Warning)
+        );
+        assert_eq!(state.status, AppStatus::Verifying);
+        assert_eq!(state.host_addresses.len(), 2);
+    }
 }
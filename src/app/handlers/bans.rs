@@ -0,0 +1,223 @@
+//! Temporary IP bans
+//!
+//! Handles the ban manager modal:
+//! - Loading the persisted ban list
+//! - Banning/unbanning addresses via live blocklist updates (no ruleset re-apply)
+
+use crate::app::{BanManagerState, BannerSeverity, Message, State};
+use crate::audit;
+use crate::core::bans::{self, Ban};
+use crate::validators;
+use iced::Task;
+use std::net::IpAddr;
+
+/// Handles opening the ban manager modal (loads the ban list)
+pub(crate) fn handle_open_ban_manager(state: &mut State) -> Task<Message> {
+    state.ban_manager = Some(BanManagerState::default());
+    load_bans_task()
+}
+
+/// Handles closing the ban manager modal
+pub(crate) fn handle_close_ban_manager(state: &mut State) {
+    state.ban_manager = None;
+}
+
+/// Handles the loaded ban list
+pub(crate) fn handle_bans_loaded(state: &mut State, result: Result<Vec<Ban>, String>) {
+    let Some(mgr) = &mut state.ban_manager else {
+        return; // Modal closed before load finished
+    };
+    match result {
+        Ok(bans) => mgr.bans = Some(bans),
+        Err(e) => {
+            mgr.bans = Some(Vec::new());
+            mgr.error = Some(format!("Failed to load bans: {e}"));
+        }
+    }
+}
+
+pub(crate) fn handle_ban_ip_changed(state: &mut State, ip: String) {
+    if let Some(mgr) = &mut state.ban_manager {
+        mgr.ip_input = ip;
+        mgr.error = None;
+    }
+}
+
+pub(crate) fn handle_ban_duration_changed(state: &mut State, duration: String) {
+    if let Some(mgr) = &mut state.ban_manager {
+        mgr.duration_input = duration;
+        mgr.error = None;
+    }
+}
+
+/// Handles submitting a new ban
+///
+/// Input errors are shown inline; the elevated element update runs async.
+pub(crate) fn handle_ban_submitted(state: &mut State) -> Task<Message> {
+    let Some(mgr) = &mut state.ban_manager else {
+        tracing::error!(
+            "BanSubmitted sent without active ban manager. \
+             This indicates a UI state management bug."
+        );
+        return Task::none();
+    };
+    if mgr.busy {
+        return Task::none();
+    }
+
+    let Ok(ip) = mgr.ip_input.trim().parse::<IpAddr>() else {
+        mgr.error = Some(format!("Invalid IP address: {}", mgr.ip_input.trim()));
+        return Task::none();
+    };
+    if let Err(e) = validators::validate_ban_address(ip) {
+        mgr.error = Some(e.to_string());
+        return Task::none();
+    }
+    let duration = match validators::parse_ban_duration(&mgr.duration_input) {
+        Ok(duration) => duration,
+        Err(e) => {
+            mgr.error = Some(e.to_string());
+            return Task::none();
+        }
+    };
+
+    mgr.busy = true;
+    mgr.error = None;
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            let result = bans::ban(ip, duration).await;
+            let error = result.as_ref().err().map(ToString::to_string);
+            audit::log_ip_banned(
+                enable_event_log,
+                &ip.to_string(),
+                duration.as_secs(),
+                result.is_ok(),
+                error,
+            )
+            .await;
+            result.map_err(|e| e.to_string())
+        },
+        Message::BanCompleted,
+    )
+    .chain(Task::done(Message::AuditLogWritten))
+}
+
+/// Handles completion of a ban
+pub(crate) fn handle_ban_completed(
+    state: &mut State,
+    result: Result<Ban, String>,
+) -> Task<Message> {
+    if let Some(mgr) = &mut state.ban_manager {
+        mgr.busy = false;
+    }
+    match result {
+        Ok(ban) => {
+            if let Some(mgr) = &mut state.ban_manager {
+                mgr.ip_input.clear();
+            }
+            state.push_banner(format!("Banned {}", ban.ip), BannerSeverity::Success);
+            load_bans_task()
+        }
+        Err(e) => {
+            state.push_banner(ban_error_message("Ban failed", &e), BannerSeverity::Error);
+            Task::none()
+        }
+    }
+}
+
+/// Handles lifting a ban
+pub(crate) fn handle_unban_clicked(state: &mut State, ip: IpAddr) -> Task<Message> {
+    let Some(mgr) = &mut state.ban_manager else {
+        return Task::none();
+    };
+    if mgr.busy {
+        return Task::none();
+    }
+    mgr.busy = true;
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            let result = bans::unban(ip).await;
+            let error = result.as_ref().err().map(ToString::to_string);
+            audit::log_ip_unbanned(enable_event_log, &ip.to_string(), result.is_ok(), error).await;
+            result.map(|_| ip).map_err(|e| e.to_string())
+        },
+        Message::UnbanCompleted,
+    )
+    .chain(Task::done(Message::AuditLogWritten))
+}
+
+/// Handles completion of an unban
+pub(crate) fn handle_unban_completed(
+    state: &mut State,
+    result: Result<IpAddr, String>,
+) -> Task<Message> {
+    if let Some(mgr) = &mut state.ban_manager {
+        mgr.busy = false;
+    }
+    match result {
+        Ok(ip) => {
+            state.push_banner(format!("Unbanned {ip}"), BannerSeverity::Success);
+            load_bans_task()
+        }
+        Err(e) => {
+            state.push_banner(ban_error_message("Unban failed", &e), BannerSeverity::Error);
+            Task::none()
+        }
+    }
+}
+
+fn load_bans_task() -> Task<Message> {
+    Task::perform(
+        async { bans::load_bans().await.map_err(|e| e.to_string()) },
+        Message::BansLoaded,
+    )
+}
+
+/// Builds a short banner message for a failed ban/unban
+fn ban_error_message(prefix: &str, error: &str) -> String {
+    if error.contains("No authentication agent") || error.contains("No polkit") {
+        "No authentication agent available. Install and start a polkit agent.".to_string()
+    } else if error.trim().is_empty() || error.trim_end().ends_with(':') {
+        format!("{prefix}: authentication cancelled or failed")
+    } else {
+        let first_line = error.lines().next().unwrap_or(error);
+        format!("{prefix}: {first_line}")
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+
+    #[test]
+    fn test_ban_submitted_rejects_invalid_input() {
+        let mut state = create_test_state();
+        state.ban_manager = Some(BanManagerState::default());
+
+        handle_ban_ip_changed(&mut state, "not-an-ip".to_string());
+        let _ = handle_ban_submitted(&mut state);
+        let mgr = state.ban_manager.as_ref().unwrap();
+        assert!(mgr.error.is_some());
+        assert!(!mgr.busy);
+
+        handle_ban_ip_changed(&mut state, "127.0.0.1".to_string());
+        let _ = handle_ban_submitted(&mut state);
+        assert!(state.ban_manager.as_ref().unwrap().error.is_some());
+
+        handle_ban_ip_changed(&mut state, "203.0.113.7".to_string());
+        handle_ban_duration_changed(&mut state, "forever".to_string());
+        let _ = handle_ban_submitted(&mut state);
+        let mgr = state.ban_manager.as_ref().unwrap();
+        assert!(mgr.error.is_some());
+        assert!(!mgr.busy);
+    }
+}
@@ -0,0 +1,283 @@
+//! IP blocklist files
+//!
+//! Handles the blocklist manager modal:
+//! - Attaching and detaching list files (profile changes, applied on the next apply)
+//! - Re-reading the files to update the parsed entries and their counts
+
+use crate::app::{BannerSeverity, BlocklistManagerState, Message, State};
+use crate::audit;
+use crate::core::blocklists::{self, LoadedBlocklist, MAX_BLOCKLISTS};
+use crate::validators;
+use iced::Task;
+use std::path::{Path, PathBuf};
+
+/// Handles opening the blocklist manager modal (loads the parsed lists)
+pub(crate) fn handle_open_blocklist_manager(state: &mut State) -> Task<Message> {
+    state.blocklist_manager = Some(BlocklistManagerState::default());
+    Task::perform(
+        async { blocklists::load_cache().await.map_err(|e| e.to_string()) },
+        Message::BlocklistsLoaded,
+    )
+}
+
+/// Handles closing the blocklist manager modal
+pub(crate) fn handle_close_blocklist_manager(state: &mut State) {
+    state.blocklist_manager = None;
+}
+
+/// Handles the loaded blocklist cache
+pub(crate) fn handle_blocklists_loaded(
+    state: &mut State,
+    result: Result<Vec<LoadedBlocklist>, String>,
+) {
+    let Some(mgr) = &mut state.blocklist_manager else {
+        return; // Modal closed before load finished
+    };
+    match result {
+        Ok(lists) => mgr.lists = Some(lists),
+        Err(e) => {
+            mgr.lists = Some(Vec::new());
+            mgr.error = Some(format!("Failed to load blocklists: {e}"));
+        }
+    }
+}
+
+pub(crate) fn handle_blocklist_path_changed(state: &mut State, path: String) {
+    if let Some(mgr) = &mut state.blocklist_manager {
+        mgr.path_input = path;
+        mgr.error = None;
+    }
+}
+
+/// Handles picking a list file with the native file dialog
+pub(crate) fn handle_browse_blocklist() -> Task<Message> {
+    Task::perform(
+        async {
+            rfd::AsyncFileDialog::new()
+                .add_filter("IP lists", &["txt", "list", "netset", "ipset"])
+                .add_filter("All files", &["*"])
+                .pick_file()
+                .await
+                .map(|file| file.path().to_path_buf())
+        },
+        |path| path.map_or(Message::Noop, Message::AddBlocklistPath),
+    )
+}
+
+/// Handles submitting the typed path
+pub(crate) fn handle_add_blocklist_submitted(state: &mut State) -> Task<Message> {
+    let Some(mgr) = &state.blocklist_manager else {
+        return Task::none();
+    };
+    let path = PathBuf::from(mgr.path_input.trim());
+    handle_add_blocklist(state, path)
+}
+
+/// Handles attaching a list file
+///
+/// The file is read before it is attached, so unreadable or oversized lists
+/// are reported inline instead of failing the next apply.
+pub(crate) fn handle_add_blocklist(state: &mut State, path: PathBuf) -> Task<Message> {
+    let Some(mgr) = &mut state.blocklist_manager else {
+        tracing::error!(
+            "AddBlocklistPath sent without active blocklist manager. \
+             This indicates a UI state management bug."
+        );
+        return Task::none();
+    };
+    if mgr.busy {
+        return Task::none();
+    }
+
+    if let Err(e) = validators::validate_blocklist_path(&path) {
+        mgr.error = Some(e.to_string());
+        return Task::none();
+    }
+    if state.ruleset.blocklists.contains(&path) {
+        mgr.error = Some(format!("{} is already attached", path.display()));
+        return Task::none();
+    }
+    if state.ruleset.blocklists.len() >= MAX_BLOCKLISTS {
+        mgr.error = Some(format!(
+            "At most {MAX_BLOCKLISTS} blocklists can be attached"
+        ));
+        return Task::none();
+    }
+
+    mgr.busy = true;
+    mgr.error = None;
+
+    let mut paths = state.ruleset.blocklists.clone();
+    paths.push(path.clone());
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            let result = refresh_and_log(enable_event_log, &paths).await;
+            result.map(|lists| (path, lists))
+        },
+        Message::BlocklistAdded,
+    )
+    .chain(Task::done(Message::AuditLogWritten))
+}
+
+/// Handles a list file that was read (or failed to read) before attaching it
+pub(crate) fn handle_blocklist_added(
+    state: &mut State,
+    result: Result<(PathBuf, Vec<LoadedBlocklist>), String>,
+) {
+    if let Some(mgr) = &mut state.blocklist_manager {
+        mgr.busy = false;
+    }
+    match result {
+        Ok((path, lists)) => {
+            let entries = lists
+                .iter()
+                .find(|l| l.path == path)
+                .map_or(0, |l| l.entries.len());
+            state.ruleset.blocklists.push(path.clone());
+            state.mark_profile_dirty();
+            if let Some(mgr) = &mut state.blocklist_manager {
+                mgr.lists = Some(lists);
+                mgr.path_input.clear();
+            }
+            state.push_banner(
+                format!(
+                    "Attached {} ({entries} entries), apply to activate",
+                    crate::app::helpers::truncate_path_smart(&path.display().to_string(), 40)
+                ),
+                BannerSeverity::Success,
+            );
+        }
+        Err(e) => {
+            if let Some(mgr) = &mut state.blocklist_manager {
+                mgr.error = Some(e);
+            }
+        }
+    }
+}
+
+/// Handles detaching a list file (its parsed entries are no longer applied)
+pub(crate) fn handle_remove_blocklist_clicked(state: &mut State, path: &Path) {
+    let before = state.ruleset.blocklists.len();
+    state.ruleset.blocklists.retain(|p| p != path);
+    if state.ruleset.blocklists.len() != before {
+        state.mark_profile_dirty();
+    }
+}
+
+/// Handles re-reading all attached list files
+pub(crate) fn handle_refresh_blocklists(state: &mut State) -> Task<Message> {
+    let Some(mgr) = &mut state.blocklist_manager else {
+        return Task::none();
+    };
+    if mgr.busy {
+        return Task::none();
+    }
+    mgr.busy = true;
+    mgr.error = None;
+
+    let paths = state.ruleset.blocklists.clone();
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move { refresh_and_log(enable_event_log, &paths).await },
+        Message::BlocklistsRefreshed,
+    )
+    .chain(Task::done(Message::AuditLogWritten))
+}
+
+/// Handles completion of a refresh
+pub(crate) fn handle_blocklists_refreshed(
+    state: &mut State,
+    result: Result<Vec<LoadedBlocklist>, String>,
+) {
+    let Some(mgr) = &mut state.blocklist_manager else {
+        return;
+    };
+    mgr.busy = false;
+    match result {
+        Ok(lists) => {
+            mgr.lists = Some(lists);
+            state.push_banner(
+                "Blocklists reloaded, apply to update the firewall",
+                BannerSeverity::Success,
+            );
+        }
+        Err(e) => mgr.error = Some(e),
+    }
+}
+
+async fn refresh_and_log(
+    enable_event_log: bool,
+    paths: &[PathBuf],
+) -> Result<Vec<LoadedBlocklist>, String> {
+    let result = blocklists::refresh(paths).await;
+    let entries = result
+        .as_ref()
+        .map_or(0, |lists| lists.iter().map(|l| l.entries.len()).sum());
+    let error = result.as_ref().err().map(ToString::to_string);
+    audit::log_blocklists_refreshed(
+        enable_event_log,
+        paths.len(),
+        entries,
+        result.is_ok(),
+        error,
+    )
+    .await;
+    result.map_err(|e| e.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+
+    #[test]
+    fn test_add_blocklist_rejects_invalid_paths() {
+        let mut state = create_test_state();
+        state.blocklist_manager = Some(BlocklistManagerState::default());
+
+        handle_blocklist_path_changed(&mut state, "lists/drop.txt".to_string());
+        let _ = handle_add_blocklist_submitted(&mut state);
+        let mgr = state.blocklist_manager.as_ref().unwrap();
+        assert!(mgr.error.is_some());
+        assert!(!mgr.busy);
+
+        state
+            .ruleset
+            .blocklists
+            .push(PathBuf::from("/etc/drfw/drop.txt"));
+        let _ = handle_add_blocklist(&mut state, PathBuf::from("/etc/drfw/drop.txt"));
+        let mgr = state.blocklist_manager.as_ref().unwrap();
+        assert!(mgr.error.as_deref().unwrap().contains("already attached"));
+        assert!(!mgr.busy);
+    }
+
+    #[test]
+    fn test_blocklist_added_attaches_path() {
+        let mut state = create_test_state();
+        state.blocklist_manager = Some(BlocklistManagerState {
+            busy: true,
+            ..Default::default()
+        });
+        let path = PathBuf::from("/etc/drfw/drop.txt");
+        let lists = vec![LoadedBlocklist {
+            path: path.clone(),
+            loaded_at: chrono::Utc::now(),
+            entries: vec!["192.0.2.0/24".parse().unwrap()],
+            invalid_lines: 0,
+        }];
+
+        handle_blocklist_added(&mut state, Ok((path.clone(), lists)));
+        assert_eq!(state.ruleset.blocklists, vec![path.clone()]);
+        let mgr = state.blocklist_manager.as_ref().unwrap();
+        assert!(!mgr.busy);
+        assert_eq!(mgr.lists.as_ref().unwrap().len(), 1);
+
+        handle_remove_blocklist_clicked(&mut state, &path);
+        assert!(state.ruleset.blocklists.is_empty());
+    }
+}
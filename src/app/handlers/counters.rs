@@ -0,0 +1,162 @@
+//! Per-rule packet/byte counters
+//!
+//! Handles the live counters shown on the sidebar cards:
+//! - Enabling/disabling counters in the generated ruleset
+//! - Polling the kernel counters while the applied ruleset has them, plus
+//!   manual refreshes (each read is an elevated nft call)
+//! - Resetting the displayed values (baseline only, no privileged call)
+
+use crate::app::{BannerSeverity, Message, State};
+use crate::core::counters::{self, RuleCounter};
+use iced::Task;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Handles toggling per-rule counters (takes effect on the next apply)
+pub(crate) fn handle_toggle_rule_counters(state: &mut State, enabled: bool) -> Task<Message> {
+    state.ruleset.advanced_security.rule_counters = enabled;
+    state.mark_profile_dirty();
+    let enable_event_log = state.enable_event_log;
+    let desc = if enabled {
+        "Per-rule counters enabled"
+    } else {
+        "Per-rule counters disabled"
+    };
+    Task::perform(
+        async move {
+            crate::audit::log_settings_saved(enable_event_log, desc).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles the polling tick: reads the kernel counters unless polling is paused
+pub(crate) fn handle_rule_counters_tick(state: &mut State) -> Task<Message> {
+    if state.rule_counters.paused {
+        return Task::none();
+    }
+    read_rule_counters(state)
+}
+
+/// Handles a manual refresh: reads the kernel counters now and resumes polling
+pub(crate) fn handle_refresh_rule_counters(state: &mut State) -> Task<Message> {
+    if !state.counters_active() {
+        return Task::none();
+    }
+    state.rule_counters.paused = false;
+    read_rule_counters(state)
+}
+
+/// Starts a kernel counter read unless one is in flight
+fn read_rule_counters(state: &mut State) -> Task<Message> {
+    if !state.counters_active() || state.rule_counters.loading {
+        return Task::none();
+    }
+    state.rule_counters.loading = true;
+    Task::perform(
+        async {
+            counters::read_rule_counters()
+                .await
+                .map_err(|e| e.to_string())
+        },
+        Message::RuleCountersLoaded,
+    )
+}
+
+/// Handles a finished counter read
+///
+/// A failure keeps the last values and pauses polling, so a cancelled
+/// authentication prompt doesn't reappear every few seconds.
+pub(crate) fn handle_rule_counters_loaded(
+    state: &mut State,
+    result: Result<HashMap<Uuid, RuleCounter>, String>,
+) {
+    state.rule_counters.loading = false;
+    match result {
+        Ok(values) => {
+            state.rule_counters.values = values;
+            state.rule_counters.update_display();
+        }
+        Err(e) => {
+            state.rule_counters.paused = true;
+            let first_line = e
+                .lines()
+                .next()
+                .unwrap_or("authentication cancelled or failed");
+            state.push_banner(
+                format!("Rule counters paused: {first_line}"),
+                BannerSeverity::Warning,
+            );
+        }
+    }
+}
+
+/// Handles resetting the displayed counters
+pub(crate) fn handle_reset_rule_counters(state: &mut State) {
+    state.rule_counters.baseline = state.rule_counters.values.clone();
+    state.rule_counters.update_display();
+}
+
+/// Clears counter state after an apply (the kernel counters restart at zero)
+pub(crate) fn clear_rule_counters(state: &mut State) {
+    state.rule_counters.values.clear();
+    state.rule_counters.baseline.clear();
+    state.rule_counters.display.clear();
+    state.rule_counters.paused = false;
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+
+    fn counter(packets: u64, bytes: u64) -> RuleCounter {
+        RuleCounter {
+            packets,
+            bytes,
+            quota: None,
+        }
+    }
+
+    #[test]
+    fn test_rule_counters_reset_and_pause() {
+        let mut state = create_test_state();
+        let id = Uuid::new_v4();
+
+        handle_rule_counters_loaded(&mut state, Ok(HashMap::from([(id, counter(40, 2048))])));
+        assert_eq!(state.rule_counters.display[&id], "40 pkts · 2.0 KB");
+
+        // Reset shows traffic since the reset only
+        handle_reset_rule_counters(&mut state);
+        assert_eq!(state.rule_counters.display[&id], "0 pkts · 0 B");
+        handle_rule_counters_loaded(&mut state, Ok(HashMap::from([(id, counter(45, 2348))])));
+        assert_eq!(state.rule_counters.display[&id], "5 pkts · 300 B");
+
+        // A failed read pauses polling and keeps the last values
+        state.rule_counters.loading = true;
+        handle_rule_counters_loaded(&mut state, Err("Request dismissed".to_string()));
+        assert!(state.rule_counters.paused);
+        assert!(!state.rule_counters.loading);
+        assert_eq!(state.rule_counters.display[&id], "5 pkts · 300 B");
+        assert!(
+            state
+                .banners
+                .iter()
+                .any(|b| b.message == "Rule counters paused: Request dismissed")
+        );
+
+        // Ticks are skipped while paused; a manual refresh resumes polling
+        let mut applied = state.ruleset.clone();
+        applied.advanced_security.rule_counters = true;
+        state.last_applied_ruleset = Some(applied);
+        let _ = handle_rule_counters_tick(&mut state);
+        assert!(!state.rule_counters.loading);
+        let _ = handle_refresh_rule_counters(&mut state);
+        assert!(!state.rule_counters.paused);
+        assert!(state.rule_counters.loading);
+    }
+}
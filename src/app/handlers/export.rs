@@ -13,7 +13,8 @@ use iced::Task;
 
 /// Handles exporting as JSON
 pub(crate) fn handle_export_as_json(state: &State) -> Task<Message> {
-    let json = serde_json::to_string_pretty(&state.ruleset.to_nftables_json()).unwrap_or_default();
+    let json = serde_json::to_string_pretty(&state.expanded_ruleset().to_nftables_json())
+        .unwrap_or_default();
     Task::perform(
         async move {
             use rfd::AsyncFileDialog;
@@ -38,7 +39,7 @@ pub(crate) fn handle_export_as_json(state: &State) -> Task<Message> {
 
 /// Handles exporting as nft text
 pub(crate) fn handle_export_as_nft(state: &State) -> Task<Message> {
-    let text = state.ruleset.to_nft_text();
+    let text = state.expanded_ruleset().to_nft_text();
     Task::perform(
         async move {
             use rfd::AsyncFileDialog;
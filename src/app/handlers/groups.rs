@@ -0,0 +1,430 @@
+//! Rule group management
+//!
+//! Handles the group manager modal and the group controls of the sidebar:
+//! - Creating, renaming and deleting groups
+//! - Enabling/disabling, collapsing and moving a group as a unit
+//! - Dropping a dragged rule onto a group header
+
+use crate::app::{GroupEditorState, GroupManagerState, Message, State};
+use crate::audit;
+use crate::command::{
+    AddGroupCommand, DeleteGroupCommand, EditGroupCommand, MoveGroupCommand,
+    MoveRuleToGroupCommand, ToggleGroupCommand,
+};
+use crate::core::firewall::{FirewallRuleset, RuleGroup};
+use crate::validators;
+use iced::Task;
+use uuid::Uuid;
+
+/// Handles opening the group manager modal
+pub(crate) fn handle_open_group_manager(state: &mut State) {
+    state.group_manager = Some(GroupManagerState::default());
+}
+
+/// Handles closing the group manager modal
+pub(crate) fn handle_close_group_manager(state: &mut State) {
+    state.group_manager = None;
+}
+
+/// Handles starting creation of a new group
+pub(crate) fn handle_new_group_clicked(state: &mut State) {
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.deleting_id = None;
+        mgr.editor = Some(GroupEditorState::default());
+    }
+}
+
+/// Handles opening an existing group in the editor (rename)
+pub(crate) fn handle_edit_group_clicked(state: &mut State, id: Uuid) {
+    let Some(group) = state.ruleset.find_group(id) else {
+        return;
+    };
+    let name = group.name.clone();
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.deleting_id = None;
+        mgr.editor = Some(GroupEditorState {
+            original_id: Some(id),
+            name,
+            error: None,
+        });
+    }
+}
+
+pub(crate) fn handle_group_editor_name_changed(state: &mut State, name: String) {
+    if let Some(editor) = state.group_manager.as_mut().and_then(|m| m.editor.as_mut()) {
+        editor.name = name;
+        editor.error = None;
+    }
+}
+
+/// Handles canceling the group editor
+pub(crate) fn handle_cancel_group_editor(state: &mut State) {
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.editor = None;
+    }
+}
+
+/// Handles saving the group editor (create or rename)
+pub(crate) fn handle_save_group_editor(state: &mut State) -> Task<Message> {
+    let Some(editor) = state.group_manager.as_mut().and_then(|m| m.editor.as_mut()) else {
+        tracing::error!(
+            "SaveGroupEditor sent without active group editor. \
+             This indicates a UI state management bug."
+        );
+        return Task::none();
+    };
+
+    let name = editor.name.trim().to_string();
+    if let Err(e) = validate_group_name(&state.ruleset, &name, editor.original_id) {
+        editor.error = Some(e);
+        return Task::none();
+    }
+    let original_id = editor.original_id;
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.editor = None;
+    }
+
+    let created = if let Some(old_group) = original_id
+        .and_then(|id| state.ruleset.find_group(id))
+        .cloned()
+    {
+        if old_group.name == name {
+            return Task::none();
+        }
+        let new_group = RuleGroup {
+            name: name.clone(),
+            ..old_group.clone()
+        };
+        let command = EditGroupCommand {
+            old_group,
+            new_group,
+        };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+        false
+    } else {
+        let command = AddGroupCommand {
+            group: RuleGroup::new(name.clone()),
+        };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+        true
+    };
+
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_group_saved(enable_event_log, &name, created).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles requesting deletion of a group
+pub(crate) fn handle_delete_group_requested(state: &mut State, id: Uuid) {
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.editor = None;
+        mgr.deleting_id = Some(id);
+    }
+}
+
+/// Handles canceling group deletion
+pub(crate) fn handle_cancel_delete_group(state: &mut State) {
+    if let Some(mgr) = &mut state.group_manager {
+        mgr.deleting_id = None;
+    }
+}
+
+/// Handles confirming group deletion (the member rules are kept, ungrouped)
+pub(crate) fn handle_confirm_delete_group(state: &mut State) -> Task<Message> {
+    let Some(id) = state
+        .group_manager
+        .as_mut()
+        .and_then(|m| m.deleting_id.take())
+    else {
+        return Task::none();
+    };
+
+    let Some(index) = state.ruleset.groups.iter().position(|g| g.id == id) else {
+        return Task::none();
+    };
+
+    let group = state.ruleset.groups[index].clone();
+    let members: Vec<Uuid> = state.ruleset.group_members(id).map(|r| r.id).collect();
+    let name = group.name.clone();
+    let member_count = members.len();
+    let command = DeleteGroupCommand {
+        group,
+        index,
+        members,
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.collapsed_groups.remove(&id);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_group_deleted(enable_event_log, &name, member_count).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles enabling/disabling a group as a unit
+pub(crate) fn handle_toggle_group(state: &mut State, id: Uuid) -> Task<Message> {
+    let Some(group) = state.ruleset.find_group(id) else {
+        return Task::none();
+    };
+
+    let was_enabled = group.enabled;
+    let name = group.name.clone();
+    let command = ToggleGroupCommand {
+        group_id: id,
+        was_enabled,
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_group_toggled(enable_event_log, &name, !was_enabled).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles collapsing/expanding a group in the sidebar
+pub(crate) fn handle_toggle_group_collapsed(state: &mut State, id: Uuid) {
+    if !state.collapsed_groups.remove(&id) {
+        state.collapsed_groups.insert(id);
+    }
+}
+
+/// Handles moving a group's rules past the previous (`up`) or next rule or group
+pub(crate) fn handle_move_group(state: &mut State, id: Uuid, up: bool) -> Task<Message> {
+    let Some(group) = state.ruleset.find_group(id) else {
+        return Task::none();
+    };
+    let name = group.name.clone();
+
+    let mut blocks = rule_blocks(&state.ruleset);
+    let Some(index) = blocks.iter().position(|(g, _)| *g == Some(id)) else {
+        return Task::none(); // Empty group: nothing to move
+    };
+    let target = if up {
+        index.checked_sub(1)
+    } else {
+        Some(index + 1).filter(|&i| i < blocks.len())
+    };
+    let Some(target) = target else {
+        return Task::none();
+    };
+    blocks.swap(index, target);
+
+    let old_order: Vec<Uuid> = state.ruleset.rules.iter().map(|r| r.id).collect();
+    let new_order: Vec<Uuid> = blocks.into_iter().flat_map(|(_, ids)| ids).collect();
+    let command = MoveGroupCommand {
+        name: name.clone(),
+        old_order,
+        new_order,
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    let direction = if up { "up" } else { "down" };
+    Task::perform(
+        async move {
+            audit::log_rules_reordered(enable_event_log, &format!("group {name}"), direction).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles dropping the dragged rule onto a group header: the rule becomes the
+/// group's last member (or its first, staying in place, if the group is empty)
+pub(crate) fn handle_rule_dropped_on_group(state: &mut State, group_id: Uuid) -> Task<Message> {
+    let Some(drag_id) = state.dragged_rule_id else {
+        return Task::none();
+    };
+    state.clear_drag_state();
+
+    let Some(group) = state.ruleset.find_group(group_id) else {
+        return Task::none();
+    };
+    let group_name = group.name.clone();
+    let Some(old_index) = state.ruleset.rules.iter().position(|r| r.id == drag_id) else {
+        return Task::none();
+    };
+    let rule = &state.ruleset.rules[old_index];
+    if rule.group == Some(group_id) {
+        return Task::none();
+    }
+    let old_group = rule.group;
+    let label = rule.label.clone();
+
+    let last_member = state
+        .ruleset
+        .rules
+        .iter()
+        .rposition(|r| r.group == Some(group_id));
+    // Indices shift down by one once the dragged rule is removed from above
+    let new_index = match last_member {
+        Some(last) if last > old_index => last,
+        Some(last) => last + 1,
+        None => old_index,
+    };
+
+    let command = MoveRuleToGroupCommand {
+        rule_id: drag_id,
+        old_index,
+        new_index,
+        old_group,
+        new_group: Some(group_id),
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_rules_reordered(
+                enable_event_log,
+                &label,
+                &format!("into group '{group_name}'"),
+            )
+            .await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Splits the rules into the blocks shown in the sidebar: each group's members
+/// (at the position of its first member) or a single ungrouped rule
+pub(crate) fn rule_blocks(ruleset: &FirewallRuleset) -> Vec<(Option<Uuid>, Vec<Uuid>)> {
+    let mut blocks: Vec<(Option<Uuid>, Vec<Uuid>)> = Vec::new();
+    for rule in &ruleset.rules {
+        match ruleset.group_of(rule).map(|g| g.id) {
+            Some(id) => {
+                if let Some((_, ids)) = blocks.iter_mut().find(|(g, _)| *g == Some(id)) {
+                    ids.push(rule.id);
+                } else {
+                    blocks.push((Some(id), vec![rule.id]));
+                }
+            }
+            None => blocks.push((None, vec![rule.id])),
+        }
+    }
+    blocks
+}
+
+/// Validates an edited group name, including uniqueness
+fn validate_group_name(
+    ruleset: &FirewallRuleset,
+    name: &str,
+    original_id: Option<Uuid>,
+) -> Result<(), String> {
+    validators::validate_group_name(name).map_err(ToString::to_string)?;
+    if ruleset
+        .groups
+        .iter()
+        .any(|g| g.name == name && Some(g.id) != original_id)
+    {
+        return Err(format!("A group named '{name}' already exists"));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+    use crate::core::test_helpers::create_test_rule;
+
+    fn create_group(state: &mut State, name: &str) -> Uuid {
+        handle_open_group_manager(state);
+        handle_new_group_clicked(state);
+        handle_group_editor_name_changed(state, name.to_string());
+        let _ = handle_save_group_editor(state);
+        state.ruleset.groups.last().unwrap().id
+    }
+
+    #[test]
+    fn test_create_rename_and_delete_group() {
+        let mut state = create_test_state();
+        let id = create_group(&mut state, "Media server");
+        assert_eq!(state.ruleset.groups.len(), 1);
+
+        // Duplicate names are rejected inline
+        create_group(&mut state, "Media server");
+        assert_eq!(state.ruleset.groups.len(), 1);
+        let editor = state.group_manager.as_ref().unwrap().editor.as_ref();
+        assert!(editor.unwrap().error.is_some());
+
+        handle_edit_group_clicked(&mut state, id);
+        handle_group_editor_name_changed(&mut state, "Media".to_string());
+        let _ = handle_save_group_editor(&mut state);
+        assert_eq!(state.ruleset.groups[0].name, "Media");
+
+        handle_delete_group_requested(&mut state, id);
+        let _ = handle_confirm_delete_group(&mut state);
+        assert!(state.ruleset.groups.is_empty());
+    }
+
+    #[test]
+    fn test_drop_on_group_and_move_group() {
+        let mut state = create_test_state();
+        state.ruleset.rules = ["A", "B", "C", "D"]
+            .iter()
+            .map(|label| create_test_rule(label, Some(80)))
+            .collect();
+        let id = create_group(&mut state, "Dev tools");
+        let labels = |state: &State| -> Vec<String> {
+            state
+                .ruleset
+                .rules
+                .iter()
+                .map(|r| r.label.clone())
+                .collect()
+        };
+
+        // First member stays in place, later ones follow the last member
+        state.dragged_rule_id = Some(state.ruleset.rules[1].id);
+        let _ = handle_rule_dropped_on_group(&mut state, id);
+        state.dragged_rule_id = Some(state.ruleset.rules[3].id);
+        let _ = handle_rule_dropped_on_group(&mut state, id);
+        assert_eq!(labels(&state), ["A", "B", "D", "C"]);
+        assert_eq!(state.ruleset.group_members(id).count(), 2);
+
+        let _ = handle_move_group(&mut state, id, true);
+        assert_eq!(labels(&state), ["B", "D", "A", "C"]);
+        let _ = handle_move_group(&mut state, id, true);
+        assert_eq!(labels(&state), ["B", "D", "A", "C"], "already first");
+        let _ = handle_move_group(&mut state, id, false);
+        let _ = handle_move_group(&mut state, id, false);
+        assert_eq!(labels(&state), ["A", "C", "B", "D"]);
+
+        let _ = handle_toggle_group(&mut state, id);
+        assert!(!state.ruleset.groups[0].enabled);
+        state.command_history.undo(&mut state.ruleset);
+        assert!(state.ruleset.groups[0].enabled);
+    }
+}
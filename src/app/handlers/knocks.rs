@@ -0,0 +1,313 @@
+//! Port knocking management
+//!
+//! Handles the port knocking manager modal:
+//! - Creating, editing and deleting knock sequences
+//! - Validating the sequence, protected service and timeout before saving
+
+use crate::app::{KnockEditorState, KnockManagerState, Message, State};
+use crate::audit;
+use crate::command::{AddKnockCommand, DeleteKnockCommand, EditKnockCommand};
+use crate::core::firewall::{FirewallRuleset, PortKnock, Protocol};
+use crate::validators;
+use iced::Task;
+
+/// Handles opening the port knocking manager modal
+pub(crate) fn handle_open_knock_manager(state: &mut State) {
+    state.knock_manager = Some(KnockManagerState::default());
+}
+
+/// Handles closing the port knocking manager modal
+pub(crate) fn handle_close_knock_manager(state: &mut State) {
+    state.knock_manager = None;
+}
+
+/// Handles starting creation of a new knock sequence
+pub(crate) fn handle_new_knock_clicked(state: &mut State) {
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.deleting_name = None;
+        mgr.editor = Some(KnockEditorState::default());
+    }
+}
+
+/// Handles opening an existing knock sequence in the editor
+pub(crate) fn handle_edit_knock_clicked(state: &mut State, name: &str) {
+    let Some(knock) = state.ruleset.find_knock(name) else {
+        return;
+    };
+    let sequence: Vec<String> = knock.sequence.iter().map(ToString::to_string).collect();
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.deleting_name = None;
+        mgr.editor = Some(KnockEditorState {
+            original_name: Some(knock.name.clone()),
+            name: knock.name.clone(),
+            sequence: sequence.join(", "),
+            knock_protocol: knock.knock_protocol,
+            protocol: knock.protocol,
+            port: knock.port.to_string(),
+            timeout: knock.timeout_secs.to_string(),
+            error: None,
+        });
+    }
+}
+
+fn with_editor(state: &mut State, update: impl FnOnce(&mut KnockEditorState)) {
+    if let Some(editor) = state.knock_manager.as_mut().and_then(|m| m.editor.as_mut()) {
+        update(editor);
+        editor.error = None;
+    }
+}
+
+pub(crate) fn handle_knock_editor_name_changed(state: &mut State, name: String) {
+    with_editor(state, |editor| editor.name = name);
+}
+
+pub(crate) fn handle_knock_editor_sequence_changed(state: &mut State, sequence: String) {
+    with_editor(state, |editor| editor.sequence = sequence);
+}
+
+pub(crate) fn handle_knock_editor_knock_protocol_changed(state: &mut State, protocol: Protocol) {
+    with_editor(state, |editor| editor.knock_protocol = protocol);
+}
+
+pub(crate) fn handle_knock_editor_protocol_changed(state: &mut State, protocol: Protocol) {
+    with_editor(state, |editor| editor.protocol = protocol);
+}
+
+pub(crate) fn handle_knock_editor_port_changed(state: &mut State, port: String) {
+    with_editor(state, |editor| editor.port = port);
+}
+
+pub(crate) fn handle_knock_editor_timeout_changed(state: &mut State, timeout: String) {
+    with_editor(state, |editor| editor.timeout = timeout);
+}
+
+/// Handles canceling the knock editor
+pub(crate) fn handle_cancel_knock_editor(state: &mut State) {
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.editor = None;
+    }
+}
+
+/// Handles saving the knock editor (create or edit)
+///
+/// Validation errors are shown inline in the editor; on success the change is
+/// applied through the command history so it can be undone.
+pub(crate) fn handle_save_knock_editor(state: &mut State) -> Task<Message> {
+    let Some(editor) = state.knock_manager.as_mut().and_then(|m| m.editor.as_mut()) else {
+        tracing::error!(
+            "SaveKnockEditor sent without active knock editor. \
+             This indicates a UI state management bug."
+        );
+        return Task::none();
+    };
+
+    let new_knock = match build_knock(editor, &state.ruleset) {
+        Ok(knock) => knock,
+        Err(e) => {
+            editor.error = Some(e);
+            return Task::none();
+        }
+    };
+
+    let old_knock = editor
+        .original_name
+        .as_deref()
+        .and_then(|name| state.ruleset.find_knock(name))
+        .cloned();
+    let created = old_knock.is_none();
+
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.editor = None;
+    }
+
+    let name = new_knock.name.clone();
+    let summary = new_knock.summary();
+    if let Some(old_knock) = old_knock {
+        if old_knock == new_knock {
+            return Task::none();
+        }
+        let command = EditKnockCommand {
+            old_knock,
+            new_knock,
+        };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+    } else {
+        let command = AddKnockCommand { knock: new_knock };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+    }
+
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_knock_saved(enable_event_log, &name, &summary, created).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles requesting deletion of a knock sequence
+pub(crate) fn handle_delete_knock_requested(state: &mut State, name: String) {
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.editor = None;
+        mgr.deleting_name = Some(name);
+    }
+}
+
+/// Handles canceling knock deletion
+pub(crate) fn handle_cancel_delete_knock(state: &mut State) {
+    if let Some(mgr) = &mut state.knock_manager {
+        mgr.deleting_name = None;
+    }
+}
+
+/// Handles confirming knock deletion
+pub(crate) fn handle_confirm_delete_knock(state: &mut State) -> Task<Message> {
+    let Some(name) = state
+        .knock_manager
+        .as_mut()
+        .and_then(|m| m.deleting_name.take())
+    else {
+        return Task::none();
+    };
+
+    let Some(index) = state.ruleset.knocks.iter().position(|k| k.name == name) else {
+        return Task::none();
+    };
+
+    let command = DeleteKnockCommand {
+        knock: state.ruleset.knocks[index].clone(),
+        index,
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_knock_deleted(enable_event_log, &name).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Builds a `PortKnock` from editor input, validating ports, timeout and name.
+fn build_knock(editor: &KnockEditorState, ruleset: &FirewallRuleset) -> Result<PortKnock, String> {
+    let name = editor.name.trim();
+    if editor.original_name.as_deref() != Some(name) && ruleset.find_knock(name).is_some() {
+        return Err(format!("A port knock named '{name}' already exists"));
+    }
+
+    let sequence = editor
+        .sequence
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            p.parse::<u16>()
+                .map_err(|_| format!("Invalid knock port: {p}"))
+        })
+        .collect::<Result<Vec<u16>, String>>()?;
+    let port = editor
+        .port
+        .trim()
+        .parse::<u16>()
+        .map_err(|_| format!("Invalid port: {}", editor.port.trim()))?;
+    let timeout_secs = editor
+        .timeout
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| "Timeout must be a number of seconds".to_string())?;
+
+    let knock = PortKnock {
+        name: name.to_string(),
+        knock_protocol: editor.knock_protocol,
+        sequence,
+        protocol: editor.protocol,
+        port,
+        timeout_secs,
+    };
+    validators::validate_port_knock(&knock).map_err(ToString::to_string)?;
+    Ok(knock)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+
+    fn open_editor(state: &mut State, name: &str, sequence: &str) {
+        handle_open_knock_manager(state);
+        handle_new_knock_clicked(state);
+        handle_knock_editor_name_changed(state, name.to_string());
+        handle_knock_editor_sequence_changed(state, sequence.to_string());
+    }
+
+    #[test]
+    fn test_create_and_edit_knock() {
+        let mut state = create_test_state();
+        open_editor(&mut state, "ssh", "7000, 8000 9000");
+        let _ = handle_save_knock_editor(&mut state);
+
+        assert_eq!(state.ruleset.knocks.len(), 1);
+        assert_eq!(state.ruleset.knocks[0].sequence, vec![7000, 8000, 9000]);
+        assert_eq!(state.ruleset.knocks[0].port, 22);
+        assert!(state.knock_manager.as_ref().unwrap().editor.is_none());
+
+        handle_edit_knock_clicked(&mut state, "ssh");
+        handle_knock_editor_timeout_changed(&mut state, "10".to_string());
+        handle_knock_editor_protocol_changed(&mut state, Protocol::Udp);
+        handle_knock_editor_port_changed(&mut state, "51820".to_string());
+        let _ = handle_save_knock_editor(&mut state);
+        assert_eq!(state.ruleset.knocks[0].timeout_secs, 10);
+        assert_eq!(state.ruleset.knocks[0].port, 51820);
+
+        handle_delete_knock_requested(&mut state, "ssh".to_string());
+        let _ = handle_confirm_delete_knock(&mut state);
+        assert!(state.ruleset.knocks.is_empty());
+    }
+
+    #[test]
+    fn test_save_knock_rejects_invalid_input() {
+        let mut state = create_test_state();
+        open_editor(&mut state, "ssh", "7000, 22");
+        let _ = handle_save_knock_editor(&mut state);
+        assert!(state.ruleset.knocks.is_empty());
+        let editor = state
+            .knock_manager
+            .as_ref()
+            .unwrap()
+            .editor
+            .as_ref()
+            .unwrap();
+        assert!(editor.error.is_some());
+
+        handle_knock_editor_sequence_changed(&mut state, "7000, abc".to_string());
+        let _ = handle_save_knock_editor(&mut state);
+        assert!(state.ruleset.knocks.is_empty());
+
+        handle_knock_editor_sequence_changed(&mut state, "7000, 8000".to_string());
+        let _ = handle_save_knock_editor(&mut state);
+        open_editor(&mut state, "ssh", "1000, 2000");
+        let _ = handle_save_knock_editor(&mut state);
+        assert_eq!(state.ruleset.knocks.len(), 1);
+        let editor = state
+            .knock_manager
+            .as_ref()
+            .unwrap()
+            .editor
+            .as_ref()
+            .unwrap();
+        assert!(editor.error.as_deref().unwrap().contains("already exists"));
+    }
+}
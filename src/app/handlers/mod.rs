@@ -4,9 +4,15 @@
 //! `update()` method, organized by functional domain for better maintainability.
 
 pub mod apply;
+pub mod bans;
+pub mod blocklists;
+pub mod counters;
 pub mod export;
+pub mod groups;
+pub mod knocks;
 pub mod profiles;
 pub mod rules;
+pub mod sets;
 pub mod settings;
 pub mod ui_state;
 
@@ -15,8 +21,14 @@ pub mod test_utils;
 
 // Re-export all handlers for clean imports in app/mod.rs
 pub(crate) use apply::*;
+pub(crate) use bans::*;
+pub(crate) use blocklists::*;
+pub(crate) use counters::*;
 pub(crate) use export::*;
+pub(crate) use groups::*;
+pub(crate) use knocks::*;
 pub(crate) use profiles::*;
 pub(crate) use rules::*;
+pub(crate) use sets::*;
 pub(crate) use settings::*;
 pub(crate) use ui_state::*;
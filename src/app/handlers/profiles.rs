@@ -64,6 +64,7 @@ pub(crate) fn handle_profile_switched(
         },
         |()| Message::AuditLogWritten,
     )
+    .chain(state.refresh_symbolic_addresses())
 }
 
 /// Handles saving current ruleset as a new profile or saving empty profile
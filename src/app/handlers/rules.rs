@@ -10,18 +10,25 @@
 use crate::app::{Message, RuleForm, State};
 use crate::audit;
 use crate::command::{
-    AddRuleCommand, DeleteRuleCommand, EditRuleCommand, ReorderRuleCommand, ToggleRuleCommand,
+    AddRuleCommand, DeleteRuleCommand, EditRuleCommand, MoveRuleToGroupCommand, RemoveRulesCommand,
+    ReorderRuleCommand, ToggleRuleCommand,
 };
-use crate::core::firewall::{Chain, Protocol, Rule};
+use crate::core::firewall::{
+    AddressEntry, Chain, CtState, IcmpType, MatchField, Protocol, Rule, Weekday,
+};
+use crate::core::geoip::Country;
+use crate::core::rule_constraints::icmp_type_valid_for_protocol;
 use crate::validators;
 use chrono::Utc;
 use iced::Task;
+use iced::widget::text_editor;
 use std::sync::Arc;
 use uuid::Uuid;
 
 /// Handles opening the "Add New Rule" form
 pub(crate) fn handle_add_rule_clicked(state: &mut State) {
     state.rule_form = Some(RuleForm::default());
+    state.rule_description = text_editor::Content::new();
     state.form_errors = None;
 }
 
@@ -29,11 +36,19 @@ pub(crate) fn handle_add_rule_clicked(state: &mut State) {
 pub(crate) fn handle_edit_rule_clicked(state: &mut State, id: Uuid) {
     if let Some(rule) = state.ruleset.rules.iter().find(|r| r.id == id) {
         // Create form from existing rule
-        let has_advanced = !rule.destinations.is_empty()
+        let has_advanced = !rule.source_ports.is_empty()
+            || !rule.ct_states.is_empty()
+            || rule.schedule.is_some()
+            || rule.expires_at.is_some()
+            || !rule.destinations.is_empty()
+            || rule.destination_set.is_some()
             || !matches!(rule.action, crate::core::firewall::Action::Accept)
             || rule.rate_limit.is_some()
+            || rule.quota.is_some()
+            || rule.per_source.is_some()
+            || !rule.countries.is_empty()
             || rule.connection_limit > 0
-            || rule.output_interface.is_some()
+            || !rule.output_interfaces.is_empty()
             || !matches!(rule.reject_type, crate::core::firewall::RejectType::Default)
             || rule.log_enabled;
 
@@ -43,15 +58,25 @@ pub(crate) fn handle_edit_rule_clicked(state: &mut State, id: Uuid) {
             protocol: rule.protocol,
             // Multi-value fields - clone directly
             ports: rule.ports.clone(),
+            source_ports: rule.source_ports.clone(),
+            ct_states: rule.ct_states.clone(),
+            icmp_types: rule.icmp_types.clone(),
             sources: rule.sources.clone(),
             destinations: rule.destinations.clone(),
+            interfaces: rule.interfaces.clone(),
+            output_interfaces: rule.output_interfaces.clone(),
             tags: rule.tags.clone(),
+            countries: rule.countries.clone(),
+            // Named set references
+            port_set: rule.port_set.clone(),
+            source_set: rule.source_set.clone(),
+            destination_set: rule.destination_set.clone(),
+            negate: rule.negate,
             // Single-value fields
-            interface: rule.interface.clone().unwrap_or_default(),
-            output_interface: rule.output_interface.clone().unwrap_or_default(),
             chain: rule.chain,
             action: rule.action,
             reject_type: rule.reject_type,
+            icmp_code: rule.icmp_code.map(|c| c.to_string()).unwrap_or_default(),
             // Rate limiting
             rate_limit_enabled: rule.rate_limit.is_some(),
             rate_limit_count: rule
@@ -67,17 +92,67 @@ pub(crate) fn handle_edit_rule_clicked(state: &mut State, id: Uuid) {
                 .as_ref()
                 .and_then(|rl| rl.burst)
                 .map_or_else(String::new, |b| b.to_string()),
+            rate_limit_over: rule.rate_limit.is_some_and(|rl| rl.over),
+            rate_limit_measure: crate::app::RateMeasure(rule.rate_limit.and_then(|rl| rl.bytes)),
+            // Byte quota
+            quota_enabled: rule.quota.is_some(),
+            quota_amount: rule
+                .quota
+                .map_or_else(String::new, |q| q.amount.to_string()),
+            quota_unit: rule
+                .quota
+                .map_or(crate::core::firewall::ByteUnit::GBytes, |q| q.unit),
+            quota_mode: rule.quota.map(|q| q.mode).unwrap_or_default(),
             // Connection limiting
             connection_limit: if rule.connection_limit > 0 {
                 rule.connection_limit.to_string()
             } else {
                 String::new()
             },
+            // Per-source limit tracking
+            per_source_enabled: rule.per_source.is_some(),
+            per_source_size: rule.per_source.unwrap_or_default().size.to_string(),
+            per_source_timeout: rule.per_source.unwrap_or_default().timeout_secs.to_string(),
             // Per-rule logging
             log_enabled: rule.log_enabled,
+            // Port forwarding
+            port_forward_enabled: rule.port_forward.is_some(),
+            forward_address: rule
+                .port_forward
+                .map_or_else(String::new, |pf| pf.to_address.to_string()),
+            forward_port: rule
+                .port_forward
+                .and_then(|pf| pf.to_port)
+                .map_or_else(String::new, |p| p.to_string()),
+            // Schedule
+            schedule_enabled: rule.schedule.is_some(),
+            schedule_start: rule
+                .schedule
+                .as_ref()
+                .map_or_else(String::new, |s| s.start.format("%H:%M").to_string()),
+            schedule_end: rule
+                .schedule
+                .as_ref()
+                .map_or_else(String::new, |s| s.end.format("%H:%M").to_string()),
+            schedule_days: rule
+                .schedule
+                .as_ref()
+                .map_or_else(Vec::new, |s| s.days.clone()),
+            // Expiry
+            expiry_enabled: rule.expires_at.is_some(),
+            expiry: rule.expires_at.map_or_else(String::new, |t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            }),
+            // Metadata
+            description: rule.description.clone(),
+            owner: rule.owner.clone(),
+            reference: rule.reference.clone(),
             // UI state
             show_advanced: has_advanced,
         });
+        state.rule_description = text_editor::Content::with_text(&rule.description);
         state.form_errors = None;
     }
 }
@@ -101,7 +176,7 @@ pub(crate) fn handle_cancel_rule_form(state: &mut State) {
 pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
     // Validate form exists
     if let Some(form_ref) = &state.rule_form {
-        if let Some(errs) = form_ref.validate() {
+        if let Some(errs) = form_ref.validate(&state.ruleset.sets) {
             state.form_errors = Some(errs);
             return Task::none();
         }
@@ -116,16 +191,13 @@ pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
         };
 
         let sanitized_label = validators::sanitize_label(&form.label);
-        let interface = if form.interface.is_empty() {
-            None
-        } else {
-            Some(form.interface)
-        };
-        let output_interface = if form.output_interface.is_empty() {
-            None
-        } else {
-            Some(form.output_interface)
-        };
+        let port_forward = form.parsed_port_forward();
+        let schedule = form.parsed_schedule();
+        let expires_at = form.parsed_expiry();
+        let negate = form.parsed_negation();
+        let quota = form.parsed_quota();
+        let per_source = form.parsed_per_source();
+        let icmp_code = form.icmp_code.trim().parse().ok();
 
         // Parse rate limit with burst support
         let rate_limit = if form.rate_limit_enabled && !form.rate_limit_count.is_empty() {
@@ -135,6 +207,8 @@ pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
                     count,
                     unit: form.rate_limit_unit,
                     burst,
+                    over: form.rate_limit_over,
+                    bytes: form.rate_limit_measure.0,
                 }
             })
         } else {
@@ -147,6 +221,15 @@ pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
             form.connection_limit.parse().unwrap_or(0)
         };
 
+        // Group membership is changed by drag-and-drop, not in the form; the
+        // creation time survives edits (EditRuleCommand stamps modified_at)
+        let existing = form
+            .id
+            .and_then(|id| state.ruleset.rules.iter().find(|r| r.id == id));
+        let group = existing.and_then(|r| r.group);
+        let created_at = existing.map_or_else(Utc::now, |r| r.created_at);
+        let modified_at = existing.and_then(|r| r.modified_at);
+
         let mut rule = Rule {
             id: form.id.unwrap_or_else(Uuid::new_v4),
             label: sanitized_label,
@@ -155,18 +238,37 @@ pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
             ports: form.ports,
             sources: form.sources,
             destinations: form.destinations,
+            interfaces: form.interfaces,
+            output_interfaces: form.output_interfaces,
             // Single-value fields
-            interface,
-            output_interface,
             chain: form.chain,
             enabled: true,
-            created_at: Utc::now(),
+            created_at,
+            modified_at,
+            expires_at,
             tags: form.tags,
             action: form.action,
             reject_type: form.reject_type,
             rate_limit,
             connection_limit,
             log_enabled: form.log_enabled,
+            source_set: form.source_set,
+            destination_set: form.destination_set,
+            port_set: form.port_set,
+            port_forward,
+            source_ports: form.source_ports,
+            icmp_types: form.icmp_types,
+            icmp_code,
+            ct_states: form.ct_states,
+            schedule,
+            quota,
+            per_source,
+            countries: form.countries,
+            group,
+            description: form.description,
+            owner: form.owner,
+            reference: form.reference,
+            negate,
             // Cached fields - will be populated by rebuild_caches()
             label_lowercase: String::new(),
             interface_lowercase: None,
@@ -182,6 +284,7 @@ pub(crate) fn handle_save_rule_form(state: &mut State) -> Task<Message> {
             log_prefix: String::new(),
             tags_truncated: Vec::new(),
             badge_display: String::new(),
+            metadata_lowercase: String::new(),
         };
         rule.rebuild_caches();
 
@@ -273,6 +376,13 @@ pub(crate) fn handle_toggle_rule(state: &mut State, id: Uuid) -> Task<Message> {
     )
 }
 
+/// Handles expanding/collapsing the metadata details of a sidebar rule card
+pub(crate) fn handle_toggle_rule_details(state: &mut State, id: Uuid) {
+    if !state.expanded_rules.remove(&id) {
+        state.expanded_rules.insert(id);
+    }
+}
+
 /// Handles deleting a rule
 pub(crate) fn handle_delete_rule(state: &mut State, id: Uuid) -> Task<Message> {
     let Some((index, rule_clone)) = state
@@ -316,6 +426,30 @@ pub(crate) fn handle_delete_rule(state: &mut State, id: Uuid) -> Task<Message> {
 }
 
 /// Handles drag-and-drop reordering of rules
+/// Removes every expired rule in one undoable step
+pub(crate) fn handle_remove_expired_rules(state: &mut State) -> Task<Message> {
+    let now = Utc::now();
+    let cmd = RemoveRulesCommand::matching(&state.ruleset, |r| r.is_expired(now));
+    if cmd.removed.is_empty() {
+        return Task::none();
+    }
+
+    let labels: Vec<String> = cmd.removed.iter().map(|(_, r)| r.label.clone()).collect();
+    state.clear_drag_state();
+    state
+        .command_history
+        .execute(Box::new(cmd), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_expired_rules_removed(enable_event_log, &labels).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
 pub(crate) fn handle_rule_dropped(state: &mut State, dropped_id: Uuid) -> Task<Message> {
     let Some(drag_id) = state.dragged_rule_id else {
         return Task::none();
@@ -337,13 +471,35 @@ pub(crate) fn handle_rule_dropped(state: &mut State, dropped_id: Uuid) -> Task<M
         return Task::none();
     };
 
-    // Execute command
-    let cmd = Box::new(ReorderRuleCommand {
-        rule_id: drag_id,
-        old_index,
-        new_index,
-    });
-    state.command_history.execute(cmd, &mut state.ruleset);
+    // Dropping onto a rule of another group (or an ungrouped rule) moves the
+    // dragged rule into that group (or out of its own)
+    let old_group = state.ruleset.rules[old_index].group;
+    let new_group = state
+        .ruleset
+        .group_of(&state.ruleset.rules[new_index])
+        .map(|g| g.id);
+    if state
+        .ruleset
+        .group_of(&state.ruleset.rules[old_index])
+        .map(|g| g.id)
+        == new_group
+    {
+        let cmd = Box::new(ReorderRuleCommand {
+            rule_id: drag_id,
+            old_index,
+            new_index,
+        });
+        state.command_history.execute(cmd, &mut state.ruleset);
+    } else {
+        let cmd = Box::new(MoveRuleToGroupCommand {
+            rule_id: drag_id,
+            old_index,
+            new_index,
+            old_group,
+            new_group,
+        });
+        state.command_history.execute(cmd, &mut state.ruleset);
+    }
 
     state.clear_drag_state();
     state.mark_profile_dirty();
@@ -403,6 +559,15 @@ pub(crate) fn handle_rule_form_protocol_changed(state: &mut State, protocol: Pro
     // Clear ports if switching to ICMP or Any (doesn't use ports)
     if matches!(protocol, Protocol::Icmp | Protocol::Icmpv6 | Protocol::Any) {
         form.ports.clear();
+        form.source_ports.clear();
+        form.port_set = None;
+    }
+
+    // Keep only the ICMP types that exist for the new protocol
+    form.icmp_types
+        .retain(|t| icmp_type_valid_for_protocol(*t, protocol));
+    if form.icmp_types.is_empty() {
+        form.icmp_code.clear();
     }
 
     // TCP Reset reject type is only valid for TCP - auto-reset to Default
@@ -413,15 +578,16 @@ pub(crate) fn handle_rule_form_protocol_changed(state: &mut State, protocol: Pro
     }
 }
 
-pub(crate) fn handle_rule_form_interface_changed(state: &mut State, value: String) {
+pub(crate) fn handle_rule_form_toggle_negate(state: &mut State, field: MatchField) {
     let Some(form) = &mut state.rule_form else {
         tracing::error!(
-            "RuleFormInterfaceChanged sent without active form. \
+            "RuleFormToggleNegate sent without active form. \
              This indicates a UI state management bug."
         );
         return;
     };
-    form.interface = value;
+    let negated = form.negate.get(field);
+    form.negate.set(field, !negated);
 }
 
 pub(crate) fn handle_rule_form_chain_changed(state: &mut State, chain: Chain) {
@@ -496,6 +662,81 @@ pub(crate) fn handle_rule_form_rate_limit_unit_changed(
     form.rate_limit_unit = unit;
 }
 
+pub(crate) fn handle_rule_form_toggle_rate_limit_over(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleRateLimitOver sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.rate_limit_over = enabled;
+}
+
+pub(crate) fn handle_rule_form_rate_limit_measure_changed(
+    state: &mut State,
+    measure: crate::app::RateMeasure,
+) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormRateLimitMeasureChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.rate_limit_measure = measure;
+}
+
+pub(crate) fn handle_rule_form_toggle_quota(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleQuota sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.quota_enabled = enabled;
+}
+
+pub(crate) fn handle_rule_form_quota_amount_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormQuotaAmountChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.quota_amount = value;
+}
+
+pub(crate) fn handle_rule_form_quota_unit_changed(
+    state: &mut State,
+    unit: crate::core::firewall::ByteUnit,
+) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormQuotaUnitChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.quota_unit = unit;
+}
+
+pub(crate) fn handle_rule_form_quota_mode_changed(
+    state: &mut State,
+    mode: crate::core::firewall::QuotaMode,
+) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormQuotaModeChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.quota_mode = mode;
+}
+
 pub(crate) fn handle_rule_form_connection_limit_changed(state: &mut State, value: String) {
     let Some(form) = &mut state.rule_form else {
         tracing::error!(
@@ -507,6 +748,73 @@ pub(crate) fn handle_rule_form_connection_limit_changed(state: &mut State, value
     form.connection_limit = value;
 }
 
+pub(crate) fn handle_rule_form_toggle_per_source(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormTogglePerSource sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.per_source_enabled = enabled;
+}
+
+pub(crate) fn handle_rule_form_per_source_size_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormPerSourceSizeChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.per_source_size = value;
+}
+
+pub(crate) fn handle_rule_form_per_source_timeout_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormPerSourceTimeoutChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.per_source_timeout = value;
+}
+
+pub(crate) fn handle_rule_form_description_edited(state: &mut State, action: text_editor::Action) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormDescriptionEdited sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    state.rule_description.perform(action);
+    form.description = state.rule_description.text();
+}
+
+pub(crate) fn handle_rule_form_owner_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormOwnerChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.owner = value;
+}
+
+pub(crate) fn handle_rule_form_reference_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormReferenceChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.reference = value;
+}
+
 // ============================================================================
 // Search and filtering
 // ============================================================================
@@ -612,7 +920,12 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
     }
 
     match helper_type {
-        HelperType::Ports => {
+        HelperType::Ports | HelperType::SourcePorts => {
+            let ports = if helper_type == HelperType::Ports {
+                &mut form.ports
+            } else {
+                &mut form.source_ports
+            };
             // Support bulk paste: "22, 80, 443, 8000-8080"
             if input.contains(',') {
                 let (entries, errors) = validators::parse_bulk_ports(input);
@@ -620,10 +933,10 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                 let mut duplicates = 0;
 
                 for entry in entries {
-                    if form.ports.contains(&entry) {
+                    if ports.contains(&entry) {
                         duplicates += 1;
                     } else {
-                        form.ports.push(entry);
+                        ports.push(entry);
                         added += 1;
                     }
                 }
@@ -647,10 +960,10 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                 // Single port or range
                 match validators::validate_port_entry(input) {
                     Ok(entry) => {
-                        if form.ports.contains(&entry) {
+                        if ports.contains(&entry) {
                             helper.error = Some("Port already added".to_string());
                         } else {
-                            form.ports.push(entry);
+                            ports.push(entry);
                             helper.input.clear();
                             helper.error = None;
                         }
@@ -661,7 +974,7 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                 }
             }
         }
-        HelperType::SourceAddresses => match input.parse::<ipnetwork::IpNetwork>() {
+        HelperType::SourceAddresses => match input.parse::<AddressEntry>() {
             Ok(ip) => {
                 if form.sources.contains(&ip) {
                     helper.error = Some("Address already added".to_string());
@@ -670,11 +983,11 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                     helper.input.clear();
                 }
             }
-            Err(_) => {
-                helper.error = Some("Invalid IP/CIDR (e.g., 192.168.1.0/24)".to_string());
+            Err(e) => {
+                helper.error = Some(e.to_string());
             }
         },
-        HelperType::DestinationAddresses => match input.parse::<ipnetwork::IpNetwork>() {
+        HelperType::DestinationAddresses => match input.parse::<AddressEntry>() {
             Ok(ip) => {
                 if form.destinations.contains(&ip) {
                     helper.error = Some("Address already added".to_string());
@@ -683,10 +996,35 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                     helper.input.clear();
                 }
             }
-            Err(_) => {
-                helper.error = Some("Invalid IP/CIDR (e.g., 192.168.1.0/24)".to_string());
+            Err(e) => {
+                helper.error = Some(e.to_string());
             }
         },
+        HelperType::Interfaces | HelperType::OutputInterfaces => {
+            let interfaces = if helper_type == HelperType::Interfaces {
+                &mut form.interfaces
+            } else {
+                &mut form.output_interfaces
+            };
+            match validators::validate_interface(input) {
+                Ok(name) if interfaces.contains(&name) => {
+                    helper.error = Some("Interface already added".to_string());
+                }
+                Ok(name) => {
+                    let mut candidate = interfaces.clone();
+                    candidate.push(name);
+                    if let Some(e) = RuleForm::interface_list_error(&candidate) {
+                        helper.error = Some(e.to_string());
+                    } else {
+                        *interfaces = candidate;
+                        helper.input.clear();
+                    }
+                }
+                Err(e) => {
+                    helper.error = Some(e.to_string());
+                }
+            }
+        }
         HelperType::Tags => {
             let tag = validators::sanitize_label(input);
             if tag.is_empty() {
@@ -700,6 +1038,18 @@ pub(crate) fn handle_helper_add_value(state: &mut State) {
                 helper.input.clear();
             }
         }
+        // ICMP types are toggled directly (see `handle_rule_form_toggle_icmp_type`)
+        HelperType::IcmpTypes => {}
+    }
+
+    // Explicit values and a named set are mutually exclusive
+    match helper_type {
+        HelperType::Ports if !form.ports.is_empty() => form.port_set = None,
+        HelperType::SourceAddresses if !form.sources.is_empty() => form.source_set = None,
+        HelperType::DestinationAddresses if !form.destinations.is_empty() => {
+            form.destination_set = None;
+        }
+        _ => {}
     }
 }
 
@@ -718,6 +1068,16 @@ pub(crate) fn handle_helper_remove_value(state: &mut State, index: usize) {
                 form.ports.remove(index);
             }
         }
+        HelperType::SourcePorts => {
+            if index < form.source_ports.len() {
+                form.source_ports.remove(index);
+            }
+        }
+        HelperType::IcmpTypes => {
+            if index < form.icmp_types.len() {
+                form.icmp_types.remove(index);
+            }
+        }
         HelperType::SourceAddresses => {
             if index < form.sources.len() {
                 form.sources.remove(index);
@@ -728,6 +1088,16 @@ pub(crate) fn handle_helper_remove_value(state: &mut State, index: usize) {
                 form.destinations.remove(index);
             }
         }
+        HelperType::Interfaces => {
+            if index < form.interfaces.len() {
+                form.interfaces.remove(index);
+            }
+        }
+        HelperType::OutputInterfaces => {
+            if index < form.output_interfaces.len() {
+                form.output_interfaces.remove(index);
+            }
+        }
         HelperType::Tags => {
             if index < form.tags.len() {
                 form.tags.remove(index);
@@ -736,19 +1106,86 @@ pub(crate) fn handle_helper_remove_value(state: &mut State, index: usize) {
     }
 }
 
+/// Selects (or clears) the named set for the field being edited in the helper.
+///
+/// Selecting a set replaces any explicit values for that field.
+pub(crate) fn handle_helper_set_selected(state: &mut State, name: Option<String>) {
+    let (Some(form), Some(helper)) = (&mut state.rule_form, &mut state.rule_form_helper) else {
+        return;
+    };
+
+    let Some(helper_type) = helper.helper_type else {
+        return;
+    };
+
+    let clear_values = name.is_some();
+    match helper_type {
+        HelperType::Ports => {
+            if clear_values {
+                form.ports.clear();
+            }
+            form.port_set = name;
+        }
+        HelperType::SourceAddresses => {
+            if clear_values {
+                form.sources.clear();
+            }
+            form.source_set = name;
+        }
+        HelperType::DestinationAddresses => {
+            if clear_values {
+                form.destinations.clear();
+            }
+            form.destination_set = name;
+        }
+        // Named port sets only match destination ports
+        HelperType::SourcePorts
+        | HelperType::IcmpTypes
+        | HelperType::Interfaces
+        | HelperType::OutputInterfaces
+        | HelperType::Tags => {}
+    }
+    helper.error = None;
+}
+
+/// Adds an interface picked from the helper's suggestion list
+pub(crate) fn handle_helper_suggestion_selected(state: &mut State, value: String) {
+    let Some(helper) = &mut state.rule_form_helper else {
+        return;
+    };
+    helper.input = value;
+    handle_helper_add_value(state);
+}
+
 // ============================================================================
 // New rule form field handlers (backend features from additional_nft.md)
 // ============================================================================
 
-pub(crate) fn handle_rule_form_output_interface_changed(state: &mut State, value: String) {
+/// Toggles an ICMP type in the ICMP types helper
+pub(crate) fn handle_rule_form_toggle_icmp_type(state: &mut State, icmp_type: IcmpType) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleIcmpType sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    if let Some(index) = form.icmp_types.iter().position(|t| *t == icmp_type) {
+        form.icmp_types.remove(index);
+    } else {
+        form.icmp_types.push(icmp_type);
+    }
+}
+
+pub(crate) fn handle_rule_form_icmp_code_changed(state: &mut State, value: String) {
     let Some(form) = &mut state.rule_form else {
         tracing::error!(
-            "RuleFormOutputInterfaceChanged sent without active form. \
+            "RuleFormIcmpCodeChanged sent without active form. \
              This indicates a UI state management bug."
         );
         return;
     };
-    form.output_interface = value;
+    form.icmp_code = value;
 }
 
 pub(crate) fn handle_rule_form_reject_type_changed(state: &mut State, reject_type: RejectType) {
@@ -784,6 +1221,155 @@ pub(crate) fn handle_rule_form_log_enabled_toggled(state: &mut State, enabled: b
     form.log_enabled = enabled;
 }
 
+/// Adds a country to the source country selection
+pub(crate) fn handle_rule_form_country_added(state: &mut State, country: Country) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormCountryAdded sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    if !form.countries.iter().any(|c| c == country.code)
+        && form.countries.len() < validators::MAX_COUNTRIES_PER_RULE
+    {
+        form.countries.push(country.code.to_string());
+    }
+}
+
+pub(crate) fn handle_rule_form_country_removed(state: &mut State, code: &str) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormCountryRemoved sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.countries.retain(|c| c != code);
+}
+
+/// Toggles a connection state (kept sorted so output order is stable)
+pub(crate) fn handle_rule_form_toggle_ct_state(state: &mut State, ct_state: CtState) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleCtState sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    if let Some(index) = form.ct_states.iter().position(|s| *s == ct_state) {
+        form.ct_states.remove(index);
+    } else {
+        form.ct_states.push(ct_state);
+        form.ct_states.sort_unstable();
+    }
+}
+
+pub(crate) fn handle_rule_form_toggle_port_forward(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormTogglePortForward sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.port_forward_enabled = enabled;
+}
+
+pub(crate) fn handle_rule_form_forward_address_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormForwardAddressChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.forward_address = value;
+}
+
+pub(crate) fn handle_rule_form_forward_port_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormForwardPortChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.forward_port = value;
+}
+
+pub(crate) fn handle_rule_form_toggle_schedule(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleSchedule sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.schedule_enabled = enabled;
+}
+
+pub(crate) fn handle_rule_form_schedule_start_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormScheduleStartChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.schedule_start = value;
+}
+
+pub(crate) fn handle_rule_form_schedule_end_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormScheduleEndChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.schedule_end = value;
+}
+
+/// Toggles a schedule weekday (kept in week order)
+pub(crate) fn handle_rule_form_toggle_schedule_day(state: &mut State, day: Weekday) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleScheduleDay sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    if let Some(index) = form.schedule_days.iter().position(|d| *d == day) {
+        form.schedule_days.remove(index);
+    } else {
+        form.schedule_days.push(day);
+        form.schedule_days.sort_unstable();
+    }
+}
+
+pub(crate) fn handle_rule_form_toggle_expiry(state: &mut State, enabled: bool) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormToggleExpiry sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.expiry_enabled = enabled;
+}
+
+pub(crate) fn handle_rule_form_expiry_changed(state: &mut State, value: String) {
+    let Some(form) = &mut state.rule_form else {
+        tracing::error!(
+            "RuleFormExpiryChanged sent without active form. \
+             This indicates a UI state management bug."
+        );
+        return;
+    };
+    form.expiry = value;
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -827,4 +1413,354 @@ mod tests {
         handle_rule_form_label_changed(&mut state, "Test Label".to_string());
         assert_eq!(state.rule_form.as_ref().unwrap().label, "Test Label");
     }
+
+    #[test]
+    fn test_save_port_forward_rule() {
+        let mut state = create_test_state();
+        let mut form = RuleForm {
+            label: "Web VM".to_string(),
+            ports: vec![crate::core::firewall::PortEntry::Single(8080)],
+            interfaces: vec!["eth0".to_string()],
+            port_forward_enabled: true,
+            forward_address: "192.168.122.10".to_string(),
+            forward_port: "80".to_string(),
+            ..RuleForm::default()
+        };
+        // Forwards need an external interface
+        form.interfaces.clear();
+        state.rule_form = Some(form.clone());
+        let _ = handle_save_rule_form(&mut state);
+        assert!(
+            state
+                .form_errors
+                .as_ref()
+                .is_some_and(|e| e.port_forward.is_some())
+        );
+
+        form.interfaces = vec!["eth0".to_string()];
+        state.rule_form = Some(form);
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.rule_form.is_none());
+        let rule = state.ruleset.rules.last().unwrap();
+        let forward = rule.port_forward.unwrap();
+        assert_eq!(forward.to_address.to_string(), "192.168.122.10");
+        assert_eq!(forward.to_port, Some(80));
+        assert_eq!(rule.badge_display, "TCP: 8080 → 192.168.122.10:80");
+    }
+
+    #[test]
+    fn test_helper_set_selection_replaces_values() {
+        let mut state = create_test_state();
+        let mut form = RuleForm::default();
+        form.sources.push("10.0.0.1".parse().unwrap());
+        state.rule_form = Some(form);
+        handle_open_helper(&mut state, HelperType::SourceAddresses);
+
+        handle_helper_set_selected(&mut state, Some("trusted".to_string()));
+        let form = state.rule_form.as_ref().unwrap();
+        assert!(form.sources.is_empty());
+        assert_eq!(form.source_set.as_deref(), Some("trusted"));
+
+        // Adding an explicit address drops the set reference again
+        handle_helper_input_changed(&mut state, "192.168.1.0/24".to_string());
+        handle_helper_add_value(&mut state);
+        let form = state.rule_form.as_ref().unwrap();
+        assert_eq!(form.sources.len(), 1);
+        assert!(form.source_set.is_none());
+    }
+
+    #[test]
+    fn test_helper_adds_source_ports() {
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm::default());
+        handle_open_helper(&mut state, HelperType::SourcePorts);
+
+        handle_helper_input_changed(&mut state, "123, 5000-5100".to_string());
+        handle_helper_add_value(&mut state);
+        let form = state.rule_form.as_ref().unwrap();
+        assert_eq!(form.source_ports.len(), 2);
+        assert!(form.ports.is_empty());
+
+        // Switching to a portless protocol clears source ports too
+        handle_rule_form_protocol_changed(&mut state, Protocol::Icmp);
+        assert!(state.rule_form.as_ref().unwrap().source_ports.is_empty());
+    }
+
+    #[test]
+    fn test_helper_adds_address_ranges() {
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm::default());
+        handle_open_helper(&mut state, HelperType::SourceAddresses);
+
+        handle_helper_input_changed(&mut state, "192.168.1.100-192.168.1.150".to_string());
+        handle_helper_add_value(&mut state);
+        let form = state.rule_form.as_ref().unwrap();
+        assert_eq!(form.sources[0].to_string(), "192.168.1.100-192.168.1.150");
+
+        // Reversed and mixed-family ranges are rejected
+        for input in ["192.168.1.150-192.168.1.100", "10.0.0.1-fd00::1"] {
+            handle_helper_input_changed(&mut state, input.to_string());
+            handle_helper_add_value(&mut state);
+            assert!(state.rule_form_helper.as_ref().unwrap().error.is_some());
+        }
+        assert_eq!(state.rule_form.as_ref().unwrap().sources.len(), 1);
+    }
+
+    #[test]
+    fn test_helper_adds_interfaces() {
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm::default());
+        handle_open_helper(&mut state, HelperType::Interfaces);
+
+        handle_helper_suggestion_selected(&mut state, "wlan0".to_string());
+        handle_helper_input_changed(&mut state, "eth0".to_string());
+        handle_helper_add_value(&mut state);
+        assert_eq!(
+            state.rule_form.as_ref().unwrap().interfaces,
+            ["wlan0", "eth0"]
+        );
+
+        // Duplicates, invalid names and wildcards in a list are rejected
+        for input in ["wlan0", "eth0; drop", "eth*"] {
+            handle_helper_input_changed(&mut state, input.to_string());
+            handle_helper_add_value(&mut state);
+            assert!(state.rule_form_helper.as_ref().unwrap().error.is_some());
+        }
+
+        state.rule_form.as_mut().unwrap().label = "Media".to_string();
+        let _ = handle_save_rule_form(&mut state);
+        let rule = state.ruleset.rules.last().unwrap();
+        assert_eq!(rule.interfaces, ["wlan0", "eth0"]);
+        assert!(rule.output_interfaces.is_empty());
+    }
+
+    #[test]
+    fn test_icmp_types_validated_against_protocol() {
+        use crate::core::firewall::IcmpType;
+
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm {
+            label: "Ping".to_string(),
+            protocol: Protocol::Icmpv6,
+            icmp_types: vec![IcmpType::TimestampRequest],
+            ..RuleForm::default()
+        });
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.form_errors.as_ref().is_some_and(|e| e.icmp.is_some()));
+
+        // Switching protocol drops types that don't exist for it
+        handle_rule_form_toggle_icmp_type(&mut state, IcmpType::EchoRequest);
+        handle_rule_form_protocol_changed(&mut state, Protocol::Icmpv6);
+        let form = state.rule_form.as_mut().unwrap();
+        assert_eq!(form.icmp_types, [IcmpType::EchoRequest]);
+        form.icmp_code = "0".to_string();
+
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.rule_form.is_none());
+        let rule = state.ruleset.rules.last().unwrap();
+        assert_eq!(rule.icmp_types, [IcmpType::EchoRequest]);
+        assert_eq!(rule.icmp_code, Some(0));
+        assert_eq!(rule.badge_display, "ICMPv6: Echo Request");
+    }
+
+    #[test]
+    fn test_save_scheduled_rule() {
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm {
+            label: "Work hours".to_string(),
+            ports: vec![crate::core::firewall::PortEntry::Single(3389)],
+            schedule_enabled: true,
+            schedule_start: "9:00am".to_string(),
+            schedule_end: "17:30".to_string(),
+            ..RuleForm::default()
+        });
+        handle_rule_form_toggle_schedule_day(&mut state, Weekday::Friday);
+        handle_rule_form_toggle_schedule_day(&mut state, Weekday::Monday);
+
+        // Invalid time keeps the form open with a schedule error
+        let _ = handle_save_rule_form(&mut state);
+        assert!(
+            state
+                .form_errors
+                .as_ref()
+                .is_some_and(|e| e.schedule.is_some())
+        );
+
+        handle_rule_form_schedule_start_changed(&mut state, "09:00".to_string());
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.rule_form.is_none());
+
+        let rule = state.ruleset.rules.last().unwrap();
+        let schedule = rule.schedule.as_ref().unwrap();
+        assert_eq!(schedule.to_string(), "09:00-17:30 Mon,Fri");
+    }
+
+    #[test]
+    fn test_save_byte_rate_limit_round_trip() {
+        use crate::app::RateMeasure;
+        use crate::core::firewall::ByteUnit;
+
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm {
+            label: "Throttle".to_string(),
+            ports: vec![crate::core::firewall::PortEntry::Single(8080)],
+            action: crate::core::firewall::Action::Drop,
+            rate_limit_enabled: true,
+            rate_limit_count: "10".to_string(),
+            ..RuleForm::default()
+        });
+        handle_rule_form_rate_limit_measure_changed(
+            &mut state,
+            RateMeasure(Some(ByteUnit::MBytes)),
+        );
+        handle_rule_form_toggle_rate_limit_over(&mut state, true);
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.rule_form.is_none());
+
+        let rule = state.ruleset.rules.last().unwrap();
+        let rate_limit = rule.rate_limit.unwrap();
+        assert!(rate_limit.over);
+        assert_eq!(rate_limit.bytes, Some(ByteUnit::MBytes));
+        assert_eq!(rate_limit.to_string(), "over 10 MB/second");
+
+        let id = rule.id;
+        handle_edit_rule_clicked(&mut state, id);
+        let form = state.rule_form.as_ref().unwrap();
+        assert!(form.rate_limit_over);
+        assert_eq!(form.rate_limit_measure, RateMeasure(Some(ByteUnit::MBytes)));
+    }
+
+    #[test]
+    fn test_per_source_requires_a_limit() {
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm {
+            label: "SSH".to_string(),
+            ports: vec![crate::core::firewall::PortEntry::Single(22)],
+            ..RuleForm::default()
+        });
+        handle_rule_form_toggle_per_source(&mut state, true);
+        let _ = handle_save_rule_form(&mut state);
+        assert!(
+            state
+                .form_errors
+                .as_ref()
+                .is_some_and(|e| e.per_source.is_some())
+        );
+
+        handle_rule_form_connection_limit_changed(&mut state, "3".to_string());
+        handle_rule_form_per_source_timeout_changed(&mut state, "300".to_string());
+        let _ = handle_save_rule_form(&mut state);
+        assert!(state.rule_form.is_none());
+        let per_source = state.ruleset.rules.last().unwrap().per_source.unwrap();
+        assert_eq!(per_source.size, 65_535);
+        assert_eq!(per_source.timeout_secs, 300);
+    }
+
+    #[test]
+    fn test_country_chips_deduplicated() {
+        use crate::core::geoip::COUNTRIES;
+
+        let mut state = create_test_state();
+        state.rule_form = Some(RuleForm::default());
+        let germany = *COUNTRIES.iter().find(|c| c.code == "DE").unwrap();
+
+        handle_rule_form_country_added(&mut state, germany);
+        handle_rule_form_country_added(&mut state, germany);
+        assert_eq!(state.rule_form.as_ref().unwrap().countries, vec!["DE"]);
+
+        for country in COUNTRIES.iter().take(20) {
+            handle_rule_form_country_added(&mut state, *country);
+        }
+        assert_eq!(
+            state.rule_form.as_ref().unwrap().countries.len(),
+            validators::MAX_COUNTRIES_PER_RULE
+        );
+
+        handle_rule_form_country_removed(&mut state, "DE");
+        assert!(
+            !state
+                .rule_form
+                .as_ref()
+                .unwrap()
+                .countries
+                .contains(&"DE".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rule_metadata_round_trip() {
+        let mut state = create_test_state();
+        handle_add_rule_clicked(&mut state);
+        handle_rule_form_label_changed(&mut state, "Jellyfin".to_string());
+        handle_rule_form_description_edited(
+            &mut state,
+            text_editor::Action::Edit(text_editor::Edit::Paste(Arc::new(
+                "Media server\nRequested by the living room".to_string(),
+            ))),
+        );
+        handle_rule_form_owner_changed(&mut state, "Home IT".to_string());
+        handle_rule_form_reference_changed(&mut state, "not a url".to_string());
+        let _ = handle_save_rule_form(&mut state);
+        assert!(
+            state
+                .form_errors
+                .as_ref()
+                .is_some_and(|e| e.reference.is_some())
+        );
+
+        handle_rule_form_reference_changed(&mut state, "https://example.com/OPS-7".to_string());
+        let _ = handle_save_rule_form(&mut state);
+        let rule = state.ruleset.rules.last().unwrap().clone();
+        assert_eq!(
+            rule.description,
+            "Media server\nRequested by the living room"
+        );
+        assert_eq!(rule.owner, "Home IT");
+        assert_eq!(rule.modified_at, None);
+
+        // Editing keeps the creation time and stamps the modification time
+        handle_edit_rule_clicked(&mut state, rule.id);
+        assert_eq!(state.rule_description.text(), rule.description);
+        let _ = handle_save_rule_form(&mut state);
+        let edited = state.ruleset.rules.last().unwrap();
+        assert_eq!(edited.created_at, rule.created_at);
+        assert!(edited.modified_at.is_some());
+
+        handle_rule_search_changed(&mut state, "living ROOM");
+        assert_eq!(state.cached_filtered_rule_indices.len(), 1);
+    }
+
+    #[test]
+    fn test_rule_form_negation() {
+        let mut state = create_test_state();
+        handle_add_rule_clicked(&mut state);
+        handle_rule_form_label_changed(&mut state, "Not from the LAN".to_string());
+        let form = state.rule_form.as_mut().unwrap();
+        form.ports = vec![crate::core::firewall::PortEntry::Single(22)];
+        form.sources = vec!["0.0.0.0/0".parse().unwrap()];
+        handle_rule_form_toggle_negate(&mut state, MatchField::Sources);
+        handle_rule_form_toggle_negate(&mut state, MatchField::Ports);
+        // Nothing to negate: dropped on save instead of rejected
+        handle_rule_form_toggle_negate(&mut state, MatchField::Interface);
+
+        // Excluding a whole address family is rejected
+        let _ = handle_save_rule_form(&mut state);
+        assert!(
+            state
+                .form_errors
+                .as_ref()
+                .is_some_and(|e| e.source.is_some())
+        );
+
+        state.rule_form.as_mut().unwrap().sources = vec!["10.0.0.0/8".parse().unwrap()];
+        let _ = handle_save_rule_form(&mut state);
+        let rule = state.ruleset.rules.last().unwrap();
+        assert!(rule.negate.sources && rule.negate.ports);
+        assert!(!rule.negate.interface);
+        assert_eq!(rule.port_display, "not 22");
+
+        let id = rule.id;
+        handle_edit_rule_clicked(&mut state, id);
+        assert!(state.rule_form.as_ref().unwrap().negate.sources);
+    }
 }
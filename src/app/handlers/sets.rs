@@ -0,0 +1,337 @@
+//! Named set management
+//!
+//! Handles the set manager modal and sidebar set filter:
+//! - Creating, editing and deleting named address/port sets
+//! - Renaming sets (rule references follow via `EditSetCommand`)
+//! - Filtering the rule list by set usage
+
+use crate::app::{BannerSeverity, Message, SetEditorState, SetManagerState, State};
+use crate::audit;
+use crate::command::{AddSetCommand, DeleteSetCommand, EditSetCommand};
+use crate::core::firewall::{AddressEntry, NamedSet, SetType};
+use crate::validators;
+use iced::Task;
+
+/// Handles opening the set manager modal
+pub(crate) fn handle_open_set_manager(state: &mut State) {
+    state.set_manager = Some(SetManagerState::default());
+}
+
+/// Handles closing the set manager modal
+pub(crate) fn handle_close_set_manager(state: &mut State) {
+    state.set_manager = None;
+}
+
+/// Handles filtering the rule list to rules referencing a set
+pub(crate) fn handle_filter_by_set(state: &mut State, name: Option<String>) {
+    state.filter_set = name;
+    state.update_filter_cache();
+}
+
+/// Handles starting creation of a new set
+pub(crate) fn handle_new_set_clicked(state: &mut State) {
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.deleting_name = None;
+        mgr.editor = Some(SetEditorState::default());
+    }
+}
+
+/// Handles opening an existing set in the editor
+pub(crate) fn handle_edit_set_clicked(state: &mut State, name: &str) {
+    let Some(set) = state.ruleset.find_set(name) else {
+        return;
+    };
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.deleting_name = None;
+        mgr.editor = Some(SetEditorState {
+            original_name: Some(set.name.clone()),
+            name: set.name.clone(),
+            set_type: set.set_type,
+            elements: set.element_strings().join(", "),
+            error: None,
+        });
+    }
+}
+
+pub(crate) fn handle_set_editor_name_changed(state: &mut State, name: String) {
+    if let Some(editor) = state.set_manager.as_mut().and_then(|m| m.editor.as_mut()) {
+        editor.name = name;
+        editor.error = None;
+    }
+}
+
+pub(crate) fn handle_set_editor_type_changed(state: &mut State, set_type: SetType) {
+    if let Some(editor) = state.set_manager.as_mut().and_then(|m| m.editor.as_mut()) {
+        editor.set_type = set_type;
+        editor.error = None;
+    }
+}
+
+pub(crate) fn handle_set_editor_elements_changed(state: &mut State, elements: String) {
+    if let Some(editor) = state.set_manager.as_mut().and_then(|m| m.editor.as_mut()) {
+        editor.elements = elements;
+        editor.error = None;
+    }
+}
+
+/// Handles canceling the set editor
+pub(crate) fn handle_cancel_set_editor(state: &mut State) {
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.editor = None;
+    }
+}
+
+/// Handles saving the set editor (create or edit)
+///
+/// Validation errors are shown inline in the editor; on success the change is
+/// applied through the command history so it can be undone.
+pub(crate) fn handle_save_set_editor(state: &mut State) -> Task<Message> {
+    let Some(editor) = state.set_manager.as_mut().and_then(|m| m.editor.as_mut()) else {
+        tracing::error!(
+            "SaveSetEditor sent without active set editor. \
+             This indicates a UI state management bug."
+        );
+        return Task::none();
+    };
+
+    let new_set = match build_set(editor, &state.ruleset) {
+        Ok(set) => set,
+        Err(e) => {
+            editor.error = Some(e);
+            return Task::none();
+        }
+    };
+
+    let old_set = editor
+        .original_name
+        .as_deref()
+        .and_then(|name| state.ruleset.find_set(name))
+        .cloned();
+    let created = old_set.is_none();
+
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.editor = None;
+    }
+
+    let name = new_set.name.clone();
+    let elements = new_set.element_count();
+    if let Some(old_set) = old_set {
+        if old_set == new_set {
+            return Task::none();
+        }
+        let command = EditSetCommand { old_set, new_set };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+    } else {
+        let command = AddSetCommand { set: new_set };
+        state
+            .command_history
+            .execute(Box::new(command), &mut state.ruleset);
+    }
+
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_set_saved(enable_event_log, &name, elements, created).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles requesting deletion of a set
+pub(crate) fn handle_delete_set_requested(state: &mut State, name: String) {
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.editor = None;
+        mgr.deleting_name = Some(name);
+    }
+}
+
+/// Handles canceling set deletion
+pub(crate) fn handle_cancel_delete_set(state: &mut State) {
+    if let Some(mgr) = &mut state.set_manager {
+        mgr.deleting_name = None;
+    }
+}
+
+/// Handles confirming set deletion
+///
+/// Sets still referenced by rules cannot be deleted: removing them would leave
+/// dangling `@name` lookups that nftables rejects.
+pub(crate) fn handle_confirm_delete_set(state: &mut State) -> Task<Message> {
+    let Some(name) = state
+        .set_manager
+        .as_mut()
+        .and_then(|m| m.deleting_name.take())
+    else {
+        return Task::none();
+    };
+
+    let users = state.ruleset.rules_referencing_set(&name).count();
+    if users > 0 {
+        state.push_banner(
+            format!("Set '{name}' is used by {users} rule(s) and cannot be deleted"),
+            BannerSeverity::Error,
+        );
+        return Task::none();
+    }
+
+    let Some(index) = state.ruleset.sets.iter().position(|s| s.name == name) else {
+        return Task::none();
+    };
+
+    let command = DeleteSetCommand {
+        set: state.ruleset.sets[index].clone(),
+        index,
+    };
+    state
+        .command_history
+        .execute(Box::new(command), &mut state.ruleset);
+    state.mark_profile_dirty();
+
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            audit::log_set_deleted(enable_event_log, &name).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Builds a `NamedSet` from editor input, validating name, type and elements.
+fn build_set(
+    editor: &SetEditorState,
+    ruleset: &crate::core::firewall::FirewallRuleset,
+) -> Result<NamedSet, String> {
+    let name = editor.name.trim();
+    validators::validate_set_name(name).map_err(ToString::to_string)?;
+
+    if editor.original_name.as_deref() != Some(name) && ruleset.find_set(name).is_some() {
+        return Err(format!("A set named '{name}' already exists"));
+    }
+
+    // Changing the type of a referenced set could silently break rules using it
+    if let Some(original) = editor.original_name.as_deref()
+        && let Some(existing) = ruleset.find_set(original)
+        && existing.set_type != editor.set_type
+        && ruleset.rules_referencing_set(original).next().is_some()
+    {
+        return Err("Set is used by rules; its type cannot be changed".to_string());
+    }
+
+    let mut set = NamedSet::new(name, editor.set_type);
+
+    if editor.set_type.is_address() {
+        for part in editor
+            .elements
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|p| !p.is_empty())
+        {
+            let addr = part
+                .parse::<AddressEntry>()
+                .map_err(|e| format!("{part}: {e}"))?;
+            // Hostnames and interface subnets contribute their addresses of the
+            // set's family at apply time
+            let family_ok = addr.is_symbolic()
+                || match editor.set_type {
+                    SetType::Ipv6Addr => addr.is_ipv6(),
+                    _ => addr.is_ipv4(),
+                };
+            if !family_ok {
+                return Err(format!(
+                    "{part} does not belong in a {} set",
+                    editor.set_type.display_name()
+                ));
+            }
+            if !set.addresses.contains(&addr) {
+                set.addresses.push(addr);
+            }
+        }
+    } else {
+        let (entries, errors) = validators::parse_bulk_ports(&editor.elements);
+        if let Some((part, e)) = errors.first() {
+            return Err(format!("{part}: {e}"));
+        }
+        for entry in entries {
+            if !set.ports.contains(&entry) {
+                set.ports.push(entry);
+            }
+        }
+    }
+
+    if set.element_count() > validators::MAX_SET_ELEMENTS {
+        return Err(format!(
+            "Sets are limited to {} elements",
+            validators::MAX_SET_ELEMENTS
+        ));
+    }
+
+    Ok(set)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handlers::test_utils::create_test_state;
+
+    fn open_editor(state: &mut State, name: &str, set_type: SetType, elements: &str) {
+        handle_open_set_manager(state);
+        handle_new_set_clicked(state);
+        handle_set_editor_name_changed(state, name.to_string());
+        handle_set_editor_type_changed(state, set_type);
+        handle_set_editor_elements_changed(state, elements.to_string());
+    }
+
+    #[test]
+    fn test_create_set() {
+        let mut state = create_test_state();
+        open_editor(
+            &mut state,
+            "office",
+            SetType::Ipv4Addr,
+            "10.0.0.0/8, 192.168.1.0/24",
+        );
+        let _ = handle_save_set_editor(&mut state);
+
+        assert_eq!(state.ruleset.sets.len(), 1);
+        assert_eq!(state.ruleset.sets[0].addresses.len(), 2);
+        assert!(state.set_manager.as_ref().unwrap().editor.is_none());
+    }
+
+    #[test]
+    fn test_create_set_rejects_wrong_family() {
+        let mut state = create_test_state();
+        open_editor(&mut state, "office", SetType::Ipv4Addr, "fd00::/8");
+        let _ = handle_save_set_editor(&mut state);
+
+        assert!(state.ruleset.sets.is_empty());
+        let editor = state.set_manager.as_ref().unwrap().editor.as_ref().unwrap();
+        assert!(editor.error.is_some());
+    }
+
+    #[test]
+    fn test_delete_referenced_set_is_blocked() {
+        let mut state = create_test_state();
+        open_editor(&mut state, "web", SetType::InetService, "80, 443");
+        let _ = handle_save_set_editor(&mut state);
+
+        let mut rule = crate::core::test_helpers::create_test_rule("Web", None);
+        rule.port_set = Some("web".to_string());
+        state.ruleset.rules.push(rule);
+
+        handle_delete_set_requested(&mut state, "web".to_string());
+        let _ = handle_confirm_delete_set(&mut state);
+        assert_eq!(state.ruleset.sets.len(), 1);
+
+        state.ruleset.rules.clear();
+        handle_delete_set_requested(&mut state, "web".to_string());
+        let _ = handle_confirm_delete_set(&mut state);
+        assert!(state.ruleset.sets.is_empty());
+    }
+}
@@ -3,6 +3,7 @@
 //! Handles configuration changes:
 //! - Display settings (diff view, zebra striping)
 //! - Security settings (auto-revert, strict ICMP, RPF)
+//! - Internet sharing (router mode)
 //! - Logging settings (event log, dropped packet logging)
 //! - Theme and font selection
 //! - Debounced auto-save
@@ -347,6 +348,64 @@ pub(crate) fn handle_confirm_server_mode(state: &mut State) -> Task<Message> {
     )
 }
 
+/// Handles toggling internet sharing (router mode)
+pub(crate) fn handle_toggle_sharing(state: &mut State, enabled: bool) -> Task<Message> {
+    state.ruleset.advanced_security.sharing.enabled = enabled;
+    state.mark_profile_dirty();
+    let enable_event_log = state.enable_event_log;
+    let desc = if enabled {
+        "Internet sharing enabled"
+    } else {
+        "Internet sharing disabled"
+    };
+    Task::perform(
+        async move {
+            crate::audit::log_settings_saved(enable_event_log, desc).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
+/// Handles upstream (internet) interface change for sharing
+pub(crate) fn handle_sharing_upstream_changed(state: &mut State, name: &str) {
+    if let Err(e) = crate::validators::validate_interface(name) {
+        tracing::warn!("Invalid upstream interface '{name}': {e}");
+        return;
+    }
+    name.clone_into(&mut state.ruleset.advanced_security.sharing.upstream_interface);
+    state.mark_profile_dirty();
+    state.schedule_slider_log(format!("Sharing upstream interface set to '{name}'"));
+}
+
+/// Handles downstream (shared network) interface change for sharing
+pub(crate) fn handle_sharing_downstream_changed(state: &mut State, name: &str) {
+    if let Err(e) = crate::validators::validate_interface(name) {
+        tracing::warn!("Invalid downstream interface '{name}': {e}");
+        return;
+    }
+    name.clone_into(&mut state.ruleset.advanced_security.sharing.downstream_interface);
+    state.mark_profile_dirty();
+    state.schedule_slider_log(format!("Sharing downstream interface set to '{name}'"));
+}
+
+/// Handles toggling DHCP/DNS access for downstream clients
+pub(crate) fn handle_toggle_sharing_dhcp_dns(state: &mut State, enabled: bool) -> Task<Message> {
+    state.ruleset.advanced_security.sharing.allow_dhcp_dns = enabled;
+    state.mark_profile_dirty();
+    let enable_event_log = state.enable_event_log;
+    let desc = if enabled {
+        "Sharing DHCP/DNS access enabled"
+    } else {
+        "Sharing DHCP/DNS access disabled"
+    };
+    Task::perform(
+        async move {
+            crate::audit::log_settings_saved(enable_event_log, desc).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
 /// Handles periodic config save check (debounced)
 pub(crate) fn handle_check_config_save(state: &mut State) -> Task<Message> {
     const DEBOUNCE_MS: u64 = 500;
@@ -403,6 +462,43 @@ pub(crate) fn handle_mono_font_changed(
     )
 }
 
+/// Handles picking the country database with the native file dialog
+pub(crate) fn handle_browse_geoip_database() -> Task<Message> {
+    Task::perform(
+        async {
+            rfd::AsyncFileDialog::new()
+                .add_filter("GeoIP databases", &["mmdb", "csv"])
+                .add_filter("All files", &["*"])
+                .pick_file()
+                .await
+                .map(|file| file.path().to_path_buf())
+        },
+        |path| path.map_or(Message::Noop, |p| Message::GeoIpDatabaseChanged(Some(p))),
+    )
+}
+
+/// Handles setting or clearing the country database path
+///
+/// The database is only read on apply, so a bad file surfaces there.
+pub(crate) fn handle_geoip_database_changed(
+    state: &mut State,
+    path: Option<std::path::PathBuf>,
+) -> Task<Message> {
+    let desc = path.as_ref().map_or_else(
+        || "GeoIP database cleared".to_string(),
+        |p| format!("GeoIP database set to {}", p.display()),
+    );
+    state.geoip_database = path;
+    state.mark_config_dirty();
+    let enable_event_log = state.enable_event_log;
+    Task::perform(
+        async move {
+            crate::audit::log_settings_saved(enable_event_log, &desc).await;
+        },
+        |()| Message::AuditLogWritten,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -416,10 +512,37 @@ mod tests {
         assert!(state.config_dirty);
     }
 
+    #[test]
+    fn test_sharing_interface_validation() {
+        let mut state = create_test_state();
+        let _ = handle_toggle_sharing(&mut state, true);
+        handle_sharing_upstream_changed(&mut state, "wlan0");
+        handle_sharing_downstream_changed(&mut state, "eth0");
+        // Invalid names are ignored, keeping the previous value
+        handle_sharing_downstream_changed(&mut state, "*");
+
+        let sharing = &state.ruleset.advanced_security.sharing;
+        assert_eq!(sharing.upstream_interface, "wlan0");
+        assert_eq!(sharing.downstream_interface, "eth0");
+        assert!(sharing.is_active());
+    }
+
     #[test]
     fn test_handle_toggle_auto_revert() {
         let mut state = create_test_state();
         let _ = handle_toggle_auto_revert(&mut state, true);
         assert!(state.auto_revert_enabled);
     }
+
+    #[test]
+    fn test_handle_geoip_database_changed() {
+        let mut state = create_test_state();
+        let path = std::path::PathBuf::from("/var/lib/GeoIP/country.mmdb");
+        let _ = handle_geoip_database_changed(&mut state, Some(path.clone()));
+        assert_eq!(state.geoip_database, Some(path));
+        assert!(state.config_dirty);
+
+        let _ = handle_geoip_database_changed(&mut state, None);
+        assert!(state.geoip_database.is_none());
+    }
 }
@@ -419,6 +419,21 @@ pub(crate) fn handle_event(state: &mut State, event: iced::Event) -> Task<Messag
                 if state.profile_manager.is_some() {
                     return Task::done(Message::CloseProfileManager);
                 }
+                if state.set_manager.is_some() {
+                    return Task::done(Message::CloseSetManager);
+                }
+                if state.ban_manager.is_some() {
+                    return Task::done(Message::CloseBanManager);
+                }
+                if state.knock_manager.is_some() {
+                    return Task::done(Message::CloseKnockManager);
+                }
+                if state.blocklist_manager.is_some() {
+                    return Task::done(Message::CloseBlocklistManager);
+                }
+                if state.group_manager.is_some() {
+                    return Task::done(Message::CloseGroupManager);
+                }
                 if !state.rule_search.is_empty() {
                     state.rule_search.clear();
                     state.rule_search_lowercase.clear();
@@ -428,10 +443,11 @@ pub(crate) fn handle_event(state: &mut State, event: iced::Event) -> Task<Messag
             iced::keyboard::Key::Named(iced::keyboard::key::Named::F1) => {
                 return Task::done(Message::ToggleShortcutsHelp(true));
             }
-            iced::keyboard::Key::Character("n") if modifiers.command() || modifiers.control() => {
-                if !matches!(state.status, AppStatus::PendingConfirmation { .. }) {
-                    return Task::done(Message::AddRuleClicked);
-                }
+            iced::keyboard::Key::Character("n")
+                if (modifiers.command() || modifiers.control())
+                    && !matches!(state.status, AppStatus::PendingConfirmation { .. }) =>
+            {
+                return Task::done(Message::AddRuleClicked);
             }
             iced::keyboard::Key::Character("s") if modifiers.command() || modifiers.control() => {
                 return Task::done(Message::ApplyClicked);
@@ -440,23 +456,24 @@ pub(crate) fn handle_event(state: &mut State, event: iced::Event) -> Task<Messag
                 return Task::done(Message::ToggleExportModal(true));
             }
             iced::keyboard::Key::Character("z")
-                if (modifiers.command() || modifiers.control()) && !modifiers.shift() =>
+                if (modifiers.command() || modifiers.control())
+                    && !modifiers.shift()
+                    && state.command_history.can_undo() =>
             {
-                if state.command_history.can_undo() {
-                    return Task::done(Message::Undo);
-                }
+                return Task::done(Message::Undo);
             }
             iced::keyboard::Key::Character("z")
-                if (modifiers.command() || modifiers.control()) && modifiers.shift() =>
+                if (modifiers.command() || modifiers.control())
+                    && modifiers.shift()
+                    && state.command_history.can_redo() =>
             {
-                if state.command_history.can_redo() {
-                    return Task::done(Message::Redo);
-                }
+                return Task::done(Message::Redo);
             }
-            iced::keyboard::Key::Character("y") if modifiers.command() || modifiers.control() => {
-                if state.command_history.can_redo() {
-                    return Task::done(Message::Redo);
-                }
+            iced::keyboard::Key::Character("y")
+                if (modifiers.command() || modifiers.control())
+                    && state.command_history.can_redo() =>
+            {
+                return Task::done(Message::Redo);
             }
             _ => {}
         }
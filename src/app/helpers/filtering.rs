@@ -44,7 +44,7 @@ pub fn fuzzy_filter_fonts<'a>(
         .collect();
 
     // Sort by score descending (highest relevance first)
-    results.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    results.sort_unstable_by_key(|b| std::cmp::Reverse(b.1));
     results
 }
 
@@ -92,7 +92,7 @@ pub fn fuzzy_filter_themes(
         .collect();
 
     // Sort by score descending (highest relevance first)
-    results.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    results.sort_unstable_by_key(|b| std::cmp::Reverse(b.1));
     results
 }
 
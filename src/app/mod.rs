@@ -6,18 +6,19 @@ pub mod ui_components;
 mod view;
 
 // Re-export form types
-pub use forms::{FormErrors, HelperType, RuleForm, RuleFormHelper};
+pub use forms::{FormErrors, HelperType, RateMeasure, RuleForm, RuleFormHelper};
 
 use helpers::{
     calculate_max_content_width, calculate_max_content_width_from_refs, fuzzy_filter_fonts,
     fuzzy_filter_themes,
 };
 
-use crate::core::firewall::{FirewallRuleset, Protocol};
+use crate::core::firewall::{FirewallRuleset, Protocol, Rule, SetType};
 use chrono::Utc;
 use iced::widget::Id;
 use iced::widget::operation::focus;
 use iced::{Animation, Element, Task};
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::error;
@@ -50,6 +51,14 @@ impl NotificationBanner {
 pub struct State {
     pub ruleset: FirewallRuleset,
     pub last_applied_ruleset: Option<FirewallRuleset>,
+    /// Addresses of the ruleset's hostnames, as last resolved
+    pub host_addresses: crate::core::hosts::HostAddresses,
+    /// Addresses the hostnames resolved to for the last apply
+    pub last_applied_hosts: Option<crate::core::hosts::HostAddresses>,
+    /// Subnets of the local interfaces, as last read for interface subnet entries
+    pub local_subnets: Vec<crate::core::subnets::LocalSubnet>,
+    /// Interface subnets used for the last apply
+    pub last_applied_subnets: Option<Vec<crate::core::subnets::LocalSubnet>>,
     pub cached_disk_profile: Option<FirewallRuleset>,
     pub status: AppStatus,
     pub banners: std::collections::VecDeque<NotificationBanner>,
@@ -57,7 +66,6 @@ pub struct State {
     pub rule_form: Option<RuleForm>,
     pub rule_form_helper: Option<RuleFormHelper>,
     pub interface_combo: iced::widget::combo_box::State<String>,
-    pub output_interface_combo: iced::widget::combo_box::State<String>,
     pub countdown_remaining: u32,
     pub progress_animation: Animation<f32>,
     pub form_errors: Option<FormErrors>,
@@ -71,6 +79,9 @@ pub struct State {
     /// Cached truncated tag strings for tag cloud display (max 16 chars + ellipsis)
     /// Avoids format!() allocation every frame in sidebar tag cloud
     pub cached_all_tags_truncated: Vec<String>,
+    /// Cached "name (N)" chip labels for the sidebar sets section, where N is the
+    /// number of rules referencing the set (same order as `ruleset.sets`)
+    pub cached_set_chips: Vec<String>,
     pub cached_filtered_rule_indices: Vec<usize>,
     /// Cached "{filtered}/{total}" display string for sidebar header
     /// Avoids format!() allocation every frame
@@ -83,6 +94,8 @@ pub struct State {
     pub auto_revert_timeout_secs: u64,
     pub enable_event_log: bool,
     pub reduced_colors: bool,
+    /// Country database used to resolve rule countries (see `core::geoip`)
+    pub geoip_database: Option<std::path::PathBuf>,
     pub show_diagnostics: bool,
     pub diagnostics_filter: DiagnosticsFilter,
     pub show_export_modal: bool,
@@ -90,10 +103,24 @@ pub struct State {
     pub font_picker: Option<FontPickerState>,
     pub theme_picker: Option<ThemePickerState>,
     pub profile_manager: Option<ProfileManagerState>,
+    pub set_manager: Option<SetManagerState>,
+    pub ban_manager: Option<BanManagerState>,
+    pub knock_manager: Option<KnockManagerState>,
+    pub blocklist_manager: Option<BlocklistManagerState>,
+    pub group_manager: Option<GroupManagerState>,
+    /// Groups shown collapsed in the sidebar (UI only, not saved in the profile)
+    pub collapsed_groups: HashSet<uuid::Uuid>,
+    /// Rules whose sidebar card shows its description, owner and reference
+    pub expanded_rules: HashSet<uuid::Uuid>,
+    /// Multi-line editor for the rule form's description (mirrored into `RuleForm`)
+    pub rule_description: iced::widget::text_editor::Content,
+    pub rule_counters: RuleCountersState,
     pub command_history: crate::command::CommandHistory,
     pub current_theme: crate::theme::ThemeChoice,
     pub theme: crate::theme::AppTheme,
     pub filter_tag: Option<Arc<String>>,
+    /// Restricts the rule list to rules referencing this named set
+    pub filter_set: Option<String>,
     pub dragged_rule_id: Option<uuid::Uuid>,
     pub hovered_drop_target_id: Option<uuid::Uuid>,
     /// Pending hover target for debouncing (id, timestamp)
@@ -188,6 +215,137 @@ pub struct ProfileManagerState {
     pub new_name_input: String,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct SetManagerState {
+    pub editor: Option<SetEditorState>,
+    pub deleting_name: Option<String>,
+}
+
+/// Inline editor for creating or editing a named set
+#[derive(Debug, Clone, Default)]
+pub struct SetEditorState {
+    /// Name of the set being edited (None when creating a new set)
+    pub original_name: Option<String>,
+    pub name: String,
+    pub set_type: SetType,
+    /// Comma-separated elements (addresses or ports depending on `set_type`)
+    pub elements: String,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KnockManagerState {
+    pub editor: Option<KnockEditorState>,
+    pub deleting_name: Option<String>,
+}
+
+/// Inline editor for creating or editing a port-knocking sequence
+#[derive(Debug, Clone)]
+pub struct KnockEditorState {
+    /// Name of the sequence being edited (None when creating a new one)
+    pub original_name: Option<String>,
+    pub name: String,
+    /// Comma-separated knock ports, in order
+    pub sequence: String,
+    pub knock_protocol: Protocol,
+    pub protocol: Protocol,
+    pub port: String,
+    pub timeout: String,
+    pub error: Option<String>,
+}
+
+impl Default for KnockEditorState {
+    fn default() -> Self {
+        Self {
+            original_name: None,
+            name: String::new(),
+            sequence: String::new(),
+            knock_protocol: Protocol::Tcp,
+            protocol: Protocol::Tcp,
+            port: "22".to_string(),
+            timeout: "30".to_string(),
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupManagerState {
+    pub editor: Option<GroupEditorState>,
+    pub deleting_id: Option<uuid::Uuid>,
+}
+
+/// Inline editor for creating or renaming a rule group
+#[derive(Debug, Clone, Default)]
+pub struct GroupEditorState {
+    /// Group being renamed (None when creating a new group)
+    pub original_id: Option<uuid::Uuid>,
+    pub name: String,
+    pub error: Option<String>,
+}
+
+/// Temporary ban manager modal state
+#[derive(Debug, Clone)]
+pub struct BanManagerState {
+    /// Active bans as last loaded from the ban list (None while loading)
+    pub bans: Option<Vec<crate::core::bans::Ban>>,
+    pub ip_input: String,
+    pub duration_input: String,
+    pub error: Option<String>,
+    /// An elevated ban/unban is in flight
+    pub busy: bool,
+}
+
+/// Blocklist manager modal state
+#[derive(Debug, Clone, Default)]
+pub struct BlocklistManagerState {
+    /// Parsed lists as last loaded or refreshed (None while loading)
+    pub lists: Option<Vec<crate::core::blocklists::LoadedBlocklist>>,
+    pub path_input: String,
+    pub error: Option<String>,
+    /// Files are being read
+    pub busy: bool,
+}
+
+/// Live per-rule counters (only polled while the applied ruleset has counters)
+#[derive(Debug, Clone, Default)]
+pub struct RuleCountersState {
+    /// Kernel counters from the last successful read
+    pub values: std::collections::HashMap<uuid::Uuid, crate::core::counters::RuleCounter>,
+    /// Values at the last reset; cards show the difference
+    pub baseline: std::collections::HashMap<uuid::Uuid, crate::core::counters::RuleCounter>,
+    /// Cached sidebar strings (avoids format!() every frame)
+    pub display: std::collections::HashMap<uuid::Uuid, String>,
+    /// A read is in flight
+    pub loading: bool,
+    /// A read failed (e.g. authentication cancelled); polling stops until a
+    /// manual refresh
+    pub paused: bool,
+}
+
+impl RuleCountersState {
+    /// Rebuilds the cached display strings from the values and baseline
+    pub fn update_display(&mut self) {
+        self.display = self
+            .values
+            .iter()
+            .map(|(id, counter)| (*id, counter.since(self.baseline.get(id)).display()))
+            .collect();
+    }
+}
+
+impl Default for BanManagerState {
+    fn default() -> Self {
+        Self {
+            bans: None,
+            ip_input: String::new(),
+            duration_input: "1h".to_string(),
+            error: None,
+            busy: false,
+        }
+    }
+}
+
 /// Warning dialogs shown when enabling potentially disruptive features.
 /// The "Enable" prefix is intentional - these are specifically warnings
 /// about turning ON features that could break connectivity.
@@ -273,20 +431,34 @@ pub enum Message {
     SaveRuleForm,
     RuleFormLabelChanged(String),
     RuleFormProtocolChanged(Protocol),
-    RuleFormInterfaceChanged(String),
     RuleFormChainChanged(crate::core::firewall::Chain),
     RuleFormToggleAdvanced(bool),
     RuleFormActionChanged(crate::core::firewall::Action),
     RuleFormToggleRateLimit(bool),
     RuleFormRateLimitCountChanged(String),
     RuleFormRateLimitUnitChanged(crate::core::firewall::TimeUnit),
+    RuleFormToggleRateLimitOver(bool),
+    RuleFormRateLimitMeasureChanged(RateMeasure),
+    RuleFormToggleQuota(bool),
+    RuleFormQuotaAmountChanged(String),
+    RuleFormQuotaUnitChanged(crate::core::firewall::ByteUnit),
+    RuleFormQuotaModeChanged(crate::core::firewall::QuotaMode),
     RuleFormConnectionLimitChanged(String),
+    RuleFormTogglePerSource(bool),
+    RuleFormPerSourceSizeChanged(String),
+    RuleFormPerSourceTimeoutChanged(String),
+    RuleFormDescriptionEdited(iced::widget::text_editor::Action),
+    RuleFormOwnerChanged(String),
+    RuleFormReferenceChanged(String),
+    ToggleRuleDetails(uuid::Uuid),
     RuleSearchChanged(String),
     ToggleRuleEnabled(uuid::Uuid),
     DeleteRuleRequested(uuid::Uuid),
     CancelDelete,
     DeleteRule(uuid::Uuid),
     ApplyClicked,
+    HostsResolved(crate::core::hosts::HostAddresses),
+    ApplyHostsResolved(crate::core::hosts::HostAddresses),
     VerifyCompleted(Result<crate::core::verify::VerifyResult, String>),
     ProceedToApply,
     ApplyResult(Result<serde_json::Value, String>),
@@ -308,6 +480,8 @@ pub enum Message {
     AutoRevertTimeoutChanged(u64),
     ToggleEventLog(bool),
     ToggleReducedColors(bool),
+    BrowseGeoIpDatabaseClicked,
+    GeoIpDatabaseChanged(Option<std::path::PathBuf>),
     ToggleStrictIcmpRequested(bool),
     ConfirmStrictIcmp,
     IcmpRateLimitChanged(u32),
@@ -322,6 +496,10 @@ pub enum Message {
     LogPrefixChanged(String),
     ServerModeToggled(bool),
     ConfirmServerMode,
+    ToggleSharing(bool),
+    SharingUpstreamChanged(String),
+    SharingDownstreamChanged(String),
+    ToggleSharingDhcpDns(bool),
     ToggleDiagnostics(bool),
     DiagnosticsFilterChanged(DiagnosticsFilter),
     /// Audit log entries loaded asynchronously (Phase 1.1)
@@ -361,12 +539,29 @@ pub enum Message {
     HelperInputChanged(String),
     HelperAddValue,
     HelperRemoveValue(usize),
+    HelperSetSelected(Option<String>),
+    HelperSuggestionSelected(String),
 
     // New rule form fields (backend features from additional_nft.md)
-    RuleFormOutputInterfaceChanged(String),
     RuleFormRejectTypeChanged(crate::core::firewall::RejectType),
     RuleFormRateLimitBurstChanged(String),
     RuleFormLogEnabledToggled(bool),
+    RuleFormTogglePortForward(bool),
+    RuleFormForwardAddressChanged(String),
+    RuleFormForwardPortChanged(String),
+    RuleFormToggleIcmpType(crate::core::firewall::IcmpType),
+    RuleFormIcmpCodeChanged(String),
+    RuleFormToggleCtState(crate::core::firewall::CtState),
+    RuleFormCountryAdded(crate::core::geoip::Country),
+    RuleFormCountryRemoved(String),
+    RuleFormToggleSchedule(bool),
+    RuleFormScheduleStartChanged(String),
+    RuleFormScheduleEndChanged(String),
+    RuleFormToggleScheduleDay(crate::core::firewall::Weekday),
+    RuleFormToggleNegate(crate::core::firewall::MatchField),
+    RuleFormToggleExpiry(bool),
+    RuleFormExpiryChanged(String),
+    RemoveExpiredRules,
 
     // Profile messages
     ProfileSelected(String),
@@ -392,6 +587,98 @@ pub enum Message {
     CancelProfileSwitch,
     ProfileSwitchAfterSave(String),
     ProfileListUpdated(Vec<String>),
+
+    // Named set messages
+    OpenSetManager,
+    CloseSetManager,
+    FilterBySet(Option<String>),
+    NewSetClicked,
+    EditSetClicked(String),
+    SetEditorNameChanged(String),
+    SetEditorTypeChanged(SetType),
+    SetEditorElementsChanged(String),
+    SaveSetEditor,
+    CancelSetEditor,
+    DeleteSetRequested(String),
+    ConfirmDeleteSet,
+    CancelDeleteSet,
+
+    // Port knocking messages
+    OpenKnockManager,
+    CloseKnockManager,
+    NewKnockClicked,
+    EditKnockClicked(String),
+    KnockEditorNameChanged(String),
+    KnockEditorSequenceChanged(String),
+    KnockEditorKnockProtocolChanged(Protocol),
+    KnockEditorProtocolChanged(Protocol),
+    KnockEditorPortChanged(String),
+    KnockEditorTimeoutChanged(String),
+    SaveKnockEditor,
+    CancelKnockEditor,
+    DeleteKnockRequested(String),
+    ConfirmDeleteKnock,
+    CancelDeleteKnock,
+
+    // Rule group messages
+    OpenGroupManager,
+    CloseGroupManager,
+    NewGroupClicked,
+    EditGroupClicked(uuid::Uuid),
+    GroupEditorNameChanged(String),
+    SaveGroupEditor,
+    CancelGroupEditor,
+    DeleteGroupRequested(uuid::Uuid),
+    ConfirmDeleteGroup,
+    CancelDeleteGroup,
+    ToggleGroup(uuid::Uuid),
+    ToggleGroupCollapsed(uuid::Uuid),
+    MoveGroupUp(uuid::Uuid),
+    MoveGroupDown(uuid::Uuid),
+    RuleDroppedOnGroup(uuid::Uuid),
+
+    // Blocklist messages
+    OpenBlocklistManager,
+    CloseBlocklistManager,
+    BlocklistsLoaded(Result<Vec<crate::core::blocklists::LoadedBlocklist>, String>),
+    BlocklistPathChanged(String),
+    BrowseBlocklistClicked,
+    AddBlocklistSubmitted,
+    AddBlocklistPath(std::path::PathBuf),
+    BlocklistAdded(
+        Result<
+            (
+                std::path::PathBuf,
+                Vec<crate::core::blocklists::LoadedBlocklist>,
+            ),
+            String,
+        >,
+    ),
+    RemoveBlocklistClicked(std::path::PathBuf),
+    RefreshBlocklistsClicked,
+    BlocklistsRefreshed(Result<Vec<crate::core::blocklists::LoadedBlocklist>, String>),
+
+    // Temporary ban messages
+    OpenBanManager,
+    CloseBanManager,
+    BansLoaded(Result<Vec<crate::core::bans::Ban>, String>),
+    BanIpChanged(String),
+    BanDurationChanged(String),
+    BanSubmitted,
+    BanCompleted(Result<crate::core::bans::Ban, String>),
+    UnbanClicked(std::net::IpAddr),
+    UnbanCompleted(Result<std::net::IpAddr, String>),
+
+    // Rule counter messages
+    ToggleRuleCounters(bool),
+    /// Periodic tick to re-read the kernel counters (skipped while paused)
+    RuleCountersTick,
+    /// Re-read the kernel counters now (also resumes paused polling)
+    RefreshRuleCounters,
+    RuleCountersLoaded(
+        Result<std::collections::HashMap<uuid::Uuid, crate::core::counters::RuleCounter>, String>,
+    ),
+    ResetRuleCounters,
     /// Periodic tick to prune expired banners
     PruneBanners,
     /// Dismiss a specific banner (click to dismiss)
@@ -427,6 +714,7 @@ impl State {
         let auto_revert_timeout_secs = config.auto_revert_timeout_secs.min(3600);
         let enable_event_log = config.enable_event_log;
         let reduced_colors = config.reduced_colors;
+        let geoip_database = config.geoip_database;
         let active_profile_name = config.active_profile;
 
         regular_font_choice.resolve(false);
@@ -461,6 +749,10 @@ impl State {
 
         let mut state = Self {
             last_applied_ruleset: Some(ruleset.clone()),
+            host_addresses: crate::core::hosts::HostAddresses::new(),
+            last_applied_hosts: None,
+            local_subnets: Vec::new(),
+            last_applied_subnets: None,
             cached_disk_profile: Some(ruleset.clone()),
             ruleset,
             status: AppStatus::Idle,
@@ -471,9 +763,6 @@ impl State {
             interface_combo: iced::widget::combo_box::State::new(
                 crate::utils::build_interface_suggestions(),
             ),
-            output_interface_combo: iced::widget::combo_box::State::new(
-                crate::utils::build_interface_suggestions(),
-            ),
             countdown_remaining: 15,
             progress_animation: Animation::new(1.0),
             form_errors: None,
@@ -485,6 +774,7 @@ impl State {
             rule_search_lowercase: String::new(),
             cached_all_tags: Vec::new(),
             cached_all_tags_truncated: Vec::new(),
+            cached_set_chips: Vec::new(),
             cached_filtered_rule_indices: Vec::new(),
             filter_count_display: String::new(),
             deleting_id: None,
@@ -495,6 +785,7 @@ impl State {
             auto_revert_timeout_secs,
             enable_event_log,
             reduced_colors,
+            geoip_database,
             show_diagnostics: false,
             diagnostics_filter: DiagnosticsFilter::default(),
             show_export_modal: false,
@@ -502,10 +793,20 @@ impl State {
             font_picker: None,
             theme_picker: None,
             profile_manager: None,
+            set_manager: None,
+            ban_manager: None,
+            knock_manager: None,
+            blocklist_manager: None,
+            group_manager: None,
+            collapsed_groups: HashSet::new(),
+            expanded_rules: HashSet::new(),
+            rule_description: iced::widget::text_editor::Content::new(),
+            rule_counters: RuleCountersState::default(),
             command_history: crate::command::CommandHistory::default(),
             current_theme,
             theme,
             filter_tag: None,
+            filter_set: None,
             dragged_rule_id: None,
             hovered_drop_target_id: None,
             hover_pending: None,
@@ -529,7 +830,10 @@ impl State {
         // Initialize all caches properly via centralized logic
         state.update_cached_text();
 
-        (state, Task::none())
+        // Expand hostnames and interface subnets so the preview shows their addresses
+        let task = state.refresh_symbolic_addresses();
+
+        (state, task)
     }
 
     /// Creates a State instance for testing without filesystem access.
@@ -556,6 +860,10 @@ impl State {
 
         let mut state = Self {
             last_applied_ruleset: Some(ruleset.clone()),
+            host_addresses: crate::core::hosts::HostAddresses::new(),
+            last_applied_hosts: None,
+            local_subnets: Vec::new(),
+            last_applied_subnets: None,
             cached_disk_profile: Some(ruleset.clone()),
             ruleset,
             status: AppStatus::Idle,
@@ -564,7 +872,6 @@ impl State {
             rule_form: None,
             rule_form_helper: None,
             interface_combo: iced::widget::combo_box::State::new(Vec::new()),
-            output_interface_combo: iced::widget::combo_box::State::new(Vec::new()),
             countdown_remaining: 15,
             progress_animation: Animation::new(1.0),
             form_errors: None,
@@ -576,6 +883,7 @@ impl State {
             rule_search_lowercase: String::new(),
             cached_all_tags: Vec::new(),
             cached_all_tags_truncated: Vec::new(),
+            cached_set_chips: Vec::new(),
             cached_filtered_rule_indices: Vec::new(),
             filter_count_display: String::new(),
             deleting_id: None,
@@ -586,6 +894,7 @@ impl State {
             auto_revert_timeout_secs: 15,
             enable_event_log: false,
             reduced_colors: false,
+            geoip_database: None,
             show_diagnostics: false,
             diagnostics_filter: DiagnosticsFilter::default(),
             show_export_modal: false,
@@ -593,10 +902,20 @@ impl State {
             font_picker: None,
             theme_picker: None,
             profile_manager: None,
+            set_manager: None,
+            ban_manager: None,
+            knock_manager: None,
+            blocklist_manager: None,
+            group_manager: None,
+            collapsed_groups: HashSet::new(),
+            expanded_rules: HashSet::new(),
+            rule_description: iced::widget::text_editor::Content::new(),
+            rule_counters: RuleCountersState::default(),
             command_history: crate::command::CommandHistory::default(),
             current_theme,
             theme,
             filter_tag: None,
+            filter_set: None,
             dragged_rule_id: None,
             hovered_drop_target_id: None,
             hover_pending: None,
@@ -654,15 +973,65 @@ impl State {
         self.banners.retain(|banner| !banner.is_expired());
     }
 
+    /// Returns the ruleset with hostnames and interface subnets replaced by their
+    /// last resolved addresses, as used for the preview, verify and apply
+    pub fn expanded_ruleset(&self) -> std::borrow::Cow<'_, FirewallRuleset> {
+        crate::core::subnets::expanded_ruleset(
+            &self.ruleset,
+            &self.local_subnets,
+            &self.host_addresses,
+        )
+    }
+
+    /// Re-reads the local interface subnets (if the ruleset uses them)
+    pub(crate) fn refresh_local_subnets(&mut self) {
+        if crate::core::subnets::uses_subnets(&self.ruleset) {
+            self.local_subnets = crate::core::subnets::local_subnets();
+        }
+    }
+
+    /// Re-reads the interface subnets and resolves the hostnames not looked up yet
+    /// in the background, so the preview shows their addresses before the next apply
+    pub(crate) fn refresh_symbolic_addresses(&mut self) -> Task<Message> {
+        let subnets = self.local_subnets.clone();
+        self.refresh_local_subnets();
+        if self.local_subnets != subnets {
+            self.update_cached_text();
+        }
+
+        let names: std::collections::BTreeSet<String> =
+            crate::core::hosts::hostnames(&self.ruleset)
+                .into_iter()
+                .filter(|name| !self.host_addresses.contains_key(name))
+                .collect();
+        if names.is_empty() {
+            return Task::none();
+        }
+        Task::perform(
+            crate::core::hosts::resolve_all(names),
+            Message::HostsResolved,
+        )
+    }
+
     fn update_cached_text(&mut self) {
         use std::collections::BTreeSet;
 
-        let nft_text = self.ruleset.to_nft_text();
+        let nft_text = self.expanded_ruleset().to_nft_text();
 
         self.cached_nft_tokens = syntax_cache::tokenize_nft(&nft_text);
 
         self.cached_diff_tokens = if let Some(ref last) = self.last_applied_ruleset {
-            let old_text = last.to_nft_text();
+            // Without a recorded apply, compare against the current addresses
+            let last_hosts = self
+                .last_applied_hosts
+                .as_ref()
+                .unwrap_or(&self.host_addresses);
+            let last_subnets = self
+                .last_applied_subnets
+                .as_deref()
+                .unwrap_or(&self.local_subnets);
+            let old_text = crate::core::subnets::expanded_ruleset(last, last_subnets, last_hosts)
+                .to_nft_text();
             syntax_cache::compute_and_tokenize_diff(&old_text, &nft_text)
         } else {
             None
@@ -690,15 +1059,26 @@ impl State {
         self.cached_all_tags = all_tags.iter().map(|s| Arc::new((*s).clone())).collect();
         self.cached_all_tags_truncated = all_tags
             .into_iter()
-            .map(|s| {
-                if s.len() > 16 {
-                    format!("{}…", &s[..15])
-                } else {
-                    s.clone()
-                }
+            .map(|s| Rule::truncate_tag(s))
+            .collect();
+
+        self.cached_set_chips = self
+            .ruleset
+            .sets
+            .iter()
+            .map(|set| {
+                let users = self.ruleset.rules_referencing_set(&set.name).count();
+                format!("{} ({users})", set.name)
             })
             .collect();
 
+        // Reset set filter if the selected set was deleted or renamed
+        if let Some(ref current_set) = self.filter_set
+            && self.ruleset.find_set(current_set).is_none()
+        {
+            self.filter_set = None;
+        }
+
         // Reset tag filter if the currently selected tag no longer exists
         if let Some(ref current_filter) = self.filter_tag
             && !self
@@ -737,6 +1117,12 @@ impl State {
                         return false;
                     }
 
+                    if let Some(ref filter_set) = self.filter_set
+                        && !r.references_set(filter_set)
+                    {
+                        return false;
+                    }
+
                     if self.rule_search.is_empty() {
                         return true;
                     }
@@ -744,6 +1130,7 @@ impl State {
                     let search_term = self.rule_search_lowercase.as_str();
                     r.label_lowercase.contains(search_term)
                         || r.protocol_lowercase.contains(search_term)
+                        || r.metadata_lowercase.contains(search_term)
                         || r.interface_lowercase
                             .as_ref()
                             .is_some_and(|i| i.contains(search_term))
@@ -786,6 +1173,10 @@ impl State {
     pub fn is_dirty(&self) -> bool {
         self.last_applied_ruleset.as_ref().is_none_or(|last| {
             last.rules != self.ruleset.rules
+                || last.sets != self.ruleset.sets
+                || last.knocks != self.ruleset.knocks
+                || last.blocklists != self.ruleset.blocklists
+                || last.groups != self.ruleset.groups
                 || last.advanced_security != self.ruleset.advanced_security
         })
     }
@@ -793,10 +1184,21 @@ impl State {
     pub fn is_profile_dirty(&self) -> bool {
         self.cached_disk_profile.as_ref().is_some_and(|disk| {
             disk.rules != self.ruleset.rules
+                || disk.sets != self.ruleset.sets
+                || disk.knocks != self.ruleset.knocks
+                || disk.blocklists != self.ruleset.blocklists
+                || disk.groups != self.ruleset.groups
                 || disk.advanced_security != self.ruleset.advanced_security
         })
     }
 
+    /// Returns true if the applied ruleset carries per-rule counters worth polling
+    pub fn counters_active(&self) -> bool {
+        self.last_applied_ruleset
+            .as_ref()
+            .is_some_and(|r| r.advanced_security.rule_counters)
+    }
+
     /// Returns true if any operation is in progress that should block new operations
     pub fn is_busy(&self) -> bool {
         matches!(
@@ -822,6 +1224,7 @@ impl State {
             auto_revert_timeout_secs: self.auto_revert_timeout_secs,
             enable_event_log: self.enable_event_log,
             reduced_colors: self.reduced_colors,
+            geoip_database: self.geoip_database.clone(),
         };
 
         Task::perform(
@@ -867,14 +1270,14 @@ impl State {
             Message::AddRuleClicked => handlers::handle_add_rule_clicked(self),
             Message::EditRuleClicked(id) => handlers::handle_edit_rule_clicked(self, id),
             Message::CancelRuleForm => handlers::handle_cancel_rule_form(self),
-            Message::SaveRuleForm => return handlers::handle_save_rule_form(self),
+            Message::SaveRuleForm => {
+                let task = handlers::handle_save_rule_form(self);
+                return Task::batch([task, self.refresh_symbolic_addresses()]);
+            }
             Message::RuleFormLabelChanged(s) => handlers::handle_rule_form_label_changed(self, s),
             Message::RuleFormProtocolChanged(p) => {
                 handlers::handle_rule_form_protocol_changed(self, p);
             }
-            Message::RuleFormInterfaceChanged(s) => {
-                handlers::handle_rule_form_interface_changed(self, s);
-            }
             Message::RuleFormChainChanged(chain) => {
                 handlers::handle_rule_form_chain_changed(self, chain);
             }
@@ -893,9 +1296,44 @@ impl State {
             Message::RuleFormRateLimitUnitChanged(unit) => {
                 handlers::handle_rule_form_rate_limit_unit_changed(self, unit);
             }
+            Message::RuleFormToggleRateLimitOver(enabled) => {
+                handlers::handle_rule_form_toggle_rate_limit_over(self, enabled);
+            }
+            Message::RuleFormRateLimitMeasureChanged(measure) => {
+                handlers::handle_rule_form_rate_limit_measure_changed(self, measure);
+            }
+            Message::RuleFormToggleQuota(enabled) => {
+                handlers::handle_rule_form_toggle_quota(self, enabled);
+            }
+            Message::RuleFormQuotaAmountChanged(s) => {
+                handlers::handle_rule_form_quota_amount_changed(self, s);
+            }
+            Message::RuleFormQuotaUnitChanged(unit) => {
+                handlers::handle_rule_form_quota_unit_changed(self, unit);
+            }
+            Message::RuleFormQuotaModeChanged(mode) => {
+                handlers::handle_rule_form_quota_mode_changed(self, mode);
+            }
             Message::RuleFormConnectionLimitChanged(s) => {
                 handlers::handle_rule_form_connection_limit_changed(self, s);
             }
+            Message::RuleFormTogglePerSource(enabled) => {
+                handlers::handle_rule_form_toggle_per_source(self, enabled);
+            }
+            Message::RuleFormPerSourceSizeChanged(s) => {
+                handlers::handle_rule_form_per_source_size_changed(self, s);
+            }
+            Message::RuleFormPerSourceTimeoutChanged(s) => {
+                handlers::handle_rule_form_per_source_timeout_changed(self, s);
+            }
+            Message::RuleFormDescriptionEdited(action) => {
+                handlers::handle_rule_form_description_edited(self, action);
+            }
+            Message::RuleFormOwnerChanged(s) => handlers::handle_rule_form_owner_changed(self, s),
+            Message::RuleFormReferenceChanged(s) => {
+                handlers::handle_rule_form_reference_changed(self, s);
+            }
+            Message::ToggleRuleDetails(id) => handlers::handle_toggle_rule_details(self, id),
             Message::RuleSearchChanged(s) => handlers::handle_rule_search_changed(self, &s),
             Message::ToggleRuleEnabled(id) => return handlers::handle_toggle_rule(self, id),
             Message::DeleteRuleRequested(id) => handlers::handle_delete_rule_requested(self, id),
@@ -904,6 +1342,10 @@ impl State {
 
             // Apply domain
             Message::ApplyClicked => return handlers::handle_apply_clicked(self),
+            Message::HostsResolved(addresses) => handlers::handle_hosts_resolved(self, addresses),
+            Message::ApplyHostsResolved(addresses) => {
+                return handlers::handle_apply_hosts_resolved(self, addresses);
+            }
             Message::VerifyCompleted(result) => {
                 return handlers::handle_verify_completed(self, result);
             }
@@ -961,6 +1403,10 @@ impl State {
             Message::ToggleReducedColors(enabled) => {
                 return handlers::handle_toggle_reduced_colors(self, enabled);
             }
+            Message::BrowseGeoIpDatabaseClicked => return handlers::handle_browse_geoip_database(),
+            Message::GeoIpDatabaseChanged(path) => {
+                return handlers::handle_geoip_database_changed(self, path);
+            }
             Message::ToggleStrictIcmpRequested(enabled) => {
                 return handlers::handle_toggle_strict_icmp_requested(self, enabled);
             }
@@ -986,6 +1432,18 @@ impl State {
                 return handlers::handle_server_mode_toggled(self, enabled);
             }
             Message::ConfirmServerMode => return handlers::handle_confirm_server_mode(self),
+            Message::ToggleSharing(enabled) => {
+                return handlers::handle_toggle_sharing(self, enabled);
+            }
+            Message::SharingUpstreamChanged(name) => {
+                handlers::handle_sharing_upstream_changed(self, &name);
+            }
+            Message::SharingDownstreamChanged(name) => {
+                handlers::handle_sharing_downstream_changed(self, &name);
+            }
+            Message::ToggleSharingDhcpDns(enabled) => {
+                return handlers::handle_toggle_sharing_dhcp_dns(self, enabled);
+            }
             Message::ToggleDiagnostics(show) => {
                 return handlers::handle_toggle_diagnostics(self, show);
             }
@@ -1042,11 +1500,12 @@ impl State {
             Message::HelperInputChanged(s) => handlers::handle_helper_input_changed(self, s),
             Message::HelperAddValue => handlers::handle_helper_add_value(self),
             Message::HelperRemoveValue(index) => handlers::handle_helper_remove_value(self, index),
+            Message::HelperSetSelected(name) => handlers::handle_helper_set_selected(self, name),
+            Message::HelperSuggestionSelected(value) => {
+                handlers::handle_helper_suggestion_selected(self, value);
+            }
 
             // New rule form field messages
-            Message::RuleFormOutputInterfaceChanged(s) => {
-                handlers::handle_rule_form_output_interface_changed(self, s);
-            }
             Message::RuleFormRejectTypeChanged(reject_type) => {
                 handlers::handle_rule_form_reject_type_changed(self, reject_type);
             }
@@ -1056,6 +1515,50 @@ impl State {
             Message::RuleFormLogEnabledToggled(enabled) => {
                 handlers::handle_rule_form_log_enabled_toggled(self, enabled);
             }
+            Message::RuleFormTogglePortForward(enabled) => {
+                handlers::handle_rule_form_toggle_port_forward(self, enabled);
+            }
+            Message::RuleFormForwardAddressChanged(s) => {
+                handlers::handle_rule_form_forward_address_changed(self, s);
+            }
+            Message::RuleFormForwardPortChanged(s) => {
+                handlers::handle_rule_form_forward_port_changed(self, s);
+            }
+            Message::RuleFormToggleIcmpType(icmp_type) => {
+                handlers::handle_rule_form_toggle_icmp_type(self, icmp_type);
+            }
+            Message::RuleFormIcmpCodeChanged(s) => {
+                handlers::handle_rule_form_icmp_code_changed(self, s);
+            }
+            Message::RuleFormToggleCtState(ct_state) => {
+                handlers::handle_rule_form_toggle_ct_state(self, ct_state);
+            }
+            Message::RuleFormCountryAdded(country) => {
+                handlers::handle_rule_form_country_added(self, country);
+            }
+            Message::RuleFormCountryRemoved(code) => {
+                handlers::handle_rule_form_country_removed(self, &code);
+            }
+            Message::RuleFormToggleSchedule(enabled) => {
+                handlers::handle_rule_form_toggle_schedule(self, enabled);
+            }
+            Message::RuleFormScheduleStartChanged(s) => {
+                handlers::handle_rule_form_schedule_start_changed(self, s);
+            }
+            Message::RuleFormScheduleEndChanged(s) => {
+                handlers::handle_rule_form_schedule_end_changed(self, s);
+            }
+            Message::RuleFormToggleScheduleDay(day) => {
+                handlers::handle_rule_form_toggle_schedule_day(self, day);
+            }
+            Message::RuleFormToggleNegate(field) => {
+                handlers::handle_rule_form_toggle_negate(self, field);
+            }
+            Message::RuleFormToggleExpiry(enabled) => {
+                handlers::handle_rule_form_toggle_expiry(self, enabled);
+            }
+            Message::RuleFormExpiryChanged(s) => handlers::handle_rule_form_expiry_changed(self, s),
+            Message::RemoveExpiredRules => return handlers::handle_remove_expired_rules(self),
 
             Message::ProfileSelected(name) => return handlers::handle_profile_selected(self, name),
             Message::ProfileSwitched(name, ruleset) => {
@@ -1098,6 +1601,130 @@ impl State {
             Message::ProfileSwitchAfterSave(name) => {
                 return handlers::handle_profile_switch_after_save(self, name);
             }
+
+            // Named sets domain
+            Message::OpenSetManager => handlers::handle_open_set_manager(self),
+            Message::CloseSetManager => handlers::handle_close_set_manager(self),
+            Message::FilterBySet(name) => handlers::handle_filter_by_set(self, name),
+            Message::NewSetClicked => handlers::handle_new_set_clicked(self),
+            Message::EditSetClicked(name) => handlers::handle_edit_set_clicked(self, &name),
+            Message::SetEditorNameChanged(name) => {
+                handlers::handle_set_editor_name_changed(self, name);
+            }
+            Message::SetEditorTypeChanged(set_type) => {
+                handlers::handle_set_editor_type_changed(self, set_type);
+            }
+            Message::SetEditorElementsChanged(elements) => {
+                handlers::handle_set_editor_elements_changed(self, elements);
+            }
+            Message::SaveSetEditor => {
+                let task = handlers::handle_save_set_editor(self);
+                return Task::batch([task, self.refresh_symbolic_addresses()]);
+            }
+            Message::CancelSetEditor => handlers::handle_cancel_set_editor(self),
+            Message::DeleteSetRequested(name) => handlers::handle_delete_set_requested(self, name),
+            Message::ConfirmDeleteSet => return handlers::handle_confirm_delete_set(self),
+            Message::CancelDeleteSet => handlers::handle_cancel_delete_set(self),
+
+            // Port knocking domain
+            Message::OpenKnockManager => handlers::handle_open_knock_manager(self),
+            Message::CloseKnockManager => handlers::handle_close_knock_manager(self),
+            Message::NewKnockClicked => handlers::handle_new_knock_clicked(self),
+            Message::EditKnockClicked(name) => handlers::handle_edit_knock_clicked(self, &name),
+            Message::KnockEditorNameChanged(name) => {
+                handlers::handle_knock_editor_name_changed(self, name);
+            }
+            Message::KnockEditorSequenceChanged(sequence) => {
+                handlers::handle_knock_editor_sequence_changed(self, sequence);
+            }
+            Message::KnockEditorKnockProtocolChanged(protocol) => {
+                handlers::handle_knock_editor_knock_protocol_changed(self, protocol);
+            }
+            Message::KnockEditorProtocolChanged(protocol) => {
+                handlers::handle_knock_editor_protocol_changed(self, protocol);
+            }
+            Message::KnockEditorPortChanged(port) => {
+                handlers::handle_knock_editor_port_changed(self, port);
+            }
+            Message::KnockEditorTimeoutChanged(timeout) => {
+                handlers::handle_knock_editor_timeout_changed(self, timeout);
+            }
+            Message::SaveKnockEditor => return handlers::handle_save_knock_editor(self),
+            Message::CancelKnockEditor => handlers::handle_cancel_knock_editor(self),
+            Message::DeleteKnockRequested(name) => {
+                handlers::handle_delete_knock_requested(self, name);
+            }
+            Message::ConfirmDeleteKnock => return handlers::handle_confirm_delete_knock(self),
+            Message::CancelDeleteKnock => handlers::handle_cancel_delete_knock(self),
+
+            // Blocklists domain
+            // Rule group domain
+            Message::OpenGroupManager => handlers::handle_open_group_manager(self),
+            Message::CloseGroupManager => handlers::handle_close_group_manager(self),
+            Message::NewGroupClicked => handlers::handle_new_group_clicked(self),
+            Message::EditGroupClicked(id) => handlers::handle_edit_group_clicked(self, id),
+            Message::GroupEditorNameChanged(name) => {
+                handlers::handle_group_editor_name_changed(self, name);
+            }
+            Message::SaveGroupEditor => return handlers::handle_save_group_editor(self),
+            Message::CancelGroupEditor => handlers::handle_cancel_group_editor(self),
+            Message::DeleteGroupRequested(id) => handlers::handle_delete_group_requested(self, id),
+            Message::ConfirmDeleteGroup => return handlers::handle_confirm_delete_group(self),
+            Message::CancelDeleteGroup => handlers::handle_cancel_delete_group(self),
+            Message::ToggleGroup(id) => return handlers::handle_toggle_group(self, id),
+            Message::ToggleGroupCollapsed(id) => handlers::handle_toggle_group_collapsed(self, id),
+            Message::MoveGroupUp(id) => return handlers::handle_move_group(self, id, true),
+            Message::MoveGroupDown(id) => return handlers::handle_move_group(self, id, false),
+            Message::RuleDroppedOnGroup(id) => {
+                return handlers::handle_rule_dropped_on_group(self, id);
+            }
+
+            Message::OpenBlocklistManager => return handlers::handle_open_blocklist_manager(self),
+            Message::CloseBlocklistManager => handlers::handle_close_blocklist_manager(self),
+            Message::BlocklistsLoaded(result) => handlers::handle_blocklists_loaded(self, result),
+            Message::BlocklistPathChanged(path) => {
+                handlers::handle_blocklist_path_changed(self, path);
+            }
+            Message::BrowseBlocklistClicked => return handlers::handle_browse_blocklist(),
+            Message::AddBlocklistSubmitted => {
+                return handlers::handle_add_blocklist_submitted(self);
+            }
+            Message::AddBlocklistPath(path) => return handlers::handle_add_blocklist(self, path),
+            Message::BlocklistAdded(result) => handlers::handle_blocklist_added(self, result),
+            Message::RemoveBlocklistClicked(path) => {
+                handlers::handle_remove_blocklist_clicked(self, &path);
+            }
+            Message::RefreshBlocklistsClicked => return handlers::handle_refresh_blocklists(self),
+            Message::BlocklistsRefreshed(result) => {
+                handlers::handle_blocklists_refreshed(self, result);
+            }
+
+            // Temporary bans domain
+            Message::OpenBanManager => return handlers::handle_open_ban_manager(self),
+            Message::CloseBanManager => handlers::handle_close_ban_manager(self),
+            Message::BansLoaded(result) => handlers::handle_bans_loaded(self, result),
+            Message::BanIpChanged(ip) => handlers::handle_ban_ip_changed(self, ip),
+            Message::BanDurationChanged(duration) => {
+                handlers::handle_ban_duration_changed(self, duration);
+            }
+            Message::BanSubmitted => return handlers::handle_ban_submitted(self),
+            Message::BanCompleted(result) => return handlers::handle_ban_completed(self, result),
+            Message::UnbanClicked(ip) => return handlers::handle_unban_clicked(self, ip),
+            Message::UnbanCompleted(result) => {
+                return handlers::handle_unban_completed(self, result);
+            }
+
+            // Rule counters domain
+            Message::ToggleRuleCounters(enabled) => {
+                return handlers::handle_toggle_rule_counters(self, enabled);
+            }
+            Message::RuleCountersTick => return handlers::handle_rule_counters_tick(self),
+            Message::RefreshRuleCounters => return handlers::handle_refresh_rule_counters(self),
+            Message::RuleCountersLoaded(result) => {
+                handlers::handle_rule_counters_loaded(self, result);
+            }
+            Message::ResetRuleCounters => handlers::handle_reset_rule_counters(self),
+
             Message::PruneBanners => handlers::handle_prune_banners(self),
             Message::DismissBanner(index) => handlers::handle_dismiss_banner(self, index),
             Message::CheckConfigSave => return handlers::handle_check_config_save(self),
@@ -1163,6 +1790,12 @@ impl State {
             } else {
                 iced::Subscription::none()
             },
+            // Per-rule counter polling (each read is an elevated nft call)
+            if self.counters_active() && !self.rule_counters.paused {
+                iced::time::every(Duration::from_secs(5)).map(|_| Message::RuleCountersTick)
+            } else {
+                iced::Subscription::none()
+            },
             // Audit log auto-refresh when diagnostics modal is open
             if self.show_diagnostics {
                 iced::time::every(Duration::from_millis(100)).map(|_| Message::CheckAuditLogRefresh)
@@ -243,6 +243,8 @@ enum NftToken {
     Comma,
     #[token(".")]
     Period,
+    #[token("!=")]
+    NotEqual,
     #[token("<=")]
     LessEqual,
     #[token("<")]
@@ -442,6 +444,15 @@ fn parse_nft_tokens(line: &str) -> Vec<Token> {
                     italic: false,
                 });
             }
+            // Negated matches - bold so an inverted condition stands out
+            NotEqual => {
+                tokens.push(Token {
+                    text: Cow::Borrowed("!="),
+                    color: TokenColor::Keyword,
+                    bold: true,
+                    italic: false,
+                });
+            }
             LessEqual => {
                 tokens.push(Token {
                     text: Cow::Borrowed("<="),
@@ -1,6 +1,7 @@
 use crate::theme::AppTheme;
 use iced::widget::{
-    button, checkbox, container, pick_list, rule, scrollable, slider, text_input, toggler,
+    button, checkbox, container, pick_list, rule, scrollable, slider, text_editor, text_input,
+    toggler,
 };
 use iced::{Border, Color, Gradient, Shadow, Vector};
 
@@ -817,6 +818,24 @@ pub fn themed_text_input(theme: &AppTheme, status: text_input::Status) -> text_i
     }
 }
 
+/// Multi-line text editor styling, matching [`themed_text_input`]
+pub fn themed_text_editor(theme: &AppTheme, status: text_editor::Status) -> text_editor::Style {
+    let input_status = match status {
+        text_editor::Status::Active => text_input::Status::Active,
+        text_editor::Status::Hovered => text_input::Status::Hovered,
+        text_editor::Status::Focused { is_hovered } => text_input::Status::Focused { is_hovered },
+        text_editor::Status::Disabled => text_input::Status::Disabled,
+    };
+    let style = themed_text_input(theme, input_status);
+    text_editor::Style {
+        background: style.background,
+        border: style.border,
+        placeholder: style.placeholder,
+        value: style.value,
+        selection: style.selection,
+    }
+}
+
 /// Pick list (dropdown) styling with theme-aware colors
 pub fn themed_pick_list(theme: &AppTheme, status: pick_list::Status) -> pick_list::Style {
     match status {
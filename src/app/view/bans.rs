@@ -0,0 +1,176 @@
+//! Temporary ban manager modal
+//!
+//! Lists active bans with their remaining time and lets the user ban or unban
+//! addresses. Changes update the live blocklist directly (no ruleset apply).
+
+use crate::app::ui_components::{
+    card_container, inset_container_bordered, primary_button, secondary_button,
+    section_header_container, themed_scrollable, themed_text_input,
+};
+use crate::app::{BanManagerState, Message, State};
+use crate::core::bans::{self, Ban};
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input};
+use iced::{Alignment, Element, Length};
+
+pub fn view_ban_manager<'a>(state: &'a State, mgr: &'a BanManagerState) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let now = chrono::Utc::now();
+
+    let active: Vec<&Ban> = mgr
+        .bans
+        .iter()
+        .flatten()
+        .filter(|b| b.remaining(now).is_some())
+        .collect();
+
+    let bans_list: Element<'_, Message> = if mgr.bans.is_none() {
+        text("Loading bans...")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else if active.is_empty() {
+        text("No active bans. Banned addresses are dropped before any other rule.")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for ban in &active {
+            list = list.push(view_ban_row(state, mgr, ban, now));
+        }
+
+        // Wrap scrollable in bordered container
+        container(
+            scrollable(container(list).width(Length::Fill).padding(8))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(0),
+                ))
+                .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(220.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme))
+        .into()
+    };
+
+    let submit = if mgr.busy {
+        None
+    } else {
+        Some(Message::BanSubmitted)
+    };
+
+    let mut form = column![
+        row![
+            text_input("IP address to ban...", &mgr.ip_input)
+                .on_input(Message::BanIpChanged)
+                .on_submit_maybe(submit.clone())
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fill),
+            text_input("1h", &mgr.duration_input)
+                .on_input(Message::BanDurationChanged)
+                .on_submit_maybe(submit.clone())
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fixed(80.0)),
+            button(
+                text(if mgr.busy { "Working..." } else { "Ban" })
+                    .size(12)
+                    .font(state.font_regular)
+            )
+            .on_press_maybe(submit)
+            .padding([8, 14])
+            .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        text("Duration: e.g. 30m, 1h, 24h, 7d")
+            .size(11)
+            .font(state.font_regular)
+            .color(theme.fg_muted),
+    ]
+    .spacing(6);
+
+    if let Some(err) = &mgr.error {
+        form = form.push(
+            text(err)
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger),
+        );
+    }
+
+    container(
+        column![
+            container(
+                text("Temporary Bans")
+                    .size(18)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary)
+            )
+            .padding([4, 8])
+            .style(move |_| section_header_container(theme)),
+            bans_list,
+            form,
+            row![
+                container(
+                    text(format!("{} active", active.len()))
+                        .size(10)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                space::Space::new().width(Length::Fill),
+                button(text("Close").size(14).font(state.font_regular))
+                    .on_press(Message::CloseBanManager)
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+            ]
+            .align_y(Alignment::Center)
+        ]
+        .spacing(16)
+        .padding(24)
+        .width(Length::Fixed(550.0)),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
+
+/// Renders a ban entry: address, time left and unban button
+fn view_ban_row<'a>(
+    state: &'a State,
+    mgr: &'a BanManagerState,
+    ban: &Ban,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let remaining = ban.remaining(now).unwrap_or_default();
+
+    container(
+        row![
+            text(ban.ip.to_string())
+                .size(13)
+                .font(state.font_mono)
+                .color(theme.fg_primary)
+                .width(Length::Fill),
+            text(format!("{} left", bans::format_remaining(remaining)))
+                .size(11)
+                .font(state.font_regular)
+                .color(theme.fg_muted),
+            button(text("Unban").size(12).font(state.font_regular))
+                .on_press_maybe((!mgr.busy).then_some(Message::UnbanClicked(ban.ip)))
+                .padding([4, 10])
+                .style(move |_, status| secondary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding([6, 10]),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
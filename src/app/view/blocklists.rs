@@ -0,0 +1,208 @@
+//! Blocklist manager modal
+//!
+//! Lists the IP list files attached to the ruleset with their parsed entry
+//! counts, and lets the user attach, detach and re-read files.
+
+use crate::app::ui_components::{
+    card_container, inset_container_bordered, primary_button, secondary_button,
+    section_header_container, themed_scrollable, themed_text_input,
+};
+use crate::app::{BlocklistManagerState, Message, State};
+use crate::core::blocklists::LoadedBlocklist;
+use iced::widget::text::Wrapping;
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input};
+use iced::{Alignment, Element, Length};
+use std::path::Path;
+
+pub fn view_blocklist_manager<'a>(
+    state: &'a State,
+    mgr: &'a BlocklistManagerState,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let attached = &state.ruleset.blocklists;
+
+    let files_list: Element<'_, Message> = if attached.is_empty() {
+        text("No blocklists attached. Entries from attached files are dropped before established connections are accepted.")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for path in attached {
+            let loaded = mgr.lists.iter().flatten().find(|l| &l.path == path);
+            list = list.push(view_blocklist_row(state, mgr, path, loaded));
+        }
+
+        // Wrap scrollable in bordered container
+        container(
+            scrollable(container(list).width(Length::Fill).padding(8))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(0),
+                ))
+                .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(220.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme))
+        .into()
+    };
+
+    let submit = (!mgr.busy).then_some(Message::AddBlocklistSubmitted);
+
+    let mut form = column![
+        row![
+            text_input("/path/to/blocklist.txt", &mgr.path_input)
+                .on_input(Message::BlocklistPathChanged)
+                .on_submit_maybe(submit.clone())
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fill),
+            button(text("Browse...").size(12).font(state.font_regular))
+                .on_press_maybe((!mgr.busy).then_some(Message::BrowseBlocklistClicked))
+                .padding([8, 14])
+                .style(move |_, status| secondary_button(theme, status)),
+            button(
+                text(if mgr.busy { "Reading..." } else { "Attach" })
+                    .size(12)
+                    .font(state.font_regular)
+            )
+            .on_press_maybe(submit)
+            .padding([8, 14])
+            .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        text(
+            "One IP or CIDR per line, # and ; start comments. Changes take effect on the \
+             next apply; `drfw blocklist refresh` reloads the live sets."
+        )
+        .size(11)
+        .font(state.font_regular)
+        .color(theme.fg_muted),
+    ]
+    .spacing(6);
+
+    if let Some(err) = &mgr.error {
+        form = form.push(
+            text(err)
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger),
+        );
+    }
+
+    let total: usize = mgr
+        .lists
+        .iter()
+        .flatten()
+        .filter(|l| attached.contains(&l.path))
+        .map(|l| l.entries.len())
+        .sum();
+
+    container(
+        column![
+            container(
+                text("Blocklists")
+                    .size(18)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary)
+            )
+            .padding([4, 8])
+            .style(move |_| section_header_container(theme)),
+            files_list,
+            form,
+            row![
+                container(
+                    text(format!("{} files · {total} entries", attached.len()))
+                        .size(10)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                space::Space::new().width(Length::Fill),
+                button(text("Reload Files").size(14).font(state.font_regular))
+                    .on_press_maybe(
+                        (!mgr.busy && !attached.is_empty())
+                            .then_some(Message::RefreshBlocklistsClicked)
+                    )
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+                button(text("Close").size(14).font(state.font_regular))
+                    .on_press(Message::CloseBlocklistManager)
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+            ]
+            .spacing(8)
+            .align_y(Alignment::Center)
+        ]
+        .spacing(16)
+        .padding(24)
+        .width(Length::Fixed(600.0)),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
+
+/// Renders an attached file: path, entry count and detach button
+fn view_blocklist_row<'a>(
+    state: &'a State,
+    mgr: &'a BlocklistManagerState,
+    path: &'a Path,
+    loaded: Option<&LoadedBlocklist>,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let details = match loaded {
+        Some(list) if list.invalid_lines > 0 => format!(
+            "{} entries · {} invalid lines skipped · read {}",
+            list.entries.len(),
+            list.invalid_lines,
+            list.loaded_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        Some(list) => format!(
+            "{} entries · read {}",
+            list.entries.len(),
+            list.loaded_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        None if mgr.lists.is_none() => "Loading...".to_string(),
+        None => "Not read yet, reload files before applying".to_string(),
+    };
+
+    container(
+        row![
+            column![
+                text(path.display().to_string())
+                    .size(12)
+                    .font(state.font_mono)
+                    .color(theme.fg_primary)
+                    .wrapping(Wrapping::WordOrGlyph),
+                text(details)
+                    .size(11)
+                    .font(state.font_regular)
+                    .color(if loaded.is_some() {
+                        theme.fg_muted
+                    } else {
+                        theme.warning
+                    }),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            button(text("×").size(14).color(theme.fg_muted))
+                .on_press(Message::RemoveBlocklistClicked(path.to_path_buf()))
+                .padding(6)
+                .style(button::text),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding([6, 10]),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
@@ -151,6 +151,13 @@ pub fn format_audit_event<'a>(
                 event.details["label"].as_str().unwrap_or("")
             ),
         ),
+        (EventType::ExpiredRulesRemoved, _) => (
+            theme.danger,
+            format!(
+                "Removed {} expired rule(s)",
+                event.details["count"].as_u64().unwrap_or(0)
+            ),
+        ),
         (EventType::RuleModified, _) => (
             theme.accent,
             format!(
@@ -182,6 +189,144 @@ pub fn format_audit_event<'a>(
                 event.details["direction"].as_str().unwrap_or("")
             ),
         ),
+        (EventType::SetCreated, _) => (
+            theme.success,
+            format!(
+                "Created set '{}' ({} elements)",
+                event.details["name"].as_str().unwrap_or(""),
+                event.details["elements"]
+            ),
+        ),
+        (EventType::SetModified, _) => (
+            theme.accent,
+            format!(
+                "Modified set '{}' ({} elements)",
+                event.details["name"].as_str().unwrap_or(""),
+                event.details["elements"]
+            ),
+        ),
+        (EventType::SetDeleted, _) => (
+            theme.danger,
+            format!(
+                "Deleted set '{}'",
+                event.details["name"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::KnockCreated, _) => (
+            theme.success,
+            format!(
+                "Created port knock '{}' ({})",
+                event.details["name"].as_str().unwrap_or(""),
+                event.details["summary"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::KnockModified, _) => (
+            theme.accent,
+            format!(
+                "Modified port knock '{}' ({})",
+                event.details["name"].as_str().unwrap_or(""),
+                event.details["summary"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::KnockDeleted, _) => (
+            theme.danger,
+            format!(
+                "Deleted port knock '{}'",
+                event.details["name"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::GroupCreated, _) => (
+            theme.success,
+            format!(
+                "Created group '{}'",
+                event.details["name"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::GroupModified, _) => (
+            theme.accent,
+            format!(
+                "Renamed group to '{}'",
+                event.details["name"].as_str().unwrap_or("")
+            ),
+        ),
+        (EventType::GroupDeleted, _) => (
+            theme.danger,
+            format!(
+                "Deleted group '{}' ({} rules ungrouped)",
+                event.details["name"].as_str().unwrap_or(""),
+                event.details["members"].as_u64().unwrap_or(0)
+            ),
+        ),
+        (EventType::GroupToggled, _) => (
+            theme.accent,
+            format!(
+                "Group '{}' {}",
+                event.details["name"].as_str().unwrap_or(""),
+                if event.details["enabled"].as_bool().unwrap_or(false) {
+                    "enabled"
+                } else {
+                    "disabled"
+                }
+            ),
+        ),
+        (EventType::IpBanned, true) => (
+            theme.warning,
+            format!(
+                "Banned {} for {}",
+                event.details["ip"].as_str().unwrap_or(""),
+                crate::core::bans::format_remaining(std::time::Duration::from_secs(
+                    event.details["duration_secs"].as_u64().unwrap_or(0)
+                ))
+            ),
+        ),
+        (EventType::IpBanned, false) => (
+            theme.danger,
+            format!(
+                "Ban of {} failed: {}",
+                event.details["ip"].as_str().unwrap_or(""),
+                format_error_for_display(event.error.as_deref())
+            ),
+        ),
+        (EventType::IpUnbanned, true) => (
+            theme.accent,
+            format!("Unbanned {}", event.details["ip"].as_str().unwrap_or("")),
+        ),
+        (EventType::IpUnbanned, false) => (
+            theme.danger,
+            format!(
+                "Unban of {} failed: {}",
+                event.details["ip"].as_str().unwrap_or(""),
+                format_error_for_display(event.error.as_deref())
+            ),
+        ),
+        (EventType::BlocklistsRefreshed, true) => (
+            theme.accent,
+            format!(
+                "Refreshed {} blocklist file(s) ({} entries)",
+                event.details["files"], event.details["entries"]
+            ),
+        ),
+        (EventType::BlocklistsRefreshed, false) => (
+            theme.danger,
+            format!(
+                "Blocklist refresh failed: {}",
+                format_error_for_display(event.error.as_deref())
+            ),
+        ),
+        (EventType::GeoIpRebuilt, true) => (
+            theme.accent,
+            format!(
+                "Rebuilt GeoIP sets for {} countries ({} ranges)",
+                event.details["countries"], event.details["ranges"]
+            ),
+        ),
+        (EventType::GeoIpRebuilt, false) => (
+            theme.danger,
+            format!(
+                "GeoIP rebuild failed: {}",
+                format_error_for_display(event.error.as_deref())
+            ),
+        ),
         (EventType::Undone, _) => (
             theme.warning,
             format!(
@@ -0,0 +1,214 @@
+//! Rule group manager modal
+//!
+//! Lists rule groups with their member counts, with an inline editor for
+//! creating and renaming groups. Rules are moved into groups by dragging them
+//! onto a group header in the sidebar.
+
+use crate::app::ui_components::{
+    card_button, card_container, danger_button, inset_container_bordered, primary_button,
+    secondary_button, section_header_container, themed_scrollable, themed_text_input,
+};
+use crate::app::{GroupEditorState, GroupManagerState, Message, State};
+use crate::core::firewall::RuleGroup;
+use iced::widget::{button, column, container, row, scrollable, space, text, text_input};
+use iced::{Alignment, Element, Length};
+
+pub fn view_group_manager<'a>(
+    state: &'a State,
+    mgr: &'a GroupManagerState,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let groups_list: Element<'_, Message> = if state.ruleset.groups.is_empty() {
+        text("No groups yet. Groups compile to their own chain and can be toggled as a unit.")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for group in &state.ruleset.groups {
+            let item: Element<'_, Message> = if mgr.deleting_id == Some(group.id) {
+                // Delete confirmation mode
+                container(
+                    row![
+                        text("Delete this group? Its rules are kept.")
+                            .size(12)
+                            .font(state.font_regular)
+                            .color(theme.danger)
+                            .width(Length::Fill),
+                        button(text("Cancel").size(12).font(state.font_regular))
+                            .on_press(Message::CancelDeleteGroup)
+                            .padding([4, 10])
+                            .style(move |_, status| secondary_button(theme, status)),
+                        button(text("Delete").size(12).font(state.font_regular))
+                            .on_press(Message::ConfirmDeleteGroup)
+                            .padding([4, 10])
+                            .style(move |_, status| danger_button(theme, status)),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .padding(8.0),
+                )
+                .style(move |_| card_container(theme))
+                .into()
+            } else {
+                view_group_row(state, group)
+            };
+            list = list.push(item);
+        }
+
+        // Wrap scrollable in bordered container
+        container(
+            scrollable(container(list).width(Length::Fill).padding(8))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(0),
+                ))
+                .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(260.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme))
+        .into()
+    };
+
+    let footer_action: Element<'_, Message> = if let Some(editor) = &mgr.editor {
+        view_group_editor(state, editor)
+    } else {
+        button(text("+ New Group").size(12).font(state.font_regular))
+            .on_press(Message::NewGroupClicked)
+            .padding([8, 12])
+            .style(move |_, status| primary_button(theme, status))
+            .into()
+    };
+
+    container(
+        column![
+            container(
+                text("Rule Groups")
+                    .size(18)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary)
+            )
+            .padding([4, 8])
+            .style(move |_| section_header_container(theme)),
+            groups_list,
+            footer_action,
+            row![
+                container(
+                    text(format!("{} groups", state.ruleset.groups.len()))
+                        .size(10)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                space::Space::new().width(Length::Fill),
+                button(text("Close").size(14).font(state.font_regular))
+                    .on_press(Message::CloseGroupManager)
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+            ]
+            .align_y(Alignment::Center)
+        ]
+        .spacing(16)
+        .padding(24)
+        .width(Length::Fixed(550.0)),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
+
+/// Renders a group entry: name, state and member count
+fn view_group_row<'a>(state: &'a State, group: &'a RuleGroup) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let members = state.ruleset.group_members(group.id).count();
+    let summary = format!(
+        "{} · {members} {}",
+        if group.enabled { "Enabled" } else { "Disabled" },
+        if members == 1 { "rule" } else { "rules" }
+    );
+
+    button(
+        row![
+            column![
+                text(&group.name)
+                    .size(13)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary),
+                text(summary)
+                    .size(11)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            button(text("✎").size(14).color(theme.fg_muted))
+                .on_press(Message::EditGroupClicked(group.id))
+                .style(button::text),
+            button(text("×").size(14).color(theme.fg_muted))
+                .on_press(Message::DeleteGroupRequested(group.id))
+                .padding(6)
+                .style(button::text),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding([6, 10]),
+    )
+    .width(Length::Fill)
+    .on_press(Message::EditGroupClicked(group.id))
+    .style(move |_, status| card_button(theme, status))
+    .into()
+}
+
+/// Renders the inline create/rename form
+fn view_group_editor<'a>(state: &'a State, editor: &'a GroupEditorState) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let mut content = column![
+        text_input("Group name (e.g. Media server)...", &editor.name)
+            .on_input(Message::GroupEditorNameChanged)
+            .on_submit(Message::SaveGroupEditor)
+            .padding(8)
+            .font(state.font_regular)
+            .style(move |_, status| themed_text_input(theme, status)),
+    ]
+    .spacing(8);
+
+    if let Some(err) = &editor.error {
+        content = content.push(
+            text(err)
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger),
+        );
+    }
+
+    content = content.push(
+        row![
+            space::Space::new().width(Length::Fill),
+            button(text("Cancel").size(12).font(state.font_regular))
+                .on_press(Message::CancelGroupEditor)
+                .style(move |_, status| secondary_button(theme, status)),
+            button(
+                text(if editor.original_id.is_some() {
+                    "Rename"
+                } else {
+                    "Create"
+                })
+                .size(12)
+                .font(state.font_regular)
+            )
+            .on_press(Message::SaveGroupEditor)
+            .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    );
+
+    container(content)
+        .padding(12)
+        .style(move |_| card_container(theme))
+        .into()
+}
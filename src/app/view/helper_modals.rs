@@ -1,23 +1,37 @@
 //! Helper modals for multi-value field editing
 //!
 //! Provides reusable modal components for editing Vec fields in rule forms:
-//! - Ports: Single ports or ranges (e.g., "22", "8000-8080")
-//! - Addresses: IP/CIDR addresses (e.g., "192.168.1.0/24", "`fd00::1`")
+//! - Ports: Single ports or ranges (e.g., "22", "8000-8080"), destination or source
+//! - ICMP types: Toggle chips for the protocol's types, plus an optional code
+//! - Addresses: IP/CIDR addresses, ranges, hostnames or interface subnets (e.g.,
+//!   "192.168.1.0/24", "`fd00::1`", "192.168.1.100-192.168.1.150", "nas.local",
+//!   "subnet:wlan0")
+//! - Interfaces: Input or output interface names, with system interfaces and
+//!   wildcards (e.g., "eth*") suggested
 //! - Tags: Organizational labels
+//!
+//! Ports and addresses can alternatively reference a named set, picked from
+//! the sets of a compatible type.
 
 use crate::app::ui_components::{
-    card_container, inset_container_bordered, primary_button, section_header_container, tag_button,
+    active_tag_button, card_container, inset_container_bordered, primary_button,
+    section_header_container, tag_button, themed_pick_list, themed_pick_list_menu,
     themed_scrollable, themed_text_input,
 };
 use crate::app::{HelperType, Message, RuleForm, RuleFormHelper};
-use crate::core::firewall::PortEntry;
-use iced::widget::{Space, button, column, container, row, scrollable, text, text_input};
+use crate::core::firewall::{AddressEntry, IcmpType, NamedSet, PortEntry, Rule};
+use crate::core::rule_constraints::available_icmp_types_for_protocol;
+use iced::widget::{
+    Space, button, column, combo_box, container, pick_list, row, scrollable, text, text_input,
+};
 use iced::{Alignment, Element, Length};
 
 /// Renders the helper modal based on current helper type
 pub fn view_helper_modal<'a>(
     form: &'a RuleForm,
     helper: &'a RuleFormHelper,
+    sets: &'a [NamedSet],
+    interface_combo: &'a combo_box::State<String>,
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
     mono_font: iced::Font,
@@ -27,31 +41,64 @@ pub fn view_helper_modal<'a>(
     };
 
     match helper_type {
-        HelperType::Ports => view_ports_helper(form, helper, theme, regular_font, mono_font),
+        HelperType::Ports => {
+            view_ports_helper(form, helper, sets, theme, regular_font, mono_font, false)
+        }
+        HelperType::SourcePorts => {
+            view_ports_helper(form, helper, sets, theme, regular_font, mono_font, true)
+        }
+        HelperType::IcmpTypes => view_icmp_types_helper(form, theme, regular_font, mono_font),
         HelperType::SourceAddresses => {
-            view_addresses_helper(form, helper, theme, regular_font, mono_font, true)
+            view_addresses_helper(form, helper, sets, theme, regular_font, mono_font, true)
         }
         HelperType::DestinationAddresses => {
-            view_addresses_helper(form, helper, theme, regular_font, mono_font, false)
+            view_addresses_helper(form, helper, sets, theme, regular_font, mono_font, false)
         }
+        HelperType::Interfaces => view_interfaces_helper(
+            form,
+            helper,
+            interface_combo,
+            theme,
+            regular_font,
+            mono_font,
+            false,
+        ),
+        HelperType::OutputInterfaces => view_interfaces_helper(
+            form,
+            helper,
+            interface_combo,
+            theme,
+            regular_font,
+            mono_font,
+            true,
+        ),
         HelperType::Tags => view_tags_helper(form, helper, theme, regular_font, mono_font),
     }
 }
 
-/// Ports helper modal
+/// Ports helper modal (reused for destination and source ports)
 fn view_ports_helper<'a>(
     form: &'a RuleForm,
     helper: &'a RuleFormHelper,
+    sets: &'a [NamedSet],
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
     mono_font: iced::Font,
+    is_source: bool,
 ) -> Element<'a, Message> {
+    let (title, header_text, ports) = if is_source {
+        (
+            "Configure Source Ports",
+            "CONFIGURED SOURCE PORTS",
+            &form.source_ports,
+        )
+    } else {
+        ("Configure Ports", "CONFIGURED PORTS", &form.ports)
+    };
+
     let content = column![
         // Header
-        text("Configure Ports")
-            .size(18)
-            .font(regular_font)
-            .color(theme.info),
+        text(title).size(18).font(regular_font).color(theme.info),
         text("Add single ports (22) or ranges (8000-8080)")
             .size(12)
             .font(regular_font)
@@ -72,6 +119,21 @@ fn view_ports_helper<'a>(
         ]
         .spacing(8)
         .align_y(Alignment::Center),
+        // Named set alternative (replaces explicit ports, destination only)
+        if is_source {
+            Space::new().into()
+        } else {
+            view_set_picker(
+                sets.iter()
+                    .filter(|set| !set.set_type.is_address())
+                    .map(|set| set.name.clone())
+                    .collect(),
+                form.port_set.as_ref(),
+                theme,
+                regular_font,
+                mono_font,
+            )
+        },
         // Error message
         {
             if let Some(err) = &helper.error {
@@ -82,7 +144,7 @@ fn view_ports_helper<'a>(
         },
         // Current values list
         container(
-            text("CONFIGURED PORTS")
+            text(header_text)
                 .size(9)
                 .font(mono_font)
                 .color(theme.fg_muted)
@@ -93,7 +155,7 @@ fn view_ports_helper<'a>(
         container(
             scrollable(
                 container(
-                    column(form.ports.iter().enumerate().map(|(i, port)| {
+                    column(ports.iter().enumerate().map(|(i, port)| {
                         let port_text = match port {
                             PortEntry::Single(p) => p.to_string(),
                             PortEntry::Range { start, end } => format!("{start}-{end}"),
@@ -145,10 +207,88 @@ fn view_ports_helper<'a>(
         .into()
 }
 
+/// ICMP types helper modal
+///
+/// Only the types that exist for the form's protocol are offered, so IPv4-only
+/// types cannot be picked for `ICMPv6` (and vice versa).
+fn view_icmp_types_helper<'a>(
+    form: &'a RuleForm,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+) -> Element<'a, Message> {
+    let chips = row(available_icmp_types_for_protocol(form.protocol)
+        .into_iter()
+        .map(|icmp_type| {
+            let selected = form.icmp_types.contains(&icmp_type);
+            button(text(icmp_type.display_name()).size(11).font(regular_font))
+                .on_press(Message::RuleFormToggleIcmpType(icmp_type))
+                .padding([4, 8])
+                .style(move |_, status| {
+                    if selected {
+                        active_tag_button(theme, status)
+                    } else {
+                        tag_button(theme, status)
+                    }
+                })
+                .into()
+        }))
+    .spacing(6)
+    .wrap();
+
+    let content = column![
+        // Header
+        text("Configure ICMP Types")
+            .size(18)
+            .font(regular_font)
+            .color(theme.info),
+        text("Select the message types to match. None selected = all types.")
+            .size(12)
+            .font(regular_font)
+            .color(theme.fg_muted),
+        container(chips)
+            .width(Length::Fill)
+            .padding(8)
+            .style(move |_| inset_container_bordered(theme)),
+        // Optional code (only meaningful for a single type)
+        container(
+            text("ICMP CODE (OPTIONAL)")
+                .size(9)
+                .font(mono_font)
+                .color(theme.fg_muted)
+        )
+        .padding([2, 6])
+        .style(move |_| section_header_container(theme)),
+        text_input("e.g. 4 (requires a single type)", &form.icmp_code)
+            .on_input(Message::RuleFormIcmpCodeChanged)
+            .padding(8)
+            .width(Length::Fill)
+            .font(mono_font)
+            .style(move |_, status| themed_text_input(theme, status)),
+        // Footer
+        row![
+            button(text("Done").size(14).font(regular_font))
+                .on_press(Message::CloseHelper)
+                .padding([10, 24])
+                .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(16)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(12)
+    .padding(20);
+
+    container(content)
+        .max_width(400)
+        .style(move |_| card_container(theme))
+        .into()
+}
+
 /// Addresses helper modal (reused for source and destination)
 fn view_addresses_helper<'a>(
     form: &'a RuleForm,
     helper: &'a RuleFormHelper,
+    sets: &'a [NamedSet],
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
     mono_font: iced::Font,
@@ -169,23 +309,31 @@ fn view_addresses_helper<'a>(
     } else {
         "DESTINATION ADDRESSES"
     };
+    let selected_set = if is_source {
+        form.source_set.as_ref()
+    } else {
+        form.destination_set.as_ref()
+    };
 
     let content = column![
         // Header
         text(title).size(18).font(regular_font).color(theme.info),
-        text("Add IP addresses or CIDR blocks (e.g., 192.168.1.0/24)")
+        text("Add IP addresses, CIDR blocks, ranges, hostnames or subnet:<interface>")
             .size(12)
             .font(regular_font)
             .color(theme.fg_muted),
         // Input row
         row![
-            text_input("e.g. 192.168.1.0/24 or 10.0.0.1", &helper.input)
-                .on_input(Message::HelperInputChanged)
-                .on_submit(Message::HelperAddValue)
-                .padding(8)
-                .width(Length::Fill)
-                .font(mono_font)
-                .style(move |_, status| themed_text_input(theme, status)),
+            text_input(
+                "e.g. 192.168.1.0/24, 10.0.0.5-10.0.0.9, nas.local or subnet:wlan0",
+                &helper.input
+            )
+            .on_input(Message::HelperInputChanged)
+            .on_submit(Message::HelperAddValue)
+            .padding(8)
+            .width(Length::Fill)
+            .font(mono_font)
+            .style(move |_, status| themed_text_input(theme, status)),
             button(text("+").size(16).font(regular_font))
                 .on_press(Message::HelperAddValue)
                 .padding([8, 16])
@@ -193,6 +341,17 @@ fn view_addresses_helper<'a>(
         ]
         .spacing(8)
         .align_y(Alignment::Center),
+        // Named set alternative (replaces explicit addresses)
+        view_set_picker(
+            sets.iter()
+                .filter(|set| set.set_type.is_address())
+                .map(|set| set.name.clone())
+                .collect(),
+            selected_set,
+            theme,
+            regular_font,
+            mono_font,
+        ),
         // Error message
         {
             if let Some(err) = &helper.error {
@@ -262,6 +421,171 @@ fn view_addresses_helper<'a>(
         .into()
 }
 
+/// Interfaces helper modal (reused for input and output interfaces)
+///
+/// The input is a combo box offering system interfaces and wildcards; picking a
+/// suggestion adds it, typed names are added with "+".
+fn view_interfaces_helper<'a>(
+    form: &'a RuleForm,
+    helper: &'a RuleFormHelper,
+    interface_combo: &'a combo_box::State<String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+    is_output: bool,
+) -> Element<'a, Message> {
+    let (title, header_text, interfaces) = if is_output {
+        (
+            "Configure Output Interfaces",
+            "OUTPUT INTERFACES",
+            &form.output_interfaces,
+        )
+    } else {
+        (
+            "Configure Input Interfaces",
+            "INPUT INTERFACES",
+            &form.interfaces,
+        )
+    };
+
+    let content = column![
+        // Header
+        text(title).size(18).font(regular_font).color(theme.info),
+        text("Add interface names or wildcards (e.g., wlan0, eth*)")
+            .size(12)
+            .font(regular_font)
+            .color(theme.fg_muted),
+        // Input row
+        row![
+            combo_box(
+                interface_combo,
+                "Type or select...",
+                None,
+                Message::HelperSuggestionSelected,
+            )
+            .on_input(Message::HelperInputChanged)
+            .padding(8)
+            .font(mono_font)
+            .width(Length::Fill)
+            .input_style(move |_, status| themed_text_input(theme, status))
+            .menu_style(move |_| themed_pick_list_menu(theme)),
+            button(text("+").size(16).font(regular_font))
+                .on_press(Message::HelperAddValue)
+                .padding([8, 16])
+                .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        // Error message
+        {
+            if let Some(err) = &helper.error {
+                container(text(err).size(12).font(regular_font).color(theme.danger))
+            } else {
+                container(Space::new())
+            }
+        },
+        // Current values list
+        container(
+            text(header_text)
+                .size(9)
+                .font(mono_font)
+                .color(theme.fg_muted)
+        )
+        .padding([2, 6])
+        .style(move |_| section_header_container(theme)),
+        // STYLE.md Section 17, Pattern 2: Bordered scrollable
+        container(
+            scrollable(
+                container(
+                    column(interfaces.iter().enumerate().map(|(i, iface)| {
+                        row![
+                            text(iface).size(13).font(mono_font).color(theme.fg_primary),
+                            Space::new().width(Length::Fill),
+                            button(text("×").size(14).font(regular_font).color(theme.danger))
+                                .on_press(Message::HelperRemoveValue(i))
+                                .padding(4)
+                                .style(button::text),
+                        ]
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .into()
+                    }))
+                    .spacing(4),
+                )
+                .width(Length::Fill)
+                .padding(8),
+            )
+            .direction(scrollable::Direction::Vertical(
+                scrollable::Scrollbar::new().spacing(0),
+            ))
+            .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(150.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme)),
+        // Footer
+        row![
+            button(text("Done").size(14).font(regular_font))
+                .on_press(Message::CloseHelper)
+                .padding([10, 24])
+                .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(16)
+        .align_y(Alignment::Center),
+    ]
+    .spacing(12)
+    .padding(20);
+
+    container(content)
+        .max_width(400)
+        .style(move |_| card_container(theme))
+        .into()
+}
+
+/// Named set picker shown in ports/address helpers when compatible sets exist.
+///
+/// Choosing a set replaces the explicit values; "×" clears the reference.
+fn view_set_picker<'a>(
+    names: Vec<String>,
+    selected: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+) -> Element<'a, Message> {
+    if names.is_empty() && selected.is_none() {
+        return Space::new().into();
+    }
+
+    let mut picker_row = row![
+        text("Or use set")
+            .size(12)
+            .font(regular_font)
+            .color(theme.fg_muted),
+        pick_list(names, selected.cloned(), |name| {
+            Message::HelperSetSelected(Some(name))
+        })
+        .placeholder("Choose a named set...")
+        .width(Length::Fill)
+        .padding(8)
+        .font(mono_font)
+        .style(move |_, status| themed_pick_list(theme, status))
+        .menu_style(move |_| themed_pick_list_menu(theme)),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    if selected.is_some() {
+        picker_row = picker_row.push(
+            button(text("×").size(14).font(regular_font).color(theme.danger))
+                .on_press(Message::HelperSetSelected(None))
+                .padding(4)
+                .style(button::text),
+        );
+    }
+
+    picker_row.into()
+}
+
 /// Tags helper modal
 fn view_tags_helper<'a>(
     form: &'a RuleForm,
@@ -326,11 +650,7 @@ fn view_tags_helper<'a>(
                     } else {
                         row(form.tags.iter().enumerate().map(|(i, tag)| {
                             // Truncate long tags for display (same as tag cloud)
-                            let display_tag: std::borrow::Cow<'_, str> = if tag.len() > 16 {
-                                format!("{}…", &tag[..15]).into()
-                            } else {
-                                tag.as_str().into()
-                            };
+                            let display_tag = Rule::truncate_tag(tag);
                             button(
                                 row![
                                     text(display_tag).size(10).font(regular_font),
@@ -381,8 +701,10 @@ fn view_tags_helper<'a>(
 }
 
 /// Returns a summary string for ports (used in main form)
-pub fn ports_summary(ports: &[PortEntry]) -> String {
-    if ports.is_empty() {
+pub fn ports_summary(ports: &[PortEntry], port_set: Option<&String>) -> String {
+    if let Some(name) = port_set {
+        format!("@{name}")
+    } else if ports.is_empty() {
         "All ports".to_string()
     } else if ports.len() == 1 {
         match &ports[0] {
@@ -394,9 +716,29 @@ pub fn ports_summary(ports: &[PortEntry]) -> String {
     }
 }
 
+/// Returns a summary string for source ports (used in main form)
+pub fn source_ports_summary(ports: &[PortEntry]) -> String {
+    if ports.is_empty() {
+        "Any".to_string()
+    } else {
+        ports_summary(ports, None)
+    }
+}
+
+/// Returns a summary string for ICMP types (used in main form)
+pub fn icmp_types_summary(icmp_types: &[IcmpType]) -> String {
+    match icmp_types {
+        [] => "All types".to_string(),
+        [icmp_type] => icmp_type.display_name().to_string(),
+        _ => format!("{} types", icmp_types.len()),
+    }
+}
+
 /// Returns a summary string for addresses (used in main form)
-pub fn addresses_summary(addresses: &[ipnetwork::IpNetwork]) -> String {
-    if addresses.is_empty() {
+pub fn addresses_summary(addresses: &[AddressEntry], set: Option<&String>) -> String {
+    if let Some(name) = set {
+        format!("@{name}")
+    } else if addresses.is_empty() {
         "Any".to_string()
     } else if addresses.len() == 1 {
         addresses[0].to_string()
@@ -405,17 +747,22 @@ pub fn addresses_summary(addresses: &[ipnetwork::IpNetwork]) -> String {
     }
 }
 
+/// Returns a summary string for interfaces (used in main form)
+pub fn interfaces_summary(interfaces: &[String]) -> String {
+    match interfaces {
+        [] => "Any".to_string(),
+        [iface] => iface.clone(),
+        _ => format!("{} interfaces", interfaces.len()),
+    }
+}
+
 /// Returns a summary string for tags (used in main form)
 pub fn tags_summary(tags: &[String]) -> String {
     if tags.is_empty() {
         "No tags".to_string()
     } else if tags.len() == 1 {
         // Truncate long tags for display (same as tag cloud)
-        if tags[0].len() > 16 {
-            format!("{}…", &tags[0][..15])
-        } else {
-            tags[0].clone()
-        }
+        Rule::truncate_tag(&tags[0])
     } else {
         format!("{} tags", tags.len())
     }
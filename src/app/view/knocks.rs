@@ -0,0 +1,278 @@
+//! Port knocking manager modal
+//!
+//! Lists knock sequences with the service they protect, with an inline editor
+//! for the knock ports, protected service and timeout.
+
+use crate::app::ui_components::{
+    card_button, card_container, danger_button, inset_container_bordered, primary_button,
+    secondary_button, section_header_container, themed_pick_list, themed_pick_list_menu,
+    themed_scrollable, themed_text_input,
+};
+use crate::app::{KnockEditorState, KnockManagerState, Message, State};
+use crate::core::firewall::{PortKnock, Protocol};
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element, Length};
+
+/// Protocols selectable for knocks and protected services
+const KNOCK_PROTOCOLS: [Protocol; 2] = [Protocol::Tcp, Protocol::Udp];
+
+pub fn view_knock_manager<'a>(
+    state: &'a State,
+    mgr: &'a KnockManagerState,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let knocks_list: Element<'_, Message> = if state.ruleset.knocks.is_empty() {
+        text("No port knocking sequences. A knock keeps a port closed until a client hits a secret sequence of ports.")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for knock in &state.ruleset.knocks {
+            let item: Element<'_, Message> = if let Some(del_name) = &mgr.deleting_name
+                && del_name == &knock.name
+            {
+                // Delete confirmation mode
+                container(
+                    row![
+                        text("Delete this port knock?")
+                            .size(12)
+                            .font(state.font_regular)
+                            .color(theme.danger)
+                            .width(Length::Fill),
+                        button(text("Cancel").size(12).font(state.font_regular))
+                            .on_press(Message::CancelDeleteKnock)
+                            .padding([4, 10])
+                            .style(move |_, status| secondary_button(theme, status)),
+                        button(text("Delete").size(12).font(state.font_regular))
+                            .on_press(Message::ConfirmDeleteKnock)
+                            .padding([4, 10])
+                            .style(move |_, status| danger_button(theme, status)),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .padding(8.0),
+                )
+                .style(move |_| card_container(theme))
+                .into()
+            } else {
+                view_knock_row(state, knock)
+            };
+            list = list.push(item);
+        }
+
+        // Wrap scrollable in bordered container
+        container(
+            scrollable(container(list).width(Length::Fill).padding(8))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(0),
+                ))
+                .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(220.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme))
+        .into()
+    };
+
+    let footer_action: Element<'_, Message> = if let Some(editor) = &mgr.editor {
+        view_knock_editor(state, editor)
+    } else {
+        button(text("+ New Port Knock").size(12).font(state.font_regular))
+            .on_press(Message::NewKnockClicked)
+            .padding([8, 12])
+            .style(move |_, status| primary_button(theme, status))
+            .into()
+    };
+
+    container(
+        column![
+            container(
+                text("Port Knocking")
+                    .size(18)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary)
+            )
+            .padding([4, 8])
+            .style(move |_| section_header_container(theme)),
+            knocks_list,
+            footer_action,
+            row![
+                container(
+                    text(format!("{} sequences", state.ruleset.knocks.len()))
+                        .size(10)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                space::Space::new().width(Length::Fill),
+                button(text("Close").size(14).font(state.font_regular))
+                    .on_press(Message::CloseKnockManager)
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+            ]
+            .align_y(Alignment::Center)
+        ]
+        .spacing(16)
+        .padding(24)
+        .width(Length::Fixed(550.0)),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
+
+/// Renders a knock entry: name, sequence, protected service and timeout
+fn view_knock_row<'a>(state: &'a State, knock: &'a PortKnock) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    button(
+        row![
+            column![
+                text(&knock.name)
+                    .size(13)
+                    .font(state.font_mono)
+                    .color(theme.fg_primary),
+                text(format!("{} · {}s", knock.summary(), knock.timeout_secs))
+                    .size(11)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            button(text("✎").size(14).color(theme.fg_muted))
+                .on_press(Message::EditKnockClicked(knock.name.clone()))
+                .style(button::text),
+            button(text("×").size(14).color(theme.fg_muted))
+                .on_press(Message::DeleteKnockRequested(knock.name.clone()))
+                .padding(6)
+                .style(button::text),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding([6, 10]),
+    )
+    .width(Length::Fill)
+    .on_press(Message::EditKnockClicked(knock.name.clone()))
+    .style(move |_, status| card_button(theme, status))
+    .into()
+}
+
+/// Renders the inline create/edit form
+fn view_knock_editor<'a>(state: &'a State, editor: &'a KnockEditorState) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let label = |s: &'static str| {
+        text(s)
+            .size(11)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .width(Length::Fixed(70.0))
+    };
+    let protocol_pick = |selected: Protocol, on_select: fn(Protocol) -> Message| {
+        pick_list(KNOCK_PROTOCOLS, Some(selected), on_select)
+            .padding(8)
+            .font(state.font_regular)
+            .style(move |_, status| themed_pick_list(theme, status))
+            .menu_style(move |_| themed_pick_list_menu(theme))
+    };
+
+    let mut content = column![
+        row![
+            label("NAME"),
+            text_input("e.g. ssh", &editor.name)
+                .on_input(Message::KnockEditorNameChanged)
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fill),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        row![
+            label("KNOCKS"),
+            protocol_pick(
+                editor.knock_protocol,
+                Message::KnockEditorKnockProtocolChanged
+            ),
+            text_input("e.g. 7000, 8000, 9000", &editor.sequence)
+                .on_input(Message::KnockEditorSequenceChanged)
+                .on_submit(Message::SaveKnockEditor)
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fill),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        row![
+            label("OPENS"),
+            protocol_pick(editor.protocol, Message::KnockEditorProtocolChanged),
+            text_input("22", &editor.port)
+                .on_input(Message::KnockEditorPortChanged)
+                .on_submit(Message::SaveKnockEditor)
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fixed(90.0)),
+            label("TIMEOUT (S)"),
+            text_input("30", &editor.timeout)
+                .on_input(Message::KnockEditorTimeoutChanged)
+                .on_submit(Message::SaveKnockEditor)
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fixed(70.0)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        text(
+            "Each knock must follow the previous one within the timeout; \
+             the port then stays open for the same time."
+        )
+        .size(11)
+        .font(state.font_regular)
+        .color(theme.fg_muted),
+    ]
+    .spacing(8);
+
+    if let Some(err) = &editor.error {
+        content = content.push(
+            text(err)
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger),
+        );
+    }
+
+    content = content.push(
+        row![
+            space::Space::new().width(Length::Fill),
+            button(text("Cancel").size(12).font(state.font_regular))
+                .on_press(Message::CancelKnockEditor)
+                .style(move |_, status| secondary_button(theme, status)),
+            button(
+                text(if editor.original_name.is_some() {
+                    "Update"
+                } else {
+                    "Create"
+                })
+                .size(12)
+                .font(state.font_regular)
+            )
+            .on_press(Message::SaveKnockEditor)
+            .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    );
+
+    container(content)
+        .padding(12)
+        .style(move |_| card_container(theme))
+        .into()
+}
@@ -9,13 +9,18 @@ pub const SIDEBAR_TAGS_SCROLLABLE_ID: &str = "sidebar-tags";
 pub const WORKSPACE_SCROLLABLE_ID: &str = "workspace-preview";
 
 // Submodule declarations
+mod bans;
+mod blocklists;
 mod confirmation;
 mod diagnostics;
+mod groups;
 mod helper_modals;
+mod knocks;
 mod modals;
 mod pickers;
 mod profile;
 mod rule_form;
+mod sets;
 mod settings;
 mod shortcuts;
 mod sidebar;
@@ -95,9 +100,8 @@ pub fn view(state: &State) -> Element<'_, Message> {
         Some(
             center(rule_form::view_rule_form(
                 form,
+                &state.rule_description,
                 state.form_errors.as_ref(),
-                &state.interface_combo,
-                &state.output_interface_combo,
                 theme,
                 state.font_regular,
                 state.font_mono,
@@ -159,6 +163,8 @@ pub fn view(state: &State) -> Element<'_, Message> {
                         center(helper_modals::view_helper_modal(
                             form,
                             helper,
+                            &state.ruleset.sets,
+                            &state.interface_combo,
                             theme,
                             state.font_regular,
                             state.font_mono,
@@ -301,10 +307,83 @@ pub fn view(state: &State) -> Element<'_, Message> {
         stack![with_profile_confirm, iced::widget::Space::new()].into()
     };
 
+    // Set manager modal overlay
+    let with_set_manager: Element<'_, Message> = if let Some(ref mgr_state) = state.set_manager {
+        stack![
+            with_profile_manager,
+            opaque(
+                center(sets::view_set_manager(state, mgr_state))
+                    .style(move |_| modal_backdrop(theme))
+            )
+        ]
+        .into()
+    } else {
+        stack![with_profile_manager, iced::widget::Space::new()].into()
+    };
+
+    // Ban manager modal overlay
+    let with_ban_manager: Element<'_, Message> = if let Some(ref mgr_state) = state.ban_manager {
+        stack![
+            with_set_manager,
+            opaque(
+                center(bans::view_ban_manager(state, mgr_state))
+                    .style(move |_| modal_backdrop(theme))
+            )
+        ]
+        .into()
+    } else {
+        stack![with_set_manager, iced::widget::Space::new()].into()
+    };
+
+    // Port knocking manager modal overlay
+    let with_knock_manager: Element<'_, Message> = if let Some(ref mgr_state) = state.knock_manager
+    {
+        stack![
+            with_ban_manager,
+            opaque(
+                center(knocks::view_knock_manager(state, mgr_state))
+                    .style(move |_| modal_backdrop(theme))
+            )
+        ]
+        .into()
+    } else {
+        stack![with_ban_manager, iced::widget::Space::new()].into()
+    };
+
+    // Blocklist manager modal overlay
+    let with_blocklist_manager: Element<'_, Message> =
+        if let Some(ref mgr_state) = state.blocklist_manager {
+            stack![
+                with_knock_manager,
+                opaque(
+                    center(blocklists::view_blocklist_manager(state, mgr_state))
+                        .style(move |_| modal_backdrop(theme))
+                )
+            ]
+            .into()
+        } else {
+            stack![with_knock_manager, iced::widget::Space::new()].into()
+        };
+
+    // Rule group manager modal overlay
+    let with_group_manager: Element<'_, Message> = if let Some(ref mgr_state) = state.group_manager
+    {
+        stack![
+            with_blocklist_manager,
+            opaque(
+                center(groups::view_group_manager(state, mgr_state))
+                    .style(move |_| modal_backdrop(theme))
+            )
+        ]
+        .into()
+    } else {
+        stack![with_blocklist_manager, iced::widget::Space::new()].into()
+    };
+
     // Keyboard shortcuts help overlay
     if state.show_shortcuts_help {
         stack![
-            with_profile_manager,
+            with_group_manager,
             opaque(
                 center(shortcuts::view_shortcuts_help(
                     theme,
@@ -316,6 +395,6 @@ pub fn view(state: &State) -> Element<'_, Message> {
         ]
         .into()
     } else {
-        stack![with_profile_manager, iced::widget::Space::new()].into()
+        stack![with_group_manager, iced::widget::Space::new()].into()
     }
 }
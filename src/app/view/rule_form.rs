@@ -5,22 +5,28 @@
 
 use super::helper_modals;
 use crate::app::ui_components::{
-    card_container, primary_button, secondary_button, section_header_container, themed_checkbox,
-    themed_pick_list, themed_pick_list_menu, themed_text_input,
+    active_tag_button, card_container, primary_button, secondary_button, section_header_container,
+    tag_button, themed_checkbox, themed_pick_list, themed_pick_list_menu, themed_text_editor,
+    themed_text_input,
+};
+use crate::app::{HelperType, Message, RateMeasure, RuleForm};
+use crate::core::firewall::{
+    ByteUnit, CtState, MatchField, Protocol, QuotaMode, RejectType, Weekday,
+};
+use crate::core::geoip::{COUNTRIES, Country, country_name};
+use crate::core::rule_constraints::{
+    available_reject_types_for_protocol, protocol_supports_icmp_types, protocol_supports_ports,
 };
-use crate::app::{HelperType, Message, RuleForm};
-use crate::core::firewall::{Protocol, RejectType};
-use crate::core::rule_constraints::{available_reject_types_for_protocol, protocol_supports_ports};
 use iced::widget::{
-    Space, button, checkbox, column, combo_box, container, pick_list, row, text, text_input,
+    Space, button, checkbox, column, container, pick_list, row, text, text_editor, text_input,
 };
 use iced::{Alignment, Element, Length};
+use strum::IntoEnumIterator;
 
 pub fn view_rule_form<'a>(
     form: &'a RuleForm,
+    description: &'a text_editor::Content,
     errors: Option<&'a crate::app::FormErrors>,
-    interface_combo: &'a combo_box::State<String>,
-    output_interface_combo: &'a combo_box::State<String>,
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
     mono_font: iced::Font,
@@ -39,17 +45,29 @@ pub fn view_rule_form<'a>(
 
     // Extract errors
     let port_error = errors.and_then(|e| e.port.as_ref());
+    let source_port_error = errors.and_then(|e| e.source_port.as_ref());
+    let icmp_error = errors.and_then(|e| e.icmp.as_ref());
     let source_error = errors.and_then(|e| e.source.as_ref());
     let destination_error = errors.and_then(|e| e.destination.as_ref());
     let rate_limit_error = errors.and_then(|e| e.rate_limit.as_ref());
+    let quota_error = errors.and_then(|e| e.quota.as_ref());
     let connection_limit_error = errors.and_then(|e| e.connection_limit.as_ref());
+    let per_source_error = errors.and_then(|e| e.per_source.as_ref());
     let reject_type_error = errors.and_then(|e| e.reject_type.as_ref());
+    let interface_error = errors.and_then(|e| e.interface.as_ref());
     let output_interface_error = errors.and_then(|e| e.output_interface.as_ref());
+    let port_forward_error = errors.and_then(|e| e.port_forward.as_ref());
+    let schedule_error = errors.and_then(|e| e.schedule.as_ref());
+    let expiry_error = errors.and_then(|e| e.expiry.as_ref());
+    let countries_error = errors.and_then(|e| e.countries.as_ref());
+    let reference_error = errors.and_then(|e| e.reference.as_ref());
 
     // Summary strings for multi-value fields
-    let ports_summary = helper_modals::ports_summary(&form.ports);
-    let sources_summary = helper_modals::addresses_summary(&form.sources);
-    let destinations_summary = helper_modals::addresses_summary(&form.destinations);
+    let ports_summary = helper_modals::ports_summary(&form.ports, form.port_set.as_ref());
+    let sources_summary = helper_modals::addresses_summary(&form.sources, form.source_set.as_ref());
+    let source_ports_summary = helper_modals::source_ports_summary(&form.source_ports);
+    let destinations_summary =
+        helper_modals::addresses_summary(&form.destinations, form.destination_set.as_ref());
     let tags_summary = helper_modals::tags_summary(&form.tags);
 
     let form_box = column![
@@ -68,7 +86,7 @@ pub fn view_rule_form<'a>(
         // Basic Info Section
         column![
             container(
-                text("DESCRIPTION")
+                text("LABEL")
                     .size(11)
                     .font(regular_font)
                     .color(theme.fg_muted)
@@ -121,23 +139,34 @@ pub fn view_rule_form<'a>(
                 ]
                 .spacing(4)
                 .width(Length::Fill),
-                // Ports summary button
+                // Ports summary button (ICMP types for single-family ICMP protocols)
                 {
                     let mut port_col = column![
-                        container(
-                            text("PORTS")
-                                .size(11)
-                                .font(regular_font)
-                                .color(theme.fg_muted)
-                        )
-                        .padding([2, 6])
-                        .style(move |_| section_header_container(theme)),
+                        if protocol_supports_icmp_types(form.protocol) {
+                            container(
+                                text("ICMP TYPES")
+                                    .size(11)
+                                    .font(regular_font)
+                                    .color(theme.fg_muted),
+                            )
+                            .padding([2, 6])
+                            .style(move |_| section_header_container(theme))
+                            .into()
+                        } else {
+                            view_negatable_header(
+                                "PORTS",
+                                form,
+                                MatchField::Ports,
+                                theme,
+                                regular_font,
+                            )
+                        },
                         view_ports_summary(form, ports_summary.clone(), theme, regular_font),
                     ]
                     .spacing(4)
                     .width(Length::Fill);
 
-                    if let Some(err) = port_error {
+                    if let Some(err) = port_error.or(icmp_error) {
                         port_col = port_col
                             .push(text(err).size(12).font(regular_font).color(theme.danger));
                     }
@@ -153,18 +182,17 @@ pub fn view_rule_form<'a>(
                 // Source addresses summary button
                 {
                     let mut source_col = column![
-                        container(
-                            text("SOURCE ADDRESS")
-                                .size(11)
-                                .font(regular_font)
-                                .color(theme.fg_muted)
-                        )
-                        .padding([2, 6])
-                        .style(move |_| section_header_container(theme)),
+                        view_negatable_header(
+                            "SOURCE ADDRESS",
+                            form,
+                            MatchField::Sources,
+                            theme,
+                            regular_font,
+                        ),
                         view_summary_button(
                             sources_summary.clone(),
                             HelperType::SourceAddresses,
-                            !form.sources.is_empty(),
+                            !form.sources.is_empty() || form.source_set.is_some(),
                             theme,
                             regular_font,
                         ),
@@ -177,23 +205,30 @@ pub fn view_rule_form<'a>(
                     }
                     source_col
                 },
-                // Interface combo_box(es) with autocomplete (supports wildcards like eth*, docker*)
-                // In server mode: Input + Output interface side by side
+                // Interface summary button(s); the helper suggests system interfaces and
+                // wildcards (eth*, docker*)
+                // In server mode or for FORWARD rules: Input + Output interface side by side
                 // Otherwise: just "INTERFACE" (input only)
                 view_interface_fields(
                     form,
-                    interface_combo,
-                    output_interface_combo,
+                    interface_error,
                     output_interface_error,
                     theme,
                     regular_font,
-                    server_mode,
+                    server_mode || form.chain == crate::core::firewall::Chain::Forward,
                 ),
             ]
             .spacing(6);
 
-            // Chain selection (only visible in Server Mode)
-            if server_mode {
+            // Chain selection (OUTPUT only offered in Server Mode)
+            {
+                let mut chains = vec![
+                    crate::core::firewall::Chain::Input,
+                    crate::core::firewall::Chain::Forward,
+                ];
+                if server_mode {
+                    chains.insert(1, crate::core::firewall::Chain::Output);
+                }
                 context_col = context_col.push(
                     column![
                         container(
@@ -204,25 +239,20 @@ pub fn view_rule_form<'a>(
                         )
                         .padding([2, 6])
                         .style(move |_| section_header_container(theme)),
-                        pick_list(
-                            vec![
-                                crate::core::firewall::Chain::Input,
-                                crate::core::firewall::Chain::Output,
-                            ],
-                            Some(form.chain),
-                            Message::RuleFormChainChanged
-                        )
-                        .width(Length::Fill)
-                        .padding(8)
-                        .font(regular_font)
-                        .style(move |_, status| themed_pick_list(theme, status))
-                        .menu_style(move |_| themed_pick_list_menu(theme))
+                        pick_list(chains, Some(form.chain), Message::RuleFormChainChanged)
+                            .width(Length::Fill)
+                            .padding(8)
+                            .font(regular_font)
+                            .style(move |_, status| themed_pick_list(theme, status))
+                            .menu_style(move |_| themed_pick_list_menu(theme))
                     ]
                     .spacing(4),
                 );
             }
             context_col
         },
+        // Port forwarding (DNAT)
+        view_port_forward_section(form, port_forward_error, theme, regular_font, mono_font),
         // Tags summary button
         column![
             container(
@@ -242,14 +272,23 @@ pub fn view_rule_form<'a>(
             ),
         ]
         .spacing(4),
+        // Description, owner and reference (kept out of nftables)
+        view_metadata_section(form, description, reference_error, theme, regular_font),
         // Advanced Options Section
         view_advanced_section(
             form,
+            source_ports_summary,
+            source_port_error,
             destinations_summary,
             destination_error,
+            countries_error,
             rate_limit_error,
+            quota_error,
             connection_limit_error,
+            per_source_error,
             reject_type_error,
+            schedule_error,
+            expiry_error,
             theme,
             regular_font,
             mono_font,
@@ -278,7 +317,8 @@ pub fn view_rule_form<'a>(
         .into()
 }
 
-/// Renders ports summary button or "Not applicable" for non-port protocols
+/// Renders ports summary button, ICMP types summary button for ICMP/ICMPv6,
+/// or "Not applicable" for other protocols
 fn view_ports_summary<'a>(
     form: &RuleForm,
     summary: String,
@@ -290,7 +330,15 @@ fn view_ports_summary<'a>(
         view_summary_button(
             summary,
             HelperType::Ports,
-            !form.ports.is_empty(),
+            !form.ports.is_empty() || form.port_set.is_some(),
+            theme,
+            regular_font,
+        )
+    } else if protocol_supports_icmp_types(form.protocol) {
+        view_summary_button(
+            helper_modals::icmp_types_summary(&form.icmp_types),
+            HelperType::IcmpTypes,
+            !form.icmp_types.is_empty(),
             theme,
             regular_font,
         )
@@ -347,100 +395,472 @@ fn view_summary_button(
     .into()
 }
 
-/// Interface fields - single or side-by-side based on server mode
+/// Interface fields - single or side-by-side (server mode or FORWARD rules)
+///
+/// Each opens a helper modal, since a rule can match several interfaces.
 fn view_interface_fields<'a>(
     form: &'a RuleForm,
-    interface_combo: &'a combo_box::State<String>,
-    output_interface_combo: &'a combo_box::State<String>,
+    interface_error: Option<&'a String>,
     output_interface_error: Option<&'a String>,
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
-    server_mode: bool,
+    show_output_interface: bool,
 ) -> Element<'a, Message> {
-    let input_label = if server_mode {
+    let input_label = if show_output_interface {
         "INPUT INTERFACE"
     } else {
         "INTERFACE"
     };
 
-    let input_iface_col = column![
+    let mut input_iface_col = column![
+        view_negatable_header(
+            input_label,
+            form,
+            MatchField::Interface,
+            theme,
+            regular_font
+        ),
+        view_summary_button(
+            helper_modals::interfaces_summary(&form.interfaces),
+            HelperType::Interfaces,
+            !form.interfaces.is_empty(),
+            theme,
+            regular_font,
+        ),
+    ]
+    .spacing(4)
+    .width(Length::Fill);
+
+    if let Some(err) = interface_error {
+        input_iface_col =
+            input_iface_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+    }
+
+    if show_output_interface {
+        let mut output_iface_col = column![
+            view_negatable_header(
+                "OUTPUT INTERFACE",
+                form,
+                MatchField::OutputInterface,
+                theme,
+                regular_font,
+            ),
+            view_summary_button(
+                helper_modals::interfaces_summary(&form.output_interfaces),
+                HelperType::OutputInterfaces,
+                !form.output_interfaces.is_empty(),
+                theme,
+                regular_font,
+            ),
+        ]
+        .spacing(4)
+        .width(Length::Fill);
+
+        if let Some(err) = output_interface_error {
+            output_iface_col =
+                output_iface_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+        }
+
+        row![input_iface_col, output_iface_col].spacing(12).into()
+    } else {
+        input_iface_col.into()
+    }
+}
+
+/// Renders a field header with a "NOT" toggle that inverts the field's match
+/// (`!=`). The toggle is inactive while the field is empty.
+fn view_negatable_header<'a>(
+    label: &'a str,
+    form: &'a RuleForm,
+    field: MatchField,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+) -> Element<'a, Message> {
+    let negated = form.negate.get(field);
+    let toggle = button(text("NOT").size(10).font(regular_font))
+        .on_press_maybe(
+            form.has_negation_target(field)
+                .then_some(Message::RuleFormToggleNegate(field)),
+        )
+        .padding([1, 6])
+        .style(move |_, status| {
+            if negated {
+                active_tag_button(theme, status)
+            } else {
+                tag_button(theme, status)
+            }
+        });
+
+    row![
         container(
-            text(input_label)
+            text(label)
                 .size(11)
                 .font(regular_font)
                 .color(theme.fg_muted)
         )
         .padding([2, 6])
         .style(move |_| section_header_container(theme)),
-        combo_box(
-            interface_combo,
-            "Any (type or select)",
-            if form.interface.is_empty() {
-                None
-            } else {
-                Some(&form.interface)
-            },
-            Message::RuleFormInterfaceChanged,
+        Space::new().width(Length::Fill),
+        toggle,
+    ]
+    .align_y(Alignment::Center)
+    .into()
+}
+
+/// Free-text description plus owner and ticket/URL reference
+///
+/// None of these reach the nft comment, so they are not limited like the label.
+fn view_metadata_section<'a>(
+    form: &'a RuleForm,
+    description: &'a text_editor::Content,
+    reference_error: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+) -> Element<'a, Message> {
+    let mut reference_col = column![
+        container(
+            text("REFERENCE")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted)
         )
-        .on_input(Message::RuleFormInterfaceChanged)
-        .padding(8)
-        .font(regular_font)
-        .width(Length::Fill)
-        .input_style(move |_, status| themed_text_input(theme, status))
-        .menu_style(move |_| themed_pick_list_menu(theme))
+        .padding([2, 6])
+        .style(move |_| section_header_container(theme)),
+        text_input("Ticket ID or URL", &form.reference)
+            .on_input(Message::RuleFormReferenceChanged)
+            .padding(8)
+            .font(regular_font)
+            .style(move |_, status| themed_text_input(theme, status)),
     ]
     .spacing(4)
     .width(Length::Fill);
 
-    if server_mode {
-        let mut output_iface_col = column![
+    if let Some(err) = reference_error {
+        reference_col =
+            reference_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+    }
+
+    column![
+        column![
             container(
-                text("OUTPUT INTERFACE")
+                text("DESCRIPTION")
                     .size(11)
                     .font(regular_font)
                     .color(theme.fg_muted)
             )
             .padding([2, 6])
             .style(move |_| section_header_container(theme)),
-            combo_box(
-                output_interface_combo,
-                "Any (type or select)",
-                if form.output_interface.is_empty() {
-                    None
-                } else {
-                    Some(&form.output_interface)
-                },
-                Message::RuleFormOutputInterfaceChanged,
-            )
-            .on_input(Message::RuleFormOutputInterfaceChanged)
-            .padding(8)
-            .font(regular_font)
-            .width(Length::Fill)
-            .input_style(move |_, status| themed_text_input(theme, status))
-            .menu_style(move |_| themed_pick_list_menu(theme))
+            text_editor(description)
+                .placeholder("Why the rule exists, who asked for it...")
+                .on_action(Message::RuleFormDescriptionEdited)
+                .height(64)
+                .padding(8)
+                .size(13)
+                .font(regular_font)
+                .style(move |_, status| themed_text_editor(theme, status)),
         ]
-        .spacing(4)
-        .width(Length::Fill);
+        .spacing(4),
+        row![
+            column![
+                container(
+                    text("OWNER")
+                        .size(11)
+                        .font(regular_font)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                text_input("e.g. Platform team", &form.owner)
+                    .on_input(Message::RuleFormOwnerChanged)
+                    .padding(8)
+                    .font(regular_font)
+                    .style(move |_, status| themed_text_input(theme, status)),
+            ]
+            .spacing(4)
+            .width(Length::Fill),
+            reference_col,
+        ]
+        .spacing(12),
+    ]
+    .spacing(6)
+    .into()
+}
 
-        if let Some(err) = output_interface_error {
-            output_iface_col =
-                output_iface_col.push(text(err).size(12).font(regular_font).color(theme.danger));
-        }
+/// Port forwarding toggle with the internal host and port fields
+fn view_port_forward_section<'a>(
+    form: &'a RuleForm,
+    port_forward_error: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+) -> Element<'a, Message> {
+    let mut col = column![
+        checkbox(form.port_forward_enabled)
+            .label("Forward to Internal Host (DNAT)")
+            .on_toggle(Message::RuleFormTogglePortForward)
+            .size(16)
+            .spacing(8)
+            .text_size(12)
+            .font(regular_font)
+            .style(move |_, status| themed_checkbox(theme, status)),
+    ]
+    .spacing(4);
 
-        row![input_iface_col, output_iface_col].spacing(12).into()
-    } else {
-        input_iface_col.into()
+    if form.port_forward_enabled {
+        col = col.push(
+            row![
+                column![
+                    container(
+                        text("INTERNAL ADDRESS")
+                            .size(11)
+                            .font(regular_font)
+                            .color(theme.fg_muted)
+                    )
+                    .padding([2, 6])
+                    .style(move |_| section_header_container(theme)),
+                    text_input("e.g. 192.168.122.10", &form.forward_address)
+                        .on_input(Message::RuleFormForwardAddressChanged)
+                        .padding(8)
+                        .font(mono_font)
+                        .style(move |_, status| themed_text_input(theme, status)),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+                column![
+                    container(
+                        text("INTERNAL PORT")
+                            .size(11)
+                            .font(regular_font)
+                            .color(theme.fg_muted)
+                    )
+                    .padding([2, 6])
+                    .style(move |_| section_header_container(theme)),
+                    text_input("same as external", &form.forward_port)
+                        .on_input(Message::RuleFormForwardPortChanged)
+                        .padding(8)
+                        .font(mono_font)
+                        .style(move |_, status| themed_text_input(theme, status)),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+            ]
+            .spacing(8),
+        );
+        col = col.push(
+            text("Interface and port above are the external side. Requires IP forwarding (net.ipv4.ip_forward=1).")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted),
+        );
+    }
+    if let Some(err) = port_forward_error {
+        col = col.push(text(err).size(12).font(regular_font).color(theme.danger));
     }
+
+    col.into()
 }
 
-/// Advanced options section with destination, action, reject type, rate limiting, etc.
+/// Schedule toggle with the time window fields and weekday chips
+fn view_schedule_section<'a>(
+    form: &'a RuleForm,
+    schedule_error: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+) -> Element<'a, Message> {
+    let mut col = column![
+        checkbox(form.schedule_enabled)
+            .label("Only Active on a Schedule")
+            .on_toggle(Message::RuleFormToggleSchedule)
+            .size(16)
+            .spacing(8)
+            .text_size(12)
+            .font(regular_font)
+            .style(move |_, status| themed_checkbox(theme, status)),
+    ]
+    .spacing(4);
+
+    if form.schedule_enabled {
+        col = col.push(
+            row![
+                column![
+                    container(
+                        text("FROM")
+                            .size(11)
+                            .font(regular_font)
+                            .color(theme.fg_muted)
+                    )
+                    .padding([2, 6])
+                    .style(move |_| section_header_container(theme)),
+                    text_input("HH:MM", &form.schedule_start)
+                        .on_input(Message::RuleFormScheduleStartChanged)
+                        .padding(8)
+                        .font(mono_font)
+                        .style(move |_, status| themed_text_input(theme, status)),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+                column![
+                    container(
+                        text("UNTIL")
+                            .size(11)
+                            .font(regular_font)
+                            .color(theme.fg_muted)
+                    )
+                    .padding([2, 6])
+                    .style(move |_| section_header_container(theme)),
+                    text_input("HH:MM", &form.schedule_end)
+                        .on_input(Message::RuleFormScheduleEndChanged)
+                        .padding(8)
+                        .font(mono_font)
+                        .style(move |_, status| themed_text_input(theme, status)),
+                ]
+                .spacing(4)
+                .width(Length::Fill),
+            ]
+            .spacing(8),
+        );
+        col = col.push(
+            row(Weekday::iter().map(|day| {
+                let selected = form.schedule_days.contains(&day);
+                button(text(day.short_name()).size(11).font(regular_font))
+                    .on_press(Message::RuleFormToggleScheduleDay(day))
+                    .padding([4, 8])
+                    .style(move |_, status| {
+                        if selected {
+                            active_tag_button(theme, status)
+                        } else {
+                            tag_button(theme, status)
+                        }
+                    })
+                    .into()
+            }))
+            .spacing(6),
+        );
+        col = col.push(
+            text("Local time. A window ending before it starts wraps past midnight; equal times mean all day. No days selected = every day.")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted),
+        );
+    }
+    if let Some(err) = schedule_error {
+        col = col.push(text(err).size(12).font(regular_font).color(theme.danger));
+    }
+
+    col.into()
+}
+
+fn view_expiry_section<'a>(
+    form: &'a RuleForm,
+    expiry_error: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+    mono_font: iced::Font,
+) -> Element<'a, Message> {
+    let mut col = column![
+        checkbox(form.expiry_enabled)
+            .label("Expire on a Date")
+            .on_toggle(Message::RuleFormToggleExpiry)
+            .size(16)
+            .spacing(8)
+            .text_size(12)
+            .font(regular_font)
+            .style(move |_, status| themed_checkbox(theme, status)),
+    ]
+    .spacing(4);
+
+    if form.expiry_enabled {
+        col = col.push(
+            text_input("YYYY-MM-DD HH:MM or 7d", &form.expiry)
+                .on_input(Message::RuleFormExpiryChanged)
+                .padding(8)
+                .font(mono_font)
+                .style(move |_, status| themed_text_input(theme, status)),
+        );
+        col = col.push(
+            text("Local time. A bare date lasts until the end of that day; a duration counts from when the rule is saved. Expired rules are skipped on the next apply.")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted),
+        );
+    }
+    if let Some(err) = expiry_error {
+        col = col.push(text(err).size(12).font(regular_font).color(theme.danger));
+    }
+
+    col.into()
+}
+
+/// Renders the source country picker with the selected countries as removable chips
+fn view_countries_section<'a>(
+    form: &'a RuleForm,
+    countries_error: Option<&'a String>,
+    theme: &'a crate::theme::AppTheme,
+    regular_font: iced::Font,
+) -> Element<'a, Message> {
+    let mut col = column![
+        container(
+            text("SOURCE COUNTRIES")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted)
+        )
+        .padding([2, 6])
+        .style(move |_| section_header_container(theme)),
+        pick_list(COUNTRIES, None::<Country>, Message::RuleFormCountryAdded)
+            .placeholder("Add country...")
+            .width(Length::Fill)
+            .padding(8)
+            .font(regular_font)
+            .style(move |_, status| themed_pick_list(theme, status))
+            .menu_style(move |_| themed_pick_list_menu(theme)),
+    ]
+    .spacing(4);
+
+    if !form.countries.is_empty() {
+        col = col.push(
+            row(form.countries.iter().map(|code| {
+                let label = country_name(code)
+                    .map_or_else(|| format!("{code} ×"), |name| format!("{name} ({code}) ×"));
+                button(text(label).size(11).font(regular_font))
+                    .on_press(Message::RuleFormCountryRemoved(code.clone()))
+                    .padding([4, 8])
+                    .style(move |_, status| active_tag_button(theme, status))
+                    .into()
+            }))
+            .spacing(6)
+            .wrap(),
+        );
+        col = col.push(
+            text("Matched against the GeoIP database set in Settings")
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted),
+        );
+    }
+    if let Some(err) = countries_error {
+        col = col.push(text(err).size(12).font(regular_font).color(theme.danger));
+    }
+
+    col.into()
+}
+
+/// Advanced options section with source ports, destination, action, reject type, rate limiting, etc.
 fn view_advanced_section<'a>(
     form: &'a RuleForm,
+    source_ports_summary: String,
+    source_port_error: Option<&'a String>,
     destinations_summary: String,
     destination_error: Option<&'a String>,
+    countries_error: Option<&'a String>,
     rate_limit_error: Option<&'a String>,
+    quota_error: Option<&'a String>,
     connection_limit_error: Option<&'a String>,
+    per_source_error: Option<&'a String>,
     reject_type_error: Option<&'a String>,
+    schedule_error: Option<&'a String>,
+    expiry_error: Option<&'a String>,
     theme: &'a crate::theme::AppTheme,
     regular_font: iced::Font,
     mono_font: iced::Font,
@@ -458,20 +878,45 @@ fn view_advanced_section<'a>(
     .spacing(6);
 
     if form.show_advanced {
+        // Source ports (e.g. replies from a fixed-port server)
+        if protocol_supports_ports(form.protocol) {
+            let mut sport_col = column![
+                view_negatable_header(
+                    "SOURCE PORTS",
+                    form,
+                    MatchField::SourcePorts,
+                    theme,
+                    regular_font,
+                ),
+                view_summary_button(
+                    source_ports_summary,
+                    HelperType::SourcePorts,
+                    !form.source_ports.is_empty(),
+                    theme,
+                    regular_font,
+                ),
+            ]
+            .spacing(4);
+            if let Some(err) = source_port_error {
+                sport_col =
+                    sport_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+            }
+            adv_col = adv_col.push(sport_col);
+        }
+
         // Destination addresses
         let mut dest_col = column![
-            container(
-                text("DESTINATION ADDRESS")
-                    .size(11)
-                    .font(regular_font)
-                    .color(theme.fg_muted)
-            )
-            .padding([2, 6])
-            .style(move |_| section_header_container(theme)),
+            view_negatable_header(
+                "DESTINATION ADDRESS",
+                form,
+                MatchField::Destinations,
+                theme,
+                regular_font,
+            ),
             view_summary_button(
                 destinations_summary,
                 HelperType::DestinationAddresses,
-                !form.destinations.is_empty(),
+                !form.destinations.is_empty() || form.destination_set.is_some(),
                 theme,
                 regular_font,
             ),
@@ -482,6 +927,14 @@ fn view_advanced_section<'a>(
         }
         adv_col = adv_col.push(dest_col);
 
+        // Source countries (resolved through the GeoIP database)
+        adv_col = adv_col.push(view_countries_section(
+            form,
+            countries_error,
+            theme,
+            regular_font,
+        ));
+
         // Action and Reject Type (side by side when Reject is selected)
         {
             let action_col = column![
@@ -588,6 +1041,23 @@ fn view_advanced_section<'a>(
                     ]
                     .spacing(4)
                     .width(Length::Fill),
+                    column![
+                        container(text("OF").size(11).font(regular_font).color(theme.fg_muted))
+                            .padding([2, 6])
+                            .style(move |_| section_header_container(theme)),
+                        pick_list(
+                            RateMeasure::ALL,
+                            Some(form.rate_limit_measure),
+                            Message::RuleFormRateLimitMeasureChanged
+                        )
+                        .width(Length::Fill)
+                        .padding(8)
+                        .font(regular_font)
+                        .style(move |_, status| themed_pick_list(theme, status))
+                        .menu_style(move |_| themed_pick_list_menu(theme))
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
                     column![
                         container(
                             text("PER")
@@ -635,6 +1105,26 @@ fn view_advanced_section<'a>(
                 ]
                 .spacing(8),
             );
+            rate_limit_col = rate_limit_col.push(
+                checkbox(form.rate_limit_over)
+                    .label("Match only traffic over the rate")
+                    .on_toggle(Message::RuleFormToggleRateLimitOver)
+                    .size(16)
+                    .spacing(8)
+                    .text_size(12)
+                    .font(regular_font)
+                    .style(move |_, status| themed_checkbox(theme, status)),
+            );
+            rate_limit_col = rate_limit_col.push(
+                text(if form.rate_limit_over {
+                    "Traffic above the rate matches (use with Drop or Reject)"
+                } else {
+                    "Traffic below the rate matches; the excess falls through to later rules"
+                })
+                .size(11)
+                .font(regular_font)
+                .color(theme.fg_muted),
+            );
         }
         if let Some(err) = rate_limit_error {
             rate_limit_col =
@@ -642,6 +1132,97 @@ fn view_advanced_section<'a>(
         }
         adv_col = adv_col.push(rate_limit_col);
 
+        // Byte quota
+        let mut quota_col = column![
+            checkbox(form.quota_enabled)
+                .label("Enable Byte Quota")
+                .on_toggle(Message::RuleFormToggleQuota)
+                .size(16)
+                .spacing(8)
+                .text_size(12)
+                .font(regular_font)
+                .style(move |_, status| themed_checkbox(theme, status)),
+        ]
+        .spacing(4);
+        if form.quota_enabled {
+            quota_col = quota_col.push(
+                row![
+                    column![
+                        container(
+                            text("MATCH")
+                                .size(11)
+                                .font(regular_font)
+                                .color(theme.fg_muted)
+                        )
+                        .padding([2, 6])
+                        .style(move |_| section_header_container(theme)),
+                        pick_list(
+                            QuotaMode::iter().collect::<Vec<_>>(),
+                            Some(form.quota_mode),
+                            Message::RuleFormQuotaModeChanged
+                        )
+                        .width(Length::Fill)
+                        .padding(8)
+                        .font(regular_font)
+                        .style(move |_, status| themed_pick_list(theme, status))
+                        .menu_style(move |_| themed_pick_list_menu(theme))
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                    column![
+                        container(
+                            text("AMOUNT")
+                                .size(11)
+                                .font(regular_font)
+                                .color(theme.fg_muted)
+                        )
+                        .padding([2, 6])
+                        .style(move |_| section_header_container(theme)),
+                        text_input("e.g. 5", &form.quota_amount)
+                            .on_input(Message::RuleFormQuotaAmountChanged)
+                            .padding(8)
+                            .font(mono_font)
+                            .style(move |_, status| themed_text_input(theme, status)),
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                    column![
+                        container(
+                            text("UNIT")
+                                .size(11)
+                                .font(regular_font)
+                                .color(theme.fg_muted)
+                        )
+                        .padding([2, 6])
+                        .style(move |_| section_header_container(theme)),
+                        pick_list(
+                            ByteUnit::iter().collect::<Vec<_>>(),
+                            Some(form.quota_unit),
+                            Message::RuleFormQuotaUnitChanged
+                        )
+                        .width(Length::Fill)
+                        .padding(8)
+                        .font(regular_font)
+                        .style(move |_, status| themed_pick_list(theme, status))
+                        .menu_style(move |_| themed_pick_list_menu(theme))
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                ]
+                .spacing(8),
+            );
+            quota_col = quota_col.push(
+                text("\"until\" matches until the quota is used up, \"over\" only after. Usage is kept across applies.")
+                    .size(11)
+                    .font(regular_font)
+                    .color(theme.fg_muted),
+            );
+        }
+        if let Some(err) = quota_error {
+            quota_col = quota_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+        }
+        adv_col = adv_col.push(quota_col);
+
         // Connection Limiting
         let mut conn_col = column![
             container(
@@ -664,6 +1245,119 @@ fn view_advanced_section<'a>(
         }
         adv_col = adv_col.push(conn_col);
 
+        // Per-source tracking of the rate and connection limits
+        let mut per_source_col = column![
+            checkbox(form.per_source_enabled)
+                .label("Apply limits per source address")
+                .on_toggle(Message::RuleFormTogglePerSource)
+                .size(16)
+                .spacing(8)
+                .text_size(12)
+                .font(regular_font)
+                .style(move |_, status| themed_checkbox(theme, status)),
+        ]
+        .spacing(4);
+        if form.per_source_enabled {
+            per_source_col = per_source_col.push(
+                row![
+                    column![
+                        container(
+                            text("TRACKED ADDRESSES")
+                                .size(11)
+                                .font(regular_font)
+                                .color(theme.fg_muted)
+                        )
+                        .padding([2, 6])
+                        .style(move |_| section_header_container(theme)),
+                        text_input("65535", &form.per_source_size)
+                            .on_input(Message::RuleFormPerSourceSizeChanged)
+                            .padding(8)
+                            .font(mono_font)
+                            .style(move |_, status| themed_text_input(theme, status)),
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                    column![
+                        container(
+                            text("IDLE TIMEOUT (S)")
+                                .size(11)
+                                .font(regular_font)
+                                .color(theme.fg_muted)
+                        )
+                        .padding([2, 6])
+                        .style(move |_| section_header_container(theme)),
+                        text_input("60", &form.per_source_timeout)
+                            .on_input(Message::RuleFormPerSourceTimeoutChanged)
+                            .padding(8)
+                            .font(mono_font)
+                            .style(move |_, status| themed_text_input(theme, status)),
+                    ]
+                    .spacing(4)
+                    .width(Length::Fill),
+                ]
+                .spacing(8),
+            );
+            per_source_col = per_source_col.push(
+                text("Each client gets its own allowance instead of sharing one")
+                    .size(11)
+                    .font(regular_font)
+                    .color(theme.fg_muted),
+            );
+        }
+        if let Some(err) = per_source_error {
+            per_source_col =
+                per_source_col.push(text(err).size(12).font(regular_font).color(theme.danger));
+        }
+        adv_col = adv_col.push(per_source_col);
+
+        // Connection state (none selected = any state)
+        adv_col = adv_col.push(
+            column![
+                container(
+                    text("CONNECTION STATE")
+                        .size(11)
+                        .font(regular_font)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                row(CtState::iter().map(|ct_state| {
+                    let selected = form.ct_states.contains(&ct_state);
+                    button(text(ct_state.to_string()).size(11).font(regular_font))
+                        .on_press(Message::RuleFormToggleCtState(ct_state))
+                        .padding([4, 8])
+                        .style(move |_, status| {
+                            if selected {
+                                active_tag_button(theme, status)
+                            } else {
+                                tag_button(theme, status)
+                            }
+                        })
+                        .into()
+                }))
+                .spacing(6),
+            ]
+            .spacing(4),
+        );
+
+        // Time-of-day / weekday schedule
+        adv_col = adv_col.push(view_schedule_section(
+            form,
+            schedule_error,
+            theme,
+            regular_font,
+            mono_font,
+        ));
+
+        // Expiry for temporary access
+        adv_col = adv_col.push(view_expiry_section(
+            form,
+            expiry_error,
+            theme,
+            regular_font,
+            mono_font,
+        ));
+
         // Per-rule logging
         adv_col = adv_col.push(
             checkbox(form.log_enabled)
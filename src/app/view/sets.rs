@@ -0,0 +1,274 @@
+//! Named set manager modal
+//!
+//! Lists named address/port sets with their element counts and the rules
+//! referencing them, with an inline editor for creating and editing sets.
+
+use crate::app::ui_components::{
+    card_button, card_container, danger_button, inset_container_bordered, primary_button,
+    secondary_button, section_header_container, themed_pick_list, themed_pick_list_menu,
+    themed_scrollable, themed_text_input,
+};
+use crate::app::{Message, SetEditorState, SetManagerState, State};
+use crate::core::firewall::{NamedSet, SetType};
+use iced::widget::text::Wrapping;
+use iced::widget::{
+    button, column, container, pick_list, row, scrollable, space, text, text_input,
+};
+use iced::{Alignment, Element, Length};
+
+pub fn view_set_manager<'a>(state: &'a State, mgr: &'a SetManagerState) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let sets_list: Element<'_, Message> = if state.ruleset.sets.is_empty() {
+        text("No named sets yet. Sets let many rules share one address or port list.")
+            .size(12)
+            .font(state.font_regular)
+            .color(theme.fg_muted)
+            .into()
+    } else {
+        let mut list = column![].spacing(6);
+        for set in &state.ruleset.sets {
+            let item: Element<'_, Message> = if let Some(del_name) = &mgr.deleting_name
+                && del_name == &set.name
+            {
+                // Delete confirmation mode
+                container(
+                    row![
+                        text("Delete this set?")
+                            .size(12)
+                            .font(state.font_regular)
+                            .color(theme.danger)
+                            .width(Length::Fill),
+                        button(text("Cancel").size(12).font(state.font_regular))
+                            .on_press(Message::CancelDeleteSet)
+                            .padding([4, 10])
+                            .style(move |_, status| secondary_button(theme, status)),
+                        button(text("Delete").size(12).font(state.font_regular))
+                            .on_press(Message::ConfirmDeleteSet)
+                            .padding([4, 10])
+                            .style(move |_, status| danger_button(theme, status)),
+                    ]
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .padding(8.0),
+                )
+                .style(move |_| card_container(theme))
+                .into()
+            } else {
+                view_set_row(state, set)
+            };
+            list = list.push(item);
+        }
+
+        // Wrap scrollable in bordered container
+        container(
+            scrollable(container(list).width(Length::Fill).padding(8))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(0),
+                ))
+                .style(move |_, status| themed_scrollable(theme, status)),
+        )
+        .height(Length::Fixed(260.0))
+        .width(Length::Fill)
+        .style(move |_| inset_container_bordered(theme))
+        .into()
+    };
+
+    let footer_action: Element<'_, Message> = if let Some(editor) = &mgr.editor {
+        view_set_editor(state, editor)
+    } else {
+        button(text("+ New Set").size(12).font(state.font_regular))
+            .on_press(Message::NewSetClicked)
+            .padding([8, 12])
+            .style(move |_, status| primary_button(theme, status))
+            .into()
+    };
+
+    container(
+        column![
+            container(
+                text("Named Sets")
+                    .size(18)
+                    .font(state.font_regular)
+                    .color(theme.fg_primary)
+            )
+            .padding([4, 8])
+            .style(move |_| section_header_container(theme)),
+            sets_list,
+            footer_action,
+            row![
+                container(
+                    text(format!("{} sets", state.ruleset.sets.len()))
+                        .size(10)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted)
+                )
+                .padding([2, 6])
+                .style(move |_| section_header_container(theme)),
+                space::Space::new().width(Length::Fill),
+                button(text("Close").size(14).font(state.font_regular))
+                    .on_press(Message::CloseSetManager)
+                    .padding([10, 20])
+                    .style(move |_, status| secondary_button(theme, status)),
+            ]
+            .align_y(Alignment::Center)
+        ]
+        .spacing(16)
+        .padding(24)
+        .width(Length::Fixed(550.0)),
+    )
+    .style(move |_| card_container(theme))
+    .into()
+}
+
+/// Renders a set entry: name, type, element count and referencing rules
+fn view_set_row<'a>(state: &'a State, set: &'a NamedSet) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let users: Vec<&str> = state
+        .ruleset
+        .rules_referencing_set(&set.name)
+        .map(|r| {
+            if r.label.is_empty() {
+                "Unnamed Rule"
+            } else {
+                r.label.as_str()
+            }
+        })
+        .collect();
+    let used_by = if users.is_empty() {
+        "Not used by any rule".to_string()
+    } else {
+        format!("Used by: {}", users.join(", "))
+    };
+    let in_use = !users.is_empty();
+
+    button(
+        row![
+            column![
+                row![
+                    text(format!("@{}", set.name))
+                        .size(13)
+                        .font(state.font_mono)
+                        .color(theme.fg_primary),
+                    text(format!(
+                        "{} · {} elements",
+                        set.set_type.display_name(),
+                        set.element_count()
+                    ))
+                    .size(11)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted),
+                ]
+                .spacing(8)
+                .align_y(Alignment::Center),
+                text(used_by)
+                    .size(11)
+                    .font(state.font_regular)
+                    .color(if in_use {
+                        theme.fg_secondary
+                    } else {
+                        theme.fg_muted
+                    })
+                    .wrapping(Wrapping::WordOrGlyph),
+            ]
+            .spacing(2)
+            .width(Length::Fill),
+            button(text("✎").size(14).color(theme.fg_muted))
+                .on_press(Message::EditSetClicked(set.name.clone()))
+                .style(button::text),
+            // Referenced sets cannot be deleted (would leave dangling lookups)
+            if in_use {
+                button(text("").size(14)).style(button::text) // Placeholder for alignment
+            } else {
+                button(text("×").size(14).color(theme.fg_muted))
+                    .on_press(Message::DeleteSetRequested(set.name.clone()))
+                    .padding(6)
+                    .style(button::text)
+            },
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding([6, 10]),
+    )
+    .width(Length::Fill)
+    .on_press(Message::EditSetClicked(set.name.clone()))
+    .style(move |_, status| card_button(theme, status))
+    .into()
+}
+
+/// Renders the inline create/edit form
+fn view_set_editor<'a>(state: &'a State, editor: &'a SetEditorState) -> Element<'a, Message> {
+    let theme = &state.theme;
+
+    let placeholder = match editor.set_type {
+        SetType::Ipv4Addr => "e.g. 10.0.0.0/8, 192.168.1.100-192.168.1.150, backup.lan",
+        SetType::Ipv6Addr => "e.g. fd00::/8, 2001:db8::1",
+        SetType::InetService => "e.g. 22, 80, 443, 8000-8080",
+    };
+
+    let mut content = column![
+        row![
+            text_input("Set name...", &editor.name)
+                .on_input(Message::SetEditorNameChanged)
+                .padding(8)
+                .font(state.font_mono)
+                .style(move |_, status| themed_text_input(theme, status))
+                .width(Length::Fill),
+            pick_list(
+                [SetType::Ipv4Addr, SetType::Ipv6Addr, SetType::InetService],
+                Some(editor.set_type),
+                Message::SetEditorTypeChanged
+            )
+            .padding(8)
+            .font(state.font_regular)
+            .style(move |_, status| themed_pick_list(theme, status))
+            .menu_style(move |_| themed_pick_list_menu(theme)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+        text_input(placeholder, &editor.elements)
+            .on_input(Message::SetEditorElementsChanged)
+            .on_submit(Message::SaveSetEditor)
+            .padding(8)
+            .font(state.font_mono)
+            .style(move |_, status| themed_text_input(theme, status)),
+    ]
+    .spacing(8);
+
+    if let Some(err) = &editor.error {
+        content = content.push(
+            text(err)
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger),
+        );
+    }
+
+    content = content.push(
+        row![
+            space::Space::new().width(Length::Fill),
+            button(text("Cancel").size(12).font(state.font_regular))
+                .on_press(Message::CancelSetEditor)
+                .style(move |_, status| secondary_button(theme, status)),
+            button(
+                text(if editor.original_name.is_some() {
+                    "Update"
+                } else {
+                    "Create"
+                })
+                .size(12)
+                .font(state.font_regular)
+            )
+            .on_press(Message::SaveSetEditor)
+            .style(move |_, status| primary_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center),
+    );
+
+    container(content)
+        .padding(12)
+        .style(move |_| card_container(theme))
+        .into()
+}
@@ -181,6 +181,40 @@ pub fn view_settings(state: &State) -> Element<'_, Message> {
             state.font_regular,
         ));
 
+        let geoip_label = state.geoip_database.as_ref().map_or_else(
+            || "Choose file...".to_string(),
+            |p| crate::app::helpers::truncate_path_smart(&p.to_string_lossy(), 36),
+        );
+        let mut geoip_control = row![
+            button(
+                text(geoip_label)
+                    .size(13)
+                    .font(state.font_regular)
+                    .wrapping(Wrapping::None)
+            )
+            .on_press(Message::BrowseGeoIpDatabaseClicked)
+            .width(Length::Fill)
+            .padding(8)
+            .style(move |_, status| secondary_button(theme, status))
+        ]
+        .spacing(4)
+        .align_y(Alignment::Center);
+        if state.geoip_database.is_some() {
+            geoip_control = geoip_control.push(
+                button(text("×").size(14).color(theme.fg_muted))
+                    .on_press(Message::GeoIpDatabaseChanged(None))
+                    .padding(6)
+                    .style(button::text),
+            );
+        }
+        behavior_items.push(render_settings_row(
+            "GeoIP database",
+            "Local MaxMind .mmdb or CSV file used by country-based rules",
+            geoip_control.into(),
+            theme,
+            state.font_regular,
+        ));
+
         container(column![
             container(
                 text("BEHAVIOR")
@@ -298,6 +332,18 @@ pub fn view_settings(state: &State) -> Element<'_, Message> {
             );
         }
 
+        security_items.push(render_settings_row(
+            "Per-rule counters",
+            "Show packets/bytes matched by each rule in the sidebar (reads nft every 5s)",
+            toggler(advanced.rule_counters)
+                .on_toggle(Message::ToggleRuleCounters)
+                .width(Length::Shrink)
+                .style(move |_, status| themed_toggler(theme, status))
+                .into(),
+            theme,
+            state.font_regular,
+        ));
+
         security_items.push(render_settings_row(
             "Server Mode",
             "Block all outbound connections by default (recommended for servers)",
@@ -327,7 +373,92 @@ pub fn view_settings(state: &State) -> Element<'_, Message> {
         .style(move |_| card_container(theme))
     };
 
-    column![appearance_card, behavior_card, security_card,]
+    let sharing_card = {
+        let sharing = &advanced.sharing;
+        let mut sharing_items: Vec<Element<'_, Message>> = vec![render_settings_row(
+            "Internet sharing",
+            "Route and masquerade a downstream network through the upstream connection",
+            toggler(sharing.enabled)
+                .on_toggle(Message::ToggleSharing)
+                .width(Length::Shrink)
+                .style(move |_, status| themed_toggler(theme, status))
+                .into(),
+            theme,
+            state.font_regular,
+        )];
+
+        if sharing.enabled {
+            sharing_items.push(render_settings_row(
+                "   └ Upstream interface",
+                "Interface with the internet connection",
+                text_input("e.g. wlan0", &sharing.upstream_interface)
+                    .on_input(Message::SharingUpstreamChanged)
+                    .padding(8)
+                    .size(13)
+                    .font(state.font_mono)
+                    .style(move |_, status| themed_text_input(theme, status))
+                    .into(),
+                theme,
+                state.font_regular,
+            ));
+            sharing_items.push(render_settings_row(
+                "   └ Downstream interface",
+                "Interface of the shared network (switch, hotspot)",
+                text_input("e.g. eth0", &sharing.downstream_interface)
+                    .on_input(Message::SharingDownstreamChanged)
+                    .padding(8)
+                    .size(13)
+                    .font(state.font_mono)
+                    .style(move |_, status| themed_text_input(theme, status))
+                    .into(),
+                theme,
+                state.font_regular,
+            ));
+            sharing_items.push(render_settings_row(
+                "   └ Allow DHCP/DNS",
+                "Let downstream clients use this host's DHCP and DNS services",
+                toggler(sharing.allow_dhcp_dns)
+                    .on_toggle(Message::ToggleSharingDhcpDns)
+                    .width(Length::Shrink)
+                    .style(move |_, status| themed_toggler(theme, status))
+                    .into(),
+                theme,
+                state.font_regular,
+            ));
+            if !sharing.is_active() {
+                sharing_items.push(
+                    text("Set two different interfaces to activate sharing.")
+                        .size(12)
+                        .font(state.font_regular)
+                        .color(theme.warning)
+                        .into(),
+                );
+            }
+            sharing_items.push(
+                text("Requires IP forwarding (sysctl net.ipv4.ip_forward=1).")
+                    .size(12)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted)
+                    .into(),
+            );
+        }
+
+        container(column![
+            container(
+                text("INTERNET SHARING")
+                    .size(12)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted)
+            )
+            .padding([8, 12])
+            .width(Length::Fill)
+            .style(move |_| section_header_container(theme)),
+            Column::with_children(sharing_items).spacing(16).padding(16)
+        ])
+        .style(move |_| card_container(theme))
+    };
+
+    column![appearance_card, behavior_card, security_card, sharing_card,]
         .spacing(24)
         .into()
 }
@@ -6,13 +6,16 @@ use crate::app::ui_components::{
     tag_button, themed_checkbox, themed_horizontal_rule, themed_scrollable, themed_text_input,
 };
 use crate::app::{Message, State};
+use crate::core::firewall::{Rule, RuleGroup};
 use iced::widget::text::Wrapping;
 use iced::widget::{
     Id, button, checkbox, column, container, mouse_area, row, rule, scrollable, text, text_input,
     tooltip,
 };
 use iced::{Alignment, Border, Color, Element, Length};
+use std::collections::HashMap;
 use std::sync::Arc;
+use uuid::Uuid;
 
 pub fn view_sidebar(state: &State) -> Element<'_, Message> {
     let theme = &state.theme;
@@ -150,6 +153,66 @@ pub fn view_sidebar(state: &State) -> Element<'_, Message> {
         .into()
     };
 
+    // Named sets: chips filter the rule list to rules referencing the set
+    // Performance: Use cached "name (N)" chip labels (avoids format! every frame)
+    let mut set_chips: Vec<Element<'_, Message>> = Vec::with_capacity(state.ruleset.sets.len());
+    for (set, chip) in state.ruleset.sets.iter().zip(&state.cached_set_chips) {
+        let is_selected = state.filter_set.as_ref() == Some(&set.name);
+        set_chips.push(
+            button(text(chip).size(10).font(state.font_mono))
+                .on_press(Message::FilterBySet(if is_selected {
+                    None
+                } else {
+                    Some(set.name.clone())
+                }))
+                .padding([4, 8])
+                .style(move |_, status| {
+                    if is_selected {
+                        active_tag_button(theme, status)
+                    } else {
+                        tag_button(theme, status)
+                    }
+                })
+                .into(),
+        );
+    }
+
+    let mut sets_section = column![
+        row![
+            container(
+                text("SETS")
+                    .size(9)
+                    .font(state.font_mono)
+                    .color(theme.fg_muted)
+            )
+            .padding([2, 6])
+            .style(move |_| section_header_container(theme)),
+            container(row![]).width(Length::Fill),
+            button(
+                text("Manage ⚙")
+                    .size(10)
+                    .font(state.font_regular)
+                    .color(theme.fg_muted)
+            )
+            .on_press(Message::OpenSetManager)
+            .padding([2, 6])
+            .style(button::text),
+        ]
+        .align_y(Alignment::Center),
+    ]
+    .spacing(8);
+
+    if !set_chips.is_empty() {
+        sets_section = sets_section.push(
+            container(row(set_chips).spacing(6).wrap())
+                .width(Length::Fill)
+                .max_height(80)
+                .padding(6)
+                .clip(true)
+                .style(move |_| inset_container(theme)),
+        );
+    }
+
     let search_area = column![
         text_input("Search rules...", &state.rule_search)
             .on_input(Message::RuleSearchChanged)
@@ -158,11 +221,12 @@ pub fn view_sidebar(state: &State) -> Element<'_, Message> {
             .font(state.font_regular)
             .style(move |_, status| themed_text_input(theme, status)),
         tag_cloud,
+        sets_section,
     ]
     .spacing(16);
 
     // 4. Rule List Header
-    let list_header = row![
+    let mut list_header = row![
         container(
             text("RULES")
                 .size(9)
@@ -172,13 +236,72 @@ pub fn view_sidebar(state: &State) -> Element<'_, Message> {
         .padding([2, 6])
         .style(move |_| section_header_container(theme)),
         container(row![]).width(Length::Fill),
-        // Performance: Use cached filter_count_display (avoids format! every frame)
+        button(
+            text("Groups ⚙")
+                .size(9)
+                .font(state.font_regular)
+                .color(theme.fg_muted)
+        )
+        .on_press(Message::OpenGroupManager)
+        .padding([0, 4])
+        .style(button::text),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+    let expired_count = state.ruleset.expired_rules(chrono::Utc::now()).count();
+    if expired_count > 0 {
+        list_header = list_header.push(
+            button(
+                text(format!("Remove expired ({expired_count})"))
+                    .size(9)
+                    .font(state.font_regular)
+                    .color(theme.danger),
+            )
+            .on_press(Message::RemoveExpiredRules)
+            .padding([0, 4])
+            .style(button::text),
+        );
+    }
+    if state.counters_active() {
+        // Counters are polled; a manual refresh reads now and resumes after a failed read
+        list_header = list_header.push(
+            button(
+                text(if state.rule_counters.loading {
+                    "Reading counters…"
+                } else if state.rule_counters.paused {
+                    "Resume counters"
+                } else {
+                    "Refresh counters"
+                })
+                .size(9)
+                .font(state.font_regular)
+                .color(theme.fg_muted),
+            )
+            .on_press_maybe((!state.rule_counters.loading).then_some(Message::RefreshRuleCounters))
+            .padding([0, 4])
+            .style(button::text),
+        );
+        if !state.rule_counters.values.is_empty() {
+            list_header = list_header.push(
+                button(
+                    text("Reset counters")
+                        .size(9)
+                        .font(state.font_regular)
+                        .color(theme.fg_muted),
+                )
+                .on_press(Message::ResetRuleCounters)
+                .padding([0, 4])
+                .style(button::text),
+            );
+        }
+    }
+    // Performance: Use cached filter_count_display (avoids format! every frame)
+    let list_header = list_header.push(
         text(&state.filter_count_display)
             .size(9)
             .font(state.font_mono)
             .color(theme.fg_muted),
-    ]
-    .align_y(Alignment::Center);
+    );
 
     // 5. Rule List (Scrollable)
     let rule_list: Element<'_, Message> = if filtered_rules.is_empty() {
@@ -196,91 +319,468 @@ pub fn view_sidebar(state: &State) -> Element<'_, Message> {
         .center_x(Length::Fill)
         .into()
     } else {
+        // Group members are listed under their group's header, at the position of
+        // the group's first member (where the group's chain is jumped to)
+        let mut members: HashMap<Uuid, Vec<&Rule>> = HashMap::new();
+        for rule in &filtered_rules {
+            if let Some(group) = state.ruleset.group_of(rule) {
+                members.entry(group.id).or_default().push(rule);
+            }
+        }
+
         // Phase 5: Pre-allocate Vec for better performance
-        let mut rule_cards = Vec::with_capacity(filtered_rules.len());
-
-        for rule in filtered_rules {
-            // ... (Rule card logic remains the same)
-            let is_editing = state.rule_form.as_ref().and_then(|f| f.id) == Some(rule.id);
-            let is_deleting = state.deleting_id == Some(rule.id);
-            let is_being_dragged = state.dragged_rule_id == Some(rule.id);
-            let any_drag_active = state.dragged_rule_id.is_some();
-            let is_hover_target = state.hovered_drop_target_id == Some(rule.id);
-            // When drag is active and this card is a potential drop target,
-            // all click handlers should trigger RuleDropped instead of their normal action
-            let is_drop_target = any_drag_active && !is_being_dragged;
-
-            let card_content: Element<'_, Message> = if is_deleting {
+        let mut rule_cards = Vec::with_capacity(filtered_rules.len() + state.ruleset.groups.len());
+
+        for rule in &filtered_rules {
+            let Some(group) = state.ruleset.group_of(rule) else {
+                rule_cards.push(view_rule_card(state, rule));
+                continue;
+            };
+            let Some(group_rules) = members.remove(&group.id) else {
+                continue; // Already listed with the first member
+            };
+            rule_cards.push(view_group_header(state, group, group_rules.len()));
+            if !state.collapsed_groups.contains(&group.id) {
+                let cards: Vec<Element<'_, Message>> = group_rules
+                    .into_iter()
+                    .map(|r| view_rule_card(state, r))
+                    .collect();
+                rule_cards.push(
+                    container(column(cards).spacing(8))
+                        .padding(iced::Padding::new(0.0).left(12.0))
+                        .into(),
+                );
+            }
+        }
+
+        // Empty groups stay listed as drop targets
+        if state.filter_tag.is_none() && state.filter_set.is_none() && state.rule_search.is_empty()
+        {
+            for group in &state.ruleset.groups {
+                if state.ruleset.group_members(group.id).next().is_none() {
+                    rule_cards.push(view_group_header(state, group, 0));
+                }
+            }
+        }
+
+        // Build column from pre-allocated Vec
+        column(rule_cards).spacing(8).into()
+    };
+
+    // 6. Sidebar Footer (Pinned Action)
+    let footer = column![
+        rule::horizontal(1).style(move |_| themed_horizontal_rule(theme)),
+        container(
+            button(
                 row![
-                    text("Delete this rule?")
-                        .size(12)
-                        .font(state.font_regular)
-                        .color(theme.danger)
-                        .width(Length::Fill),
-                    button(text("Cancel").size(12).font(state.font_regular))
-                        .on_press(Message::CancelDelete)
-                        .padding([4, 10])
-                        .style(move |_, status| secondary_button(theme, status)),
-                    button(text("Delete").size(12).font(state.font_regular))
-                        .on_press(Message::DeleteRule(rule.id))
-                        .padding([4, 10])
-                        .style(move |_, status| danger_button(theme, status)),
+                    text("+").size(18).font(state.font_regular),
+                    text("Add Access Rule").size(14).font(state.font_regular)
                 ]
-                .spacing(8)
-                .align_y(Alignment::Center)
-                .padding(8.0)
-                .into()
-            } else {
-                let handle_action = if any_drag_active {
-                    Message::RuleDropped(rule.id)
-                } else {
-                    Message::RuleDragStart(rule.id)
-                };
+                .spacing(10)
+                .align_y(Alignment::Center),
+            )
+            .width(Length::Fill)
+            .padding(14)
+            .style(move |_, status| primary_button(theme, status))
+            .on_press(Message::AddRuleClicked)
+        )
+        .padding(iced::Padding::new(0.0).top(16.0))
+    ];
+
+    container(
+        column![
+            profile_header,
+            search_area,
+            column![
+                list_header,
+                scrollable(
+                    container(rule_list)
+                        .width(Length::Fill)
+                        .padding(iced::Padding::new(0.0).bottom(4.0)),
+                )
+                .id(Id::new(super::SIDEBAR_SCROLLABLE_ID))
+                .direction(scrollable::Direction::Vertical(
+                    scrollable::Scrollbar::new().spacing(8),
+                ))
+                .height(Length::Fill)
+                .style(move |_, status| themed_scrollable(theme, status)),
+            ]
+            .spacing(8)
+            .height(Length::Fill),
+            footer,
+        ]
+        .spacing(16)
+        .padding(16),
+    )
+    .width(Length::Fixed(330.0))
+    .height(Length::Fill)
+    .style(move |_| sidebar_container(theme))
+    .into()
+}
 
-                let handle_color = if is_being_dragged {
-                    theme.accent
-                } else if any_drag_active {
-                    theme.success
+/// Renders a rule card (with its drag-and-drop and delete confirmation states)
+fn view_rule_card<'a>(state: &'a State, rule: &'a Rule) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let is_editing = state.rule_form.as_ref().and_then(|f| f.id) == Some(rule.id);
+    let is_deleting = state.deleting_id == Some(rule.id);
+    let is_being_dragged = state.dragged_rule_id == Some(rule.id);
+    let any_drag_active = state.dragged_rule_id.is_some();
+    let is_hover_target = state.hovered_drop_target_id == Some(rule.id);
+    // When drag is active and this card is a potential drop target,
+    // all click handlers should trigger RuleDropped instead of their normal action
+    let is_drop_target = any_drag_active && !is_being_dragged;
+
+    let card_content: Element<'_, Message> = if is_deleting {
+        row![
+            text("Delete this rule?")
+                .size(12)
+                .font(state.font_regular)
+                .color(theme.danger)
+                .width(Length::Fill),
+            button(text("Cancel").size(12).font(state.font_regular))
+                .on_press(Message::CancelDelete)
+                .padding([4, 10])
+                .style(move |_, status| secondary_button(theme, status)),
+            button(text("Delete").size(12).font(state.font_regular))
+                .on_press(Message::DeleteRule(rule.id))
+                .padding([4, 10])
+                .style(move |_, status| danger_button(theme, status)),
+        ]
+        .spacing(8)
+        .align_y(Alignment::Center)
+        .padding(8.0)
+        .into()
+    } else {
+        let handle_action = if any_drag_active {
+            Message::RuleDropped(rule.id)
+        } else {
+            Message::RuleDragStart(rule.id)
+        };
+
+        let handle_color = if is_being_dragged {
+            theme.accent
+        } else if any_drag_active {
+            theme.success
+        } else {
+            theme.fg_muted
+        };
+
+        // Protocol/Port badge with chain arrow in Server Mode
+        // Performance: Use cached badge_display string (avoids format! every frame)
+        // Chain arrow still computed since it depends on app-level state (egress profile)
+        let server_mode = state.ruleset.advanced_security.egress_profile
+            == crate::core::firewall::EgressProfile::Server;
+
+        // Chain arrow: always mark FORWARD rules, INPUT/OUTPUT only in Server Mode
+        // (static str, no allocation)
+        let chain_arrow = match rule.chain {
+            crate::core::firewall::Chain::Forward => "⇄",
+            crate::core::firewall::Chain::Input if server_mode => "↓",
+            crate::core::firewall::Chain::Output if server_mode => "↑",
+            _ => "",
+        };
+
+        // Use cached badge_display when no chain arrow needed (most common case)
+        // Only allocate format string in Server Mode
+        let badge_text: std::borrow::Cow<'_, str> = if chain_arrow.is_empty() {
+            std::borrow::Cow::Borrowed(&rule.badge_display)
+        } else {
+            std::borrow::Cow::Owned(format!("{chain_arrow}{}", &rule.badge_display))
+        };
+
+        let badge = container(
+            text(badge_text)
+                .size(9)
+                .font(state.font_mono)
+                .color(if rule.enabled {
+                    theme.syntax_type
                 } else {
                     theme.fg_muted
-                };
+                })
+                .wrapping(Wrapping::None), // Never wrap - clip instead
+        )
+        .padding([2, 6])
+        .style(move |_| container::Style {
+            background: Some(theme.bg_base.into()),
+            border: Border {
+                radius: 4.0.into(),
+                color: theme.border,
+                width: 1.0,
+            },
+            ..Default::default()
+        })
+        .width(Length::Shrink) // Only take needed space
+        .clip(true); // Clip if extreme edge case
 
-                // Protocol/Port badge with chain arrow in Server Mode
-                // Performance: Use cached badge_display string (avoids format! every frame)
-                // Chain arrow still computed since it depends on app-level state (egress profile)
-                let server_mode = state.ruleset.advanced_security.egress_profile
-                    == crate::core::firewall::EgressProfile::Server;
-
-                // Chain arrow: only show in Server Mode (static str, no allocation)
-                let chain_arrow = if server_mode {
-                    match rule.chain {
-                        crate::core::firewall::Chain::Input => "↓",
-                        crate::core::firewall::Chain::Output => "↑",
-                    }
+        // Action badge (DROP/REJECT) - only if not Accept
+        let action_badge = if rule.action == crate::core::firewall::Action::Accept {
+            None
+        } else {
+            // Phase 2.3: Use cached action_display string (no allocation)
+            Some(
+                container(
+                    text(&rule.action_display)
+                        .size(9)
+                        .font(state.font_mono)
+                        .color(theme.fg_on_accent)
+                        .wrapping(Wrapping::None),
+                )
+                .padding([2, 6])
+                .style(move |_| container::Style {
+                    background: Some(theme.danger.into()),
+                    border: Border {
+                        radius: 4.0.into(),
+                        color: theme.danger,
+                        width: 1.0,
+                    },
+                    ..Default::default()
+                })
+                .width(Length::Shrink)
+                .clip(true),
+            )
+        };
+
+        // Main Content: Label + Tags
+        // Issue #20: Pre-allocate tag items Vec with exact capacity
+        let mut tag_items: Vec<Element<'_, Message>> = Vec::with_capacity(rule.tags.len());
+        // Issue #15: Pre-compute all tag colors (enabled/disabled variants)
+        let is_enabled = rule.enabled;
+        let tag_text_color = if is_enabled {
+            theme.fg_on_accent
+        } else {
+            Color {
+                a: 0.5,
+                ..theme.fg_muted
+            }
+        };
+        let tag_bg_color = if is_enabled {
+            theme.accent
+        } else {
+            Color {
+                a: 0.3,
+                ..theme.accent
+            }
+        };
+        // Performance: Use cached tags_truncated (avoids format! every frame)
+        // Note: Display-only tag badges use 3.0px radius (smaller than interactive
+        // tag_button's 4.0px) for visual hierarchy - badges are compact indicators,
+        // not clickable controls.
+        for truncated_tag in &rule.tags_truncated {
+            tag_items.push(
+                container(
+                    text(truncated_tag)
+                        .size(8)
+                        .font(state.font_regular)
+                        .color(tag_text_color)
+                        .wrapping(Wrapping::None),
+                )
+                .padding([1, 4])
+                .style(move |_: &_| container::Style {
+                    background: Some(tag_bg_color.into()),
+                    border: Border {
+                        radius: 3.0.into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .clip(true)
+                .into(),
+            );
+        }
+
+        // Row 1: Controls (Drag) + Label + Accent + Controls (Toggle, Delete)
+        let top_row = row![
+            // Drag Handle (Between Checkbox and Label)
+            tooltip(
+                button(
+                    container(
+                        text(if is_being_dragged {
+                            "●"
+                        } else if any_drag_active {
+                            if is_hover_target { "◎" } else { "○" }
+                        } else {
+                            "⠿"
+                        })
+                        .size(14)
+                        .color(handle_color),
+                    )
+                    .center_x(Length::Fixed(20.0))
+                )
+                .on_press(handle_action)
+                .padding([0, 2])
+                .style(button::text),
+                container(
+                    text("Click to reorder")
+                        .size(12)
+                        .font(state.font_regular)
+                        .color(theme.fg_primary)
+                )
+                .padding([6, 10])
+                .style(move |_| popup_container(theme)),
+                tooltip::Position::Top
+            )
+            .delay(std::time::Duration::from_secs(1)),
+            // Label (Clickable area for editing with distinctive popup Tooltip)
+            button(
+                tooltip(
+                    container(
+                        text(if rule.label.is_empty() {
+                            "Unnamed Rule"
+                        } else {
+                            &rule.label
+                        })
+                        .size(13)
+                        .font(state.font_regular)
+                        .color(if rule.enabled {
+                            theme.fg_primary
+                        } else {
+                            theme.fg_muted
+                        })
+                        .wrapping(Wrapping::None)
+                    )
+                    .max_width(140.0)
+                    .padding([2, 8])
+                    .style(move |_| section_header_container(theme))
+                    .align_x(iced::alignment::Horizontal::Left)
+                    .clip(true),
+                    container(
+                        text(if rule.label.is_empty() {
+                            "Unnamed Rule"
+                        } else {
+                            &rule.label
+                        })
+                        .size(12)
+                        .font(state.font_regular)
+                        .color(theme.fg_primary)
+                    )
+                    .padding([6, 10])
+                    .style(move |_| popup_container(theme)),
+                    tooltip::Position::Bottom
+                )
+                .delay(std::time::Duration::from_secs(1)),
+            )
+            .on_press(if is_drop_target {
+                Message::RuleDropped(rule.id)
+            } else {
+                Message::EditRuleClicked(rule.id)
+            })
+            .padding(0)
+            .style(button::text),
+            // Accent Line (Absorbs all remaining space)
+            rule::horizontal(1).style(move |_| themed_horizontal_rule(theme)),
+            // Management Cluster (Always stays on far right)
+            {
+                // Checkbox: disable interaction during drag (clicks fall through to mouse_area)
+                let cb = checkbox(rule.enabled)
+                    .size(16)
+                    .spacing(0)
+                    .style(move |_, status| themed_checkbox(theme, status));
+                let cb: Element<'_, Message> = if is_drop_target {
+                    cb.into() // Non-interactive during drag
                 } else {
-                    ""
+                    cb.on_toggle(move |_| Message::ToggleRuleEnabled(rule.id))
+                        .into()
                 };
 
-                // Use cached badge_display when no chain arrow needed (most common case)
-                // Only allocate format string in Server Mode
-                let badge_text: std::borrow::Cow<'_, str> = if chain_arrow.is_empty() {
-                    std::borrow::Cow::Borrowed(&rule.badge_display)
+                // Delete button: redirect to drop during drag
+                let delete_btn = button(
+                    text("×")
+                        .size(14)
+                        .font(state.font_regular)
+                        .color(theme.fg_muted),
+                )
+                .on_press(if is_drop_target {
+                    Message::RuleDropped(rule.id)
                 } else {
-                    std::borrow::Cow::Owned(format!("{chain_arrow}{}", &rule.badge_display))
-                };
+                    Message::DeleteRuleRequested(rule.id)
+                })
+                .padding(6)
+                .style(button::text);
+
+                row![cb, delete_btn].spacing(8).align_y(Alignment::Center)
+            },
+        ]
+        .spacing(8)
+        .padding([0, 8]) // Add horizontal padding to match other rows
+        .align_y(Alignment::Center);
+
+        // Row 2: Detail Row (Interface, Action, Protocol/Ports) - now full width
+        // Re-build detail_items to ensure interface is far left and protocol is far right
+        let mut detail_items: Vec<Element<'_, Message>> = Vec::with_capacity(6);
 
-                let badge = container(
-                    text(badge_text)
+        // 1. Interface (Far Left)
+        if !rule.interfaces.is_empty() {
+            // Phase 2.3: Use cached interface_display string (no allocation)
+            detail_items.push(
+                container(
+                    text(&rule.interface_display)
                         .size(9)
                         .font(state.font_mono)
+                        .color(if rule.enabled {
+                            theme.fg_muted
+                        } else {
+                            Color {
+                                a: 0.5,
+                                ..theme.fg_muted
+                            }
+                        })
+                        .wrapping(Wrapping::None),
+                )
+                .clip(true)
+                .into(),
+            );
+        }
+
+        // 2. Action badge (Next to interface)
+        if let Some(action_badge_elem) = action_badge {
+            detail_items.push(action_badge_elem.into());
+        }
+
+        // 3. Negation markers for fields the card doesn't otherwise show
+        //    (negated ports and interface read "not ..." in their own badges)
+        for (negated, marker) in [
+            (rule.negate.sources, "not src"),
+            (rule.negate.destinations, "not dst"),
+            (rule.negate.output_interface, "not oif"),
+        ] {
+            if negated {
+                detail_items.push(
+                    text(marker)
+                        .size(9)
+                        .font(state.font_mono)
+                        .color(theme.warning)
+                        .wrapping(Wrapping::None)
+                        .into(),
+                );
+            }
+        }
+
+        // 4. Live counters (cached display string, only while counters are polled)
+        if let Some(counter_text) = state.rule_counters.display.get(&rule.id) {
+            detail_items.push(
+                text(counter_text)
+                    .size(9)
+                    .font(state.font_mono)
+                    .color(theme.fg_muted)
+                    .wrapping(Wrapping::None)
+                    .into(),
+            );
+        }
+
+        // 5. Clock badge for scheduled rules (only active in their time window)
+        if rule.schedule.is_some() {
+            detail_items.push(
+                container(
+                    text("⏲")
+                        .size(9)
                         .color(if rule.enabled {
                             theme.syntax_type
                         } else {
                             theme.fg_muted
                         })
-                        .wrapping(Wrapping::None), // Never wrap - clip instead
+                        .wrapping(Wrapping::None),
                 )
-                .padding([2, 6])
+                .padding([2, 4])
                 .style(move |_| container::Style {
                     background: Some(theme.bg_base.into()),
                     border: Border {
@@ -290,388 +790,314 @@ pub fn view_sidebar(state: &State) -> Element<'_, Message> {
                     },
                     ..Default::default()
                 })
-                .width(Length::Shrink) // Only take needed space
-                .clip(true); // Clip if extreme edge case
-
-                // Action badge (DROP/REJECT) - only if not Accept
-                let action_badge = if rule.action == crate::core::firewall::Action::Accept {
-                    None
-                } else {
-                    // Phase 2.3: Use cached action_display string (no allocation)
-                    Some(
-                        container(
-                            text(&rule.action_display)
-                                .size(9)
-                                .font(state.font_mono)
-                                .color(theme.fg_on_accent)
-                                .wrapping(Wrapping::None),
-                        )
-                        .padding([2, 6])
-                        .style(move |_| container::Style {
-                            background: Some(theme.danger.into()),
-                            border: Border {
-                                radius: 4.0.into(),
-                                color: theme.danger,
-                                width: 1.0,
-                            },
-                            ..Default::default()
-                        })
-                        .width(Length::Shrink)
-                        .clip(true),
-                    )
-                };
+                .into(),
+            );
+        }
 
-                // Main Content: Label + Tags
-                // Issue #20: Pre-allocate tag items Vec with exact capacity
-                let mut tag_items: Vec<Element<'_, Message>> = Vec::with_capacity(rule.tags.len());
-                // Issue #15: Pre-compute all tag colors (enabled/disabled variants)
-                let is_enabled = rule.enabled;
-                let tag_text_color = if is_enabled {
-                    theme.fg_on_accent
-                } else {
-                    Color {
-                        a: 0.5,
-                        ..theme.fg_muted
-                    }
-                };
-                let tag_bg_color = if is_enabled {
-                    theme.accent
-                } else {
-                    Color {
-                        a: 0.3,
-                        ..theme.accent
-                    }
-                };
-                // Performance: Use cached tags_truncated (avoids format! every frame)
-                // Note: Display-only tag badges use 3.0px radius (smaller than interactive
-                // tag_button's 4.0px) for visual hierarchy - badges are compact indicators,
-                // not clickable controls.
-                for truncated_tag in &rule.tags_truncated {
-                    tag_items.push(
-                        container(
-                            text(truncated_tag)
-                                .size(8)
-                                .font(state.font_regular)
-                                .color(tag_text_color)
-                                .wrapping(Wrapping::None),
-                        )
-                        .padding([1, 4])
-                        .style(move |_: &_| container::Style {
-                            background: Some(tag_bg_color.into()),
-                            border: Border {
-                                radius: 3.0.into(),
-                                ..Default::default()
-                            },
-                            ..Default::default()
+        // 6. Expiry badge (expired rules are skipped when the ruleset is built)
+        if let Some(expires_at) = rule.expires_at {
+            let expired = expires_at <= chrono::Utc::now();
+            detail_items.push(
+                container(
+                    text(if expired { "expired" } else { "⌛" })
+                        .size(9)
+                        .font(state.font_mono)
+                        .color(if expired {
+                            theme.danger
+                        } else {
+                            theme.fg_muted
                         })
-                        .clip(true)
-                        .into(),
-                    );
-                }
+                        .wrapping(Wrapping::None),
+                )
+                .padding([2, 4])
+                .style(move |_| container::Style {
+                    background: Some(theme.bg_base.into()),
+                    border: Border {
+                        radius: 4.0.into(),
+                        color: if expired { theme.danger } else { theme.border },
+                        width: 1.0,
+                    },
+                    ..Default::default()
+                })
+                .into(),
+            );
+        }
 
-                // Row 1: Controls (Drag) + Label + Accent + Controls (Toggle, Delete)
-                let top_row = row![
-                    // Drag Handle (Between Checkbox and Label)
-                    tooltip(
-                        button(
-                            container(
-                                text(if is_being_dragged {
-                                    "●"
-                                } else if any_drag_active {
-                                    if is_hover_target { "◎" } else { "○" }
-                                } else {
-                                    "⠿"
-                                })
-                                .size(14)
-                                .color(handle_color),
-                            )
-                            .center_x(Length::Fixed(20.0))
-                        )
-                        .on_press(handle_action)
-                        .padding([0, 2])
-                        .style(button::text),
-                        container(
-                            text("Click to reorder")
-                                .size(12)
-                                .font(state.font_regular)
-                                .color(theme.fg_primary)
-                        )
-                        .padding([6, 10])
-                        .style(move |_| popup_container(theme)),
-                        tooltip::Position::Top
-                    )
-                    .delay(std::time::Duration::from_millis(1000)),
-                    // Label (Clickable area for editing with distinctive popup Tooltip)
-                    button(
-                        tooltip(
-                            container(
-                                text(if rule.label.is_empty() {
-                                    "Unnamed Rule"
-                                } else {
-                                    &rule.label
-                                })
-                                .size(13)
-                                .font(state.font_regular)
-                                .color(if rule.enabled {
-                                    theme.fg_primary
-                                } else {
-                                    theme.fg_muted
-                                })
-                                .wrapping(Wrapping::None)
-                            )
-                            .max_width(140.0)
-                            .padding([2, 8])
-                            .style(move |_| section_header_container(theme))
-                            .align_x(iced::alignment::Horizontal::Left)
-                            .clip(true),
-                            container(
-                                text(if rule.label.is_empty() {
-                                    "Unnamed Rule"
-                                } else {
-                                    &rule.label
-                                })
-                                .size(12)
-                                .font(state.font_regular)
-                                .color(theme.fg_primary)
-                            )
-                            .padding([6, 10])
-                            .style(move |_| popup_container(theme)),
-                            tooltip::Position::Bottom
-                        )
-                        .delay(std::time::Duration::from_millis(1000)),
-                    )
-                    .on_press(if is_drop_target {
-                        Message::RuleDropped(rule.id)
+        // 7. Details toggle (description, owner, reference, timestamps)
+        let is_expanded = state.expanded_rules.contains(&rule.id);
+        detail_items.push(
+            button(
+                text(if is_expanded { "ⓘ▾" } else { "ⓘ" })
+                    .size(10)
+                    .color(if rule.has_metadata() {
+                        theme.syntax_type
                     } else {
-                        Message::EditRuleClicked(rule.id)
+                        theme.fg_muted
                     })
-                    .padding(0)
-                    .style(button::text),
-                    // Accent Line (Absorbs all remaining space)
-                    rule::horizontal(1).style(move |_| themed_horizontal_rule(theme)),
-                    // Management Cluster (Always stays on far right)
-                    {
-                        // Checkbox: disable interaction during drag (clicks fall through to mouse_area)
-                        let cb = checkbox(rule.enabled)
-                            .size(16)
-                            .spacing(0)
-                            .style(move |_, status| themed_checkbox(theme, status));
-                        let cb: Element<'_, Message> = if is_drop_target {
-                            cb.into() // Non-interactive during drag
-                        } else {
-                            cb.on_toggle(move |_| Message::ToggleRuleEnabled(rule.id))
-                                .into()
-                        };
-
-                        // Delete button: redirect to drop during drag
-                        let delete_btn = button(
-                            text("×")
-                                .size(14)
-                                .font(state.font_regular)
-                                .color(theme.fg_muted),
-                        )
-                        .on_press(if is_drop_target {
-                            Message::RuleDropped(rule.id)
-                        } else {
-                            Message::DeleteRuleRequested(rule.id)
-                        })
-                        .padding(6)
-                        .style(button::text);
+                    .wrapping(Wrapping::None),
+            )
+            .on_press(if is_drop_target {
+                Message::RuleDropped(rule.id)
+            } else {
+                Message::ToggleRuleDetails(rule.id)
+            })
+            .padding(0)
+            .style(button::text)
+            .into(),
+        );
 
-                        row![cb, delete_btn].spacing(8).align_y(Alignment::Center)
-                    },
-                ]
-                .spacing(8)
-                .padding([0, 8]) // Add horizontal padding to match other rows
-                .align_y(Alignment::Center);
-
-                // Row 2: Detail Row (Interface, Action, Protocol/Ports) - now full width
-                // Re-build detail_items to ensure interface is far left and protocol is far right
-                let mut detail_items: Vec<Element<'_, Message>> = Vec::with_capacity(4);
-
-                // 1. Interface (Far Left)
-                if rule.interface.is_some() {
-                    // Phase 2.3: Use cached interface_display string (no allocation)
-                    detail_items.push(
-                        container(
-                            text(&rule.interface_display)
-                                .size(9)
-                                .font(state.font_mono)
-                                .color(if rule.enabled {
-                                    theme.fg_muted
-                                } else {
-                                    Color {
-                                        a: 0.5,
-                                        ..theme.fg_muted
-                                    }
-                                })
-                                .wrapping(Wrapping::None),
-                        )
-                        .clip(true)
-                        .into(),
-                    );
-                }
+        // 8. Spacer (Fills middle to push protocol to right)
+        detail_items.push(container(column![]).width(Length::Fill).into());
 
-                // 2. Action badge (Next to interface)
-                if let Some(action_badge_elem) = action_badge {
-                    detail_items.push(action_badge_elem.into());
-                }
+        // 9. Protocol Badge (Far Right)
+        detail_items.push(badge.into());
 
-                // 3. Spacer (Fills middle to push protocol to right)
-                detail_items.push(container(column![]).width(Length::Fill).into());
+        let details_row = button(
+            container(row(detail_items).spacing(8).align_y(Alignment::Center)).width(Length::Fill),
+        )
+        .on_press(if is_drop_target {
+            Message::RuleDropped(rule.id)
+        } else {
+            Message::EditRuleClicked(rule.id)
+        })
+        .padding([0, 8]) // Match outer padding
+        .style(button::text)
+        .width(Length::Fill);
 
-                // 4. Protocol Badge (Far Right)
-                detail_items.push(badge.into());
+        // Row 3: Tags (if present)
+        let mut card_rows = vec![top_row.into(), details_row.into()];
 
-                let details_row = button(
-                    container(row(detail_items).spacing(8).align_y(Alignment::Center))
-                        .width(Length::Fill),
-                )
-                .on_press(if is_drop_target {
-                    Message::RuleDropped(rule.id)
-                } else {
-                    Message::EditRuleClicked(rule.id)
-                })
-                .padding([0, 8]) // Match outer padding
-                .style(button::text)
-                .width(Length::Fill);
+        if !rule.tags.is_empty() {
+            let tag_row = button(
+                container(row(tag_items).spacing(4).align_y(Alignment::Center)).width(Length::Fill),
+            )
+            .on_press(if is_drop_target {
+                Message::RuleDropped(rule.id)
+            } else {
+                Message::EditRuleClicked(rule.id)
+            })
+            .padding([0, 8])
+            .style(button::text)
+            .width(Length::Fill);
 
-                // Row 3: Tags (if present)
-                let mut card_rows = vec![top_row.into(), details_row.into()];
+            card_rows.push(tag_row.into());
+        }
 
-                if !rule.tags.is_empty() {
-                    let tag_row = button(
-                        container(row(tag_items).spacing(4).align_y(Alignment::Center))
-                            .width(Length::Fill),
-                    )
+        // Row 4: Expanded details
+        if is_expanded {
+            card_rows.push(
+                button(view_rule_details(state, rule))
                     .on_press(if is_drop_target {
                         Message::RuleDropped(rule.id)
                     } else {
                         Message::EditRuleClicked(rule.id)
                     })
-                    .padding([0, 8])
+                    .padding([2, 8])
                     .style(button::text)
-                    .width(Length::Fill);
-
-                    card_rows.push(tag_row.into());
-                }
+                    .width(Length::Fill)
+                    .into(),
+            );
+        }
 
-                column(card_rows).spacing(2).padding([4, 0]).into()
-            };
+        column(card_rows).spacing(2).padding([4, 0]).into()
+    };
 
-            let card = container(card_content).style(move |_| {
-                let mut style = if is_editing {
-                    active_card_container(theme)
-                } else if is_being_dragged {
-                    container::Style {
-                        background: Some(theme.bg_active.into()),
-                        border: Border {
-                            color: theme.accent,
-                            width: 2.0,
-                            radius: 8.0.into(),
-                        },
-                        shadow: iced::Shadow {
-                            color: theme.shadow_color,
-                            offset: iced::Vector::new(0.0, 4.0),
-                            blur_radius: 8.0,
-                        },
-                        ..Default::default()
-                    }
-                } else if is_hover_target {
-                    container::Style {
-                        background: Some(theme.bg_surface.into()),
-                        border: Border {
-                            color: theme.success,
-                            width: 2.0,
-                            radius: 8.0.into(),
-                        },
-                        shadow: iced::Shadow {
-                            color: theme.shadow_color,
-                            offset: iced::Vector::new(0.0, 3.0),
-                            blur_radius: 6.0,
-                        },
-                        ..Default::default()
-                    }
-                } else {
-                    card_container(theme)
-                };
+    let card = container(card_content).style(move |_| {
+        let mut style = if is_editing {
+            active_card_container(theme)
+        } else if is_being_dragged {
+            container::Style {
+                background: Some(theme.bg_active.into()),
+                border: Border {
+                    color: theme.accent,
+                    width: 2.0,
+                    radius: 8.0.into(),
+                },
+                shadow: iced::Shadow {
+                    color: theme.shadow_color,
+                    offset: iced::Vector::new(0.0, 4.0),
+                    blur_radius: 8.0,
+                },
+                ..Default::default()
+            }
+        } else if is_hover_target {
+            container::Style {
+                background: Some(theme.bg_surface.into()),
+                border: Border {
+                    color: theme.success,
+                    width: 2.0,
+                    radius: 8.0.into(),
+                },
+                shadow: iced::Shadow {
+                    color: theme.shadow_color,
+                    offset: iced::Vector::new(0.0, 3.0),
+                    blur_radius: 6.0,
+                },
+                ..Default::default()
+            }
+        } else {
+            card_container(theme)
+        };
 
-                // Dim the card if the rule is disabled
-                if !rule.enabled && !is_editing && !is_being_dragged && !is_hover_target {
-                    style.background = style.background.map(|b| match b {
-                        iced::Background::Color(c) => {
-                            iced::Background::Color(Color { a: 0.6, ..c })
-                        }
-                        iced::Background::Gradient(_) => b,
-                    });
-                }
-                style
+        // Dim the card if the rule is disabled
+        if !rule.enabled && !is_editing && !is_being_dragged && !is_hover_target {
+            style.background = style.background.map(|b| match b {
+                iced::Background::Color(c) => iced::Background::Color(Color { a: 0.6, ..c }),
+                iced::Background::Gradient(_) => b,
             });
+        }
+        style
+    });
 
-            let card_element: Element<'_, Message> = if any_drag_active && !is_being_dragged {
-                mouse_area(card)
-                    .on_enter(Message::RuleHoverStart(rule.id))
-                    .on_exit(Message::RuleHoverEnd)
-                    .on_press(Message::RuleDropped(rule.id))
-                    .into()
-            } else {
-                card.into()
-            };
+    if any_drag_active && !is_being_dragged {
+        mouse_area(card)
+            .on_enter(Message::RuleHoverStart(rule.id))
+            .on_exit(Message::RuleHoverEnd)
+            .on_press(Message::RuleDropped(rule.id))
+            .into()
+    } else {
+        card.into()
+    }
+}
+
+/// Renders the expanded metadata of a rule card: description, owner,
+/// reference and creation/modification/expiry times (local time)
+fn view_rule_details<'a>(state: &'a State, rule: &'a Rule) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let mut details = column![].spacing(3);
 
-            rule_cards.push(card_element);
+    if !rule.description.is_empty() {
+        details = details.push(
+            text(&rule.description)
+                .size(11)
+                .font(state.font_regular)
+                .color(theme.fg_secondary),
+        );
+    }
+    for (name, value) in [("Owner", &rule.owner), ("Ref", &rule.reference)] {
+        if !value.is_empty() {
+            details = details.push(
+                row![
+                    text(name)
+                        .size(9)
+                        .font(state.font_mono)
+                        .color(theme.fg_muted),
+                    text(value)
+                        .size(10)
+                        .font(state.font_regular)
+                        .color(theme.fg_primary)
+                        .wrapping(Wrapping::Glyph),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            );
         }
+    }
 
-        // Build column from pre-allocated Vec
-        column(rule_cards).spacing(8).into()
+    let format_time = |t: chrono::DateTime<chrono::Utc>| {
+        t.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
     };
+    let mut times = format!("Created {}", format_time(rule.created_at));
+    if let Some(modified) = rule.modified_at {
+        times = format!("{times} · Modified {}", format_time(modified));
+    }
+    if let Some(expires) = rule.expires_at {
+        let verb = if expires <= chrono::Utc::now() {
+            "Expired"
+        } else {
+            "Expires"
+        };
+        times = format!("{times} · {verb} {}", format_time(expires));
+    }
+    details = details.push(
+        text(times)
+            .size(9)
+            .font(state.font_mono)
+            .color(theme.fg_muted),
+    );
 
-    // 6. Sidebar Footer (Pinned Action)
-    let footer = column![
-        rule::horizontal(1).style(move |_| themed_horizontal_rule(theme)),
-        container(
+    container(details)
+        .width(Length::Fill)
+        .padding(iced::Padding::new(0.0).top(4.0))
+        .into()
+}
+
+/// Renders a group header: collapse toggle, name, member count, ordering and
+/// enable toggle. While a rule is dragged the header is a drop target that
+/// moves the rule into the group.
+fn view_group_header<'a>(
+    state: &'a State,
+    group: &'a RuleGroup,
+    count: usize,
+) -> Element<'a, Message> {
+    let theme = &state.theme;
+    let id = group.id;
+    let collapsed = state.collapsed_groups.contains(&id);
+    let is_hover_target = state.hovered_drop_target_id == Some(id);
+
+    let header = container(
+        row![
             button(
-                row![
-                    text("+").size(18).font(state.font_regular),
-                    text("Add Access Rule").size(14).font(state.font_regular)
-                ]
-                .spacing(10)
-                .align_y(Alignment::Center),
+                text(if collapsed { "▸" } else { "▾" })
+                    .size(12)
+                    .color(theme.fg_muted)
             )
-            .width(Length::Fill)
-            .padding(14)
-            .style(move |_, status| primary_button(theme, status))
-            .on_press(Message::AddRuleClicked)
-        )
-        .padding(iced::Padding::new(0.0).top(16.0))
-    ];
-
-    container(
-        column![
-            profile_header,
-            search_area,
-            column![
-                list_header,
-                scrollable(
-                    container(rule_list)
-                        .width(Length::Fill)
-                        .padding(iced::Padding::new(0.0).bottom(4.0)),
-                )
-                .id(Id::new(super::SIDEBAR_SCROLLABLE_ID))
-                .direction(scrollable::Direction::Vertical(
-                    scrollable::Scrollbar::new().spacing(8),
-                ))
-                .height(Length::Fill)
-                .style(move |_, status| themed_scrollable(theme, status)),
-            ]
-            .spacing(8)
-            .height(Length::Fill),
-            footer,
+            .on_press(Message::ToggleGroupCollapsed(id))
+            .padding([0, 4])
+            .style(button::text),
+            text(&group.name)
+                .size(12)
+                .font(state.font_regular)
+                .color(if group.enabled {
+                    theme.fg_primary
+                } else {
+                    theme.fg_muted
+                })
+                .wrapping(Wrapping::None)
+                .width(Length::Fill),
+            text(count.to_string())
+                .size(9)
+                .font(state.font_mono)
+                .color(theme.fg_muted),
+            button(text("▲").size(9).color(theme.fg_muted))
+                .on_press(Message::MoveGroupUp(id))
+                .padding([0, 2])
+                .style(button::text),
+            button(text("▼").size(9).color(theme.fg_muted))
+                .on_press(Message::MoveGroupDown(id))
+                .padding([0, 2])
+                .style(button::text),
+            checkbox(group.enabled)
+                .on_toggle(move |_| Message::ToggleGroup(id))
+                .size(16)
+                .spacing(0)
+                .style(move |_, status| themed_checkbox(theme, status)),
         ]
-        .spacing(16)
-        .padding(16),
+        .spacing(6)
+        .align_y(Alignment::Center),
     )
-    .width(Length::Fixed(330.0))
-    .height(Length::Fill)
-    .style(move |_| sidebar_container(theme))
-    .into()
+    .padding([4, 8])
+    .style(move |_| {
+        let mut style = section_header_container(theme);
+        if is_hover_target {
+            style.border = Border {
+                color: theme.success,
+                width: 2.0,
+                radius: style.border.radius,
+            };
+        }
+        style
+    });
+
+    if state.dragged_rule_id.is_some() {
+        mouse_area(header)
+            .on_enter(Message::RuleHoverStart(id))
+            .on_exit(Message::RuleHoverEnd)
+            .on_press(Message::RuleDroppedOnGroup(id))
+            .into()
+    } else {
+        header.into()
+    }
 }
@@ -22,6 +22,18 @@ pub fn view_workspace<'a>(
         view_tab_button("Settings", WorkspaceTab::Settings, state.active_tab, theme),
         container(row![]).width(Length::Fill),
         // Global Utility Tools
+        button(text("Knocking").size(13).font(state.font_regular))
+            .on_press(Message::OpenKnockManager)
+            .padding([8, 16])
+            .style(move |_, status| secondary_button(theme, status)),
+        button(text("Blocklists").size(13).font(state.font_regular))
+            .on_press(Message::OpenBlocklistManager)
+            .padding([8, 16])
+            .style(move |_, status| secondary_button(theme, status)),
+        button(text("Bans").size(13).font(state.font_regular))
+            .on_press(Message::OpenBanManager)
+            .padding([8, 16])
+            .style(move |_, status| secondary_button(theme, status)),
         button(text("Export").size(13).font(state.font_regular))
             .on_press(Message::ToggleExportModal(true))
             .padding([8, 16])
@@ -150,7 +162,7 @@ pub fn view_workspace<'a>(
             .style(move |_| popup_container(theme)),
             tooltip::Position::Top
         )
-        .delay(Duration::from_millis(1000)),
+        .delay(Duration::from_secs(1)),
         tooltip(
             button(text("↷").size(18))
                 .on_press_maybe(state.command_history.can_redo().then_some(Message::Redo))
@@ -166,7 +178,7 @@ pub fn view_workspace<'a>(
             .style(move |_| popup_container(theme)),
             tooltip::Position::Top
         )
-        .delay(Duration::from_millis(1000)),
+        .delay(Duration::from_secs(1)),
     ]
     .spacing(12);
 
@@ -41,9 +41,36 @@ pub enum EventType {
     RuleModified,
     RuleToggled,
     RulesReordered,
+    ExpiredRulesRemoved,
     Undone,
     Redone,
 
+    // Named set CRUD operations
+    SetCreated,
+    SetModified,
+    SetDeleted,
+
+    // Port-knocking sequence CRUD operations
+    KnockCreated,
+    KnockModified,
+    KnockDeleted,
+
+    // Rule group CRUD operations
+    GroupCreated,
+    GroupModified,
+    GroupDeleted,
+    GroupToggled,
+
+    // Temporary bans (live blocklist changes)
+    IpBanned,
+    IpUnbanned,
+
+    // Blocklist files re-read (and live sets replaced from the CLI)
+    BlocklistsRefreshed,
+
+    // GeoIP country ranges rebuilt from the database (and live sets replaced)
+    GeoIpRebuilt,
+
     // Data export
     ExportCompleted,
     ExportFailed,
@@ -390,6 +417,18 @@ pub async fn log_rule_deleted(enable_event_log: bool, label: &str) {
     .await;
 }
 
+/// Logs removal of expired rules (from the sidebar or `drfw prune-expired`)
+pub async fn log_expired_rules_removed(enable_event_log: bool, labels: &[String]) {
+    log_event_internal(
+        enable_event_log,
+        EventType::ExpiredRulesRemoved,
+        true,
+        serde_json::json!({ "count": labels.len(), "labels": labels }),
+        None,
+    )
+    .await;
+}
+
 /// Logs a rule modification event
 pub async fn log_rule_modified(
     enable_event_log: bool,
@@ -407,6 +446,173 @@ pub async fn log_rule_modified(
     .await;
 }
 
+/// Logs a named set creation or modification event
+pub async fn log_set_saved(enable_event_log: bool, name: &str, elements: usize, created: bool) {
+    log_event_internal(
+        enable_event_log,
+        if created {
+            EventType::SetCreated
+        } else {
+            EventType::SetModified
+        },
+        true,
+        serde_json::json!({ "name": name, "elements": elements }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a named set deletion event
+pub async fn log_set_deleted(enable_event_log: bool, name: &str) {
+    log_event_internal(
+        enable_event_log,
+        EventType::SetDeleted,
+        true,
+        serde_json::json!({ "name": name }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a port-knocking sequence creation or modification event
+pub async fn log_knock_saved(enable_event_log: bool, name: &str, summary: &str, created: bool) {
+    log_event_internal(
+        enable_event_log,
+        if created {
+            EventType::KnockCreated
+        } else {
+            EventType::KnockModified
+        },
+        true,
+        serde_json::json!({ "name": name, "summary": summary }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a port-knocking sequence deletion event
+pub async fn log_knock_deleted(enable_event_log: bool, name: &str) {
+    log_event_internal(
+        enable_event_log,
+        EventType::KnockDeleted,
+        true,
+        serde_json::json!({ "name": name }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a rule group creation or rename event
+pub async fn log_group_saved(enable_event_log: bool, name: &str, created: bool) {
+    log_event_internal(
+        enable_event_log,
+        if created {
+            EventType::GroupCreated
+        } else {
+            EventType::GroupModified
+        },
+        true,
+        serde_json::json!({ "name": name }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a rule group deletion event
+pub async fn log_group_deleted(enable_event_log: bool, name: &str, members: usize) {
+    log_event_internal(
+        enable_event_log,
+        EventType::GroupDeleted,
+        true,
+        serde_json::json!({ "name": name, "members": members }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a rule group toggle event (enabled/disabled)
+pub async fn log_group_toggled(enable_event_log: bool, name: &str, enabled: bool) {
+    log_event_internal(
+        enable_event_log,
+        EventType::GroupToggled,
+        true,
+        serde_json::json!({ "name": name, "enabled": enabled }),
+        None,
+    )
+    .await;
+}
+
+/// Logs a blocklist refresh
+pub async fn log_blocklists_refreshed(
+    enable_event_log: bool,
+    files: usize,
+    entries: usize,
+    success: bool,
+    error: Option<String>,
+) {
+    log_event_internal(
+        enable_event_log,
+        EventType::BlocklistsRefreshed,
+        success,
+        serde_json::json!({ "files": files, "entries": entries }),
+        error,
+    )
+    .await;
+}
+
+/// Logs a rebuild of the country sets
+pub async fn log_geoip_rebuilt(
+    enable_event_log: bool,
+    countries: usize,
+    ranges: usize,
+    success: bool,
+    error: Option<String>,
+) {
+    log_event_internal(
+        enable_event_log,
+        EventType::GeoIpRebuilt,
+        success,
+        serde_json::json!({ "countries": countries, "ranges": ranges }),
+        error,
+    )
+    .await;
+}
+
+/// Logs a temporary ban
+pub async fn log_ip_banned(
+    enable_event_log: bool,
+    ip: &str,
+    duration_secs: u64,
+    success: bool,
+    error: Option<String>,
+) {
+    log_event_internal(
+        enable_event_log,
+        EventType::IpBanned,
+        success,
+        serde_json::json!({ "ip": ip, "duration_secs": duration_secs }),
+        error,
+    )
+    .await;
+}
+
+/// Logs lifting a temporary ban
+pub async fn log_ip_unbanned(
+    enable_event_log: bool,
+    ip: &str,
+    success: bool,
+    error: Option<String>,
+) {
+    log_event_internal(
+        enable_event_log,
+        EventType::IpUnbanned,
+        success,
+        serde_json::json!({ "ip": ip }),
+        error,
+    )
+    .await;
+}
+
 /// Logs a rule toggle event (enabled/disabled)
 pub async fn log_rule_toggled(enable_event_log: bool, label: &str, enabled: bool) {
     log_event_internal(
@@ -8,9 +8,17 @@
 //! Each modification to the firewall ruleset is encapsulated as a [`Command`]:
 //! - [`AddRuleCommand`]: Adds a new rule
 //! - [`DeleteRuleCommand`]: Removes an existing rule
+//! - [`RemoveRulesCommand`]: Removes several rules at once (e.g. expired ones)
 //! - [`EditRuleCommand`]: Modifies an existing rule
 //! - [`ToggleRuleCommand`]: Enables/disables a rule
 //! - [`ReorderRuleCommand`]: Changes rule priority order
+//! - [`AddSetCommand`], [`EditSetCommand`], [`DeleteSetCommand`]: Manage named address/port sets
+//! - [`AddKnockCommand`], [`EditKnockCommand`], [`DeleteKnockCommand`]: Manage port-knocking
+//!   sequences
+//! - [`AddGroupCommand`], [`EditGroupCommand`], [`DeleteGroupCommand`]: Manage rule groups
+//! - [`ToggleGroupCommand`]: Enables/disables a group as a unit
+//! - [`MoveGroupCommand`]: Moves a group's rules as a block
+//! - [`MoveRuleToGroupCommand`]: Moves a rule into, out of or between groups
 //!
 //! The [`CommandHistory`] manages the undo/redo stacks with configurable depth.
 //!
@@ -19,7 +27,7 @@
 //! ```no_run
 //! use drfw::command::{CommandHistory, AddRuleCommand};
 //! use drfw::core::firewall::{
-//!     FirewallRuleset, Rule, Protocol, PortEntry, Chain, Action, RejectType,
+//!     FirewallRuleset, Rule, Protocol, PortEntry, Chain, Action, RejectType, Negation,
 //! };
 //! use uuid::Uuid;
 //!
@@ -33,17 +41,36 @@
 //!     ports: vec![PortEntry::single(80)],
 //!     sources: vec![],
 //!     destinations: vec![],
-//!     interface: None,
-//!     output_interface: None,
+//!     interfaces: Vec::new(),
+//!     output_interfaces: Vec::new(),
 //!     chain: Chain::Input,
 //!     enabled: true,
 //!     tags: vec![],
 //!     created_at: chrono::Utc::now(),
+//!     modified_at: None,
+//!     expires_at: None,
 //!     action: Action::Accept,
 //!     reject_type: RejectType::Default,
 //!     rate_limit: None,
 //!     connection_limit: 0,
 //!     log_enabled: false,
+//!     source_set: None,
+//!     destination_set: None,
+//!     port_set: None,
+//!     port_forward: None,
+//!     source_ports: Vec::new(),
+//!     icmp_types: Vec::new(),
+//!     icmp_code: None,
+//!     ct_states: Vec::new(),
+//!     schedule: None,
+//!     quota: None,
+//!     per_source: None,
+//!     countries: Vec::new(),
+//!     group: None,
+//!     description: String::new(),
+//!     owner: String::new(),
+//!     reference: String::new(),
+//!     negate: Negation::default(),
 //!     // Cached fields (populated by rebuild_caches())
 //!     label_lowercase: String::new(),
 //!     interface_lowercase: None,
@@ -59,6 +86,7 @@
 //!     log_prefix: String::new(),
 //!     tags_truncated: Vec::new(),
 //!     badge_display: String::new(),
+//!     metadata_lowercase: String::new(),
 //! };
 //! rule.rebuild_caches();
 //!
@@ -69,7 +97,7 @@
 //! history.undo(&mut ruleset);
 //! ```
 
-use crate::core::firewall::{FirewallRuleset, Rule};
+use crate::core::firewall::{FirewallRuleset, NamedSet, PortKnock, Rule, RuleGroup};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -151,7 +179,62 @@ impl Command for DeleteRuleCommand {
     }
 }
 
+/// Removes several rules in one undoable step
+///
+/// Used to clear out expired rules. Each entry keeps the rule's original
+/// index so undo restores the exact ordering.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveRulesCommand {
+    /// Removed rules with their original indices, in ascending index order
+    pub removed: Vec<(usize, Rule)>,
+}
+
+impl RemoveRulesCommand {
+    /// Captures the rules matching `pred` along with their current indices
+    pub fn matching(ruleset: &FirewallRuleset, pred: impl Fn(&Rule) -> bool) -> Self {
+        Self {
+            removed: ruleset
+                .rules
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| pred(r))
+                .map(|(i, r)| (i, r.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl Command for RemoveRulesCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset
+            .rules
+            .retain(|r| !self.removed.iter().any(|(_, removed)| removed.id == r.id));
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        // Ascending order means each index is valid once earlier ones are back
+        for (index, rule) in &self.removed {
+            if *index <= ruleset.rules.len() {
+                ruleset.rules.insert(*index, rule.clone());
+            } else {
+                ruleset.rules.push(rule.clone());
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Remove {} expired rule(s)", self.removed.len())
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
 /// Edits an existing rule
+///
+/// Stamps the rule's `modified_at` with the time the edit is (re)applied;
+/// undo restores the previous rule including its old timestamp.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EditRuleCommand {
     pub old_rule: Rule,
@@ -162,6 +245,7 @@ impl Command for EditRuleCommand {
     fn execute(&self, ruleset: &mut FirewallRuleset) {
         if let Some(rule) = ruleset.rules.iter_mut().find(|r| r.id == self.old_rule.id) {
             *rule = self.new_rule.clone();
+            rule.modified_at = Some(chrono::Utc::now());
         }
     }
 
@@ -251,6 +335,416 @@ impl Command for ReorderRuleCommand {
     }
 }
 
+/// Adds a new named set to the ruleset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddSetCommand {
+    pub set: NamedSet,
+}
+
+impl Command for AddSetCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.sets.push(self.set.clone());
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.sets.retain(|s| s.name != self.set.name);
+    }
+
+    fn description(&self) -> String {
+        format!("Add set: {}", self.set.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Deletes an unreferenced named set from the ruleset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteSetCommand {
+    pub set: NamedSet,
+    pub index: usize,
+}
+
+impl Command for DeleteSetCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.sets.retain(|s| s.name != self.set.name);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        // Insert at original index to preserve order
+        if self.index <= ruleset.sets.len() {
+            ruleset.sets.insert(self.index, self.set.clone());
+        } else {
+            ruleset.sets.push(self.set.clone());
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Delete set: {}", self.set.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Edits a named set, renaming rule references if the name changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditSetCommand {
+    pub old_set: NamedSet,
+    pub new_set: NamedSet,
+}
+
+impl EditSetCommand {
+    fn replace(ruleset: &mut FirewallRuleset, from: &NamedSet, to: &NamedSet) {
+        if let Some(set) = ruleset.sets.iter_mut().find(|s| s.name == from.name) {
+            *set = to.clone();
+        }
+
+        if from.name == to.name {
+            return;
+        }
+
+        for rule in &mut ruleset.rules {
+            if !rule.references_set(&from.name) {
+                continue;
+            }
+            for reference in [
+                &mut rule.source_set,
+                &mut rule.destination_set,
+                &mut rule.port_set,
+            ] {
+                if reference.as_deref() == Some(from.name.as_str()) {
+                    *reference = Some(to.name.clone());
+                }
+            }
+            rule.rebuild_caches();
+        }
+    }
+}
+
+impl Command for EditSetCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        Self::replace(ruleset, &self.old_set, &self.new_set);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        Self::replace(ruleset, &self.new_set, &self.old_set);
+    }
+
+    fn description(&self) -> String {
+        format!("Edit set: {}", self.new_set.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Adds a new port-knocking sequence to the ruleset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddKnockCommand {
+    pub knock: PortKnock,
+}
+
+impl Command for AddKnockCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.knocks.push(self.knock.clone());
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.knocks.retain(|k| k.name != self.knock.name);
+    }
+
+    fn description(&self) -> String {
+        format!("Add port knock: {}", self.knock.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Deletes a port-knocking sequence from the ruleset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteKnockCommand {
+    pub knock: PortKnock,
+    pub index: usize,
+}
+
+impl Command for DeleteKnockCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.knocks.retain(|k| k.name != self.knock.name);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        // Insert at original index to preserve order
+        if self.index <= ruleset.knocks.len() {
+            ruleset.knocks.insert(self.index, self.knock.clone());
+        } else {
+            ruleset.knocks.push(self.knock.clone());
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Delete port knock: {}", self.knock.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Edits a port-knocking sequence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditKnockCommand {
+    pub old_knock: PortKnock,
+    pub new_knock: PortKnock,
+}
+
+impl EditKnockCommand {
+    fn replace(ruleset: &mut FirewallRuleset, from: &PortKnock, to: &PortKnock) {
+        if let Some(knock) = ruleset.knocks.iter_mut().find(|k| k.name == from.name) {
+            *knock = to.clone();
+        }
+    }
+}
+
+impl Command for EditKnockCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        Self::replace(ruleset, &self.old_knock, &self.new_knock);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        Self::replace(ruleset, &self.new_knock, &self.old_knock);
+    }
+
+    fn description(&self) -> String {
+        format!("Edit port knock: {}", self.new_knock.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Adds a new (empty) rule group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddGroupCommand {
+    pub group: RuleGroup,
+}
+
+impl Command for AddGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.groups.push(self.group.clone());
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.groups.retain(|g| g.id != self.group.id);
+    }
+
+    fn description(&self) -> String {
+        format!("Add group: {}", self.group.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Deletes a rule group; its members are kept as ungrouped rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteGroupCommand {
+    pub group: RuleGroup,
+    pub index: usize,
+    /// Rules that belonged to the group (restored on undo)
+    pub members: Vec<Uuid>,
+}
+
+impl Command for DeleteGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        ruleset.groups.retain(|g| g.id != self.group.id);
+        for rule in &mut ruleset.rules {
+            if rule.group == Some(self.group.id) {
+                rule.group = None;
+            }
+        }
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        // Insert at original index to preserve order
+        if self.index <= ruleset.groups.len() {
+            ruleset.groups.insert(self.index, self.group.clone());
+        } else {
+            ruleset.groups.push(self.group.clone());
+        }
+        for rule in &mut ruleset.rules {
+            if self.members.contains(&rule.id) {
+                rule.group = Some(self.group.id);
+            }
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Delete group: {}", self.group.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Renames a rule group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EditGroupCommand {
+    pub old_group: RuleGroup,
+    pub new_group: RuleGroup,
+}
+
+impl Command for EditGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        if let Some(group) = ruleset
+            .groups
+            .iter_mut()
+            .find(|g| g.id == self.old_group.id)
+        {
+            *group = self.new_group.clone();
+        }
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        if let Some(group) = ruleset
+            .groups
+            .iter_mut()
+            .find(|g| g.id == self.new_group.id)
+        {
+            *group = self.old_group.clone();
+        }
+    }
+
+    fn description(&self) -> String {
+        format!("Edit group: {}", self.new_group.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Toggles the enabled state of a group (its rules keep their own state)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToggleGroupCommand {
+    pub group_id: Uuid,
+    pub was_enabled: bool,
+}
+
+impl Command for ToggleGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        if let Some(group) = ruleset.groups.iter_mut().find(|g| g.id == self.group_id) {
+            group.enabled = !self.was_enabled;
+        }
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        if let Some(group) = ruleset.groups.iter_mut().find(|g| g.id == self.group_id) {
+            group.enabled = self.was_enabled;
+        }
+    }
+
+    fn description(&self) -> String {
+        if self.was_enabled {
+            "Disable group".to_string()
+        } else {
+            "Enable group".to_string()
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Moves a group's rules as a block, stored as the full rule order before and after
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveGroupCommand {
+    pub name: String,
+    pub old_order: Vec<Uuid>,
+    pub new_order: Vec<Uuid>,
+}
+
+impl MoveGroupCommand {
+    /// Sorts the rules by their position in `order` (rules not in it go last)
+    fn reorder(ruleset: &mut FirewallRuleset, order: &[Uuid]) {
+        let positions: std::collections::HashMap<Uuid, usize> =
+            order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+        ruleset
+            .rules
+            .sort_by_key(|r| positions.get(&r.id).copied().unwrap_or(usize::MAX));
+    }
+}
+
+impl Command for MoveGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        Self::reorder(ruleset, &self.new_order);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        Self::reorder(ruleset, &self.old_order);
+    }
+
+    fn description(&self) -> String {
+        format!("Move group: {}", self.name)
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+/// Moves a rule to a new position and into another group (or out of its group)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveRuleToGroupCommand {
+    pub rule_id: Uuid,
+    pub old_index: usize,
+    pub new_index: usize,
+    pub old_group: Option<Uuid>,
+    pub new_group: Option<Uuid>,
+}
+
+impl MoveRuleToGroupCommand {
+    fn place(&self, ruleset: &mut FirewallRuleset, index: usize, group: Option<Uuid>) {
+        if let Some(pos) = ruleset.rules.iter().position(|r| r.id == self.rule_id) {
+            let mut rule = ruleset.rules.remove(pos);
+            rule.group = group;
+            let insert_pos = index.min(ruleset.rules.len());
+            ruleset.rules.insert(insert_pos, rule);
+        }
+    }
+}
+
+impl Command for MoveRuleToGroupCommand {
+    fn execute(&self, ruleset: &mut FirewallRuleset) {
+        self.place(ruleset, self.new_index, self.new_group);
+    }
+
+    fn undo(&self, ruleset: &mut FirewallRuleset) {
+        self.place(ruleset, self.old_index, self.old_group);
+    }
+
+    fn description(&self) -> String {
+        if self.new_group.is_some() {
+            "Move rule into group".to_string()
+        } else {
+            "Move rule out of group".to_string()
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
 /// Manages the undo/redo history
 #[derive(Debug, Clone)]
 pub struct CommandHistory {
@@ -344,7 +838,7 @@ mod tests {
             Protocol::Tcp,
             vec![],                              // ports
             vec![],                              // sources
-            None,                                // interface
+            vec![],                              // interfaces
             crate::core::firewall::Chain::Input, // chain
             true,                                // enabled
             chrono::Utc::now(),
@@ -387,6 +881,25 @@ mod tests {
         assert_eq!(ruleset.rules[0].label, "Test Rule");
     }
 
+    #[test]
+    fn test_remove_rules_command_restores_order() {
+        let mut ruleset = FirewallRuleset::new();
+        for label in ["a", "b", "c", "d"] {
+            ruleset.rules.push(create_test_rule(label));
+        }
+
+        let cmd = RemoveRulesCommand::matching(&ruleset, |r| r.label == "b" || r.label == "d");
+        assert_eq!(cmd.removed.len(), 2);
+
+        cmd.execute(&mut ruleset);
+        let labels: Vec<_> = ruleset.rules.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, ["a", "c"]);
+
+        cmd.undo(&mut ruleset);
+        let labels: Vec<_> = ruleset.rules.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, ["a", "b", "c", "d"]);
+    }
+
     #[test]
     fn test_edit_rule_command() {
         let mut ruleset = FirewallRuleset::new();
@@ -402,9 +915,107 @@ mod tests {
 
         cmd.execute(&mut ruleset);
         assert_eq!(ruleset.rules[0].label, "New Label");
+        assert!(ruleset.rules[0].modified_at.is_some());
 
         cmd.undo(&mut ruleset);
         assert_eq!(ruleset.rules[0].label, "Old Label");
+        assert_eq!(ruleset.rules[0].modified_at, None);
+    }
+
+    #[test]
+    fn test_set_commands() {
+        use crate::core::firewall::SetType;
+
+        let mut ruleset = FirewallRuleset::new();
+        let set = NamedSet::new("trusted".to_string(), SetType::Ipv4Addr);
+
+        let add = AddSetCommand { set: set.clone() };
+        add.execute(&mut ruleset);
+        assert_eq!(ruleset.sets.len(), 1);
+        add.undo(&mut ruleset);
+        assert!(ruleset.sets.is_empty());
+
+        add.execute(&mut ruleset);
+        let delete = DeleteSetCommand {
+            set: set.clone(),
+            index: 0,
+        };
+        delete.execute(&mut ruleset);
+        assert!(ruleset.sets.is_empty());
+        delete.undo(&mut ruleset);
+        assert_eq!(ruleset.sets[0].name, "trusted");
+    }
+
+    #[test]
+    fn test_edit_set_command_renames_references() {
+        use crate::core::firewall::SetType;
+
+        let mut ruleset = FirewallRuleset::new();
+        let old_set = NamedSet::new("trusted".to_string(), SetType::Ipv4Addr);
+        ruleset.sets.push(old_set.clone());
+
+        let mut rule = create_test_rule("Uses set");
+        rule.source_set = Some("trusted".to_string());
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let mut new_set = old_set.clone();
+        new_set.name = "office".to_string();
+        let cmd = EditSetCommand { old_set, new_set };
+
+        cmd.execute(&mut ruleset);
+        assert_eq!(ruleset.sets[0].name, "office");
+        assert_eq!(ruleset.rules[0].source_set.as_deref(), Some("office"));
+        assert_eq!(ruleset.rules[0].sources_display, "@office");
+
+        cmd.undo(&mut ruleset);
+        assert_eq!(ruleset.sets[0].name, "trusted");
+        assert_eq!(ruleset.rules[0].source_set.as_deref(), Some("trusted"));
+    }
+
+    #[test]
+    fn test_knock_commands() {
+        use crate::core::firewall::Protocol;
+
+        let mut ruleset = FirewallRuleset::new();
+        let knock = PortKnock {
+            name: "ssh".to_string(),
+            knock_protocol: Protocol::Tcp,
+            sequence: vec![7000, 8000, 9000],
+            protocol: Protocol::Tcp,
+            port: 22,
+            timeout_secs: 30,
+        };
+
+        let add = AddKnockCommand {
+            knock: knock.clone(),
+        };
+        add.execute(&mut ruleset);
+        assert_eq!(ruleset.knocks.len(), 1);
+
+        let mut new_knock = knock.clone();
+        new_knock.name = "admin".to_string();
+        new_knock.timeout_secs = 10;
+        let edit = EditKnockCommand {
+            old_knock: knock.clone(),
+            new_knock,
+        };
+        edit.execute(&mut ruleset);
+        assert_eq!(ruleset.knocks[0].name, "admin");
+        edit.undo(&mut ruleset);
+        assert_eq!(ruleset.knocks[0], knock);
+
+        let delete = DeleteKnockCommand {
+            knock: knock.clone(),
+            index: 0,
+        };
+        delete.execute(&mut ruleset);
+        assert!(ruleset.knocks.is_empty());
+        delete.undo(&mut ruleset);
+        assert_eq!(ruleset.knocks[0].name, "ssh");
+
+        add.undo(&mut ruleset);
+        assert!(ruleset.knocks.is_empty());
     }
 
     #[test]
@@ -530,4 +1141,82 @@ mod tests {
         history.execute(Box::new(AddRuleCommand { rule: rule2 }), &mut ruleset);
         assert!(!history.can_redo());
     }
+
+    #[test]
+    fn test_group_commands() {
+        let mut ruleset = FirewallRuleset::new();
+        let rule1 = create_test_rule("Rule 1");
+        let rule2 = create_test_rule("Rule 2");
+        let rule3 = create_test_rule("Rule 3");
+        ruleset
+            .rules
+            .extend([rule1.clone(), rule2.clone(), rule3.clone()]);
+        let group = RuleGroup::new("Media".to_string());
+
+        let add = AddGroupCommand {
+            group: group.clone(),
+        };
+        add.execute(&mut ruleset);
+        assert_eq!(ruleset.groups.len(), 1);
+
+        // Drop Rule 3 onto the group, then Rule 1 after it
+        let into = MoveRuleToGroupCommand {
+            rule_id: rule3.id,
+            old_index: 2,
+            new_index: 2,
+            old_group: None,
+            new_group: Some(group.id),
+        };
+        into.execute(&mut ruleset);
+        let second = MoveRuleToGroupCommand {
+            rule_id: rule1.id,
+            old_index: 0,
+            new_index: 2,
+            old_group: None,
+            new_group: Some(group.id),
+        };
+        second.execute(&mut ruleset);
+        assert_eq!(ruleset.group_members(group.id).count(), 2);
+        assert_eq!(ruleset.rules[0].label, "Rule 2");
+
+        let old_order: Vec<Uuid> = ruleset.rules.iter().map(|r| r.id).collect();
+        let move_up = MoveGroupCommand {
+            name: group.name.clone(),
+            old_order: old_order.clone(),
+            new_order: vec![rule3.id, rule1.id, rule2.id],
+        };
+        move_up.execute(&mut ruleset);
+        assert_eq!(ruleset.rules[2].label, "Rule 2");
+        move_up.undo(&mut ruleset);
+        assert_eq!(
+            ruleset.rules.iter().map(|r| r.id).collect::<Vec<_>>(),
+            old_order
+        );
+
+        let toggle = ToggleGroupCommand {
+            group_id: group.id,
+            was_enabled: true,
+        };
+        let now = chrono::Utc::now();
+        toggle.execute(&mut ruleset);
+        assert!(!ruleset.is_rule_active(&ruleset.rules[1], now));
+        assert!(ruleset.is_rule_active(&ruleset.rules[0], now));
+        toggle.undo(&mut ruleset);
+        assert!(ruleset.is_rule_active(&ruleset.rules[1], now));
+
+        let delete = DeleteGroupCommand {
+            group: group.clone(),
+            index: 0,
+            members: vec![rule3.id, rule1.id],
+        };
+        delete.execute(&mut ruleset);
+        assert!(ruleset.groups.is_empty());
+        assert!(ruleset.rules.iter().all(|r| r.group.is_none()));
+        delete.undo(&mut ruleset);
+        assert_eq!(ruleset.group_members(group.id).count(), 2);
+
+        second.undo(&mut ruleset);
+        assert_eq!(ruleset.rules[0].label, "Rule 1");
+        assert!(ruleset.rules[0].group.is_none());
+    }
 }
@@ -34,6 +34,9 @@ pub struct AppConfig {
     /// reducing visual noise while preserving the theme's overall feel.
     #[serde(default)]
     pub reduced_colors: bool,
+    /// Country database (`.mmdb` or CSV) for rules filtering by country
+    #[serde(default)]
+    pub geoip_database: Option<std::path::PathBuf>,
 }
 
 impl Default for AppConfig {
@@ -49,6 +52,7 @@ impl Default for AppConfig {
             auto_revert_timeout_secs: 15,
             enable_event_log: true, // Useful for troubleshooting, local-only so no privacy concern
             reduced_colors: false,  // Full syntax highlighting by default
+            geoip_database: None,
         }
     }
 }
@@ -0,0 +1,369 @@
+//! Temporary IP bans
+//!
+//! Bans are stored in two dynamic nftables sets inside the `drfw` table
+//! (`flags timeout`, one per IP family) that are matched early in the input chain.
+//! Banning and unbanning add/delete set elements directly, so they never flush
+//! and re-apply the ruleset.
+//!
+//! A normal apply runs `flush table`, which only deletes the table's rules: set
+//! elements and stateful objects are kept, so bans in a live table survive it.
+//! Active bans are still persisted to `bans.json` in the state directory and
+//! re-injected with their remaining timeout whenever the ruleset is applied, which
+//! restores them when the table did not exist (after a reboot or a manual delete).
+//! Re-adding an element that is already in the set is not an error.
+//!
+//! # Concurrent Access
+//!
+//! Like profiles, the ban list is not safe for concurrent writes from multiple
+//! DRFW processes (e.g. the GUI and `drfw ban` at the same moment).
+
+use crate::core::error::{Error, Result};
+use crate::utils::get_state_dir;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// Timeout set holding banned IPv4 addresses
+pub const BAN_SET_V4: &str = "drfw_banned_v4";
+
+/// Timeout set holding banned IPv6 addresses
+pub const BAN_SET_V6: &str = "drfw_banned_v6";
+
+/// Maximum number of simultaneous bans (prevents unbounded state file growth)
+pub const MAX_BANS: usize = 4096;
+
+/// Timeout for element add/delete operations
+const NFT_BAN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A temporary ban of a single address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ban {
+    pub ip: IpAddr,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Ban {
+    /// Returns the time left on the ban, or `None` if it has expired
+    pub fn remaining(&self, now: DateTime<Utc>) -> Option<Duration> {
+        (self.expires_at - now)
+            .to_std()
+            .ok()
+            .filter(|d| !d.is_zero())
+    }
+}
+
+/// Returns the blocklist set for an address family
+pub fn set_for(ip: IpAddr) -> &'static str {
+    match ip {
+        IpAddr::V4(_) => BAN_SET_V4,
+        IpAddr::V6(_) => BAN_SET_V6,
+    }
+}
+
+/// Formats a remaining duration compactly for display (e.g. `1d 4h`, `12m`)
+pub fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (days, hours, mins) = (secs / 86_400, (secs % 86_400) / 3600, (secs % 3600) / 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {mins}m")
+    } else if mins > 0 {
+        format!("{mins}m")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// Returns the path of the persisted ban list
+fn bans_path() -> Result<PathBuf> {
+    let mut path = get_state_dir()
+        .ok_or_else(|| Error::Internal("Failed to get state directory".to_string()))?;
+    path.push("bans.json");
+    Ok(path)
+}
+
+/// Loads the persisted ban list, dropping expired entries.
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+pub async fn load_bans() -> Result<Vec<Ban>> {
+    let path = bans_path()?;
+    if !tokio::fs::try_exists(&path).await? {
+        return Ok(Vec::new());
+    }
+
+    let json = tokio::fs::read_to_string(&path).await?;
+    let mut bans: Vec<Ban> = serde_json::from_str(&json)?;
+    bans.truncate(MAX_BANS);
+
+    let now = Utc::now();
+    bans.retain(|b| b.remaining(now).is_some());
+    Ok(bans)
+}
+
+/// Saves the ban list using the same atomic write pattern as the app config.
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+pub async fn save_bans(bans: &[Ban]) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let path = bans_path()?;
+    crate::utils::ensure_dirs()?;
+
+    let json = serde_json::to_string_pretty(bans)?;
+    let temp_path = path.with_extension("json.tmp");
+
+    #[cfg(unix)]
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600) // User read/write only
+        .open(&temp_path)
+        .await?;
+
+    #[cfg(not(unix))]
+    let mut file = tokio::fs::File::create(&temp_path).await?;
+
+    file.write_all(json.as_bytes()).await?;
+    file.sync_all().await?;
+
+    tokio::fs::rename(temp_path, path).await?;
+    Ok(())
+}
+
+/// Creates the `add set` command for a blocklist set
+pub fn ban_set_json(name: &str, set_type: &str) -> Value {
+    json!({
+        "add": {
+            "set": {
+                "family": "inet",
+                "table": "drfw",
+                "name": name,
+                "type": set_type,
+                "flags": ["timeout"]
+            }
+        }
+    })
+}
+
+/// Creates the table and blocklist sets if missing (no flush, existing elements are kept)
+fn ensure_ban_sets() -> [Value; 3] {
+    [
+        json!({ "add": { "table": { "family": "inet", "name": "drfw" } } }),
+        ban_set_json(BAN_SET_V4, "ipv4_addr"),
+        ban_set_json(BAN_SET_V6, "ipv6_addr"),
+    ]
+}
+
+/// Creates an element command for a single address
+fn element_json(op: &str, ip: IpAddr, timeout: Option<Duration>) -> Value {
+    let elem = match timeout {
+        Some(timeout) => json!({ "elem": { "val": ip.to_string(), "timeout": timeout.as_secs() } }),
+        None => json!(ip.to_string()),
+    };
+    json!({
+        op: {
+            "element": {
+                "family": "inet",
+                "table": "drfw",
+                "name": set_for(ip),
+                "elem": [elem]
+            }
+        }
+    })
+}
+
+/// Appends `add element` commands for all active bans to an apply payload.
+///
+/// Must be called on the output of `to_nftables_json()` (which creates the sets)
+/// so bans are restored when the table is created from scratch.
+pub fn append_active_bans(json_payload: &mut Value, bans: &[Ban], now: DateTime<Utc>) {
+    let Some(nft_rules) = json_payload["nftables"].as_array_mut() else {
+        return;
+    };
+    for ban in bans {
+        if let Some(remaining) = ban.remaining(now) {
+            // nft timeouts have second granularity; round up so short bans aren't zero
+            let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+            nft_rules.push(element_json("add", ban.ip, Some(Duration::from_secs(secs))));
+        }
+    }
+}
+
+/// Bans an address for the given duration.
+///
+/// The element is added to the live blocklist set and the ban is persisted.
+/// Re-banning an already banned address replaces its timeout.
+pub async fn ban(ip: IpAddr, duration: Duration) -> Result<Ban> {
+    crate::validators::validate_ban_address(ip).map_err(|e| Error::Validation {
+        field: "address".to_string(),
+        message: e.to_string(),
+    })?;
+    if duration.as_secs() == 0 || duration.as_secs() > crate::validators::MAX_BAN_DURATION_SECS {
+        return Err(Error::Validation {
+            field: "duration".to_string(),
+            message: "Duration must be between 1 second and 365 days".to_string(),
+        });
+    }
+
+    let mut bans = load_bans().await?;
+    bans.retain(|b| b.ip != ip);
+    if bans.len() >= MAX_BANS {
+        return Err(Error::Validation {
+            field: "address".to_string(),
+            message: format!("Too many active bans (maximum: {MAX_BANS})"),
+        });
+    }
+
+    // add + delete + add: `add element` alone keeps the old timeout of an existing element
+    let mut batch = ensure_ban_sets().to_vec();
+    batch.push(element_json("add", ip, None));
+    batch.push(element_json("delete", ip, None));
+    batch.push(element_json("add", ip, Some(duration)));
+    run_element_batch(&json!({ "nftables": batch })).await?;
+
+    let now = Utc::now();
+    let ban = Ban {
+        ip,
+        created_at: now,
+        expires_at: now + duration,
+    };
+    bans.push(ban.clone());
+    save_bans(&bans).await?;
+
+    info!("Banned {ip} for {}s", duration.as_secs());
+    Ok(ban)
+}
+
+/// Lifts a ban. Returns `false` if the address was not in the persisted ban list.
+///
+/// The element is removed from the kernel set either way, so this also clears
+/// bans that were added outside of DRFW's bookkeeping.
+pub async fn unban(ip: IpAddr) -> Result<bool> {
+    // add (no-op if present) + delete: deleting a missing element is an error
+    let mut batch = ensure_ban_sets().to_vec();
+    batch.push(element_json("add", ip, None));
+    batch.push(element_json("delete", ip, None));
+    run_element_batch(&json!({ "nftables": batch })).await?;
+
+    let mut bans = load_bans().await?;
+    let before = bans.len();
+    bans.retain(|b| b.ip != ip);
+    let was_banned = bans.len() != before;
+    save_bans(&bans).await?;
+
+    info!("Unbanned {ip}");
+    Ok(was_banned)
+}
+
+/// Runs a set element batch with elevated `nft --json -f -`
+///
+/// Also used by `core::blocklists` and `core::geoip` to replace the contents of
+/// their live sets.
+pub(crate) async fn run_element_batch(json_payload: &Value) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let json_string = serde_json::to_string(json_payload)?;
+
+    let mut child = crate::elevation::create_elevated_nft_command(&["--json", "-f", "-"])
+        .map_err(|e| {
+            error!("Privilege escalation unavailable: {e}");
+            Error::Internal(format!("Privilege escalation unavailable: {e}"))
+        })?
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            error!("Failed to spawn elevated nft: {e}");
+            Error::Internal(format!("Failed to spawn elevated nft: {e}"))
+        })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(json_string.as_bytes()).await?;
+    }
+
+    let output = match tokio::time::timeout(NFT_BAN_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            error!("Failed to read nft output: {e}");
+            return Err(Error::Internal(format!("Failed to read nft output: {e}")));
+        }
+        Err(_) => {
+            error!(
+                "nft set update timed out after {} seconds",
+                NFT_BAN_TIMEOUT.as_secs()
+            );
+            return Err(Error::Internal(format!(
+                "nft set update timed out after {} seconds",
+                NFT_BAN_TIMEOUT.as_secs()
+            )));
+        }
+    };
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        error!("Set update failed: {stderr}");
+        Err(Error::Nftables {
+            message: stderr.clone(),
+            stderr: Some(stderr),
+            exit_code: output.status.code(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_ban(ip: &str, secs: i64, now: DateTime<Utc>) -> Ban {
+        Ban {
+            ip: ip.parse().unwrap(),
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(secs),
+        }
+    }
+
+    #[test]
+    fn test_append_active_bans_skips_expired() {
+        let now = Utc::now();
+        let bans = vec![
+            test_ban("203.0.113.7", 3600, now),
+            test_ban("2001:db8::1", 60, now),
+            test_ban("198.51.100.1", -10, now),
+        ];
+        let mut payload = json!({ "nftables": [] });
+        append_active_bans(&mut payload, &bans, now);
+
+        let elements = payload["nftables"].as_array().unwrap();
+        assert_eq!(elements.len(), 2);
+        assert_eq!(elements[0]["add"]["element"]["name"], BAN_SET_V4);
+        assert_eq!(
+            elements[0]["add"]["element"]["elem"][0]["elem"]["timeout"],
+            3600
+        );
+        assert_eq!(elements[1]["add"]["element"]["name"], BAN_SET_V6);
+        assert_eq!(
+            elements[1]["add"]["element"]["elem"][0]["elem"]["val"],
+            "2001:db8::1"
+        );
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(45)), "45s");
+        assert_eq!(format_remaining(Duration::from_mins(12)), "12m");
+        assert_eq!(format_remaining(Duration::from_mins(65)), "1h 5m");
+        assert_eq!(format_remaining(Duration::from_hours(50)), "2d 2h");
+    }
+}
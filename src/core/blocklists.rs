@@ -0,0 +1,455 @@
+//! IP blocklists loaded from local files
+//!
+//! A ruleset can attach plain-text list files (FireHOL/Spamhaus style: one
+//! IP or CIDR per line, `#` and `;` comments allowed). Matching addresses are
+//! dropped early in the input chain through two interval sets (one per IP
+//! family), so lists with tens of thousands of entries cost a single set lookup
+//! and never count toward `MAX_RULES`.
+//!
+//! Files are parsed when they are attached or refreshed and the result is kept
+//! in `blocklists.json` in the state directory. Like ban elements, the entries
+//! are not part of the generated ruleset: they are merged into non-overlapping
+//! ranges and appended to the apply payload, and `drfw blocklist refresh`
+//! replaces the live set contents without re-applying the ruleset.
+
+use crate::core::error::{Error, Result};
+use crate::utils::get_state_dir;
+use chrono::{DateTime, Utc};
+use ipnetwork::IpNetwork;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+
+/// Interval set holding blocklisted IPv4 ranges
+pub const BLOCKLIST_SET_V4: &str = "drfw_blocklist_v4";
+
+/// Interval set holding blocklisted IPv6 ranges
+pub const BLOCKLIST_SET_V6: &str = "drfw_blocklist_v6";
+
+/// Maximum number of list files attached to a ruleset
+pub const MAX_BLOCKLISTS: usize = 16;
+
+/// Maximum entries accepted from a single file (prevents memory exhaustion)
+pub const MAX_BLOCKLIST_ENTRIES: usize = 1 << 19;
+
+/// Maximum size of a list file
+const MAX_BLOCKLIST_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// A parsed blocklist file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadedBlocklist {
+    pub path: PathBuf,
+    pub loaded_at: DateTime<Utc>,
+    /// Unique networks from the file (host bits cleared)
+    pub entries: Vec<IpNetwork>,
+    /// Lines that were neither comments nor valid addresses
+    pub invalid_lines: usize,
+}
+
+/// Parses a list file: one address or CIDR per line, anything after `#` or
+/// `;` is a comment. Returns the unique networks and the number of invalid lines.
+///
+/// `/0` networks are counted as invalid: they would drop all traffic.
+pub fn parse_blocklist(text: &str) -> (Vec<IpNetwork>, usize) {
+    let mut entries = Vec::new();
+    let mut invalid_lines = 0;
+
+    for line in text.lines() {
+        let line = line.split(['#', ';']).next().unwrap_or_default();
+        let Some(token) = line.split_whitespace().next() else {
+            continue;
+        };
+        match token.parse::<IpNetwork>() {
+            Ok(net) if net.prefix() > 0 => {
+                // Normalize 192.0.2.7/24 to 192.0.2.0/24 so duplicates collapse
+                if let Ok(net) = IpNetwork::new(net.network(), net.prefix()) {
+                    entries.push(net);
+                }
+            }
+            _ => invalid_lines += 1,
+        }
+    }
+
+    entries.sort_unstable();
+    entries.dedup();
+    (entries, invalid_lines)
+}
+
+/// Merges overlapping and adjacent ranges (input need not be sorted)
+fn merge_ranges<T: Ord + Copy>(mut ranges: Vec<(T, T)>, next: fn(T) -> Option<T>) -> Vec<(T, T)> {
+    ranges.sort_unstable();
+    let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        if let Some(last) = merged.last_mut()
+            && next(last.1).is_none_or(|after| start <= after)
+        {
+            last.1 = last.1.max(end);
+        } else {
+            merged.push((start, end));
+        }
+    }
+    merged
+}
+
+/// Non-overlapping, sorted address ranges (inclusive) per IP family
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergedRanges {
+    pub v4: Vec<(Ipv4Addr, Ipv4Addr)>,
+    pub v6: Vec<(Ipv6Addr, Ipv6Addr)>,
+}
+
+impl MergedRanges {
+    /// Total number of ranges in both families
+    pub fn len(&self) -> usize {
+        self.v4.len() + self.v6.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.v4.is_empty() && self.v6.is_empty()
+    }
+}
+
+/// Merges networks into non-overlapping IPv4 and IPv6 ranges
+pub fn merge_networks<'a>(networks: impl IntoIterator<Item = &'a IpNetwork>) -> MergedRanges {
+    merge_address_ranges(networks.into_iter().map(|net| match net {
+        IpNetwork::V4(net) => (IpAddr::V4(net.network()), IpAddr::V4(net.broadcast())),
+        IpNetwork::V6(net) => {
+            let start = u128::from(net.network());
+            let host_mask = u128::MAX.checked_shr(u32::from(net.prefix())).unwrap_or(0);
+            (
+                IpAddr::V6(net.network()),
+                IpAddr::V6(Ipv6Addr::from(start | host_mask)),
+            )
+        }
+    }))
+}
+
+/// Merges inclusive address ranges into non-overlapping IPv4 and IPv6 ranges.
+///
+/// Ranges whose ends are of different families or reversed are skipped.
+pub fn merge_address_ranges(ranges: impl IntoIterator<Item = (IpAddr, IpAddr)>) -> MergedRanges {
+    let mut v4 = Vec::new();
+    let mut v6 = Vec::new();
+    for range in ranges {
+        match range {
+            (IpAddr::V4(start), IpAddr::V4(end)) if start <= end => {
+                v4.push((u32::from(start), u32::from(end)));
+            }
+            (IpAddr::V6(start), IpAddr::V6(end)) if start <= end => {
+                v6.push((u128::from(start), u128::from(end)));
+            }
+            _ => {}
+        }
+    }
+
+    let v4 = merge_ranges(v4, |a| a.checked_add(1))
+        .into_iter()
+        .map(|(s, e)| (Ipv4Addr::from(s), Ipv4Addr::from(e)))
+        .collect();
+    let v6 = merge_ranges(v6, |a| a.checked_add(1))
+        .into_iter()
+        .map(|(s, e)| (Ipv6Addr::from(s), Ipv6Addr::from(e)))
+        .collect();
+    MergedRanges { v4, v6 }
+}
+
+/// Builds set elements: single addresses as values, everything else as ranges
+pub(crate) fn range_elements<T: PartialEq + ToString>(ranges: &[(T, T)]) -> Vec<Value> {
+    ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                json!(start.to_string())
+            } else {
+                json!({ "range": [start.to_string(), end.to_string()] })
+            }
+        })
+        .collect()
+}
+
+/// Creates the `add set` command for an interval set (blocklists, countries)
+pub fn interval_set_json(name: &str, set_type: &str) -> Value {
+    json!({
+        "add": {
+            "set": {
+                "family": "inet",
+                "table": "drfw",
+                "name": name,
+                "type": set_type,
+                "flags": ["interval"]
+            }
+        }
+    })
+}
+
+/// Creates `add element` commands for the entries of the attached lists
+fn element_commands(attached: &[PathBuf], loaded: &[LoadedBlocklist]) -> Vec<Value> {
+    let networks = loaded
+        .iter()
+        .filter(|list| attached.contains(&list.path))
+        .flat_map(|list| &list.entries);
+    let merged = merge_networks(networks);
+
+    [
+        (BLOCKLIST_SET_V4, range_elements(&merged.v4)),
+        (BLOCKLIST_SET_V6, range_elements(&merged.v6)),
+    ]
+    .into_iter()
+    .filter(|(_, elements)| !elements.is_empty())
+    .map(|(name, elements)| {
+        json!({
+            "add": {
+                "element": {
+                    "family": "inet",
+                    "table": "drfw",
+                    "name": name,
+                    "elem": elements
+                }
+            }
+        })
+    })
+    .collect()
+}
+
+/// Returns `flush set` commands for both blocklist sets
+fn flush_commands() -> [Value; 2] {
+    [BLOCKLIST_SET_V4, BLOCKLIST_SET_V6].map(
+        |name| json!({ "flush": { "set": { "family": "inet", "table": "drfw", "name": name } } }),
+    )
+}
+
+/// Appends the blocklist entries of the attached lists to an apply payload.
+///
+/// Must be called on the output of `to_nftables_json()` of a ruleset with
+/// blocklists attached (which creates the sets). Lists that were never loaded
+/// are skipped. `flush table` keeps set elements, so both sets are flushed
+/// first: entries of detached lists or removed from a file are dropped, and
+/// merged ranges cannot overlap intervals left in the kernel.
+pub fn append_blocklist_elements(
+    json_payload: &mut Value,
+    attached: &[PathBuf],
+    loaded: &[LoadedBlocklist],
+) {
+    if attached.is_empty() {
+        return;
+    }
+    if let Some(nft_rules) = json_payload["nftables"].as_array_mut() {
+        nft_rules.extend(flush_commands());
+        nft_rules.extend(element_commands(attached, loaded));
+    }
+}
+
+/// Returns the path of the parsed blocklist cache
+fn cache_path() -> Result<PathBuf> {
+    let mut path = get_state_dir()
+        .ok_or_else(|| Error::Internal("Failed to get state directory".to_string()))?;
+    path.push("blocklists.json");
+    Ok(path)
+}
+
+/// Loads the parsed blocklists (empty if nothing was loaded yet).
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+pub async fn load_cache() -> Result<Vec<LoadedBlocklist>> {
+    let path = cache_path()?;
+    if !tokio::fs::try_exists(&path).await? {
+        return Ok(Vec::new());
+    }
+    let json = tokio::fs::read_to_string(&path).await?;
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Saves the parsed blocklists using the same atomic write pattern as the ban list.
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+async fn save_cache(lists: &[LoadedBlocklist]) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let path = cache_path()?;
+    crate::utils::ensure_dirs()?;
+
+    let json = serde_json::to_string(lists)?;
+    let temp_path = path.with_extension("json.tmp");
+
+    #[cfg(unix)]
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600) // User read/write only
+        .open(&temp_path)
+        .await?;
+
+    #[cfg(not(unix))]
+    let mut file = tokio::fs::File::create(&temp_path).await?;
+
+    file.write_all(json.as_bytes()).await?;
+    file.sync_all().await?;
+
+    tokio::fs::rename(temp_path, path).await?;
+    Ok(())
+}
+
+/// Reads and parses a single list file
+async fn read_blocklist(path: &Path) -> Result<LoadedBlocklist> {
+    let invalid = |message: String| Error::Validation {
+        field: "blocklist".to_string(),
+        message,
+    };
+
+    let metadata = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| invalid(format!("{}: {e}", path.display())))?;
+    if metadata.len() > MAX_BLOCKLIST_FILE_BYTES {
+        return Err(invalid(format!(
+            "{}: file too large (max {} MB)",
+            path.display(),
+            MAX_BLOCKLIST_FILE_BYTES / (1024 * 1024)
+        )));
+    }
+
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|e| invalid(format!("{}: {e}", path.display())))?;
+    let (entries, invalid_lines) = parse_blocklist(&String::from_utf8_lossy(&bytes));
+    if entries.len() > MAX_BLOCKLIST_ENTRIES {
+        return Err(invalid(format!(
+            "{}: too many entries ({}, maximum: {MAX_BLOCKLIST_ENTRIES})",
+            path.display(),
+            entries.len()
+        )));
+    }
+
+    Ok(LoadedBlocklist {
+        path: path.to_path_buf(),
+        loaded_at: Utc::now(),
+        entries,
+        invalid_lines,
+    })
+}
+
+/// Re-reads the given list files and replaces the cache with the result.
+///
+/// Fails without touching the cache if any file cannot be read.
+pub async fn refresh(paths: &[PathBuf]) -> Result<Vec<LoadedBlocklist>> {
+    let mut lists = Vec::with_capacity(paths.len());
+    for path in paths {
+        lists.push(read_blocklist(path).await?);
+    }
+    save_cache(&lists).await?;
+    Ok(lists)
+}
+
+/// Replaces the contents of the live blocklist sets (no ruleset re-apply).
+///
+/// The sets are created if missing, so this is harmless before the first apply;
+/// the drop rules themselves only exist once a ruleset with blocklists is applied.
+pub async fn update_live_sets(attached: &[PathBuf], loaded: &[LoadedBlocklist]) -> Result<()> {
+    let mut batch = vec![
+        json!({ "add": { "table": { "family": "inet", "name": "drfw" } } }),
+        interval_set_json(BLOCKLIST_SET_V4, "ipv4_addr"),
+        interval_set_json(BLOCKLIST_SET_V6, "ipv6_addr"),
+    ];
+    batch.extend(flush_commands());
+    batch.extend(element_commands(attached, loaded));
+    crate::core::bans::run_element_batch(&json!({ "nftables": batch })).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_blocklist() {
+        let text = "\
+# FireHOL style header
+192.0.2.0/24
+192.0.2.7/24 ; duplicate after normalization
+198.51.100.7
+2001:db8::/32   # inline comment
+
+not-an-address
+0.0.0.0/0
+";
+        let (entries, invalid) = parse_blocklist(text);
+        assert_eq!(invalid, 2);
+        assert_eq!(
+            entries,
+            vec![
+                "192.0.2.0/24".parse().unwrap(),
+                "198.51.100.7/32".parse().unwrap(),
+                "2001:db8::/32".parse().unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_networks() {
+        let networks: Vec<IpNetwork> = [
+            "10.0.0.0/25",
+            "10.0.0.128/25",
+            "10.0.0.5",
+            "10.0.2.0/24",
+            "203.0.113.9",
+            "2001:db8::/64",
+            "2001:db8:0:1::/64",
+        ]
+        .iter()
+        .map(|s| s.parse().unwrap())
+        .collect();
+
+        let MergedRanges { v4, v6 } = merge_networks(&networks);
+        assert_eq!(
+            v4,
+            vec![
+                ("10.0.0.0".parse().unwrap(), "10.0.0.255".parse().unwrap()),
+                ("10.0.2.0".parse().unwrap(), "10.0.2.255".parse().unwrap()),
+                (
+                    "203.0.113.9".parse().unwrap(),
+                    "203.0.113.9".parse().unwrap()
+                ),
+            ]
+        );
+        assert_eq!(v6.len(), 1);
+        assert_eq!(
+            v6[0].1,
+            "2001:db8:0:1:ffff:ffff:ffff:ffff"
+                .parse::<Ipv6Addr>()
+                .unwrap()
+        );
+
+        let elements = range_elements(&v4);
+        assert_eq!(elements[0]["range"][1], "10.0.0.255");
+        assert_eq!(elements[2], "203.0.113.9");
+    }
+
+    #[test]
+    fn test_append_skips_detached_lists() {
+        let attached = vec![PathBuf::from("/etc/drfw/spamhaus.txt")];
+        let loaded = vec![
+            LoadedBlocklist {
+                path: attached[0].clone(),
+                loaded_at: Utc::now(),
+                entries: vec!["192.0.2.0/24".parse().unwrap()],
+                invalid_lines: 0,
+            },
+            LoadedBlocklist {
+                path: PathBuf::from("/etc/drfw/old.txt"),
+                loaded_at: Utc::now(),
+                entries: vec!["2001:db8::/32".parse().unwrap()],
+                invalid_lines: 0,
+            },
+        ];
+
+        let mut payload = json!({ "nftables": [] });
+        append_blocklist_elements(&mut payload, &attached, &loaded);
+        let commands = payload["nftables"].as_array().unwrap();
+        assert_eq!(commands.len(), 3, "Empty IPv6 set gets no element command");
+        // The IPv6 set is still flushed, dropping the detached list's entries
+        assert_eq!(commands[1]["flush"]["set"]["name"], BLOCKLIST_SET_V6);
+        assert_eq!(commands[2]["add"]["element"]["name"], BLOCKLIST_SET_V4);
+    }
+}
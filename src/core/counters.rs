@@ -0,0 +1,294 @@
+//! Per-rule packet/byte counters
+//!
+//! When `rule_counters` is enabled in the advanced security settings, every user
+//! rule gets a `counter` statement and a comment ending in the rule's UUID
+//! (`Allow SSH [<uuid>]`). Reading `nft --json list table inet drfw` then maps the
+//! kernel counters back to `Rule.id`; rules split into an IPv4 and an IPv6 variant
+//! are summed. Rules with a byte quota also report how much of it is used, read
+//! from the named quota object all variants of the rule share.
+//!
+//! Kernel counters start at zero whenever the ruleset is applied. Resetting in the
+//! GUI only records a baseline, so it needs no extra privileged call.
+
+use crate::core::error::{Error, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::error;
+use uuid::Uuid;
+
+/// Timeout for reading the counters
+const NFT_LIST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Packets and bytes matched by a rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleCounter {
+    pub packets: u64,
+    pub bytes: u64,
+    /// Consumption of the rule's quota, if it has one
+    pub quota: Option<QuotaUsage>,
+}
+
+/// Bytes used of a rule's quota
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    pub used: u64,
+    pub total: u64,
+}
+
+impl RuleCounter {
+    /// Returns the traffic counted since `baseline` (saturating, in case the
+    /// kernel counters were reset by an apply in between). Quota usage is kept as is.
+    pub fn since(self, baseline: Option<&RuleCounter>) -> Self {
+        let baseline = baseline.copied().unwrap_or_default();
+        Self {
+            packets: self.packets.saturating_sub(baseline.packets),
+            bytes: self.bytes.saturating_sub(baseline.bytes),
+            quota: self.quota,
+        }
+    }
+
+    /// Formats the counter compactly for the sidebar (e.g. `1.2K pkts · 340 KB`,
+    /// followed by `· quota 1.0 GB/5.0 GB` for rules with a quota)
+    pub fn display(self) -> String {
+        let counts = format!(
+            "{} pkts · {}",
+            format_count(self.packets, 1000, ""),
+            format_count(self.bytes, 1024, "B")
+        );
+        match self.quota {
+            Some(quota) => format!(
+                "{counts} · quota {}/{}",
+                format_count(quota.used, 1024, "B"),
+                format_count(quota.total, 1024, "B")
+            ),
+            None => counts,
+        }
+    }
+}
+
+/// Formats a count with K/M/G/T suffixes (one decimal below 10)
+#[allow(clippy::cast_precision_loss)] // Display only, precision loss is irrelevant
+fn format_count(value: u64, base: u64, unit: &str) -> String {
+    const SUFFIXES: [&str; 4] = ["K", "M", "G", "T"];
+    let sep = if unit.is_empty() { "" } else { " " };
+    if value < base {
+        return format!("{value}{sep}{unit}");
+    }
+    let base = base as f64;
+    let mut scaled = value as f64 / base;
+    let mut index = 0;
+    while scaled >= base && index < SUFFIXES.len() - 1 {
+        scaled /= base;
+        index += 1;
+    }
+    let suffix = SUFFIXES[index];
+    if scaled < 10.0 {
+        format!("{scaled:.1}{sep}{suffix}{unit}")
+    } else {
+        format!("{scaled:.0}{sep}{suffix}{unit}")
+    }
+}
+
+/// Bytes [`tagged_comment`] adds to a label: `" [<uuid>]"`
+pub const TAG_LEN: usize = 39;
+
+/// Builds the comment tying a kernel rule back to its `Rule.id`
+pub fn tagged_comment(id: Uuid, label: &str) -> String {
+    if label.is_empty() {
+        format!("[{id}]")
+    } else {
+        format!("{label} [{id}]")
+    }
+}
+
+/// Extracts the rule ID from a comment built by [`tagged_comment`]
+pub fn rule_id_from_comment(comment: &str) -> Option<Uuid> {
+    let tag = comment.strip_suffix(']')?;
+    let start = tag.rfind('[')?;
+    Uuid::parse_str(&tag[start + 1..]).ok()
+}
+
+/// Collects the counters of tagged rules from `nft --json list table` output
+pub fn parse_rule_counters(listing: &Value) -> HashMap<Uuid, RuleCounter> {
+    let mut counters: HashMap<Uuid, RuleCounter> = HashMap::new();
+    let Some(items) = listing["nftables"].as_array() else {
+        return counters;
+    };
+    // Quota objects by name (a rule references its quota as `{"quota": "<name>"}`)
+    let quotas: HashMap<&str, QuotaUsage> = items
+        .iter()
+        .filter_map(|item| item.get("quota"))
+        .filter_map(|quota| {
+            let usage = QuotaUsage {
+                used: quota["used"].as_u64().unwrap_or(0),
+                total: quota["bytes"].as_u64().unwrap_or(0),
+            };
+            Some((quota["name"].as_str()?, usage))
+        })
+        .collect();
+    for rule in items.iter().filter_map(|item| item.get("rule")) {
+        let Some(id) = rule["comment"].as_str().and_then(rule_id_from_comment) else {
+            continue;
+        };
+        let Some(expr) = rule["expr"].as_array() else {
+            continue;
+        };
+        let counter = expr.iter().find_map(|e| e.get("counter"));
+        let quota = expr
+            .iter()
+            .find_map(|e| e["quota"].as_str())
+            .and_then(|name| quotas.get(name));
+        if counter.is_none() && quota.is_none() {
+            continue;
+        }
+        let entry = counters.entry(id).or_default();
+        if let Some(counter) = counter {
+            entry.packets += counter["packets"].as_u64().unwrap_or(0);
+            entry.bytes += counter["bytes"].as_u64().unwrap_or(0);
+        }
+        // Variants share the quota object, so its usage is taken once, not summed
+        if let Some(quota) = quota {
+            entry.quota = Some(*quota);
+        }
+    }
+    counters
+}
+
+/// Reads the current per-rule counters with elevated `nft --json list table inet drfw`
+pub async fn read_rule_counters() -> Result<HashMap<Uuid, RuleCounter>> {
+    let child =
+        crate::elevation::create_elevated_nft_command(&["--json", "list", "table", "inet", "drfw"])
+            .map_err(|e| {
+                error!("Privilege escalation unavailable: {e}");
+                Error::Internal(format!("Privilege escalation unavailable: {e}"))
+            })?
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                error!("Failed to spawn elevated nft: {e}");
+                Error::Internal(format!("Failed to spawn elevated nft: {e}"))
+            })?;
+
+    let output = match tokio::time::timeout(NFT_LIST_TIMEOUT, child.wait_with_output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            error!("Failed to read nft output: {e}");
+            return Err(Error::Internal(format!("Failed to read nft output: {e}")));
+        }
+        Err(_) => {
+            return Err(Error::Internal(format!(
+                "nft counter read timed out after {} seconds",
+                NFT_LIST_TIMEOUT.as_secs()
+            )));
+        }
+    };
+
+    if output.status.success() {
+        let listing: Value = serde_json::from_slice(&output.stdout)?;
+        Ok(parse_rule_counters(&listing))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        Err(Error::Nftables {
+            message: stderr.clone(),
+            stderr: Some(stderr),
+            exit_code: output.status.code(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rule_id_round_trip() {
+        let id = Uuid::new_v4();
+        assert_eq!(
+            rule_id_from_comment(&tagged_comment(id, "Allow SSH")),
+            Some(id)
+        );
+        assert_eq!(rule_id_from_comment(&tagged_comment(id, "")), Some(id));
+        assert_eq!(rule_id_from_comment("allow tracked connections"), None);
+        assert_eq!(rule_id_from_comment("Backup [nightly]"), None);
+    }
+
+    #[test]
+    fn test_parse_rule_counters_sums_split_rules() {
+        let id = Uuid::new_v4();
+        let comment = tagged_comment(id, "Web");
+        let listing = json!({ "nftables": [
+            { "metainfo": { "version": "1.0.9" } },
+            { "table": { "family": "inet", "name": "drfw" } },
+            { "rule": { "chain": "input", "comment": "allow icmp",
+                "expr": [ { "counter": { "packets": 99, "bytes": 9999 } }, { "accept": null } ] } },
+            { "rule": { "chain": "input", "comment": comment,
+                "expr": [ { "counter": { "packets": 3, "bytes": 180 } }, { "accept": null } ] } },
+            { "rule": { "chain": "input", "comment": comment,
+                "expr": [ { "counter": { "packets": 2, "bytes": 120 } }, { "accept": null } ] } },
+        ] });
+
+        let counters = parse_rule_counters(&listing);
+        assert_eq!(counters.len(), 1);
+        assert_eq!(
+            counters[&id],
+            RuleCounter {
+                packets: 5,
+                bytes: 300,
+                quota: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_rule_counters_shared_quota() {
+        let id = Uuid::new_v4();
+        let comment = tagged_comment(id, "Lab");
+        let name = format!("drfw_quota_{}", id.simple());
+        let listing = json!({ "nftables": [
+            { "quota": { "family": "inet", "table": "drfw", "name": name,
+                "bytes": 5_368_709_120_u64, "used": 1_048_576, "inv": false } },
+            { "rule": { "chain": "input", "comment": comment,
+                "expr": [
+                    { "quota": name },
+                    { "counter": { "packets": 500, "bytes": 655_360 } },
+                    { "accept": null }
+                ] } },
+            { "rule": { "chain": "input", "comment": comment,
+                "expr": [
+                    { "quota": name },
+                    { "counter": { "packets": 300, "bytes": 393_216 } },
+                    { "accept": null }
+                ] } },
+        ] });
+
+        let counter = parse_rule_counters(&listing)[&id];
+        assert_eq!(
+            counter.quota,
+            Some(QuotaUsage {
+                used: 1024 * 1024,
+                total: 5 * 1024 * 1024 * 1024
+            })
+        );
+        assert_eq!(counter.display(), "800 pkts · 1.0 MB · quota 1.0 MB/5.0 GB");
+    }
+
+    #[test]
+    fn test_counter_display() {
+        let counter = RuleCounter {
+            packets: 1234,
+            bytes: 348_160,
+            quota: None,
+        };
+        assert_eq!(counter.display(), "1.2K pkts · 340 KB");
+        let baseline = RuleCounter {
+            packets: 1000,
+            bytes: 348_000,
+            quota: None,
+        };
+        assert_eq!(counter.since(Some(&baseline)).display(), "234 pkts · 160 B");
+    }
+}
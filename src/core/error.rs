@@ -21,7 +21,6 @@ pub enum Error {
 
     /// Input validation failed
     #[error("Validation error in {field}: {message}")]
-    #[allow(dead_code)] // Constructed in tests to verify translation
     Validation { field: String, message: String },
 
     /// Snapshot operation failed
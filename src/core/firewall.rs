@@ -10,10 +10,11 @@
 //! - Port ranges for applicable protocols
 //! - Source IP/network filtering
 //! - Network interface filtering
-//! - Chain direction (Input/Output) - only relevant in Server Mode
+//! - Chain direction (Input/Output/Forward) - Output only relevant in Server Mode
 //! - Enable/disable state
 //! - Tags for organization
 //! - Advanced options: destination IP, action (Accept/Drop/Reject), rate limiting, connection limiting
+//! - Optional port forwarding (DNAT) to an internal host
 //!
 //! # Limits
 //!
@@ -31,11 +32,13 @@
 //!     protocol: Protocol::Tcp,
 //!     ports: vec![PortEntry::single(22)],  // Single port, or vec![22.into()]
 //!     sources: vec![],  // Empty = any source. Can mix IPv4/IPv6
-//!     interface: None,
-//!     output_interface: None,
+//!     interfaces: vec![],  // Empty = any interface. Wildcards like "eth*" allowed
+//!     output_interfaces: Vec::new(),
 //!     chain: Chain::Input,
 //!     enabled: true,
 //!     created_at: chrono::Utc::now(),
+//!     modified_at: None,
+//!     expires_at: None,
 //!     tags: vec![],
 //!     // Advanced options
 //!     destinations: vec![],  // Empty = any destination. Can mix IPv4/IPv6
@@ -44,6 +47,23 @@
 //!     rate_limit: None,
 //!     connection_limit: 0,
 //!     log_enabled: false,
+//!     source_set: None,
+//!     destination_set: None,
+//!     port_set: None,
+//!     port_forward: None,
+//!     source_ports: Vec::new(),
+//!     icmp_types: Vec::new(),
+//!     icmp_code: None,
+//!     ct_states: Vec::new(),
+//!     schedule: None,
+//!     quota: None,
+//!     per_source: None,
+//!     countries: Vec::new(),
+//!     group: None,
+//!     description: String::new(),
+//!     owner: String::new(),
+//!     reference: String::new(),
+//!     negate: drfw::core::firewall::Negation::default(),
 //!     // Cached fields (populated by rebuild_caches())
 //!     label_lowercase: String::new(),
 //!     interface_lowercase: None,
@@ -59,6 +79,7 @@
 //!     log_prefix: String::new(),
 //!     tags_truncated: Vec::new(),
 //!     badge_display: String::new(),
+//!     metadata_lowercase: String::new(),
 //! };
 //! rule.rebuild_caches();
 //! ```
@@ -66,6 +87,7 @@
 use ipnetwork::IpNetwork;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::net::IpAddr;
 use uuid::Uuid;
 
 /// Maximum number of rules allowed in a single ruleset
@@ -287,6 +309,170 @@ impl From<PortRange> for PortEntry {
     }
 }
 
+/// An address entry that can be a network (CIDR or single host), a range, a hostname
+/// or the subnets of local interfaces.
+///
+/// Used in [`Rule::sources`], [`Rule::destinations`] and address [`NamedSet`]s.
+/// Ranges cover pools that don't align to a prefix, such as a DHCP pool.
+/// Hostnames and interface subnets are symbolic: they are expanded at apply time
+/// (see [`crate::core::hosts`] and [`crate::core::subnets`]). Until then they
+/// belong to neither IP family and rules using them are not generated.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::AddressEntry;
+///
+/// let lan: AddressEntry = "192.168.1.0/24".parse().unwrap();
+/// let pool: AddressEntry = "192.168.1.100-192.168.1.150".parse().unwrap();
+///
+/// assert!(pool.is_ipv4());
+/// assert_eq!(lan.to_string(), "192.168.1.0/24");
+/// assert_eq!(pool.to_string(), "192.168.1.100-192.168.1.150");
+///
+/// // Start must not be after end, and both ends must be the same family
+/// assert!("10.0.0.9-10.0.0.1".parse::<AddressEntry>().is_err());
+/// assert!("10.0.0.1-fd00::1".parse::<AddressEntry>().is_err());
+///
+/// // Anything else that is a valid DNS name is a hostname
+/// let nas: AddressEntry = "NAS.local".parse().unwrap();
+/// assert_eq!(nas.hostname(), Some("nas.local"));
+/// assert!("192.168.1".parse::<AddressEntry>().is_err());
+///
+/// // `subnet:` names the networks of an interface (wildcards allowed)
+/// let lan: AddressEntry = "subnet:wlan0".parse().unwrap();
+/// assert_eq!(lan.subnet_of(), Some("wlan0"));
+/// assert!("subnet:docker*".parse::<AddressEntry>().is_ok());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum AddressEntry {
+    /// A network prefix or single host
+    Network(IpNetwork),
+    /// An address range (inclusive, both ends in the same family)
+    Range { start: IpAddr, end: IpAddr },
+    /// A hostname (lowercase), resolved at apply time
+    Host(#[serde(deserialize_with = "deserialize_hostname")] String),
+    /// The subnets of the local interfaces matching a name (`eth0`, `docker*`),
+    /// expanded at apply time
+    InterfaceSubnet { subnet_of: String },
+}
+
+impl AddressEntry {
+    /// Creates a range entry, or `None` if the ends are mixed-family or reversed
+    pub fn range(start: IpAddr, end: IpAddr) -> Option<Self> {
+        (start.is_ipv4() == end.is_ipv4() && start <= end).then_some(Self::Range { start, end })
+    }
+
+    /// Returns true if this entry holds IPv4 addresses (false for symbolic entries)
+    pub fn is_ipv4(&self) -> bool {
+        match self {
+            Self::Network(net) => net.is_ipv4(),
+            Self::Range { start, .. } => start.is_ipv4(),
+            Self::Host(_) | Self::InterfaceSubnet { .. } => false,
+        }
+    }
+
+    /// Returns true if this entry holds IPv6 addresses (false for symbolic entries)
+    pub fn is_ipv6(&self) -> bool {
+        match self {
+            Self::Network(net) => net.is_ipv6(),
+            Self::Range { start, .. } => start.is_ipv6(),
+            Self::Host(_) | Self::InterfaceSubnet { .. } => false,
+        }
+    }
+
+    /// Returns true for hostnames and interface subnets, which only get
+    /// addresses once expanded
+    pub fn is_symbolic(&self) -> bool {
+        matches!(self, Self::Host(_) | Self::InterfaceSubnet { .. })
+    }
+
+    /// Returns the hostname of an unresolved entry
+    pub fn hostname(&self) -> Option<&str> {
+        match self {
+            Self::Host(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the interface name (or wildcard) of an interface subnet entry
+    pub fn subnet_of(&self) -> Option<&str> {
+        match self {
+            Self::InterfaceSubnet { subnet_of } => Some(subnet_of),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this entry covers every address of its family (e.g. `0.0.0.0/0`)
+    pub fn covers_family(&self) -> bool {
+        match self {
+            Self::Network(net) => net.prefix() == 0,
+            Self::Range { start, end } => match (start, end) {
+                (IpAddr::V4(s), IpAddr::V4(e)) => {
+                    s.is_unspecified() && *e == std::net::Ipv4Addr::BROADCAST
+                }
+                (IpAddr::V6(s), IpAddr::V6(e)) => s.is_unspecified() && e.to_bits() == u128::MAX,
+                _ => false,
+            },
+            Self::Host(_) | Self::InterfaceSubnet { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for AddressEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(net) => write!(f, "{net}"),
+            Self::Range { start, end } => write!(f, "{start}-{end}"),
+            Self::Host(name) => write!(f, "{name}"),
+            Self::InterfaceSubnet { subnet_of } => write!(f, "subnet:{subnet_of}"),
+        }
+    }
+}
+
+impl std::str::FromStr for AddressEntry {
+    type Err = &'static str;
+
+    /// Parses an IP, CIDR (`10.0.0.0/8`), range (`10.0.0.10-10.0.0.20`), hostname
+    /// (`nas.local`) or interface subnet (`subnet:wlan0`). A range whose ends are
+    /// equal collapses to a single host.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if let Some(interface) = input.strip_prefix("subnet:") {
+            return match crate::validators::validate_interface(interface.trim())? {
+                name if name.is_empty() => Err("Interface name required (e.g., subnet:wlan0)"),
+                subnet_of => Ok(Self::InterfaceSubnet { subnet_of }),
+            };
+        }
+        if let Ok(net) = input.parse::<IpNetwork>() {
+            return Ok(Self::Network(net));
+        }
+        if crate::validators::is_valid_hostname(input) {
+            return Ok(Self::Host(input.trim_end_matches('.').to_ascii_lowercase()));
+        }
+        let Some((start, end)) = input.split_once('-') else {
+            return Err("Invalid IP/CIDR, range or hostname (e.g., 192.168.1.0/24)");
+        };
+
+        let start: IpAddr = start.trim().parse().map_err(|_| "Invalid range start")?;
+        let end: IpAddr = end.trim().parse().map_err(|_| "Invalid range end")?;
+        if start.is_ipv4() != end.is_ipv4() {
+            return Err("Range ends must be the same IP version");
+        }
+        if start == end {
+            return Ok(Self::Network(IpNetwork::from(start)));
+        }
+        Self::range(start, end).ok_or("Range start must not be after its end")
+    }
+}
+
+impl From<IpNetwork> for AddressEntry {
+    fn from(net: IpNetwork) -> Self {
+        Self::Network(net)
+    }
+}
+
 /// Rule action (Accept, Drop, or Reject)
 ///
 /// Controls what happens when a packet matches this rule.
@@ -404,6 +590,294 @@ impl RejectType {
     }
 }
 
+/// ICMP/ICMPv6 message type for per-rule type filtering
+///
+/// Most types exist in both families, some under a different nftables name
+/// (e.g. `router-advertisement` vs `nd-router-advert`). Timestamps are
+/// IPv4-only; packet-too-big, neighbor discovery and MLD are ICMPv6-only.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, strum::EnumIter)]
+pub enum IcmpType {
+    EchoRequest,
+    EchoReply,
+    DestinationUnreachable,
+    TimeExceeded,
+    ParameterProblem,
+    RouterAdvertisement,
+    RouterSolicitation,
+    TimestampRequest,
+    TimestampReply,
+    PacketTooBig,
+    NeighborSolicitation,
+    NeighborAdvertisement,
+    MldListenerQuery,
+}
+
+impl IcmpType {
+    /// Returns display name for UI rendering
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            IcmpType::EchoRequest => "Echo Request",
+            IcmpType::EchoReply => "Echo Reply",
+            IcmpType::DestinationUnreachable => "Destination Unreachable",
+            IcmpType::TimeExceeded => "Time Exceeded",
+            IcmpType::ParameterProblem => "Parameter Problem",
+            IcmpType::RouterAdvertisement => "Router Advertisement",
+            IcmpType::RouterSolicitation => "Router Solicitation",
+            IcmpType::TimestampRequest => "Timestamp Request",
+            IcmpType::TimestampReply => "Timestamp Reply",
+            IcmpType::PacketTooBig => "Packet Too Big",
+            IcmpType::NeighborSolicitation => "Neighbor Solicitation",
+            IcmpType::NeighborAdvertisement => "Neighbor Advertisement",
+            IcmpType::MldListenerQuery => "MLD Listener Query",
+        }
+    }
+
+    /// Returns the nftables `icmp type` name, or `None` if the type is ICMPv6-only
+    pub const fn icmp_name(self) -> Option<&'static str> {
+        match self {
+            IcmpType::EchoRequest => Some("echo-request"),
+            IcmpType::EchoReply => Some("echo-reply"),
+            IcmpType::DestinationUnreachable => Some("destination-unreachable"),
+            IcmpType::TimeExceeded => Some("time-exceeded"),
+            IcmpType::ParameterProblem => Some("parameter-problem"),
+            IcmpType::RouterAdvertisement => Some("router-advertisement"),
+            IcmpType::RouterSolicitation => Some("router-solicitation"),
+            IcmpType::TimestampRequest => Some("timestamp-request"),
+            IcmpType::TimestampReply => Some("timestamp-reply"),
+            IcmpType::PacketTooBig
+            | IcmpType::NeighborSolicitation
+            | IcmpType::NeighborAdvertisement
+            | IcmpType::MldListenerQuery => None,
+        }
+    }
+
+    /// Returns the nftables `icmpv6 type` name, or `None` if the type is IPv4-only
+    pub const fn icmpv6_name(self) -> Option<&'static str> {
+        match self {
+            IcmpType::EchoRequest => Some("echo-request"),
+            IcmpType::EchoReply => Some("echo-reply"),
+            IcmpType::DestinationUnreachable => Some("destination-unreachable"),
+            IcmpType::TimeExceeded => Some("time-exceeded"),
+            IcmpType::ParameterProblem => Some("parameter-problem"),
+            IcmpType::RouterAdvertisement => Some("nd-router-advert"),
+            IcmpType::RouterSolicitation => Some("nd-router-solicit"),
+            IcmpType::PacketTooBig => Some("packet-too-big"),
+            IcmpType::NeighborSolicitation => Some("nd-neighbor-solicit"),
+            IcmpType::NeighborAdvertisement => Some("nd-neighbor-advert"),
+            IcmpType::MldListenerQuery => Some("mld-listener-query"),
+            IcmpType::TimestampRequest | IcmpType::TimestampReply => None,
+        }
+    }
+}
+
+/// Connection tracking state for per-rule `ct state` matching
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    strum::AsRefStr,
+)]
+pub enum CtState {
+    /// First packet of a connection
+    #[strum(serialize = "new")]
+    New,
+    /// Packets of a connection that has seen traffic in both directions
+    #[strum(serialize = "established")]
+    Established,
+    /// New connections related to an existing one (e.g. FTP data, ICMP errors)
+    #[strum(serialize = "related")]
+    Related,
+    /// Packets exempted from tracking (`notrack`)
+    #[strum(serialize = "untracked")]
+    Untracked,
+}
+
+impl CtState {
+    /// Returns the short uppercase name used in sidebar badges
+    pub const fn badge_name(self) -> &'static str {
+        match self {
+            CtState::New => "NEW",
+            CtState::Established => "EST",
+            CtState::Related => "REL",
+            CtState::Untracked => "UNTR",
+        }
+    }
+}
+
+/// Day of the week for rule schedules (`meta day`)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    strum::Display,
+    strum::EnumIter,
+    strum::AsRefStr,
+)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Returns the abbreviated name used in badges and summaries
+    pub const fn short_name(self) -> &'static str {
+        match self {
+            Weekday::Monday => "Mon",
+            Weekday::Tuesday => "Tue",
+            Weekday::Wednesday => "Wed",
+            Weekday::Thursday => "Thu",
+            Weekday::Friday => "Fri",
+            Weekday::Saturday => "Sat",
+            Weekday::Sunday => "Sun",
+        }
+    }
+}
+
+/// A rule field whose match can be inverted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchField {
+    Sources,
+    Destinations,
+    Ports,
+    SourcePorts,
+    Interface,
+    OutputInterface,
+}
+
+impl MatchField {
+    pub const ALL: [MatchField; 6] = [
+        MatchField::Sources,
+        MatchField::Destinations,
+        MatchField::Ports,
+        MatchField::SourcePorts,
+        MatchField::Interface,
+        MatchField::OutputInterface,
+    ];
+}
+
+/// Per-field negation: a set flag matches everything *except* the field's
+/// value (`ip saddr != 10.0.0.0/8`, `iifname != "wg0"`).
+///
+/// A negated address list only excludes addresses of its own IP family;
+/// traffic of the other family is not filtered by that field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Negation {
+    #[serde(default)]
+    pub sources: bool,
+    #[serde(default)]
+    pub destinations: bool,
+    #[serde(default)]
+    pub ports: bool,
+    #[serde(default)]
+    pub source_ports: bool,
+    #[serde(default)]
+    pub interface: bool,
+    #[serde(default)]
+    pub output_interface: bool,
+}
+
+impl Negation {
+    /// Returns whether the field is negated
+    pub const fn get(self, field: MatchField) -> bool {
+        match field {
+            MatchField::Sources => self.sources,
+            MatchField::Destinations => self.destinations,
+            MatchField::Ports => self.ports,
+            MatchField::SourcePorts => self.source_ports,
+            MatchField::Interface => self.interface,
+            MatchField::OutputInterface => self.output_interface,
+        }
+    }
+
+    /// Sets whether the field is negated
+    pub fn set(&mut self, field: MatchField, negated: bool) {
+        match field {
+            MatchField::Sources => self.sources = negated,
+            MatchField::Destinations => self.destinations = negated,
+            MatchField::Ports => self.ports = negated,
+            MatchField::SourcePorts => self.source_ports = negated,
+            MatchField::Interface => self.interface = negated,
+            MatchField::OutputInterface => self.output_interface = negated,
+        }
+    }
+
+    /// JSON match operator for a field
+    const fn json_op(negated: bool) -> &'static str {
+        if negated { "!=" } else { "==" }
+    }
+
+    /// Text form of the match operator (`"!= "` or nothing)
+    const fn text_op(negated: bool) -> &'static str {
+        if negated { "!= " } else { "" }
+    }
+}
+
+/// Time window in which a rule is active (`meta hour` / `meta day`)
+///
+/// Times are local wall-clock times; nft converts them to UTC when the ruleset
+/// is loaded. A window whose `end` is before `start` wraps past midnight, and
+/// `start == end` means the whole day (only the weekdays are matched).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Schedule {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+    /// Days on which the rule is active. Empty = every day.
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+}
+
+impl Schedule {
+    /// Returns `true` if the window spans midnight (e.g. 22:00-06:00)
+    pub fn wraps_midnight(&self) -> bool {
+        self.end < self.start
+    }
+
+    /// Returns `true` if the schedule restricts the time of day
+    pub fn has_time_window(&self) -> bool {
+        self.start != self.end
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.has_time_window() {
+            write!(
+                f,
+                "{}-{}",
+                self.start.format("%H:%M"),
+                self.end.format("%H:%M")
+            )?;
+        } else {
+            write!(f, "all day")?;
+        }
+        if !self.days.is_empty() {
+            let days: Vec<_> = self.days.iter().map(|d| d.short_name()).collect();
+            write!(f, " {}", days.join(","))?;
+        }
+        Ok(())
+    }
+}
+
 /// Time unit for rate limiting
 #[derive(
     Debug,
@@ -449,6 +923,16 @@ impl TimeUnit {
             TimeUnit::Day => "Day",
         }
     }
+
+    /// Returns the length of the unit in seconds
+    pub const fn seconds(self) -> u64 {
+        match self {
+            TimeUnit::Second => 1,
+            TimeUnit::Minute => 60,
+            TimeUnit::Hour => 3600,
+            TimeUnit::Day => 86_400,
+        }
+    }
 }
 
 /// Rate limiting configuration
@@ -460,6 +944,16 @@ impl TimeUnit {
 /// The optional `burst` field allows short bursts beyond the rate limit.
 /// For example, rate 5/minute with burst 10 allows up to 10 connections
 /// quickly, then enforces 5/minute average.
+///
+/// # Over
+/// By default the rule matches traffic *below* the rate, so excess packets fall
+/// through to later rules and the default policy. With `over` set the rule
+/// matches only the traffic *exceeding* the rate (`limit rate over`), which is
+/// what a drop or reject rule wants.
+///
+/// # Byte Rates
+/// With `bytes` set, `count` and `burst` are amounts of that unit instead of
+/// packets (`limit rate 10 mbytes/second`), for bandwidth shaping.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 pub struct RateLimit {
     pub count: u32,
@@ -467,77 +961,474 @@ pub struct RateLimit {
     /// Optional burst allowance (0 or None = no burst)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub burst: Option<u32>,
+    /// Match traffic over the rate instead of under it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub over: bool,
+    /// Byte unit for byte-based limits (None = packets)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<ByteUnit>,
 }
 
 impl fmt::Display for RateLimit {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.over {
+            f.write_str("over ")?;
+        }
+        match self.bytes {
+            Some(bytes) => write!(f, "{} {}/{}", self.count, bytes, self.unit)?,
+            None => write!(f, "{}/{}", self.count, self.unit)?,
+        }
         if let Some(burst) = self.burst {
-            write!(f, "{}/{} burst {}", self.count, self.unit, burst)
-        } else {
-            write!(f, "{}/{}", self.count, self.unit)
+            match self.bytes {
+                Some(bytes) => write!(f, " burst {burst} {bytes}")?,
+                None => write!(f, " burst {burst}")?,
+            }
         }
+        Ok(())
     }
 }
 
-/// Firewall chain for rule direction (only relevant in Server Mode)
+/// Byte unit for quotas and byte-based rate limits
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, strum::EnumIter)]
+pub enum ByteUnit {
+    Bytes,
+    KBytes,
+    MBytes,
+    GBytes,
+}
+
+impl ByteUnit {
+    /// Returns the number of bytes in one unit
+    pub const fn bytes(self) -> u64 {
+        match self {
+            ByteUnit::Bytes => 1,
+            ByteUnit::KBytes => 1024,
+            ByteUnit::MBytes => 1024 * 1024,
+            ByteUnit::GBytes => 1024 * 1024 * 1024,
+        }
+    }
+
+    /// Returns display name for UI rendering
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            ByteUnit::Bytes => "bytes",
+            ByteUnit::KBytes => "KB",
+            ByteUnit::MBytes => "MB",
+            ByteUnit::GBytes => "GB",
+        }
+    }
+
+    /// Returns the nftables value and unit for an amount of this unit
+    /// (nft has no `gbytes`, so GB is given in mbytes)
+    pub const fn nft_value(self, amount: u64) -> (u64, &'static str) {
+        match self {
+            ByteUnit::Bytes => (amount, "bytes"),
+            ByteUnit::KBytes => (amount, "kbytes"),
+            ByteUnit::MBytes => (amount, "mbytes"),
+            ByteUnit::GBytes => (amount.saturating_mul(1024), "mbytes"),
+        }
+    }
+}
+
+impl fmt::Display for ByteUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.display_name())
+    }
+}
+
+/// Whether a quota matches until its bytes are used up, or only once they are
 #[derive(
     Debug,
     Clone,
     Copy,
+    Default,
     Serialize,
     Deserialize,
     PartialEq,
     Eq,
-    Default,
     strum::Display,
-    strum::EnumString,
     strum::EnumIter,
-    strum::AsRefStr,
 )]
-pub enum Chain {
-    /// Incoming traffic (default for desktop users)
+pub enum QuotaMode {
+    /// Match while under the quota (`quota until`), e.g. "allow up to 5 GB"
     #[default]
-    #[strum(serialize = "input")]
-    Input,
-    /// Outgoing traffic (only useful in Server Mode with OUTPUT DROP policy)
-    #[strum(serialize = "output")]
-    Output,
+    #[strum(serialize = "until")]
+    Until,
+    /// Match once the quota is exceeded (`quota over`), e.g. "drop after 5 GB"
+    #[strum(serialize = "over")]
+    Over,
+}
+
+/// Byte quota of a rule
+///
+/// The quota is a named `quota` object (`drfw_quota_<id>`) that every variant of
+/// the rule references, so IPv4 and IPv6 traffic count against the same bytes.
+/// Re-applying updates the object in place, so its usage carries over.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Quota {
+    pub amount: u64,
+    pub unit: ByteUnit,
+    #[serde(default)]
+    pub mode: QuotaMode,
+}
+
+impl Quota {
+    /// Returns the quota size in bytes
+    pub const fn total_bytes(&self) -> u64 {
+        self.amount.saturating_mul(self.unit.bytes())
+    }
+
+    /// Returns the nftables value and unit
+    pub const fn nft_value(&self) -> (u64, &'static str) {
+        self.unit.nft_value(self.amount)
+    }
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.mode, self.amount, self.unit)
+    }
+}
+
+/// Per-source tracking of a rule's rate and connection limits
+///
+/// A plain `limit` or `ct count` is shared by every client, so one noisy client can
+/// use up the allowance for everybody. With per-source tracking each source address
+/// gets its own entry in a dynamic set (`update @set { ip saddr limit rate ... }`).
+/// The rule gets one set per limit and IP family; dual-stack rules are split into an
+/// IPv4 and an IPv6 variant.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PerSourceLimit {
+    /// Maximum number of tracked addresses per set
+    pub size: u32,
+    /// Seconds an idle address stays in the rate limit set. Connection limit
+    /// entries are removed by the kernel once their last connection closes.
+    pub timeout_secs: u32,
+}
+
+impl Default for PerSourceLimit {
+    fn default() -> Self {
+        Self {
+            size: 65_535,
+            timeout_secs: 60,
+        }
+    }
 }
 
+/// A port-knocking sequence guarding a service
+///
+/// Clients must hit the `sequence` ports in order, each knock within `timeout_secs`
+/// of the previous one; the protected port is then open to their address for
+/// `timeout_secs`. Every stage is a dynamic timeout set per IP family
+/// (`drfw_knock_<name>_<stage>_v4`) that the next stage's rule checks. Knock packets
+/// themselves are never accepted, and a wrong knock does not reset the sequence.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-pub struct Rule {
-    pub id: Uuid,
-    pub label: String,
+pub struct PortKnock {
+    /// Unique name, used in the stage set names
+    pub name: String,
+    /// Protocol of the knock packets (TCP or UDP)
+    pub knock_protocol: Protocol,
+    /// Ports to knock on, in order
+    pub sequence: Vec<u16>,
+    /// Protocol of the protected service (TCP or UDP)
     pub protocol: Protocol,
-    /// Port entries (single ports or ranges). Empty = all ports.
-    /// Multiple entries create nftables anonymous sets: `dport { 22, 80, 443 }`
-    #[serde(default)]
-    pub ports: Vec<PortEntry>,
-    /// Source IP/network filters. Empty = any source.
-    /// IPv4 and IPv6 addresses can be mixed; DRFW splits them into separate nft rules.
-    #[serde(default)]
-    pub sources: Vec<IpNetwork>,
-    /// Input interface filter (iifname). Supports wildcards (e.g., "eth*")
-    pub interface: Option<String>,
-    /// Output interface filter (oifname). Only for OUTPUT chain in Server Mode.
-    /// Supports wildcards (e.g., "eth*")
-    #[serde(default)]
-    pub output_interface: Option<String>,
-    /// Chain direction (Input/Output) - only relevant in Server Mode
-    #[serde(default)]
-    pub chain: Chain,
-    #[serde(default = "default_true")]
+    /// Port of the protected service
+    pub port: u16,
+    /// Seconds allowed between knocks, and how long the port stays open afterwards
+    pub timeout_secs: u32,
+}
+
+impl PortKnock {
+    /// Summary for list rendering (e.g. `tcp 7000 → 8000 → 9000 opens tcp/22`)
+    pub fn summary(&self) -> String {
+        let sequence: Vec<String> = self.sequence.iter().map(ToString::to_string).collect();
+        format!(
+            "{} {} opens {}/{}",
+            self.knock_protocol,
+            sequence.join(" → "),
+            self.protocol,
+            self.port
+        )
+    }
+}
+
+/// A named group of rules compiled to its own chain
+///
+/// Members are the rules whose `group` is the group's ID, evaluated in rule order.
+/// Per base chain, the members are written to a regular chain
+/// (`drfw_group_<chain>_<id>`) that the base chain jumps to where the first member
+/// sits, so a disabled group drops out as a unit. Port forwards span the nat and
+/// forward chains and stay there; membership only enables or disables them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RuleGroup {
+    pub id: Uuid,
+    pub name: String,
     pub enabled: bool,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    /// Tags for organizing and filtering rules
-    #[serde(default)]
-    pub tags: Vec<String>,
+}
 
-    // Advanced options
-    /// Destination IP/network filters. Empty = any destination.
-    /// IPv4 and IPv6 addresses can be mixed; DRFW splits them into separate nft rules.
+impl RuleGroup {
+    pub fn new(name: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name,
+            enabled: true,
+        }
+    }
+
+    /// Comment on the jump into the group's chain
+    pub fn jump_comment(&self) -> String {
+        crate::validators::sanitize_comment(
+            &format!("group: {}", self.name),
+            crate::validators::MAX_COMMENT_BYTES,
+        )
+    }
+
+    /// Name of the group's chain for members of the given base chain
+    pub fn chain_name(&self, chain: Chain) -> String {
+        format!("drfw_group_{chain}_{}", self.id.simple())
+    }
+}
+
+/// Port-forward (DNAT) target of a rule
+///
+/// A rule with a port forward redirects traffic arriving on its interface and
+/// external port to an internal host (VM, container or LAN machine). It generates
+/// a `dnat` rule in the `prerouting` nat chain plus a matching accept in the
+/// forward chain; the host must have IP forwarding enabled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PortForward {
+    /// Internal host receiving the forwarded traffic
+    pub to_address: IpAddr,
+    /// Internal port (None = keep the external port)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_port: Option<u16>,
+}
+
+impl PortForward {
+    /// nftables payload protocol of the target family (`ip` or `ip6`)
+    pub const fn address_protocol(&self) -> &'static str {
+        match self.to_address {
+            IpAddr::V4(_) => "ip",
+            IpAddr::V6(_) => "ip6",
+        }
+    }
+}
+
+impl fmt::Display for PortForward {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.to_address, self.to_port) {
+            (IpAddr::V6(addr), Some(port)) => write!(f, "[{addr}]:{port}"),
+            (addr, Some(port)) => write!(f, "{addr}:{port}"),
+            (addr, None) => write!(f, "{addr}"),
+        }
+    }
+}
+
+/// Element type of a [`NamedSet`]
+///
+/// Maps directly to the nftables set `type` keyword.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    strum::AsRefStr,
+)]
+pub enum SetType {
+    /// IPv4 addresses and CIDR blocks
+    #[default]
+    #[strum(serialize = "ipv4_addr")]
+    Ipv4Addr,
+    /// IPv6 addresses and CIDR blocks
+    #[strum(serialize = "ipv6_addr")]
+    Ipv6Addr,
+    /// Ports and port ranges
+    #[strum(serialize = "inet_service")]
+    InetService,
+}
+
+impl SetType {
+    /// Returns nftables set type keyword
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SetType::Ipv4Addr => "ipv4_addr",
+            SetType::Ipv6Addr => "ipv6_addr",
+            SetType::InetService => "inet_service",
+        }
+    }
+
+    /// Returns display name for UI rendering
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            SetType::Ipv4Addr => "IPv4 Addresses",
+            SetType::Ipv6Addr => "IPv6 Addresses",
+            SetType::InetService => "Ports",
+        }
+    }
+
+    /// Returns true if this set holds addresses (usable for saddr/daddr lookups)
+    pub const fn is_address(self) -> bool {
+        matches!(self, SetType::Ipv4Addr | SetType::Ipv6Addr)
+    }
+
+    /// Returns the payload protocol used for address lookups ("ip" or "ip6")
+    pub const fn address_protocol(self) -> &'static str {
+        match self {
+            SetType::Ipv6Addr => "ip6",
+            SetType::Ipv4Addr | SetType::InetService => "ip",
+        }
+    }
+}
+
+/// A named, reusable set of addresses or ports
+///
+/// Compiled to an nftables named set in the `drfw` table. Rules reference it by
+/// name (`ip saddr @office`), so editing the set updates every rule using it.
+/// Only the element list matching `set_type` is used: `addresses` for address
+/// sets, `ports` for `inet_service` sets.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NamedSet {
+    pub name: String,
+    pub set_type: SetType,
+    #[serde(default)]
+    pub addresses: Vec<AddressEntry>,
+    #[serde(default)]
+    pub ports: Vec<PortEntry>,
+}
+
+impl NamedSet {
+    /// Creates an empty set of the given type
+    pub fn new(name: impl Into<String>, set_type: SetType) -> Self {
+        Self {
+            name: name.into(),
+            set_type,
+            addresses: Vec::new(),
+            ports: Vec::new(),
+        }
+    }
+
+    /// Returns the number of elements relevant to the set type
+    pub fn element_count(&self) -> usize {
+        if self.set_type.is_address() {
+            self.addresses.len()
+        } else {
+            self.ports.len()
+        }
+    }
+
+    /// Returns the elements as display strings (e.g., "10.0.0.0/8", "8000-8080")
+    pub fn element_strings(&self) -> Vec<String> {
+        if self.set_type.is_address() {
+            self.addresses.iter().map(ToString::to_string).collect()
+        } else {
+            self.ports.iter().map(ToString::to_string).collect()
+        }
+    }
+}
+
+/// Firewall chain for rule direction (only relevant in Server Mode)
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Default,
+    strum::Display,
+    strum::EnumString,
+    strum::EnumIter,
+    strum::AsRefStr,
+)]
+pub enum Chain {
+    /// Incoming traffic (default for desktop users)
+    #[default]
+    #[strum(serialize = "input")]
+    Input,
+    /// Outgoing traffic (only useful in Server Mode with OUTPUT DROP policy)
+    #[strum(serialize = "output")]
+    Output,
+    /// Routed traffic passing through this host (policy is always DROP)
+    #[strum(serialize = "forward")]
+    Forward,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Rule {
+    pub id: Uuid,
+    pub label: String,
+    pub protocol: Protocol,
+    /// Port entries (single ports or ranges). Empty = all ports.
+    /// Multiple entries create nftables anonymous sets: `dport { 22, 80, 443 }`
+    #[serde(default)]
+    pub ports: Vec<PortEntry>,
+    /// Source port entries (single ports or ranges). Empty = any source port.
+    /// Matched with `sport`, e.g. for replies from a fixed-port NTP or DNS server.
+    #[serde(default)]
+    pub source_ports: Vec<PortEntry>,
+    /// Source IP/network/range filters. Empty = any source.
+    /// IPv4 and IPv6 addresses can be mixed; DRFW splits them into separate nft rules.
+    #[serde(default)]
+    pub sources: Vec<AddressEntry>,
+    /// Input interface filters (iifname). Empty = any interface.
+    /// Supports wildcards (e.g., "eth*"); multiple names become an anonymous set.
+    #[serde(
+        default,
+        alias = "interface",
+        deserialize_with = "deserialize_interfaces"
+    )]
+    pub interfaces: Vec<String>,
+    /// Output interface filters (oifname). Only for OUTPUT/FORWARD chains.
+    /// Supports wildcards (e.g., "eth*"); multiple names become an anonymous set.
+    #[serde(
+        default,
+        alias = "output_interface",
+        deserialize_with = "deserialize_interfaces"
+    )]
+    pub output_interfaces: Vec<String>,
+    /// Chain direction (Input/Output/Forward) - Output only relevant in Server Mode
+    #[serde(default)]
+    pub chain: Chain,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// When the rule was last edited (set by `EditRuleCommand`). `None` = never edited.
+    #[serde(default)]
+    pub modified_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the rule stops being compiled (temporary access). `None` = never expires.
+    #[serde(default)]
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Tags for organizing and filtering rules
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    // Metadata (shown in the sidebar and searched, never written to nftables)
+    /// Free-text notes, may span multiple lines
+    #[serde(default)]
+    pub description: String,
+    /// Person or team responsible for the rule
     #[serde(default)]
-    pub destinations: Vec<IpNetwork>,
+    pub owner: String,
+    /// Ticket ID or URL the rule was requested in
+    #[serde(default)]
+    pub reference: String,
+
+    // Advanced options
+    /// Destination IP/network/range filters. Empty = any destination.
+    /// IPv4 and IPv6 addresses can be mixed; DRFW splits them into separate nft rules.
+    #[serde(default)]
+    pub destinations: Vec<AddressEntry>,
+    /// Fields matched with `!=` instead of `==`
+    #[serde(default)]
+    pub negate: Negation,
     /// Action to take when packet matches (Accept/Drop/Reject)
     #[serde(default)]
     pub action: Action,
@@ -555,15 +1446,55 @@ pub struct Rule {
     /// Prefix is auto-generated from sanitized label: "DRFW-{label}: "
     #[serde(default)]
     pub log_enabled: bool,
+    /// ICMP/ICMPv6 types to match (single-family ICMP protocols only). Empty = all types.
+    #[serde(default)]
+    pub icmp_types: Vec<IcmpType>,
+    /// ICMP code to match. Only used with exactly one entry in `icmp_types`.
+    #[serde(default)]
+    pub icmp_code: Option<u8>,
+    /// Connection tracking states to match (`ct state`). Empty = any state.
+    #[serde(default)]
+    pub ct_states: Vec<CtState>,
+    /// Time-of-day / weekday window in which the rule is active. `None` = always.
+    #[serde(default)]
+    pub schedule: Option<Schedule>,
+    /// Byte quota (`quota until/over`). `None` = unlimited.
+    #[serde(default)]
+    pub quota: Option<Quota>,
+    /// Apply the rate and connection limits per source address instead of globally
+    #[serde(default)]
+    pub per_source: Option<PerSourceLimit>,
+    /// Source countries (ISO 3166 alpha-2 codes) matched through interval sets
+    /// (see `core::geoip`). Empty = any country. Used instead of source addresses.
+    #[serde(default)]
+    pub countries: Vec<String>,
+    /// Group the rule belongs to (see `FirewallRuleset::groups`). `None` = ungrouped.
+    #[serde(default)]
+    pub group: Option<Uuid>,
+
+    // Named set references (see `FirewallRuleset::sets`)
+    /// Source address set name. Replaces `sources` when set.
+    #[serde(default)]
+    pub source_set: Option<String>,
+    /// Destination address set name. Replaces `destinations` when set.
+    #[serde(default)]
+    pub destination_set: Option<String>,
+    /// Destination port set name. Replaces `ports` when set.
+    #[serde(default)]
+    pub port_set: Option<String>,
+
+    /// Port-forward target. Turns the rule into a DNAT rule for incoming traffic.
+    #[serde(default)]
+    pub port_forward: Option<PortForward>,
 
     // Cached lowercase fields for search performance (Issue #1)
     /// Cached lowercase version of `label` for fast search filtering
     #[serde(skip)]
     pub label_lowercase: String,
-    /// Cached lowercase version of `interface` for fast search filtering
+    /// Cached lowercase version of `interfaces` (space-separated) for fast search filtering
     #[serde(skip)]
     pub interface_lowercase: Option<String>,
-    /// Cached lowercase version of `output_interface` for fast search filtering
+    /// Cached lowercase version of `output_interfaces` (space-separated) for fast search filtering
     #[serde(skip)]
     pub output_interface_lowercase: Option<String>,
     /// Cached lowercase versions of all tags for fast search filtering
@@ -607,6 +1538,10 @@ pub struct Rule {
     /// Avoids format!() allocation every frame
     #[serde(skip)]
     pub badge_display: String,
+
+    /// Cached lowercase description, owner and reference for fast search filtering
+    #[serde(skip)]
+    pub metadata_lowercase: String,
 }
 
 impl Rule {
@@ -617,12 +1552,33 @@ impl Rule {
         // that may have been introduced via JSON import or malformed data
         self.label = crate::validators::sanitize_label(&self.label);
         self.label_lowercase = self.label.to_lowercase();
-        self.interface_lowercase = self.interface.as_ref().map(|i| i.to_lowercase());
-        self.output_interface_lowercase = self.output_interface.as_ref().map(|i| i.to_lowercase());
+        self.interface_lowercase = Self::interfaces_lowercase(&self.interfaces);
+        self.output_interface_lowercase = Self::interfaces_lowercase(&self.output_interfaces);
         self.tags_lowercase = self.tags.iter().map(|t| t.to_lowercase()).collect();
         self.protocol_lowercase = self.protocol.as_str();
+        // Metadata never reaches nftables, but imported JSON may still carry control characters
+        self.description = crate::validators::sanitize_description(&self.description);
+        self.owner = crate::validators::sanitize_metadata(&self.owner);
+        self.reference = crate::validators::sanitize_metadata(&self.reference);
+        self.metadata_lowercase = [&self.description, &self.owner, &self.reference]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("\n");
         // Issue #5: Cache port display string for efficient view rendering
-        self.port_display = if self.ports.is_empty() {
+        self.port_display = if let Some(ref set) = self.port_set {
+            format!("@{set}")
+        } else if matches!(self.protocol, Protocol::Icmp | Protocol::Icmpv6)
+            && !self.icmp_types.is_empty()
+        {
+            // ICMP type filter takes the place of ports in the badge
+            if self.icmp_types.len() == 1 {
+                self.icmp_types[0].display_name().to_string()
+            } else {
+                format!("{} types", self.icmp_types.len())
+            }
+        } else if self.ports.is_empty() {
             "All".to_string()
         } else if self.ports.len() == 1 {
             self.ports[0].to_string()
@@ -639,7 +1595,13 @@ impl Rule {
             }
         };
         // Cache source IPs display string for efficient view rendering
-        self.sources_display = if self.sources.is_empty() {
+        self.sources_display = if let Some(ref set) = self.source_set {
+            format!("@{set}")
+        } else if self.countries.len() > 3 {
+            format!("@geo {} countries", self.countries.len())
+        } else if !self.countries.is_empty() {
+            format!("@geo {}", self.countries.join(", "))
+        } else if self.sources.is_empty() {
             "Any".to_string()
         } else if self.sources.len() == 1 {
             self.sources[0].to_string()
@@ -653,7 +1615,9 @@ impl Rule {
             format!("{} addresses", self.sources.len())
         };
         // Cache destination IPs display string for efficient view rendering
-        self.destinations_display = if self.destinations.is_empty() {
+        self.destinations_display = if let Some(ref set) = self.destination_set {
+            format!("@{set}")
+        } else if self.destinations.is_empty() {
             "Any".to_string()
         } else if self.destinations.len() == 1 {
             self.destinations[0].to_string()
@@ -666,6 +1630,21 @@ impl Rule {
         } else {
             format!("{} addresses", self.destinations.len())
         };
+        // Negated fields read "not ..." wherever they are displayed
+        for (negated, display) in [
+            (self.negate.sources, &mut self.sources_display),
+            (self.negate.destinations, &mut self.destinations_display),
+        ] {
+            if negated && display != "Any" {
+                display.insert_str(0, "not ");
+            }
+        }
+        if self.negate.ports
+            && (self.port_set.is_some() || !self.ports.is_empty())
+            && crate::core::rule_constraints::protocol_supports_ports(self.protocol)
+        {
+            self.port_display.insert_str(0, "not ");
+        }
         // Cache rate limit display string for efficient view rendering
         self.rate_limit_display = self.rate_limit.map(|rl| {
             let unit_abbrev = match rl.unit {
@@ -674,10 +1653,22 @@ impl Rule {
                 TimeUnit::Hour => "h",
                 TimeUnit::Day => "d",
             };
+            let over = if rl.over { ">" } else { "" };
+            let bytes = rl
+                .bytes
+                .map_or(String::new(), |b| b.display_name().to_string());
+            let per_source = if self.per_source.is_some() {
+                " per IP"
+            } else {
+                ""
+            };
             if let Some(burst) = rl.burst {
-                format!("{}/{} b{}", rl.count, unit_abbrev, burst)
+                format!(
+                    "{over}{}{bytes}/{unit_abbrev} b{burst}{per_source}",
+                    rl.count
+                )
             } else {
-                format!("{}/{}", rl.count, unit_abbrev)
+                format!("{over}{}{bytes}/{unit_abbrev}{per_source}", rl.count)
             }
         });
         // Phase 2.3: Cache action display string (combines action + rate limit)
@@ -687,36 +1678,45 @@ impl Rule {
             self.action.as_char().to_string()
         };
         // Phase 2.3: Cache interface display string
-        self.interface_display = if let Some(ref iface) = self.interface {
-            format!("@{iface}")
-        } else {
-            "Any".to_string()
+        self.interface_display = match self.interfaces.as_slice() {
+            [] => "Any".to_string(),
+            [iface] => format!("@{iface}"),
+            [first, rest @ ..] => format!("@{first} +{}", rest.len()),
         };
+        if self.negate.interface && !self.interfaces.is_empty() {
+            self.interface_display = format!("not {}", self.interface_display);
+        }
         // Cache sanitized log prefix for nftables log expression
         self.log_prefix = Self::sanitize_log_prefix(&self.label);
 
         // Cache truncated tags for efficient view rendering (avoids format! every frame)
-        self.tags_truncated = self
-            .tags
-            .iter()
-            .map(|t| {
-                if t.len() > 16 {
-                    format!("{}…", &t[..15])
-                } else {
-                    t.clone()
-                }
-            })
-            .collect();
+        self.tags_truncated = self.tags.iter().map(|t| Self::truncate_tag(t)).collect();
 
         // Cache badge display string for sidebar rule cards (avoids format! every frame)
-        // Format: "{protocol}: {port_display}"
-        self.badge_display = format!("{}: {}", self.protocol.display_name(), self.port_display);
+        // Format: "{protocol}: {port_display}" (plus " → {target}" for port forwards
+        // and " [NEW/EST]" for ct state matches)
+        self.badge_display = if let Some(forward) = self.port_forward {
+            format!(
+                "{}: {} → {}",
+                self.protocol.display_name(),
+                self.port_display,
+                forward
+            )
+        } else {
+            format!("{}: {}", self.protocol.display_name(), self.port_display)
+        };
+        if !self.ct_states.is_empty() {
+            let states: Vec<_> = self.ct_states.iter().map(|s| s.badge_name()).collect();
+            self.badge_display = format!("{} [{}]", self.badge_display, states.join("/"));
+        }
     }
 
     /// Sanitizes a label for use as nftables log prefix.
     /// Format: "DRFW-{sanitized_label}: " (max 64 chars total)
+    ///
+    /// Kernel log lines should stay ASCII, so Unicode labels are transliterated first.
     fn sanitize_log_prefix(label: &str) -> String {
-        let sanitized: String = label
+        let sanitized: String = crate::validators::transliterate(label)
             .chars()
             .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
             .take(50) // Leave room for "DRFW-" prefix and ": " suffix
@@ -729,16 +1729,57 @@ impl Rule {
         }
     }
 
+    /// Shortens a tag to 16 characters for chips and the tag cloud (`longer-tag-na…`).
+    ///
+    /// Counts characters rather than bytes, since tags may hold non-ASCII text.
+    pub fn truncate_tag(tag: &str) -> String {
+        if tag.chars().count() > 16 {
+            format!("{}…", tag.chars().take(15).collect::<String>())
+        } else {
+            tag.to_string()
+        }
+    }
+
+    /// Returns true if this rule references the named set in any field
+    pub fn references_set(&self, name: &str) -> bool {
+        [&self.source_set, &self.destination_set, &self.port_set]
+            .into_iter()
+            .any(|r| r.as_deref() == Some(name))
+    }
+
+    /// Returns the symbolic entries (hostnames, interface subnets) in this rule's
+    /// sources and destinations that are still unexpanded. Such a rule is not
+    /// generated (see [`AddressEntry`]).
+    pub fn unexpanded_addresses(&self) -> impl Iterator<Item = &AddressEntry> {
+        self.sources
+            .iter()
+            .chain(&self.destinations)
+            .filter(|a| a.is_symbolic())
+    }
+
+    /// Returns true if neither interface list mixes a wildcard with other names
+    /// (see [`crate::core::rule_constraints::interfaces_valid_as_list`])
+    pub fn interface_lists_valid(&self) -> bool {
+        use crate::core::rule_constraints::interfaces_valid_as_list;
+        interfaces_valid_as_list(&self.interfaces)
+            && interfaces_valid_as_list(&self.output_interfaces)
+    }
+
     /// Updates label and its cached lowercase version
     pub fn set_label(&mut self, label: String) {
         self.label_lowercase = label.to_lowercase();
         self.label = label;
     }
 
-    /// Updates interface and its cached lowercase version
-    pub fn set_interface(&mut self, interface: Option<String>) {
-        self.interface_lowercase = interface.as_ref().map(|i| i.to_lowercase());
-        self.interface = interface;
+    /// Updates input interfaces and their cached lowercase version
+    pub fn set_interfaces(&mut self, interfaces: Vec<String>) {
+        self.interface_lowercase = Self::interfaces_lowercase(&interfaces);
+        self.interfaces = interfaces;
+    }
+
+    /// Joins interface names into the lowercase search cache (`None` if empty)
+    fn interfaces_lowercase(interfaces: &[String]) -> Option<String> {
+        (!interfaces.is_empty()).then(|| interfaces.join(" ").to_lowercase())
     }
 
     /// Updates protocol and its cached lowercase version
@@ -768,6 +1809,16 @@ impl Rule {
         self.tags = tags;
     }
 
+    /// Returns `true` if the rule has an expiry at or before `now`
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at.is_some_and(|t| t <= now)
+    }
+
+    /// Returns `true` if the rule has a description, owner or reference
+    pub fn has_metadata(&self) -> bool {
+        !self.description.is_empty() || !self.owner.is_empty() || !self.reference.is_empty()
+    }
+
     /// Creates a Rule with specified fields and auto-initializes caches.
     /// Useful for tests and manual rule creation.
     /// Advanced options (destinations, action, `rate_limit`, `connection_limit`) use defaults.
@@ -777,8 +1828,8 @@ impl Rule {
         label: String,
         protocol: Protocol,
         ports: Vec<PortEntry>,
-        sources: Vec<IpNetwork>,
-        interface: Option<String>,
+        sources: Vec<AddressEntry>,
+        interfaces: Vec<String>,
         chain: Chain,
         enabled: bool,
         created_at: chrono::DateTime<chrono::Utc>,
@@ -790,11 +1841,13 @@ impl Rule {
             protocol,
             ports,
             sources,
-            interface,
-            output_interface: None,
+            interfaces,
+            output_interfaces: Vec::new(),
             chain,
             enabled,
             created_at,
+            modified_at: None,
+            expires_at: None,
             tags,
             // Advanced options - use defaults
             destinations: Vec::new(),
@@ -803,6 +1856,23 @@ impl Rule {
             rate_limit: None,
             connection_limit: 0,
             log_enabled: false,
+            source_set: None,
+            destination_set: None,
+            port_set: None,
+            port_forward: None,
+            source_ports: Vec::new(),
+            icmp_types: Vec::new(),
+            icmp_code: None,
+            ct_states: Vec::new(),
+            schedule: None,
+            quota: None,
+            per_source: None,
+            countries: Vec::new(),
+            group: None,
+            description: String::new(),
+            owner: String::new(),
+            reference: String::new(),
+            negate: Negation::default(),
             // Initialize with empty caches - will be rebuilt next
             label_lowercase: String::new(),
             interface_lowercase: None,
@@ -818,6 +1888,7 @@ impl Rule {
             log_prefix: String::new(),
             tags_truncated: Vec::new(),
             badge_display: String::new(),
+            metadata_lowercase: String::new(),
         };
         rule.rebuild_caches();
         rule
@@ -828,6 +1899,40 @@ fn default_true() -> bool {
     true
 }
 
+/// Reads an interface list, also accepting the single name (or `null`) that
+/// profiles stored before rules could match several interfaces
+fn deserialize_interfaces<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Interfaces {
+        List(Vec<String>),
+        Single(Option<String>),
+    }
+
+    Ok(match Interfaces::deserialize(deserializer)? {
+        Interfaces::List(list) => list,
+        Interfaces::Single(name) => name.into_iter().collect(),
+    })
+}
+
+/// Reads a hostname entry, rejecting strings that are not valid hostnames so a
+/// corrupt or mistyped address fails to load instead of becoming a `Host`
+fn deserialize_hostname<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let name = String::deserialize(deserializer)?;
+    if !crate::validators::is_valid_hostname(&name) {
+        return Err(serde::de::Error::custom(format!(
+            "invalid hostname: {name}"
+        )));
+    }
+    Ok(name.trim_end_matches('.').to_ascii_lowercase())
+}
+
 // ServicePreset removed - presets dropdown removed from UI for simplicity
 /// Egress filtering profile
 #[derive(
@@ -854,6 +1959,38 @@ pub enum EgressProfile {
     Server,
 }
 
+/// Internet sharing (router) mode
+///
+/// Forwards traffic from a downstream interface (lab switch, hotspot) to an
+/// upstream interface and masquerades it behind the upstream address.
+/// The host must have IP forwarding enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct SharingSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Interface with the internet connection (e.g. "wlan0")
+    #[serde(default)]
+    pub upstream_interface: String,
+    /// Interface of the shared network (e.g. "eth0", "ap0")
+    #[serde(default)]
+    pub downstream_interface: String,
+    /// Let downstream clients reach this host's DHCP and DNS services
+    #[serde(default)]
+    pub allow_dhcp_dns: bool,
+}
+
+impl SharingSettings {
+    /// Returns `true` if sharing is enabled with two distinct, valid interfaces
+    pub fn is_active(&self) -> bool {
+        self.enabled
+            && !self.upstream_interface.is_empty()
+            && !self.downstream_interface.is_empty()
+            && self.upstream_interface != self.downstream_interface
+            && crate::validators::validate_interface(&self.upstream_interface).is_ok()
+            && crate::validators::validate_interface(&self.downstream_interface).is_ok()
+    }
+}
+
 /// Optional advanced security settings (all OFF/disabled by default for desktop compatibility)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AdvancedSecuritySettings {
@@ -884,6 +2021,14 @@ pub struct AdvancedSecuritySettings {
     /// Egress filtering profile (Desktop vs Server)
     #[serde(default)]
     pub egress_profile: EgressProfile,
+
+    /// Internet sharing (router) mode
+    #[serde(default)]
+    pub sharing: SharingSettings,
+
+    /// Add a counter to every user rule (shown live on the sidebar cards)
+    #[serde(default)]
+    pub rule_counters: bool,
 }
 
 fn default_log_rate() -> u32 {
@@ -904,6 +2049,8 @@ impl Default for AdvancedSecuritySettings {
             log_rate_per_minute: default_log_rate(),
             log_prefix: default_log_prefix(),
             egress_profile: EgressProfile::Desktop,
+            sharing: SharingSettings::default(),
+            rule_counters: false,
         }
     }
 }
@@ -913,6 +2060,100 @@ pub struct FirewallRuleset {
     pub rules: Vec<Rule>,
     #[serde(default)]
     pub advanced_security: AdvancedSecuritySettings,
+    /// Named address/port sets that rules can reference by name
+    #[serde(default)]
+    pub sets: Vec<NamedSet>,
+    /// Port-knocking sequences, evaluated before the user rules of the input chain
+    #[serde(default)]
+    pub knocks: Vec<PortKnock>,
+    /// Local IP list files whose entries are dropped early in the input chain
+    /// (entries are loaded by `core::blocklists`, not stored in the profile)
+    #[serde(default)]
+    pub blocklists: Vec<std::path::PathBuf>,
+    /// Rule groups, each compiled to its own chain (members reference them by ID)
+    #[serde(default)]
+    pub groups: Vec<RuleGroup>,
+}
+
+/// Limit of a rule that can be tracked per source address (see [`PerSourceLimit`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Meter {
+    Rate,
+    Conn,
+}
+
+impl Meter {
+    /// Per-source sets the rule needs
+    fn for_rule(rule: &Rule) -> impl Iterator<Item = Meter> {
+        let rate = rule.rate_limit.is_some().then_some(Meter::Rate);
+        let conn = (rule.connection_limit > 0).then_some(Meter::Conn);
+        rate.into_iter()
+            .chain(conn)
+            .filter(|_| rule.per_source.is_some())
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Meter::Rate => "rate",
+            Meter::Conn => "conn",
+        }
+    }
+
+    /// Set statement op: rate entries are refreshed (`update`) so they time out when
+    /// idle; `ct count` entries can't have a timeout and are garbage-collected by the kernel
+    const fn op(self) -> &'static str {
+        match self {
+            Meter::Rate => "update",
+            Meter::Conn => "add",
+        }
+    }
+}
+
+/// Named sets referenced by a rule, resolved against [`FirewallRuleset::sets`]
+#[derive(Debug, Clone, Copy, Default)]
+struct RuleSetRefs<'a> {
+    source: Option<&'a NamedSet>,
+    destination: Option<&'a NamedSet>,
+    ports: Option<&'a NamedSet>,
+}
+
+impl<'a> RuleSetRefs<'a> {
+    /// Resolves a rule's set references.
+    ///
+    /// Returns `None` if the rule can never match as written: a referenced set is
+    /// missing or has the wrong type, or source and destination sets disagree on
+    /// IP family. Such rules are skipped rather than emitted without the set
+    /// match, which would silently widen them.
+    fn resolve(rule: &Rule, sets: &'a [NamedSet]) -> Option<Self> {
+        let lookup = |name: &Option<String>, address: bool| -> Option<Option<&'a NamedSet>> {
+            match name {
+                None => Some(None),
+                Some(name) => sets
+                    .iter()
+                    .find(|s| &s.name == name && s.set_type.is_address() == address)
+                    .map(Some),
+            }
+        };
+
+        let refs = Self {
+            source: lookup(&rule.source_set, true)?,
+            destination: lookup(&rule.destination_set, true)?,
+            ports: lookup(&rule.port_set, false)?,
+        };
+
+        if let (Some(src), Some(dst)) = (refs.source, refs.destination)
+            && src.set_type != dst.set_type
+        {
+            return None;
+        }
+
+        Some(refs)
+    }
+
+    /// IP family forced by an address set reference, if any
+    fn family(&self) -> Option<SetType> {
+        self.source.or(self.destination).map(|s| s.set_type)
+    }
 }
 
 impl Default for FirewallRuleset {
@@ -926,18 +2167,134 @@ impl FirewallRuleset {
         Self {
             rules: Vec::new(),
             advanced_security: AdvancedSecuritySettings::default(),
+            sets: Vec::new(),
+            knocks: Vec::new(),
+            blocklists: Vec::new(),
+            groups: Vec::new(),
         }
     }
 
-    // ═══════════════════════════════════════════════════════════════════════════
-    // JSON Helper Functions (DRY consolidation)
-    // ═══════════════════════════════════════════════════════════════════════════
+    /// Looks up a named set by name
+    pub fn find_set(&self, name: &str) -> Option<&NamedSet> {
+        self.sets.iter().find(|s| s.name == name)
+    }
 
-    /// Creates a match expression for nft meta keys (l4proto, iifname, oifname, etc.)
-    fn meta_match(key: &str, value: impl serde::Serialize) -> serde_json::Value {
-        serde_json::json!({
-            "match": {
-                "left": { "meta": { "key": key } },
+    /// Looks up a port-knocking sequence by name
+    pub fn find_knock(&self, name: &str) -> Option<&PortKnock> {
+        self.knocks.iter().find(|k| k.name == name)
+    }
+
+    /// Looks up a rule group by ID
+    pub fn find_group(&self, id: Uuid) -> Option<&RuleGroup> {
+        self.groups.iter().find(|g| g.id == id)
+    }
+
+    /// Returns the group a rule belongs to (`None` if ungrouped or the group is gone)
+    pub fn group_of(&self, rule: &Rule) -> Option<&RuleGroup> {
+        rule.group.and_then(|id| self.find_group(id))
+    }
+
+    /// Returns `true` if the rule and its group (if any) are both enabled and the
+    /// rule hasn't expired at `now`
+    pub fn is_rule_active(&self, rule: &Rule, now: chrono::DateTime<chrono::Utc>) -> bool {
+        rule.enabled && !rule.is_expired(now) && self.group_of(rule).is_none_or(|g| g.enabled)
+    }
+
+    /// Returns the rules whose expiry is at or before `now`
+    pub fn expired_rules(&self, now: chrono::DateTime<chrono::Utc>) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().filter(move |r| r.is_expired(now))
+    }
+
+    /// Returns the member rules of a group, in rule order
+    pub fn group_members(&self, id: Uuid) -> impl Iterator<Item = &Rule> {
+        self.rules.iter().filter(move |r| r.group == Some(id))
+    }
+
+    /// Returns all rules that reference the named set
+    pub fn rules_referencing_set<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Rule> {
+        self.rules.iter().filter(move |r| r.references_set(name))
+    }
+
+    /// Returns the symbolic address entries (hostnames, interface subnets) used by
+    /// rules and address sets
+    pub fn symbolic_addresses(&self) -> impl Iterator<Item = &AddressEntry> {
+        let rule_addresses = self
+            .rules
+            .iter()
+            .flat_map(|r| r.sources.iter().chain(&r.destinations));
+        let set_addresses = self.sets.iter().flat_map(|s| s.addresses.iter());
+        rule_addresses
+            .chain(set_addresses)
+            .filter(|a| a.is_symbolic())
+    }
+
+    /// Returns the ruleset with each symbolic entry replaced by the networks `expand`
+    /// returns for it (borrowed as is if it has none).
+    ///
+    /// Address sets only take networks of their own family. An entry left without
+    /// networks is kept, so the rules using it are still skipped rather than widened.
+    pub fn expand_addresses(
+        &self,
+        expand: impl Fn(&AddressEntry) -> Vec<IpNetwork>,
+    ) -> std::borrow::Cow<'_, Self> {
+        fn expand_entries(
+            addresses: &mut Vec<AddressEntry>,
+            expand: &impl Fn(&AddressEntry) -> Vec<IpNetwork>,
+            family: Option<SetType>,
+        ) {
+            if !addresses.iter().any(AddressEntry::is_symbolic) {
+                return;
+            }
+            let mut out = Vec::with_capacity(addresses.len());
+            for entry in addresses.drain(..) {
+                let networks: Vec<_> = if entry.is_symbolic() {
+                    expand(&entry)
+                        .into_iter()
+                        .filter(|net| {
+                            family.is_none_or(|f| (f == SetType::Ipv6Addr) == net.is_ipv6())
+                        })
+                        .map(AddressEntry::Network)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                let entries = if networks.is_empty() {
+                    vec![entry]
+                } else {
+                    networks
+                };
+                for entry in entries {
+                    if !out.contains(&entry) {
+                        out.push(entry);
+                    }
+                }
+            }
+            *addresses = out;
+        }
+
+        if self.symbolic_addresses().next().is_none() {
+            return std::borrow::Cow::Borrowed(self);
+        }
+        let mut ruleset = self.clone();
+        for rule in &mut ruleset.rules {
+            expand_entries(&mut rule.sources, &expand, None);
+            expand_entries(&mut rule.destinations, &expand, None);
+        }
+        for set in &mut ruleset.sets {
+            expand_entries(&mut set.addresses, &expand, Some(set.set_type));
+        }
+        std::borrow::Cow::Owned(ruleset)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // JSON Helper Functions (DRY consolidation)
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Creates a match expression for nft meta keys (l4proto, iifname, oifname, etc.)
+    fn meta_match(key: &str, value: impl serde::Serialize) -> serde_json::Value {
+        serde_json::json!({
+            "match": {
+                "left": { "meta": { "key": key } },
                 "op": "==",
                 "right": value
             }
@@ -949,6 +2306,23 @@ impl FirewallRuleset {
         serde_json::json!({ "limit": { "rate": rate, "per": per } })
     }
 
+    /// Converts an address entry to nftables JSON format.
+    ///
+    /// Ranges become `{ "range": [start, end] }`, which nft accepts both as a match
+    /// value and as an element of an interval set. Networks go through [`Self::ip_to_nft_json`].
+    fn address_to_nft_json(entry: &AddressEntry) -> serde_json::Value {
+        match entry {
+            AddressEntry::Network(ip) => Self::ip_to_nft_json(ip),
+            AddressEntry::Range { start, end } => {
+                serde_json::json!({ "range": [start.to_string(), end.to_string()] })
+            }
+            // Not reached: rules with symbolic entries are skipped, sets filter them out
+            AddressEntry::Host(_) | AddressEntry::InterfaceSubnet { .. } => {
+                serde_json::json!(entry.to_string())
+            }
+        }
+    }
+
     /// Converts an `IpNetwork` to nftables JSON format.
     ///
     /// Per libnftables-json(5):
@@ -998,6 +2372,16 @@ impl FirewallRuleset {
     /// Generates the nftables JSON representation of the ruleset.
     /// Follows the spec in Section 4 of `PLAN_DRFW.md`.
     pub fn to_nftables_json(&self) -> serde_json::Value {
+        self.to_nftables_json_at(chrono::Utc::now())
+    }
+
+    /// Generates the nftables JSON with rule expiry evaluated at `now`.
+    ///
+    /// Every part of the output checks expiry against the same instant, so a rule
+    /// expiring mid-generation can't leave a jump or set behind without its rules.
+    /// Callers appending elements to the generated sets (e.g. country ranges) pass
+    /// the same `now`.
+    pub fn to_nftables_json_at(&self, now: chrono::DateTime<chrono::Utc>) -> serde_json::Value {
         use serde_json::json;
 
         // Issue #11: Pre-allocate Vec with estimated capacity
@@ -1009,16 +2393,49 @@ impl FirewallRuleset {
         nft_rules.push(json!({ "add": { "table": { "family": "inet", "name": "drfw" } } }));
         nft_rules.push(json!({ "flush": { "table": { "family": "inet", "name": "drfw" } } }));
 
-        // 2. Base Chains
+        // 2. Base Chains, then the regular chains of rule groups
         Self::add_base_chains(&mut nft_rules, &self.advanced_security);
+        self.add_group_chains(&mut nft_rules, now);
+
+        // 2a. NAT chains (only created when port forwards or sharing need them)
+        let has_port_forwards = self.has_port_forwards(now);
+        let sharing = &self.advanced_security.sharing;
+        if has_port_forwards {
+            Self::add_nat_chain(&mut nft_rules, "prerouting", -100);
+        }
+        if sharing.is_active() {
+            Self::add_nat_chain(&mut nft_rules, "postrouting", 100);
+        }
+
+        // 2b. Named Sets (must exist before rules reference them)
+        self.add_named_sets(&mut nft_rules);
+        Self::add_ban_sets(&mut nft_rules);
+        self.add_blocklist_sets(&mut nft_rules);
+        self.add_geo_sets(&mut nft_rules, now);
+        self.add_meter_sets(&mut nft_rules, now);
+        self.add_knock_sets(&mut nft_rules);
+        self.add_quota_objects(&mut nft_rules, now);
 
         // 3. Base Rules
-        Self::add_base_rules(&mut nft_rules, &self.advanced_security);
+        Self::add_base_rules(
+            &mut nft_rules,
+            &self.advanced_security,
+            !self.blocklists.is_empty(),
+        );
+        if self.has_forward_traffic(now) {
+            Self::add_forward_base_rules(&mut nft_rules);
+        }
+        if sharing.is_active() {
+            Self::add_sharing_rules(&mut nft_rules, &self.advanced_security);
+        }
 
-        // 4. User Rules
+        // 4. Port knocking, then User Rules
+        self.add_port_knocks(&mut nft_rules);
+        let counters = self.advanced_security.rule_counters;
+        let mut jumped = std::collections::HashSet::new();
         for rule in &self.rules {
-            if !rule.enabled {
-                continue; // Skip disabled rules
+            if !self.is_rule_active(rule, now) {
+                continue; // Skip disabled rules (and members of disabled groups)
             }
 
             // Skip OUTPUT rules in Desktop Mode (policy is ACCEPT, rules are redundant)
@@ -1028,7 +2445,26 @@ impl FirewallRuleset {
                 continue;
             }
 
-            Self::add_user_rule(&mut nft_rules, rule);
+            if let Some(forward) = rule.port_forward {
+                Self::add_port_forward(&mut nft_rules, rule, forward, &self.sets, counters);
+            } else if let Some(group) = self.group_of(rule) {
+                // The group is jumped to where its first member sits in the base chain
+                let target = group.chain_name(rule.chain);
+                if jumped.insert(target.clone()) {
+                    nft_rules.push(Self::rule_add(
+                        rule.chain.as_ref(),
+                        &[json!({ "jump": { "target": &target } })],
+                        &group.jump_comment(),
+                    ));
+                }
+                let start = nft_rules.len();
+                Self::add_user_rule(&mut nft_rules, rule, &self.sets, counters);
+                for member in &mut nft_rules[start..] {
+                    member["add"]["rule"]["chain"] = json!(&target);
+                }
+            } else {
+                Self::add_user_rule(&mut nft_rules, rule, &self.sets, counters);
+            }
         }
 
         // 5. Termination Rules
@@ -1072,12 +2508,402 @@ impl FirewallRuleset {
         }
     }
 
-    fn add_base_rules(nft_rules: &mut Vec<serde_json::Value>, advanced: &AdvancedSecuritySettings) {
+    /// Returns the groups with members in each base chain as `(chain, group)` pairs,
+    /// in the order their jumps appear (mirrors the user rule loop of `to_nftables_json`)
+    fn group_chains(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(Chain, &RuleGroup)> {
+        let mut chains: Vec<(Chain, &RuleGroup)> = Vec::new();
+        for rule in &self.rules {
+            if !self.is_rule_active(rule, now)
+                || rule.port_forward.is_some()
+                || (self.advanced_security.egress_profile == EgressProfile::Desktop
+                    && rule.chain == Chain::Output)
+            {
+                continue;
+            }
+            if let Some(group) = self.group_of(rule)
+                && !chains
+                    .iter()
+                    .any(|(c, g)| *c == rule.chain && g.id == group.id)
+            {
+                chains.push((rule.chain, group));
+            }
+        }
+        chains
+    }
+
+    /// Adds the regular chains of enabled groups (jump targets, so no hook or policy)
+    fn add_group_chains(
+        &self,
+        nft_rules: &mut Vec<serde_json::Value>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        for (chain, group) in self.group_chains(now) {
+            nft_rules.push(serde_json::json!({
+                "add": {
+                    "chain": {
+                        "family": "inet",
+                        "table": "drfw",
+                        "name": group.chain_name(chain)
+                    }
+                }
+            }));
+        }
+    }
+
+    /// Returns `true` if any active rule is a port forward
+    fn has_port_forwards(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.rules
+            .iter()
+            .any(|r| r.port_forward.is_some() && self.is_rule_active(r, now))
+    }
+
+    /// Returns `true` if anything (port forwards, sharing or user rules) accepts
+    /// traffic in the forward chain, which then needs the conntrack base rules
+    fn has_forward_traffic(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.has_port_forwards(now)
+            || self.advanced_security.sharing.is_active()
+            || self
+                .rules
+                .iter()
+                .any(|r| r.chain == Chain::Forward && self.is_rule_active(r, now))
+    }
+
+    /// Adds a nat chain named after its hook (`prerouting` for port-forward DNAT,
+    /// `postrouting` for sharing masquerade)
+    fn add_nat_chain(nft_rules: &mut Vec<serde_json::Value>, hook: &str, priority: i32) {
+        nft_rules.push(serde_json::json!({
+            "add": {
+                "chain": {
+                    "family": "inet",
+                    "table": "drfw",
+                    "name": hook,
+                    "type": "nat",
+                    "hook": hook,
+                    "prio": priority,
+                    "policy": "accept"
+                }
+            }
+        }));
+    }
+
+    /// Adds the named sets with their elements.
+    ///
+    /// `flush table` keeps set elements, so each set is flushed before its elements
+    /// are added; otherwise entries removed from a set would stay in the kernel.
+    fn add_named_sets(&self, nft_rules: &mut Vec<serde_json::Value>) {
+        use serde_json::json;
+
+        for set in &self.sets {
+            // Interval flag allows CIDR blocks and port ranges as elements
+            let set_obj = json!({
+                "family": "inet",
+                "table": "drfw",
+                "name": set.name,
+                "type": set.set_type.as_str(),
+                "flags": ["interval"]
+            });
+
+            let elements: Vec<serde_json::Value> = if set.set_type.is_address() {
+                // Unexpanded symbolic entries have no address to add yet
+                set.addresses
+                    .iter()
+                    .filter(|a| !a.is_symbolic())
+                    .map(Self::address_to_nft_json)
+                    .collect()
+            } else {
+                set.ports
+                    .iter()
+                    .copied()
+                    .map(PortEntry::to_nft_json)
+                    .collect()
+            };
+
+            nft_rules.push(json!({ "add": { "set": set_obj } }));
+            nft_rules.push(json!({
+                "flush": { "set": { "family": "inet", "table": "drfw", "name": set.name } }
+            }));
+            if !elements.is_empty() {
+                nft_rules.push(json!({
+                    "add": {
+                        "element": {
+                            "family": "inet",
+                            "table": "drfw",
+                            "name": set.name,
+                            "elem": elements
+                        }
+                    }
+                }));
+            }
+        }
+    }
+
+    /// Adds the temporary ban blocklists (elements are managed by `core::bans`)
+    fn add_ban_sets(nft_rules: &mut Vec<serde_json::Value>) {
+        use crate::core::bans::{BAN_SET_V4, BAN_SET_V6, ban_set_json};
+
+        nft_rules.push(ban_set_json(BAN_SET_V4, "ipv4_addr"));
+        nft_rules.push(ban_set_json(BAN_SET_V6, "ipv6_addr"));
+    }
+
+    /// Adds the blocklist interval sets (elements are appended by `core::blocklists`)
+    fn add_blocklist_sets(&self, nft_rules: &mut Vec<serde_json::Value>) {
+        use crate::core::blocklists::{BLOCKLIST_SET_V4, BLOCKLIST_SET_V6, interval_set_json};
+
+        if self.blocklists.is_empty() {
+            return;
+        }
+        nft_rules.push(interval_set_json(BLOCKLIST_SET_V4, "ipv4_addr"));
+        nft_rules.push(interval_set_json(BLOCKLIST_SET_V6, "ipv6_addr"));
+    }
+
+    /// Adds the interval sets of the rules' country selections
+    /// (elements are appended by `core::geoip`)
+    fn add_geo_sets(
+        &self,
+        nft_rules: &mut Vec<serde_json::Value>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        use crate::core::blocklists::interval_set_json;
+        use crate::core::geoip::{country_groups, geo_set_name};
+
+        for group in country_groups(self, now) {
+            nft_rules.push(interval_set_json(&geo_set_name(&group, false), "ipv4_addr"));
+            nft_rules.push(interval_set_json(&geo_set_name(&group, true), "ipv6_addr"));
+        }
+    }
+
+    /// Adds the dynamic sets of rules with per-source rate/connection limits
+    fn add_meter_sets(
+        &self,
+        nft_rules: &mut Vec<serde_json::Value>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        use serde_json::json;
+
+        for rule in self.rules.iter().filter(|r| self.is_rule_active(r, now)) {
+            let Some(per_source) = rule.per_source else {
+                continue;
+            };
+            for meter in Meter::for_rule(rule) {
+                for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                    let mut set_obj = json!({
+                        "family": "inet",
+                        "table": "drfw",
+                        "name": Self::meter_set_name(rule, meter, family),
+                        "type": family.as_str(),
+                        "flags": ["dynamic"],
+                        "size": per_source.size
+                    });
+                    if meter == Meter::Rate {
+                        set_obj["flags"] = json!(["dynamic", "timeout"]);
+                        set_obj["timeout"] = json!(per_source.timeout_secs);
+                    }
+                    nft_rules.push(json!({ "add": { "set": set_obj } }));
+                }
+            }
+        }
+    }
+
+    /// Name of a rule's per-source set for one limit and IP family (reserved `drfw_` prefix)
+    fn meter_set_name(rule: &Rule, meter: Meter, family: SetType) -> String {
+        let suffix = if family == SetType::Ipv6Addr {
+            "v6"
+        } else {
+            "v4"
+        };
+        format!("drfw_meter_{}_{suffix}_{}", meter.name(), rule.id.simple())
+    }
+
+    /// Adds the named quota objects of rules with a byte quota.
+    ///
+    /// The table is flushed rather than deleted on apply. `flush table` only
+    /// deletes rules, and adding an existing quota object only updates its size
+    /// and mode, so the used bytes survive a re-apply.
+    fn add_quota_objects(
+        &self,
+        nft_rules: &mut Vec<serde_json::Value>,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        use serde_json::json;
+
+        for rule in self.rules.iter().filter(|r| self.is_rule_active(r, now)) {
+            let Some(quota) = rule.quota else {
+                continue;
+            };
+            nft_rules.push(json!({
+                "add": {
+                    "quota": {
+                        "family": "inet",
+                        "table": "drfw",
+                        "name": Self::quota_name(rule),
+                        "bytes": quota.total_bytes(),
+                        "inv": quota.mode == QuotaMode::Over
+                    }
+                }
+            }));
+        }
+    }
+
+    /// Name of a rule's quota object (reserved `drfw_` prefix)
+    fn quota_name(rule: &Rule) -> String {
+        format!("drfw_quota_{}", rule.id.simple())
+    }
+
+    /// Adds the stage sets of the port-knocking sequences
+    fn add_knock_sets(&self, nft_rules: &mut Vec<serde_json::Value>) {
+        use serde_json::json;
+
+        for knock in &self.knocks {
+            for stage in 1..=knock.sequence.len() {
+                for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                    nft_rules.push(json!({
+                        "add": {
+                            "set": {
+                                "family": "inet",
+                                "table": "drfw",
+                                "name": Self::knock_set_name(knock, stage, family),
+                                "type": family.as_str(),
+                                "flags": ["dynamic", "timeout"],
+                                "timeout": knock.timeout_secs
+                            }
+                        }
+                    }));
+                }
+            }
+        }
+    }
+
+    /// Name of the set holding addresses that completed `stage` knocks (reserved `drfw_` prefix)
+    fn knock_set_name(knock: &PortKnock, stage: usize, family: SetType) -> String {
+        let suffix = if family == SetType::Ipv6Addr {
+            "v6"
+        } else {
+            "v4"
+        };
+        format!("drfw_knock_{}_{stage}_{suffix}", knock.name)
+    }
+
+    /// Adds the knock stage rules and the final accept of each port-knocking sequence.
+    ///
+    /// Stage `n` matches the `n`th knock port (from an address in the previous
+    /// stage's set) and adds the source to its own set; the protected port is
+    /// accepted for addresses in the last stage's set.
+    fn add_port_knocks(&self, nft_rules: &mut Vec<serde_json::Value>) {
+        use serde_json::json;
+
+        for knock in &self.knocks {
+            let stages = knock.sequence.len();
+            for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                let nfproto = if family == SetType::Ipv6Addr {
+                    "ipv6"
+                } else {
+                    "ipv4"
+                };
+                let address = family.address_protocol();
+                let knock_proto = knock.knock_protocol.as_str();
+
+                for (index, &port) in knock.sequence.iter().enumerate() {
+                    let mut expr = vec![
+                        Self::meta_match("nfproto", nfproto),
+                        Self::meta_match("l4proto", knock_proto),
+                        Self::port_match(knock_proto, "dport", &[PortEntry::Single(port)]),
+                    ];
+                    if index > 0 {
+                        let previous = Self::knock_set_name(knock, index, family);
+                        expr.push(Self::set_lookup(address, "saddr", &previous));
+                    }
+                    expr.push(json!({
+                        "set": {
+                            "op": "update",
+                            "elem": { "payload": { "protocol": address, "field": "saddr" } },
+                            "set": format!("@{}", Self::knock_set_name(knock, index + 1, family))
+                        }
+                    }));
+                    nft_rules.push(Self::knock_rule_json(
+                        &expr,
+                        &format!("knock {}: stage {}/{stages}", knock.name, index + 1),
+                    ));
+                }
+
+                let service_proto = knock.protocol.as_str();
+                let open_set = Self::knock_set_name(knock, stages, family);
+                let expr = vec![
+                    Self::meta_match("nfproto", nfproto),
+                    Self::meta_match("l4proto", service_proto),
+                    Self::port_match(service_proto, "dport", &[PortEntry::Single(knock.port)]),
+                    Self::set_lookup(address, "saddr", &open_set),
+                    json!({ "accept": null }),
+                ];
+                nft_rules.push(Self::knock_rule_json(
+                    &expr,
+                    &format!(
+                        "knock {}: open {}/{}",
+                        knock.name, knock.protocol, knock.port
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Wraps port-knocking expressions in an input chain rule
+    fn knock_rule_json(expr: &[serde_json::Value], comment: &str) -> serde_json::Value {
+        serde_json::json!({
+            "add": {
+                "rule": {
+                    "family": "inet",
+                    "table": "drfw",
+                    "chain": "input",
+                    "expr": expr,
+                    "comment": comment
+                }
+            }
+        })
+    }
+
+    /// Set type naming the IP family of an address
+    const fn address_family(is_ipv6: bool) -> SetType {
+        if is_ipv6 {
+            SetType::Ipv6Addr
+        } else {
+            SetType::Ipv4Addr
+        }
+    }
+
+    /// Wraps a limit statement in a per-source set update (`update @set { ip saddr <stmt> }`)
+    /// if the rule tracks its limits per source and the variant's family is known
+    fn per_source_json(
+        rule: &Rule,
+        meter: Meter,
+        family: Option<SetType>,
+        stmt: serde_json::Value,
+    ) -> serde_json::Value {
+        match family.filter(|_| rule.per_source.is_some()) {
+            Some(family) => serde_json::json!({
+                "set": {
+                    "op": meter.op(),
+                    "elem": { "payload": { "protocol": family.address_protocol(), "field": "saddr" } },
+                    "set": format!("@{}", Self::meter_set_name(rule, meter, family)),
+                    "stmt": [stmt]
+                }
+            }),
+            None => stmt,
+        }
+    }
+
+    fn add_base_rules(
+        nft_rules: &mut Vec<serde_json::Value>,
+        advanced: &AdvancedSecuritySettings,
+        blocklists: bool,
+    ) {
+        use crate::core::bans::{BAN_SET_V4, BAN_SET_V6};
+        use crate::core::blocklists::{BLOCKLIST_SET_V4, BLOCKLIST_SET_V6};
         use serde_json::json;
 
         // Rule ordering matters for performance and correctness:
         // 0. [OPTIONAL] Anti-spoofing (RPF) - must be first to check all packets
         // 1. Loopback - most common, should bypass all checks
+        // 1b. Banned addresses - before established so bans cut existing connections
+        // 1c. [OPTIONAL] Blocklisted addresses - same reasoning as bans
         // 2. Drop invalid early - avoid wasting cycles on malformed packets
         // 3. Established/related - most traffic will match here
         // 4. Block ICMP redirects - prevent MITM attacks
@@ -1107,7 +2933,7 @@ impl FirewallRuleset {
         }
 
         // Standard rules (always enabled)
-        let standard_rules = [
+        let mut standard_rules = vec![
             (
                 "allow from loopback",
                 vec![
@@ -1115,6 +2941,20 @@ impl FirewallRuleset {
                     json!({ "accept": null }),
                 ],
             ),
+            (
+                "drop banned ipv4 addresses",
+                vec![
+                    json!({ "match": { "left": { "payload": { "protocol": "ip", "field": "saddr" } }, "op": "==", "right": format!("@{BAN_SET_V4}") } }),
+                    json!({ "drop": null }),
+                ],
+            ),
+            (
+                "drop banned ipv6 addresses",
+                vec![
+                    json!({ "match": { "left": { "payload": { "protocol": "ip6", "field": "saddr" } }, "op": "==", "right": format!("@{BAN_SET_V6}") } }),
+                    json!({ "drop": null }),
+                ],
+            ),
             (
                 "early drop of invalid connections",
                 vec![
@@ -1147,6 +2987,28 @@ impl FirewallRuleset {
             ),
         ];
 
+        if blocklists {
+            standard_rules.splice(
+                3..3,
+                [
+                    (
+                        "drop blocklisted ipv4 addresses",
+                        vec![
+                            json!({ "match": { "left": { "payload": { "protocol": "ip", "field": "saddr" } }, "op": "==", "right": format!("@{BLOCKLIST_SET_V4}") } }),
+                            json!({ "drop": null }),
+                        ],
+                    ),
+                    (
+                        "drop blocklisted ipv6 addresses",
+                        vec![
+                            json!({ "match": { "left": { "payload": { "protocol": "ip6", "field": "saddr" } }, "op": "==", "right": format!("@{BLOCKLIST_SET_V6}") } }),
+                            json!({ "drop": null }),
+                        ],
+                    ),
+                ],
+            );
+        }
+
         for (comment, expr) in standard_rules {
             nft_rules.push(json!({
                 "add": {
@@ -1224,67 +3086,415 @@ impl FirewallRuleset {
                 ]}
             }});
 
-            let ipv6_expr =
-                build_icmp_rule("ipv6-icmp", Some(ipv6_types), advanced.icmp_rate_limit);
-            nft_rules.push(Self::rule_add(
-                "input",
-                &ipv6_expr,
-                "allow essential icmpv6 (strict mode)",
+            let ipv6_expr =
+                build_icmp_rule("ipv6-icmp", Some(ipv6_types), advanced.icmp_rate_limit);
+            nft_rules.push(Self::rule_add(
+                "input",
+                &ipv6_expr,
+                "allow essential icmpv6 (strict mode)",
+            ));
+        } else {
+            // Default mode: Allow all ICMP (except redirects which are already blocked)
+
+            let ipv4_expr = build_icmp_rule("icmp", None, advanced.icmp_rate_limit);
+            nft_rules.push(Self::rule_add("input", &ipv4_expr, "allow icmp"));
+
+            let ipv6_expr = build_icmp_rule("ipv6-icmp", None, advanced.icmp_rate_limit);
+            nft_rules.push(Self::rule_add("input", &ipv6_expr, "allow icmp v6"));
+        }
+    }
+
+    /// Adds user rule(s) to the `nft_rules` vector.
+    /// May generate multiple nftables rules if the DRFW rule has mixed IPv4/IPv6 addresses.
+    ///
+    /// nftables requires separate rules for IPv4 and IPv6:
+    /// - `ip saddr` only matches IPv4 addresses
+    /// - `ip6 saddr` only matches IPv6 addresses
+    ///
+    /// So if a user specifies both IPv4 and IPv6 sources, we generate two nft rules.
+    ///
+    /// A named address set pins the rule to the set's IP family: only explicit
+    /// addresses of that family are kept for the other field.
+    ///
+    /// A negated address list only excludes addresses of its own family, so the
+    /// other family gets a rule without that match (`meta nfproto` pins it).
+    ///
+    /// With `counters`, each generated rule gets a counter and an ID-tagged comment.
+    fn add_user_rule(
+        nft_rules: &mut Vec<serde_json::Value>,
+        rule: &Rule,
+        sets: &[NamedSet],
+        counters: bool,
+    ) {
+        let Some(refs) = RuleSetRefs::resolve(rule, sets) else {
+            return; // Dangling or mistyped set reference - rule cannot match
+        };
+        if rule.unexpanded_addresses().next().is_some() {
+            return; // Dropping the symbolic entry would widen the match
+        }
+        if !rule.interface_lists_valid() {
+            return; // Wildcards inside an anonymous set are not portable
+        }
+        if Self::icmp_type_names(rule).is_some_and(|(_, names)| names.is_empty()) {
+            return; // No selected ICMP type exists for the protocol - rule cannot match
+        }
+
+        // Explicit address lists are replaced by their set reference, if any
+        let sources: &[AddressEntry] = if refs.source.is_some() {
+            &[]
+        } else {
+            &rule.sources
+        };
+        let destinations: &[AddressEntry] = if refs.destination.is_some() {
+            &[]
+        } else {
+            &rule.destinations
+        };
+
+        // Country sets exist per IP family: one rule per family the destinations allow
+        if !rule.countries.is_empty() {
+            for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                if refs.family().is_some_and(|f| f != family) {
+                    continue;
+                }
+                let Some(family_dests) =
+                    Self::family_addresses(destinations, family, rule.negate.destinations)
+                else {
+                    continue;
+                };
+                Self::add_single_rule(
+                    nft_rules,
+                    rule,
+                    &refs,
+                    &[],
+                    &family_dests,
+                    Some(family),
+                    counters,
+                );
+            }
+            return;
+        }
+
+        if let Some(family) = refs.family() {
+            // Explicit addresses exist but none in the set's family - nothing can match
+            let (Some(family_sources), Some(family_dests)) = (
+                Self::family_addresses(sources, family, rule.negate.sources),
+                Self::family_addresses(destinations, family, rule.negate.destinations),
+            ) else {
+                return;
+            };
+            Self::add_single_rule(
+                nft_rules,
+                rule,
+                &refs,
+                &family_sources,
+                &family_dests,
+                None,
+                counters,
+            );
+            return;
+        }
+
+        // For rules with no IP filtering, generate a single rule (or one per family
+        // when limits are tracked per source, as each family needs its own set)
+        if sources.is_empty() && destinations.is_empty() {
+            if rule.per_source.is_some() {
+                for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                    Self::add_single_rule(nft_rules, rule, &refs, &[], &[], Some(family), counters);
+                }
+            } else {
+                Self::add_single_rule(nft_rules, rule, &refs, &[], &[], None, counters);
+            }
+            return;
+        }
+
+        // One rule per IP family the addresses allow. A family left without any
+        // address match (only possible through negation) is pinned with nfproto.
+        for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+            let (Some(family_sources), Some(family_dests)) = (
+                Self::family_addresses(sources, family, rule.negate.sources),
+                Self::family_addresses(destinations, family, rule.negate.destinations),
+            ) else {
+                continue;
+            };
+            let unfiltered = family_sources.is_empty() && family_dests.is_empty();
+            if unfiltered && !Self::protocol_allows_family(rule.protocol, family) {
+                continue;
+            }
+            Self::add_single_rule(
+                nft_rules,
+                rule,
+                &refs,
+                &family_sources,
+                &family_dests,
+                unfiltered.then_some(family),
+                counters,
+            );
+        }
+    }
+
+    /// Returns the addresses of `family` for one address field of a rule variant,
+    /// or `None` if the field rules out that family.
+    ///
+    /// An empty field matches any family. Otherwise a family without addresses is
+    /// ruled out, unless the field is negated: excluding only the other family's
+    /// addresses leaves this family unfiltered.
+    fn family_addresses(
+        addresses: &[AddressEntry],
+        family: SetType,
+        negated: bool,
+    ) -> Option<Vec<&AddressEntry>> {
+        let in_family: Vec<_> = addresses
+            .iter()
+            .filter(|ip| Self::address_family(ip.is_ipv6()) == family)
+            .collect();
+        if in_family.is_empty() && !addresses.is_empty() && !negated {
+            None
+        } else {
+            Some(in_family)
+        }
+    }
+
+    /// Returns `false` if the protocol can never carry traffic of `family`
+    fn protocol_allows_family(protocol: Protocol, family: SetType) -> bool {
+        use crate::core::rule_constraints::{protocol_requires_ipv4, protocol_requires_ipv6};
+        if family == SetType::Ipv6Addr {
+            !protocol_requires_ipv4(protocol)
+        } else {
+            !protocol_requires_ipv6(protocol)
+        }
+    }
+
+    /// Returns the payload protocol and nftables names for a rule's ICMP type filter.
+    ///
+    /// `None` means no type filter. Types are only applied to single-family ICMP
+    /// protocols; an empty name list means none of the types exist for the protocol.
+    fn icmp_type_names(rule: &Rule) -> Option<(&'static str, Vec<&'static str>)> {
+        if rule.icmp_types.is_empty() {
+            return None;
+        }
+        let (protocol, name): (_, fn(IcmpType) -> Option<&'static str>) = match rule.protocol {
+            Protocol::Icmp => ("icmp", IcmpType::icmp_name),
+            Protocol::Icmpv6 => ("icmpv6", IcmpType::icmpv6_name),
+            _ => return None,
+        };
+        Some((
+            protocol,
+            rule.icmp_types.iter().filter_map(|t| name(*t)).collect(),
+        ))
+    }
+
+    /// Creates a payload match against port entries (`<protocol> <field> { ... }`)
+    fn port_match(protocol: &str, field: &str, ports: &[PortEntry]) -> serde_json::Value {
+        let right = if ports.len() == 1 {
+            ports[0].to_nft_json()
+        } else {
+            serde_json::json!({
+                "set": ports.iter().copied().map(PortEntry::to_nft_json).collect::<Vec<_>>()
+            })
+        };
+        serde_json::json!({
+            "match": {
+                "left": { "payload": { "protocol": protocol, "field": field } },
+                "op": "==",
+                "right": right
+            }
+        })
+    }
+
+    /// Creates an interface name match (`iifname "wlan*"` or `iifname { "eth0", "wlan0" }`)
+    fn interface_match(key: &str, interfaces: &[String]) -> serde_json::Value {
+        if let [iface] = interfaces {
+            Self::meta_match(key, iface)
+        } else {
+            Self::meta_match(key, serde_json::json!({ "set": interfaces }))
+        }
+    }
+
+    /// Creates a payload match against a named set (`<protocol> <field> @name`)
+    fn set_lookup(protocol: &str, field: &str, set_name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "match": {
+                "left": { "payload": { "protocol": protocol, "field": field } },
+                "op": "==",
+                "right": format!("@{set_name}")
+            }
+        })
+    }
+
+    /// Inverts a match expression (`==` becomes `!=`) if `negated`
+    fn negate_match(mut expr: serde_json::Value, negated: bool) -> serde_json::Value {
+        if negated {
+            expr["match"]["op"] = serde_json::json!("!=");
+        }
+        expr
+    }
+
+    /// Appends a rule's ct state and schedule matches, rate limit, quota, connection limit,
+    /// log and counter expressions (before the verdict, so logging happens even if the
+    /// action is accept). `family` is the IP family of the generated variant, if known.
+    fn push_rule_options(
+        expressions: &mut Vec<serde_json::Value>,
+        rule: &Rule,
+        family: Option<SetType>,
+        counter: bool,
+    ) {
+        use serde_json::json;
+
+        // Connection state (a match, so it must precede the statements below)
+        if !rule.ct_states.is_empty() {
+            let states: Vec<&str> = rule.ct_states.iter().map(AsRef::as_ref).collect();
+            let right = if states.len() == 1 {
+                json!(states[0])
+            } else {
+                json!({ "set": states })
+            };
+            expressions.push(json!({
+                "match": { "left": { "ct": { "key": "state" } }, "op": "==", "right": right }
+            }));
+        }
+
+        // Schedule: a window spanning midnight is matched as "not in end-start"
+        if let Some(ref schedule) = rule.schedule {
+            if schedule.has_time_window() {
+                let (op, from, to) = if schedule.wraps_midnight() {
+                    ("!=", schedule.end, schedule.start)
+                } else {
+                    ("==", schedule.start, schedule.end)
+                };
+                expressions.push(json!({
+                    "match": {
+                        "left": { "meta": { "key": "hour" } },
+                        "op": op,
+                        "right": { "range": [
+                            from.format("%H:%M").to_string(),
+                            to.format("%H:%M").to_string()
+                        ] }
+                    }
+                }));
+            }
+            if schedule.days.len() == 1 {
+                expressions.push(Self::meta_match("day", schedule.days[0].as_ref()));
+            } else if !schedule.days.is_empty() {
+                let days: Vec<&str> = schedule.days.iter().map(AsRef::as_ref).collect();
+                expressions.push(Self::meta_match("day", json!({ "set": days })));
+            }
+        }
+
+        // Advanced options: rate limiting (with optional burst)
+        if let Some(rate_limit) = rule.rate_limit {
+            let mut limit_obj = json!({
+                "rate": rate_limit.count,
+                "per": rate_limit.unit.as_str()
+            });
+            if let Some(bytes) = rate_limit.bytes {
+                let (rate, unit) = bytes.nft_value(u64::from(rate_limit.count));
+                limit_obj["rate"] = json!(rate);
+                limit_obj["rate_unit"] = json!(unit);
+            }
+            if let Some(burst) = rate_limit.burst.filter(|&b| b > 0) {
+                if let Some(bytes) = rate_limit.bytes {
+                    let (burst, unit) = bytes.nft_value(u64::from(burst));
+                    limit_obj["burst"] = json!(burst);
+                    limit_obj["burst_unit"] = json!(unit);
+                } else {
+                    limit_obj["burst"] = json!(burst);
+                }
+            }
+            if rate_limit.over {
+                limit_obj["inv"] = json!(true);
+            }
+            expressions.push(Self::per_source_json(
+                rule,
+                Meter::Rate,
+                family,
+                json!({ "limit": limit_obj }),
             ));
-        } else {
-            // Default mode: Allow all ICMP (except redirects which are already blocked)
+        }
 
-            let ipv4_expr = build_icmp_rule("icmp", None, advanced.icmp_rate_limit);
-            nft_rules.push(Self::rule_add("input", &ipv4_expr, "allow icmp"));
+        // Advanced options: byte quota (shared by all variants of the rule)
+        if rule.quota.is_some() {
+            expressions.push(json!({ "quota": Self::quota_name(rule) }));
+        }
 
-            let ipv6_expr = build_icmp_rule("ipv6-icmp", None, advanced.icmp_rate_limit);
-            nft_rules.push(Self::rule_add("input", &ipv6_expr, "allow icmp v6"));
+        // Advanced options: connection limiting
+        if rule.connection_limit > 0 {
+            expressions.push(Self::per_source_json(
+                rule,
+                Meter::Conn,
+                family,
+                json!({ "ct count": { "val": rule.connection_limit } }),
+            ));
         }
-    }
 
-    /// Adds user rule(s) to the `nft_rules` vector.
-    /// May generate multiple nftables rules if the DRFW rule has mixed IPv4/IPv6 addresses.
-    ///
-    /// nftables requires separate rules for IPv4 and IPv6:
-    /// - `ip saddr` only matches IPv4 addresses
-    /// - `ip6 saddr` only matches IPv6 addresses
-    ///
-    /// So if a user specifies both IPv4 and IPv6 sources, we generate two nft rules.
-    fn add_user_rule(nft_rules: &mut Vec<serde_json::Value>, rule: &Rule) {
-        // Split sources and destinations by IP version
-        let ipv4_sources: Vec<_> = rule.sources.iter().filter(|s| s.is_ipv4()).collect();
-        let ipv6_sources: Vec<_> = rule.sources.iter().filter(|s| s.is_ipv6()).collect();
-        let ipv4_dests: Vec<_> = rule.destinations.iter().filter(|d| d.is_ipv4()).collect();
-        let ipv6_dests: Vec<_> = rule.destinations.iter().filter(|d| d.is_ipv6()).collect();
-
-        // For rules with no IP filtering, generate a single rule
-        if rule.sources.is_empty() && rule.destinations.is_empty() {
-            Self::add_single_rule(nft_rules, rule, &[], &[]);
-            return;
+        // Per-rule logging (before action, so log happens even if action is accept)
+        if rule.log_enabled {
+            expressions.push(json!({
+                "log": {
+                    "prefix": &rule.log_prefix,
+                    "level": "info"
+                }
+            }));
         }
 
-        // Generate IPv4 rule if we have IPv4 sources or destinations
-        if !ipv4_sources.is_empty() || !ipv4_dests.is_empty() {
-            Self::add_single_rule(nft_rules, rule, &ipv4_sources, &ipv4_dests);
+        // Per-rule counter (after all matches and limits, so it counts what the verdict sees)
+        if counter {
+            expressions.push(json!({ "counter": null }));
         }
+    }
 
-        // Generate IPv6 rule if we have IPv6 sources or destinations
-        if !ipv6_sources.is_empty() || !ipv6_dests.is_empty() {
-            Self::add_single_rule(nft_rules, rule, &ipv6_sources, &ipv6_dests);
+    /// Returns the comment for a user rule: its label, tagged with the rule ID when
+    /// counters are enabled so the kernel rule can be mapped back to `Rule.id`.
+    ///
+    /// The label is cut to fit the kernel's comment limit (leaving room for the tag).
+    fn user_rule_comment(rule: &Rule, counters: bool) -> Option<String> {
+        use crate::validators::{MAX_COMMENT_BYTES, sanitize_comment};
+
+        if counters {
+            let budget = MAX_COMMENT_BYTES - crate::core::counters::TAG_LEN;
+            let label = sanitize_comment(&rule.label, budget);
+            Some(crate::core::counters::tagged_comment(rule.id, &label))
+        } else if rule.label.is_empty() {
+            None
+        } else {
+            Some(sanitize_comment(&rule.label, MAX_COMMENT_BYTES))
         }
     }
 
     /// Generates a single nftables rule with the given sources and destinations.
+    ///
+    /// `nfproto` restricts a rule without address matches to one IP family
+    /// (for country rules, it selects the family's country set).
     fn add_single_rule(
         nft_rules: &mut Vec<serde_json::Value>,
         rule: &Rule,
-        sources: &[&IpNetwork],
-        destinations: &[&IpNetwork],
+        refs: &RuleSetRefs<'_>,
+        sources: &[&AddressEntry],
+        destinations: &[&AddressEntry],
+        nfproto: Option<SetType>,
+        counters: bool,
     ) {
         use serde_json::json;
 
         let mut expressions = Vec::with_capacity(8);
 
+        // IP family of this variant (needed for per-source limit sets)
+        let family = nfproto.or(refs.family()).or_else(|| {
+            sources
+                .first()
+                .or(destinations.first())
+                .map(|ip| Self::address_family(ip.is_ipv6()))
+        });
+        // Country set lookups already pin the family
+        if let Some(family) = nfproto.filter(|_| rule.countries.is_empty()) {
+            let nfproto = if family == SetType::Ipv6Addr {
+                "ipv6"
+            } else {
+                "ipv4"
+            };
+            expressions.push(Self::meta_match("nfproto", nfproto));
+        }
+
         // Protocol matching
         match rule.protocol {
             Protocol::Any => {}
@@ -1308,45 +3518,113 @@ impl FirewallRuleset {
             }
         }
 
+        // ICMP type/code filtering
+        if let Some((protocol, names)) = Self::icmp_type_names(rule) {
+            let right = if names.len() == 1 {
+                json!(names[0])
+            } else {
+                json!({ "set": names })
+            };
+            expressions.push(json!({
+                "match": {
+                    "left": { "payload": { "protocol": protocol, "field": "type" } },
+                    "op": "==",
+                    "right": right
+                }
+            }));
+            if let Some(code) = rule.icmp_code.filter(|_| names.len() == 1) {
+                expressions.push(json!({
+                    "match": {
+                        "left": { "payload": { "protocol": protocol, "field": "code" } },
+                        "op": "==",
+                        "right": code
+                    }
+                }));
+            }
+        }
+
         // Source IP filtering (all sources should be same IP version)
-        if !sources.is_empty() {
+        let negate = rule.negate;
+        if let Some(set) = refs.source {
+            expressions.push(Self::negate_match(
+                Self::set_lookup(set.set_type.address_protocol(), "saddr", &set.name),
+                negate.sources,
+            ));
+        } else if let Some(family) = family.filter(|_| !rule.countries.is_empty()) {
+            expressions.push(Self::negate_match(
+                Self::set_lookup(
+                    family.address_protocol(),
+                    "saddr",
+                    &crate::core::geoip::geo_set_name(&rule.countries, family == SetType::Ipv6Addr),
+                ),
+                negate.sources,
+            ));
+        } else if !sources.is_empty() {
             let is_ipv6 = sources[0].is_ipv6();
             let protocol = if is_ipv6 { "ip6" } else { "ip" };
 
             let src_val = if sources.len() == 1 {
-                Self::ip_to_nft_json(sources[0])
+                Self::address_to_nft_json(sources[0])
             } else {
-                let src_set: Vec<serde_json::Value> =
-                    sources.iter().map(|ip| Self::ip_to_nft_json(ip)).collect();
+                let src_set: Vec<serde_json::Value> = sources
+                    .iter()
+                    .map(|ip| Self::address_to_nft_json(ip))
+                    .collect();
                 json!({ "set": src_set })
             };
 
             expressions.push(json!({
                 "match": {
                     "left": { "payload": { "protocol": protocol, "field": "saddr" } },
-                    "op": "==",
+                    "op": Negation::json_op(negate.sources),
                     "right": src_val
                 }
             }));
         }
 
-        // Input interface
-        if let Some(ref iface) = rule.interface {
-            expressions.push(Self::meta_match("iifname", iface));
+        // Input interfaces
+        if !rule.interfaces.is_empty() {
+            expressions.push(Self::negate_match(
+                Self::interface_match("iifname", &rule.interfaces),
+                negate.interface,
+            ));
         }
 
-        // Output interface
-        if let Some(ref oiface) = rule.output_interface {
-            expressions.push(Self::meta_match("oifname", oiface));
+        // Output interfaces
+        if !rule.output_interfaces.is_empty() {
+            expressions.push(Self::negate_match(
+                Self::interface_match("oifname", &rule.output_interfaces),
+                negate.output_interface,
+            ));
         }
 
         // Port filtering
-        if !rule.ports.is_empty()
-            && matches!(
-                rule.protocol,
-                Protocol::Tcp | Protocol::Udp | Protocol::TcpAndUdp
-            )
-        {
+        let supports_ports = matches!(
+            rule.protocol,
+            Protocol::Tcp | Protocol::Udp | Protocol::TcpAndUdp
+        );
+        if !rule.source_ports.is_empty() && supports_ports {
+            let protocol_key = if matches!(rule.protocol, Protocol::TcpAndUdp) {
+                "th"
+            } else {
+                rule.protocol.as_str()
+            };
+            expressions.push(Self::negate_match(
+                Self::port_match(protocol_key, "sport", &rule.source_ports),
+                negate.source_ports,
+            ));
+        }
+        if let Some(set) = refs.ports.filter(|_| supports_ports) {
+            let protocol_key = if matches!(rule.protocol, Protocol::TcpAndUdp) {
+                "th"
+            } else {
+                rule.protocol.as_str()
+            };
+            expressions.push(Self::negate_match(
+                Self::set_lookup(protocol_key, "dport", &set.name),
+                negate.ports,
+            ));
+        } else if !rule.ports.is_empty() && supports_ports {
             let port_val = if rule.ports.len() == 1 {
                 rule.ports[0].to_nft_json()
             } else {
@@ -1368,23 +3646,28 @@ impl FirewallRuleset {
             expressions.push(json!({
                 "match": {
                     "left": { "payload": { "protocol": protocol_key, "field": "dport" } },
-                    "op": "==",
+                    "op": Negation::json_op(negate.ports),
                     "right": port_val
                 }
             }));
         }
 
         // Destination IP filtering (all destinations should be same IP version)
-        if !destinations.is_empty() {
+        if let Some(set) = refs.destination {
+            expressions.push(Self::negate_match(
+                Self::set_lookup(set.set_type.address_protocol(), "daddr", &set.name),
+                negate.destinations,
+            ));
+        } else if !destinations.is_empty() {
             let is_ipv6 = destinations[0].is_ipv6();
             let protocol = if is_ipv6 { "ip6" } else { "ip" };
 
             let dest_val = if destinations.len() == 1 {
-                Self::ip_to_nft_json(destinations[0])
+                Self::address_to_nft_json(destinations[0])
             } else {
                 let dest_set: Vec<serde_json::Value> = destinations
                     .iter()
-                    .map(|ip| Self::ip_to_nft_json(ip))
+                    .map(|ip| Self::address_to_nft_json(ip))
                     .collect();
                 json!({ "set": dest_set })
             };
@@ -1392,40 +3675,13 @@ impl FirewallRuleset {
             expressions.push(json!({
                 "match": {
                     "left": { "payload": { "protocol": protocol, "field": "daddr" } },
-                    "op": "==",
+                    "op": Negation::json_op(negate.destinations),
                     "right": dest_val
                 }
             }));
         }
 
-        // Advanced options: rate limiting (with optional burst)
-        if let Some(rate_limit) = rule.rate_limit {
-            let mut limit_obj = json!({
-                "rate": rate_limit.count,
-                "per": rate_limit.unit.as_str()
-            });
-            if let Some(burst) = rate_limit.burst.filter(|&b| b > 0) {
-                limit_obj["burst"] = json!(burst);
-            }
-            expressions.push(json!({ "limit": limit_obj }));
-        }
-
-        // Advanced options: connection limiting
-        if rule.connection_limit > 0 {
-            expressions.push(json!({
-                "ct count": { "val": rule.connection_limit }
-            }));
-        }
-
-        // Per-rule logging (before action, so log happens even if action is accept)
-        if rule.log_enabled {
-            expressions.push(json!({
-                "log": {
-                    "prefix": &rule.log_prefix,
-                    "level": "info"
-                }
-            }));
-        }
+        Self::push_rule_options(&mut expressions, rule, family, counters);
 
         // Action (Accept/Drop/Reject with optional reject type)
         match rule.action {
@@ -1456,7 +3712,296 @@ impl FirewallRuleset {
                     "table": "drfw",
                     "chain": rule.chain.as_ref(),
                     "expr": expressions,
-                    "comment": if rule.label.is_empty() { None } else { Some(&rule.label) }
+                    "comment": Self::user_rule_comment(rule, counters)
+                }
+            }
+        }));
+    }
+
+    /// Adds the forward chain rules needed for port forwards to carry replies
+    fn add_forward_base_rules(nft_rules: &mut Vec<serde_json::Value>) {
+        use serde_json::json;
+
+        nft_rules.push(Self::rule_add(
+            "forward",
+            &[
+                json!({ "match": { "left": { "ct": { "key": "state" } }, "op": "==", "right": "invalid" } }),
+                json!({ "drop": null }),
+            ],
+            "drop invalid forwarded connections",
+        ));
+        nft_rules.push(Self::rule_add(
+            "forward",
+            &[
+                json!({ "match": { "left": { "ct": { "key": "state" } }, "op": "==", "right": {"set": ["established", "related"]} } }),
+                json!({ "accept": null }),
+            ],
+            "allow tracked forwarded connections",
+        ));
+    }
+
+    /// Adds the internet sharing rules: masquerade on the upstream interface,
+    /// downstream→upstream forwarding and the optional DHCP/DNS accepts.
+    fn add_sharing_rules(
+        nft_rules: &mut Vec<serde_json::Value>,
+        advanced: &AdvancedSecuritySettings,
+    ) {
+        use serde_json::json;
+
+        let sharing = &advanced.sharing;
+        let upstream = sharing.upstream_interface.as_str();
+        let downstream = sharing.downstream_interface.as_str();
+
+        nft_rules.push(Self::rule_add(
+            "postrouting",
+            &[
+                Self::meta_match("oifname", upstream),
+                json!({ "masquerade": null }),
+            ],
+            "masquerade shared connection",
+        ));
+        nft_rules.push(Self::rule_add(
+            "forward",
+            &[
+                Self::meta_match("iifname", downstream),
+                Self::meta_match("oifname", upstream),
+                json!({ "accept": null }),
+            ],
+            "allow shared connection from downstream",
+        ));
+
+        if !sharing.allow_dhcp_dns {
+            return;
+        }
+        let th_port = |field: &str, ports: serde_json::Value| json!({ "match": { "left": { "payload": { "protocol": "th", "field": field } }, "op": "==", "right": ports } });
+        nft_rules.push(Self::rule_add(
+            "input",
+            &[
+                Self::meta_match("iifname", downstream),
+                Self::meta_match("l4proto", "udp"),
+                th_port("dport", json!(67)),
+                json!({ "accept": null }),
+            ],
+            "allow dhcp from downstream",
+        ));
+        nft_rules.push(Self::rule_add(
+            "input",
+            &[
+                Self::meta_match("iifname", downstream),
+                Self::meta_match("l4proto", json!({"set": ["tcp", "udp"]})),
+                th_port("dport", json!(53)),
+                json!({ "accept": null }),
+            ],
+            "allow dns from downstream",
+        ));
+        // Server Mode drops output by default, so replies need an explicit accept
+        if advanced.egress_profile == EgressProfile::Server {
+            nft_rules.push(Self::rule_add(
+                "output",
+                &[
+                    Self::meta_match("oifname", downstream),
+                    Self::meta_match("l4proto", json!({"set": ["tcp", "udp"]})),
+                    th_port("sport", json!({"set": [53, 67]})),
+                    json!({ "accept": null }),
+                ],
+                "allow dhcp and dns replies to downstream",
+            ));
+        }
+    }
+
+    /// Returns the sources a port forward can match, or `None` if it can never match.
+    ///
+    /// DNAT cannot change the IP family, so sources outside the target's family are
+    /// dropped. A forward whose sources (or source set) are all in the other family,
+    /// whose protocol has no ports, whose ports are negated (no external port
+    /// to map) or whose symbolic addresses are unexpanded is skipped rather than widened.
+    fn port_forward_sources<'r>(
+        rule: &'r Rule,
+        forward: PortForward,
+        refs: &RuleSetRefs<'_>,
+    ) -> Option<Vec<&'r AddressEntry>> {
+        if !matches!(
+            rule.protocol,
+            Protocol::Tcp | Protocol::Udp | Protocol::TcpAndUdp
+        ) || rule.negate.ports
+            || rule.unexpanded_addresses().next().is_some()
+            || !rule.interface_lists_valid()
+        {
+            return None;
+        }
+        let target_v6 = forward.to_address.is_ipv6();
+        if refs
+            .source
+            .is_some_and(|set| (set.set_type == SetType::Ipv6Addr) != target_v6)
+        {
+            return None;
+        }
+        let sources: Vec<_> = rule
+            .sources
+            .iter()
+            .filter(|s| s.is_ipv6() == target_v6)
+            .collect();
+        if sources.is_empty()
+            && !rule.sources.is_empty()
+            && refs.source.is_none()
+            && !rule.negate.sources
+        {
+            return None;
+        }
+        Some(sources)
+    }
+
+    /// Adds a port forward: a `dnat` rule in the `prerouting` nat chain and the
+    /// matching accept in the forward chain.
+    ///
+    /// The forward-chain accept only matches connections that were actually
+    /// translated (`ct status dnat`), so it cannot be used to reach the internal
+    /// host directly.
+    fn add_port_forward(
+        nft_rules: &mut Vec<serde_json::Value>,
+        rule: &Rule,
+        forward: PortForward,
+        sets: &[NamedSet],
+        counters: bool,
+    ) {
+        use serde_json::json;
+
+        let Some(refs) = RuleSetRefs::resolve(rule, sets) else {
+            return; // Dangling or mistyped set reference - rule cannot match
+        };
+        let Some(sources) = Self::port_forward_sources(rule, forward, &refs) else {
+            return;
+        };
+
+        let family = forward.address_protocol();
+        let protocol_key = if rule.protocol == Protocol::TcpAndUdp {
+            "th"
+        } else {
+            rule.protocol.as_str()
+        };
+        let dport_match = |right: serde_json::Value| {
+            json!({
+                "match": {
+                    "left": { "payload": { "protocol": protocol_key, "field": "dport" } },
+                    "op": "==",
+                    "right": right
+                }
+            })
+        };
+        let external_ports = if let Some(set) = refs.ports {
+            Some(json!(format!("@{}", set.name)))
+        } else if rule.ports.len() == 1 {
+            Some(rule.ports[0].to_nft_json())
+        } else if rule.ports.is_empty() {
+            None
+        } else {
+            let port_set: Vec<_> = rule
+                .ports
+                .iter()
+                .copied()
+                .map(PortEntry::to_nft_json)
+                .collect();
+            Some(json!({ "set": port_set }))
+        };
+
+        // Matches shared by both rules: family, interface, protocol, source ports, sources
+        let mut matches = Vec::with_capacity(8);
+        matches.push(Self::meta_match(
+            "nfproto",
+            if family == "ip6" { "ipv6" } else { "ipv4" },
+        ));
+        if !rule.interfaces.is_empty() {
+            matches.push(Self::negate_match(
+                Self::interface_match("iifname", &rule.interfaces),
+                rule.negate.interface,
+            ));
+        }
+        if rule.protocol == Protocol::TcpAndUdp {
+            matches.push(Self::meta_match("l4proto", json!({"set": ["tcp", "udp"]})));
+        } else {
+            matches.push(Self::meta_match("l4proto", rule.protocol.as_str()));
+        }
+        if !rule.source_ports.is_empty() {
+            matches.push(Self::negate_match(
+                Self::port_match(protocol_key, "sport", &rule.source_ports),
+                rule.negate.source_ports,
+            ));
+        }
+        if let Some(set) = refs.source {
+            matches.push(Self::negate_match(
+                Self::set_lookup(family, "saddr", &set.name),
+                rule.negate.sources,
+            ));
+        } else if !sources.is_empty() {
+            let src_val = if sources.len() == 1 {
+                Self::address_to_nft_json(sources[0])
+            } else {
+                json!({ "set": sources.iter().map(|ip| Self::address_to_nft_json(ip)).collect::<Vec<_>>() })
+            };
+            matches.push(json!({
+                "match": {
+                    "left": { "payload": { "protocol": family, "field": "saddr" } },
+                    "op": Negation::json_op(rule.negate.sources),
+                    "right": src_val
+                }
+            }));
+        }
+        let comment = Self::user_rule_comment(rule, counters);
+
+        // prerouting: rewrite the destination to the internal host
+        let mut dnat_expr = matches.clone();
+        if let Some(ref ports) = external_ports {
+            dnat_expr.push(dport_match(ports.clone()));
+        }
+        let mut dnat = json!({ "family": family, "addr": forward.to_address.to_string() });
+        if let Some(port) = forward.to_port {
+            dnat["port"] = json!(port);
+        }
+        dnat_expr.push(json!({ "dnat": dnat }));
+        nft_rules.push(json!({
+            "add": {
+                "rule": {
+                    "family": "inet",
+                    "table": "drfw",
+                    "chain": "prerouting",
+                    "expr": dnat_expr,
+                    "comment": comment
+                }
+            }
+        }));
+
+        // forward: accept the rewritten connection towards the internal host
+        let mut accept_expr = matches;
+        accept_expr.push(json!({
+            "match": {
+                "left": { "payload": { "protocol": family, "field": "daddr" } },
+                "op": "==",
+                "right": forward.to_address.to_string()
+            }
+        }));
+        if let Some(port) = forward.to_port {
+            accept_expr.push(dport_match(json!(port)));
+        } else if let Some(ports) = external_ports {
+            accept_expr.push(dport_match(ports));
+        }
+        accept_expr.push(json!({
+            "match": { "left": { "ct": { "key": "status" } }, "op": "in", "right": "dnat" }
+        }));
+        Self::push_rule_options(
+            &mut accept_expr,
+            rule,
+            Some(Self::address_family(forward.to_address.is_ipv6())),
+            counters,
+        );
+        accept_expr.push(json!({ "accept": null }));
+        nft_rules.push(json!({
+            "add": {
+                "rule": {
+                    "family": "inet",
+                    "table": "drfw",
+                    "chain": "forward",
+                    "expr": accept_expr,
+                    "comment": comment
                 }
             }
         }));
@@ -1523,10 +4068,14 @@ impl FirewallRuleset {
     pub fn to_nft_text(&self) -> String {
         use std::fmt::Write;
 
+        // One instant for all expiry checks (see `to_nftables_json_at`)
+        let now = chrono::Utc::now();
         let mut out = String::new();
 
         let _ = writeln!(out, "table inet drfw {{");
 
+        self.write_named_sets_text(&mut out, now);
+
         let _ = writeln!(out, "    chain input {{");
 
         let _ = writeln!(
@@ -1534,12 +4083,30 @@ impl FirewallRuleset {
             "        type filter hook input priority -10; policy drop;\n"
         );
 
-        Self::write_base_rules_text(&mut out, &self.advanced_security);
+        Self::write_base_rules_text(
+            &mut out,
+            &self.advanced_security,
+            !self.blocklists.is_empty(),
+        );
 
-        if !self.rules.is_empty() {
-            self.write_user_rules_text(&mut out);
+        let sharing = &self.advanced_security.sharing;
+        if sharing.is_active() && sharing.allow_dhcp_dns {
+            let _ = writeln!(out, "        # --- Internet Sharing ---");
+            let _ = writeln!(
+                out,
+                "        iifname \"{}\" udp dport 67 accept comment \"allow dhcp from downstream\"",
+                sharing.downstream_interface
+            );
+            let _ = writeln!(
+                out,
+                "        iifname \"{}\" meta l4proto {{ tcp, udp }} th dport 53 accept comment \"allow dns from downstream\"\n",
+                sharing.downstream_interface
+            );
         }
 
+        self.write_port_knocks_text(&mut out);
+        self.write_user_rules_text(&mut out, Chain::Input, now);
+
         let _ = writeln!(out, "        # --- Rejects (End of Chain) ---");
 
         if self.advanced_security.log_dropped {
@@ -1559,6 +4126,31 @@ impl FirewallRuleset {
 
         let _ = writeln!(out, "    }}\n");
 
+        let has_port_forwards = self.has_port_forwards(now);
+        if has_port_forwards {
+            let _ = writeln!(out, "    chain prerouting {{");
+            let _ = writeln!(
+                out,
+                "        type nat hook prerouting priority -100; policy accept;\n"
+            );
+            self.write_port_forwards_text(&mut out, true, now);
+            let _ = writeln!(out, "    }}\n");
+        }
+
+        if sharing.is_active() {
+            let _ = writeln!(out, "    chain postrouting {{");
+            let _ = writeln!(
+                out,
+                "        type nat hook postrouting priority 100; policy accept;\n"
+            );
+            let _ = writeln!(
+                out,
+                "        oifname \"{}\" masquerade comment \"masquerade shared connection\"",
+                sharing.upstream_interface
+            );
+            let _ = writeln!(out, "    }}\n");
+        }
+
         let _ = writeln!(out, "    chain forward {{");
 
         let _ = writeln!(
@@ -1566,6 +4158,29 @@ impl FirewallRuleset {
             "        type filter hook forward priority -10; policy drop;"
         );
 
+        if self.has_forward_traffic(now) {
+            let _ = writeln!(out);
+            let _ = writeln!(
+                out,
+                "        ct state invalid drop comment \"drop invalid forwarded connections\""
+            );
+            let _ = writeln!(
+                out,
+                "        ct state established,related accept comment \"allow tracked forwarded connections\""
+            );
+        }
+        if sharing.is_active() {
+            let _ = writeln!(
+                out,
+                "        iifname \"{}\" oifname \"{}\" accept comment \"allow shared connection from downstream\"",
+                sharing.downstream_interface, sharing.upstream_interface
+            );
+        }
+        if has_port_forwards {
+            self.write_port_forwards_text(&mut out, false, now);
+        }
+        self.write_user_rules_text(&mut out, Chain::Forward, now);
+
         let _ = writeln!(out, "    }}\n");
 
         let _ = writeln!(out, "    chain output {{");
@@ -1580,21 +4195,150 @@ impl FirewallRuleset {
             "        type filter hook output priority -10; policy {output_policy};"
         );
 
+        if sharing.is_active()
+            && sharing.allow_dhcp_dns
+            && self.advanced_security.egress_profile == EgressProfile::Server
+        {
+            let _ = writeln!(
+                out,
+                "\n        oifname \"{}\" meta l4proto {{ tcp, udp }} th sport {{ 53, 67 }} accept comment \"allow dhcp and dns replies to downstream\"",
+                sharing.downstream_interface
+            );
+        }
+
+        self.write_user_rules_text(&mut out, Chain::Output, now);
+
         let _ = writeln!(out, "    }}\n");
 
+        self.write_group_chains_text(&mut out, now);
+
         let _ = writeln!(out, "}}");
 
         out
     }
 
-    fn write_base_rules_text(out: &mut String, advanced: &AdvancedSecuritySettings) {
+    fn write_named_sets_text(&self, out: &mut String, now: chrono::DateTime<chrono::Utc>) {
+        use crate::core::bans::{BAN_SET_V4, BAN_SET_V6};
+        use std::fmt::Write;
+
+        for set in &self.sets {
+            let _ = writeln!(out, "    set {} {{", set.name);
+            let _ = writeln!(out, "        type {}", set.set_type.as_str());
+            let _ = writeln!(out, "        flags interval");
+            let elements: Vec<_> = if set.set_type.is_address() {
+                // Mirror JSON generation: unexpanded symbolic entries are left out
+                set.addresses
+                    .iter()
+                    .filter(|a| !a.is_symbolic())
+                    .map(ToString::to_string)
+                    .collect()
+            } else {
+                set.element_strings()
+            };
+            if !elements.is_empty() {
+                let _ = writeln!(out, "        elements = {{ {} }}", elements.join(", "));
+            }
+            let _ = writeln!(out, "    }}\n");
+        }
+
+        // Ban blocklists: elements are added at runtime with per-element timeouts
+        for (name, set_type) in [(BAN_SET_V4, "ipv4_addr"), (BAN_SET_V6, "ipv6_addr")] {
+            let _ = writeln!(out, "    set {name} {{");
+            let _ = writeln!(out, "        type {set_type}");
+            let _ = writeln!(out, "        flags timeout");
+            let _ = writeln!(out, "    }}\n");
+        }
+
+        // Blocklist sets: elements are loaded from the attached files at apply time
+        if !self.blocklists.is_empty() {
+            use crate::core::blocklists::{BLOCKLIST_SET_V4, BLOCKLIST_SET_V6};
+
+            for (name, set_type) in [
+                (BLOCKLIST_SET_V4, "ipv4_addr"),
+                (BLOCKLIST_SET_V6, "ipv6_addr"),
+            ] {
+                let _ = writeln!(out, "    set {name} {{");
+                let _ = writeln!(out, "        type {set_type}");
+                let _ = writeln!(out, "        flags interval");
+                for path in &self.blocklists {
+                    let _ = writeln!(out, "        # from {}", path.display());
+                }
+                let _ = writeln!(out, "    }}\n");
+            }
+        }
+
+        // Country sets: ranges are read from the GeoIP database at apply time
+        for group in crate::core::geoip::country_groups(self, now) {
+            for (ipv6, set_type) in [(false, "ipv4_addr"), (true, "ipv6_addr")] {
+                let name = crate::core::geoip::geo_set_name(&group, ipv6);
+                let _ = writeln!(out, "    set {name} {{");
+                let _ = writeln!(out, "        type {set_type}");
+                let _ = writeln!(out, "        flags interval");
+                let _ = writeln!(out, "        # GeoIP ranges of {}", group.join(", "));
+                let _ = writeln!(out, "    }}\n");
+            }
+        }
+
+        // Port-knocking stage sets
+        for knock in &self.knocks {
+            for stage in 1..=knock.sequence.len() {
+                for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                    let name = Self::knock_set_name(knock, stage, family);
+                    let _ = writeln!(out, "    set {name} {{");
+                    let _ = writeln!(out, "        type {}", family.as_str());
+                    let _ = writeln!(out, "        flags dynamic,timeout");
+                    let _ = writeln!(out, "        timeout {}s", knock.timeout_secs);
+                    let _ = writeln!(out, "    }}\n");
+                }
+            }
+        }
+
+        // Per-source limit sets, filled by the rules at runtime
+        for rule in self.rules.iter().filter(|r| self.is_rule_active(r, now)) {
+            let Some(per_source) = rule.per_source else {
+                continue;
+            };
+            for meter in Meter::for_rule(rule) {
+                for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                    let name = Self::meter_set_name(rule, meter, family);
+                    let _ = writeln!(out, "    set {name} {{");
+                    let _ = writeln!(out, "        type {}", family.as_str());
+                    let _ = writeln!(out, "        size {}", per_source.size);
+                    if meter == Meter::Rate {
+                        let _ = writeln!(out, "        flags dynamic,timeout");
+                        let _ = writeln!(out, "        timeout {}s", per_source.timeout_secs);
+                    } else {
+                        let _ = writeln!(out, "        flags dynamic");
+                    }
+                    let _ = writeln!(out, "    }}\n");
+                }
+            }
+        }
+
+        // Quota objects shared by the variants of rules with a byte quota
+        for rule in self.rules.iter().filter(|r| self.is_rule_active(r, now)) {
+            let Some(quota) = rule.quota else {
+                continue;
+            };
+            let (val, unit) = quota.nft_value();
+            let _ = writeln!(out, "    quota {} {{", Self::quota_name(rule));
+            let _ = writeln!(out, "        {} {val} {unit}", quota.mode);
+            let _ = writeln!(out, "    }}\n");
+        }
+    }
+
+    fn write_base_rules_text(
+        out: &mut String,
+        advanced: &AdvancedSecuritySettings,
+        blocklists: bool,
+    ) {
         use std::fmt::Write;
 
         let _ = writeln!(out, "        # --- Base Rules ---");
 
         let _ = writeln!(
             out,
-            "        # Rule ordering: loopback → invalid drop → established → block redirects → ICMP"
+            "        # Rule ordering: loopback → bans → invalid drop → established → block redirects → ICMP"
         );
 
         // Optional: Anti-spoofing (RPF)
@@ -1611,6 +4355,31 @@ impl FirewallRuleset {
             "        iifname \"lo\" accept comment \"allow from loopback\""
         );
 
+        let _ = writeln!(
+            out,
+            "        ip saddr @{} drop comment \"drop banned ipv4 addresses\"",
+            crate::core::bans::BAN_SET_V4
+        );
+
+        let _ = writeln!(
+            out,
+            "        ip6 saddr @{} drop comment \"drop banned ipv6 addresses\"",
+            crate::core::bans::BAN_SET_V6
+        );
+
+        if blocklists {
+            let _ = writeln!(
+                out,
+                "        ip saddr @{} drop comment \"drop blocklisted ipv4 addresses\"",
+                crate::core::blocklists::BLOCKLIST_SET_V4
+            );
+            let _ = writeln!(
+                out,
+                "        ip6 saddr @{} drop comment \"drop blocklisted ipv6 addresses\"",
+                crate::core::blocklists::BLOCKLIST_SET_V6
+            );
+        }
+
         let _ = writeln!(
             out,
             "        ct state invalid drop comment \"early drop of invalid connections\""
@@ -1690,95 +4459,304 @@ impl FirewallRuleset {
         }
     }
 
-    fn write_user_rules_text(&self, out: &mut String) {
+    /// Writes the user rules belonging to `chain` (nothing if it has none)
+    /// Writes the port-knocking stage rules and accepts (mirrors `add_port_knocks`)
+    fn write_port_knocks_text(&self, out: &mut String) {
+        use std::fmt::Write;
+
+        if self.knocks.is_empty() {
+            return;
+        }
+        let _ = writeln!(out, "        # --- Port Knocking ---");
+        for knock in &self.knocks {
+            let stages = knock.sequence.len();
+            for family in [SetType::Ipv4Addr, SetType::Ipv6Addr] {
+                let nfproto = if family == SetType::Ipv6Addr {
+                    "ipv6"
+                } else {
+                    "ipv4"
+                };
+                let address = family.address_protocol();
+                for (index, port) in knock.sequence.iter().enumerate() {
+                    let _ = write!(
+                        out,
+                        "        meta nfproto {nfproto} {} dport {port} ",
+                        knock.knock_protocol
+                    );
+                    if index > 0 {
+                        let previous = Self::knock_set_name(knock, index, family);
+                        let _ = write!(out, "{address} saddr @{previous} ");
+                    }
+                    let _ = writeln!(
+                        out,
+                        "update @{} {{ {address} saddr }} comment \"knock {}: stage {}/{stages}\"",
+                        Self::knock_set_name(knock, index + 1, family),
+                        knock.name,
+                        index + 1
+                    );
+                }
+                let _ = writeln!(
+                    out,
+                    "        meta nfproto {nfproto} {} dport {} {address} saddr @{} accept comment \"knock {}: open {}/{}\"",
+                    knock.protocol,
+                    knock.port,
+                    Self::knock_set_name(knock, stages, family),
+                    knock.name,
+                    knock.protocol,
+                    knock.port
+                );
+            }
+        }
+        let _ = writeln!(out);
+    }
+
+    fn write_user_rules_text(
+        &self,
+        out: &mut String,
+        chain: Chain,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
         use std::fmt::Write;
+        // Skip OUTPUT rules in Desktop Mode (policy is ACCEPT, rules are redundant)
+        if self.advanced_security.egress_profile == EgressProfile::Desktop && chain == Chain::Output
+        {
+            return;
+        }
+        if !self.rules.iter().any(|r| r.chain == chain) {
+            return;
+        }
+        if chain != Chain::Input {
+            // Base chain lines above aren't followed by a blank line
+            let _ = writeln!(out);
+        }
         let _ = writeln!(out, "        # --- User Defined Rules ---");
+        let mut jumped = std::collections::HashSet::new();
         for rule in &self.rules {
-            if !rule.enabled {
+            if !self.is_rule_active(rule, now) || rule.chain != chain {
                 continue;
             }
-
-            // Skip OUTPUT rules in Desktop Mode (policy is ACCEPT, rules are redundant)
-            if self.advanced_security.egress_profile == EgressProfile::Desktop
-                && rule.chain == Chain::Output
-            {
+            // Port forwards are written to the prerouting/forward chains
+            if rule.port_forward.is_some() {
+                continue;
+            }
+            // Group members are written to the group's chain (see `write_group_chains_text`)
+            if let Some(group) = self.group_of(rule) {
+                if jumped.insert(group.id) {
+                    let _ = writeln!(
+                        out,
+                        "        jump {} comment \"{}\"",
+                        group.chain_name(chain),
+                        group.jump_comment()
+                    );
+                }
                 continue;
             }
+            self.write_user_rule_text(out, rule);
+        }
+        let _ = writeln!(out);
+    }
+
+    /// Writes the regular chain of each group jumped to by `write_user_rules_text`
+    fn write_group_chains_text(&self, out: &mut String, now: chrono::DateTime<chrono::Utc>) {
+        use std::fmt::Write;
+
+        for (chain, group) in self.group_chains(now) {
+            let _ = writeln!(out, "    chain {} {{", group.chain_name(chain));
+            let _ = writeln!(out, "        # Group: {} ({chain})", group.name);
+            for rule in self.group_members(group.id) {
+                if rule.chain == chain
+                    && rule.port_forward.is_none()
+                    && self.is_rule_active(rule, now)
+                {
+                    self.write_user_rule_text(out, rule);
+                }
+            }
+            let _ = writeln!(out, "    }}\n");
+        }
+    }
+
+    /// Writes the line(s) of one user rule (mirrors `add_user_rule`)
+    fn write_user_rule_text(&self, out: &mut String, rule: &Rule) {
+        use std::fmt::Write;
+        let counters = self.advanced_security.rule_counters;
+        // Mirror JSON generation: skip rules with unresolvable set references
+        let Some(refs) = RuleSetRefs::resolve(rule, &self.sets) else {
+            return;
+        };
+        let unexpanded: Vec<_> = rule
+            .unexpanded_addresses()
+            .map(ToString::to_string)
+            .collect();
+        if !unexpanded.is_empty() {
+            let _ = writeln!(
+                out,
+                "        # skipped \"{}\": unresolved {}",
+                rule.label,
+                unexpanded.join(", ")
+            );
+            return;
+        }
+        if !rule.interface_lists_valid() {
+            let _ = writeln!(
+                out,
+                "        # skipped \"{}\": wildcard interface in a list",
+                rule.label
+            );
+            return;
+        }
+        let icmp_types = Self::icmp_type_names(rule);
+        if icmp_types
+            .as_ref()
+            .is_some_and(|(_, names)| names.is_empty())
+        {
+            return;
+        }
+        // A named address set pins the rule to its IP family
+        let in_family = |ip: &&AddressEntry| match refs.family() {
+            Some(SetType::Ipv6Addr) => ip.is_ipv6(),
+            Some(_) => ip.is_ipv4(),
+            None => true,
+        };
+        let sources: Vec<_> = rule.sources.iter().filter(in_family).collect();
+        let destinations: Vec<_> = rule.destinations.iter().filter(in_family).collect();
+
+        // Mirror JSON generation: per-source limits and countries need one rule per IP family
+        for family in Self::rule_families(rule, &refs, &sources, &destinations) {
+            let sources: Vec<_> = sources
+                .iter()
+                .filter(|ip| family.is_none_or(|f| Self::address_family(ip.is_ipv6()) == f))
+                .collect();
+            let destinations: Vec<_> = destinations
+                .iter()
+                .filter(|ip| family.is_none_or(|f| Self::address_family(ip.is_ipv6()) == f))
+                .collect();
             let _ = write!(out, "        ");
+            if let Some(family) = family.filter(|_| {
+                refs.family().is_none()
+                    && sources.is_empty()
+                    && destinations.is_empty()
+                    && rule.countries.is_empty()
+            }) {
+                let nfproto = if family == SetType::Ipv6Addr {
+                    "ipv6"
+                } else {
+                    "ipv4"
+                };
+                let _ = write!(out, "meta nfproto {nfproto} ");
+            }
             // Source IP filtering - show all sources (may be mixed IPv4/IPv6)
             // Note: JSON generation splits by IP version, text preview shows simplified
-            if !rule.sources.is_empty() {
-                let ipv4_sources: Vec<_> = rule.sources.iter().filter(|s| s.is_ipv4()).collect();
-                let ipv6_sources: Vec<_> = rule.sources.iter().filter(|s| s.is_ipv6()).collect();
+            let negate = rule.negate;
+            let saddr_op = Negation::text_op(negate.sources);
+            let daddr_op = Negation::text_op(negate.destinations);
+            let dport_op = Negation::text_op(negate.ports);
+            let sport_op = Negation::text_op(negate.source_ports);
+            if let Some(set) = refs.source {
+                let _ = write!(
+                    out,
+                    "{} saddr {saddr_op}@{} ",
+                    set.set_type.address_protocol(),
+                    set.name
+                );
+            } else if let Some(family) = family.filter(|_| !rule.countries.is_empty()) {
+                let _ = write!(
+                    out,
+                    "{} saddr {saddr_op}@{} ",
+                    family.address_protocol(),
+                    crate::core::geoip::geo_set_name(&rule.countries, family == SetType::Ipv6Addr)
+                );
+            } else if !sources.is_empty() {
+                let ipv4_sources: Vec<_> = sources.iter().filter(|s| s.is_ipv4()).collect();
+                let ipv6_sources: Vec<_> = sources.iter().filter(|s| s.is_ipv6()).collect();
 
                 if !ipv4_sources.is_empty() {
                     if ipv4_sources.len() == 1 {
-                        let _ = write!(out, "ip saddr {} ", ipv4_sources[0]);
+                        let _ = write!(out, "ip saddr {saddr_op}{} ", ipv4_sources[0]);
                     } else {
                         let addrs = ipv4_sources
                             .iter()
                             .map(ToString::to_string)
                             .collect::<Vec<_>>()
                             .join(", ");
-                        let _ = write!(out, "ip saddr {{ {addrs} }} ");
+                        let _ = write!(out, "ip saddr {saddr_op}{{ {addrs} }} ");
                     }
                 }
                 if !ipv6_sources.is_empty() {
                     if ipv6_sources.len() == 1 {
-                        let _ = write!(out, "ip6 saddr {} ", ipv6_sources[0]);
+                        let _ = write!(out, "ip6 saddr {saddr_op}{} ", ipv6_sources[0]);
                     } else {
                         let addrs = ipv6_sources
                             .iter()
                             .map(ToString::to_string)
                             .collect::<Vec<_>>()
                             .join(", ");
-                        let _ = write!(out, "ip6 saddr {{ {addrs} }} ");
+                        let _ = write!(out, "ip6 saddr {saddr_op}{{ {addrs} }} ");
                     }
                 }
             }
             // Destination IP filtering - show all destinations
-            if !rule.destinations.is_empty() {
-                let ipv4_dests: Vec<_> = rule.destinations.iter().filter(|d| d.is_ipv4()).collect();
-                let ipv6_dests: Vec<_> = rule.destinations.iter().filter(|d| d.is_ipv6()).collect();
+            if let Some(set) = refs.destination {
+                let _ = write!(
+                    out,
+                    "{} daddr {daddr_op}@{} ",
+                    set.set_type.address_protocol(),
+                    set.name
+                );
+            } else if !destinations.is_empty() {
+                let ipv4_dests: Vec<_> = destinations.iter().filter(|d| d.is_ipv4()).collect();
+                let ipv6_dests: Vec<_> = destinations.iter().filter(|d| d.is_ipv6()).collect();
 
                 if !ipv4_dests.is_empty() {
                     if ipv4_dests.len() == 1 {
-                        let _ = write!(out, "ip daddr {} ", ipv4_dests[0]);
+                        let _ = write!(out, "ip daddr {daddr_op}{} ", ipv4_dests[0]);
                     } else {
                         let addrs = ipv4_dests
                             .iter()
                             .map(ToString::to_string)
                             .collect::<Vec<_>>()
                             .join(", ");
-                        let _ = write!(out, "ip daddr {{ {addrs} }} ");
+                        let _ = write!(out, "ip daddr {daddr_op}{{ {addrs} }} ");
                     }
                 }
                 if !ipv6_dests.is_empty() {
                     if ipv6_dests.len() == 1 {
-                        let _ = write!(out, "ip6 daddr {} ", ipv6_dests[0]);
+                        let _ = write!(out, "ip6 daddr {daddr_op}{} ", ipv6_dests[0]);
                     } else {
                         let addrs = ipv6_dests
                             .iter()
                             .map(ToString::to_string)
                             .collect::<Vec<_>>()
                             .join(", ");
-                        let _ = write!(out, "ip6 daddr {{ {addrs} }} ");
+                        let _ = write!(out, "ip6 daddr {daddr_op}{{ {addrs} }} ");
                     }
                 }
             }
-            if let Some(ref iface) = rule.interface {
-                let _ = write!(out, "iifname \"{iface}\" ");
+            if !rule.interfaces.is_empty() {
+                let op = Negation::text_op(negate.interface);
+                let _ = write!(
+                    out,
+                    "iifname {op}{} ",
+                    Self::interfaces_text(&rule.interfaces)
+                );
             }
-            if let Some(ref oiface) = rule.output_interface {
-                let _ = write!(out, "oifname \"{oiface}\" ");
+            if !rule.output_interfaces.is_empty() {
+                let op = Negation::text_op(negate.output_interface);
+                let names = Self::interfaces_text(&rule.output_interfaces);
+                let _ = write!(out, "oifname {op}{names} ");
             }
             match rule.protocol {
                 Protocol::Any => {} // No-op
                 Protocol::Tcp | Protocol::Udp => {
                     let _ = write!(out, "{} ", rule.protocol);
-                    if !rule.ports.is_empty() {
+                    if !rule.source_ports.is_empty() {
+                        let ports = Self::ports_text(&rule.source_ports);
+                        let _ = write!(out, "sport {sport_op}{ports} ");
+                    }
+                    if let Some(set) = refs.ports {
+                        let _ = write!(out, "dport {dport_op}@{} ", set.name);
+                    } else if !rule.ports.is_empty() {
                         if rule.ports.len() == 1 {
-                            let _ = write!(out, "dport {} ", rule.ports[0]);
+                            let _ = write!(out, "dport {dport_op}{} ", rule.ports[0]);
                         } else {
                             // Multiple ports - use set syntax
                             let ports_str = rule
@@ -1787,15 +4765,21 @@ impl FirewallRuleset {
                                 .map(ToString::to_string)
                                 .collect::<Vec<_>>()
                                 .join(", ");
-                            let _ = write!(out, "dport {{ {ports_str} }} ");
+                            let _ = write!(out, "dport {dport_op}{{ {ports_str} }} ");
                         }
                     }
                 }
                 Protocol::TcpAndUdp => {
                     let _ = write!(out, "meta l4proto {{ tcp, udp }} ");
-                    if !rule.ports.is_empty() {
+                    if !rule.source_ports.is_empty() {
+                        let ports = Self::ports_text(&rule.source_ports);
+                        let _ = write!(out, "th sport {sport_op}{ports} ");
+                    }
+                    if let Some(set) = refs.ports {
+                        let _ = write!(out, "th dport {dport_op}@{} ", set.name);
+                    } else if !rule.ports.is_empty() {
                         if rule.ports.len() == 1 {
-                            let _ = write!(out, "th dport {} ", rule.ports[0]);
+                            let _ = write!(out, "th dport {dport_op}{} ", rule.ports[0]);
                         } else {
                             let ports_str = rule
                                 .ports
@@ -1803,7 +4787,7 @@ impl FirewallRuleset {
                                 .map(ToString::to_string)
                                 .collect::<Vec<_>>()
                                 .join(", ");
-                            let _ = write!(out, "th dport {{ {ports_str} }} ");
+                            let _ = write!(out, "th dport {dport_op}{{ {ports_str} }} ");
                         }
                     }
                 }
@@ -1818,26 +4802,17 @@ impl FirewallRuleset {
                     let _ = write!(out, "meta l4proto {{ icmp, ipv6-icmp }} ");
                 }
             }
-            // Advanced options: rate limiting (with optional burst)
-            if let Some(rate_limit) = rule.rate_limit {
-                if let Some(burst) = rate_limit.burst {
-                    let _ = write!(
-                        out,
-                        "limit rate {}/{} burst {} packets ",
-                        rate_limit.count, rate_limit.unit, burst
-                    );
+            if let Some((protocol, names)) = &icmp_types {
+                if names.len() == 1 {
+                    let _ = write!(out, "{protocol} type {} ", names[0]);
+                    if let Some(code) = rule.icmp_code {
+                        let _ = write!(out, "{protocol} code {code} ");
+                    }
                 } else {
-                    let _ = write!(out, "limit rate {}/{} ", rate_limit.count, rate_limit.unit);
+                    let _ = write!(out, "{protocol} type {{ {} }} ", names.join(", "));
                 }
             }
-            // Advanced options: connection limiting
-            if rule.connection_limit > 0 {
-                let _ = write!(out, "ct count {} ", rule.connection_limit);
-            }
-            // Per-rule logging (before action)
-            if rule.log_enabled {
-                let _ = write!(out, "log prefix \"{}\" level info ", rule.log_prefix);
-            }
+            Self::write_rule_options_text(out, rule, family, counters);
             // Action (with optional reject type)
             match rule.action {
                 Action::Accept => {
@@ -1864,11 +4839,282 @@ impl FirewallRuleset {
                     }
                 },
             }
-            if !rule.label.is_empty() {
-                let _ = write!(out, " comment \"{}\"", rule.label);
+            if let Some(comment) = Self::user_rule_comment(rule, counters) {
+                let _ = write!(out, " comment \"{comment}\"");
+            }
+            let _ = writeln!(out);
+        }
+    }
+
+    /// IP families to write a user rule for: `[None]` (one line) unless the rule tracks
+    /// limits per source, filters by country or negates an address list and isn't
+    /// pinned to a family, like the JSON variants
+    fn rule_families(
+        rule: &Rule,
+        refs: &RuleSetRefs<'_>,
+        sources: &[&AddressEntry],
+        destinations: &[&AddressEntry],
+    ) -> Vec<Option<SetType>> {
+        let negated_addresses = (rule.negate.sources && !sources.is_empty())
+            || (rule.negate.destinations && !destinations.is_empty());
+        if rule.per_source.is_none() && rule.countries.is_empty() && !negated_addresses {
+            return vec![None];
+        }
+        if let Some(family) = refs.family() {
+            return vec![Some(family)];
+        }
+        let sources: Vec<AddressEntry> = sources.iter().map(|ip| (**ip).clone()).collect();
+        let destinations: Vec<AddressEntry> =
+            destinations.iter().map(|ip| (**ip).clone()).collect();
+        [SetType::Ipv4Addr, SetType::Ipv6Addr]
+            .into_iter()
+            .filter(|&family| {
+                let family_sources = Self::family_addresses(&sources, family, rule.negate.sources);
+                let family_dests =
+                    Self::family_addresses(&destinations, family, rule.negate.destinations);
+                match (family_sources, family_dests) {
+                    (Some(s), Some(d)) => {
+                        !(s.is_empty() && d.is_empty())
+                            || Self::protocol_allows_family(rule.protocol, family)
+                    }
+                    _ => false,
+                }
+            })
+            .map(Some)
+            .collect()
+    }
+
+    /// Writes a rule's ct state, schedule, rate limit, quota, connection limit, log and
+    /// counter options (before the verdict)
+    fn write_rule_options_text(
+        out: &mut String,
+        rule: &Rule,
+        family: Option<SetType>,
+        counter: bool,
+    ) {
+        use std::fmt::Write;
+        // Connection state
+        if rule.ct_states.len() == 1 {
+            let _ = write!(out, "ct state {} ", rule.ct_states[0]);
+        } else if !rule.ct_states.is_empty() {
+            let states: Vec<&str> = rule.ct_states.iter().map(AsRef::as_ref).collect();
+            let _ = write!(out, "ct state {{ {} }} ", states.join(", "));
+        }
+        // Schedule
+        if let Some(ref schedule) = rule.schedule {
+            if schedule.has_time_window() {
+                let (op, from, to) = if schedule.wraps_midnight() {
+                    ("!= ", schedule.end, schedule.start)
+                } else {
+                    ("", schedule.start, schedule.end)
+                };
+                let _ = write!(
+                    out,
+                    "meta hour {op}\"{}\"-\"{}\" ",
+                    from.format("%H:%M"),
+                    to.format("%H:%M")
+                );
+            }
+            if schedule.days.len() == 1 {
+                let _ = write!(out, "meta day \"{}\" ", schedule.days[0]);
+            } else if !schedule.days.is_empty() {
+                let days: Vec<_> = schedule.days.iter().map(|d| format!("\"{d}\"")).collect();
+                let _ = write!(out, "meta day {{ {} }} ", days.join(", "));
+            }
+        }
+        // Advanced options: rate limiting (with optional burst)
+        if let Some(rate_limit) = rule.rate_limit {
+            let over = if rate_limit.over { "over " } else { "" };
+            let limit = if let Some(bytes) = rate_limit.bytes {
+                let (rate, unit) = bytes.nft_value(u64::from(rate_limit.count));
+                let mut limit = format!("limit rate {over}{rate} {unit}/{} ", rate_limit.unit);
+                if let Some(burst) = rate_limit.burst.filter(|&b| b > 0) {
+                    let (burst, unit) = bytes.nft_value(u64::from(burst));
+                    let _ = write!(limit, "burst {burst} {unit} ");
+                }
+                limit
+            } else {
+                let mut limit =
+                    format!("limit rate {over}{}/{} ", rate_limit.count, rate_limit.unit);
+                if let Some(burst) = rate_limit.burst.filter(|&b| b > 0) {
+                    let _ = write!(limit, "burst {burst} packets ");
+                }
+                limit
+            };
+            Self::write_per_source_text(out, rule, Meter::Rate, family, &limit);
+        }
+        // Advanced options: byte quota (shared by all variants of the rule)
+        if rule.quota.is_some() {
+            let _ = write!(out, "quota name \"{}\" ", Self::quota_name(rule));
+        }
+        // Advanced options: connection limiting
+        if rule.connection_limit > 0 {
+            let count = format!("ct count {} ", rule.connection_limit);
+            Self::write_per_source_text(out, rule, Meter::Conn, family, &count);
+        }
+        // Per-rule logging (before action)
+        if rule.log_enabled {
+            let _ = write!(out, "log prefix \"{}\" level info ", rule.log_prefix);
+        }
+        if counter {
+            let _ = write!(out, "counter ");
+        }
+    }
+
+    /// Writes a limit statement, wrapped in a per-source set update if the rule tracks
+    /// its limits per source (mirrors `per_source_json`)
+    fn write_per_source_text(
+        out: &mut String,
+        rule: &Rule,
+        meter: Meter,
+        family: Option<SetType>,
+        stmt: &str,
+    ) {
+        use std::fmt::Write;
+        match family.filter(|_| rule.per_source.is_some()) {
+            Some(family) => {
+                let _ = write!(
+                    out,
+                    "{} @{} {{ {} saddr {stmt}}} ",
+                    meter.op(),
+                    Self::meter_set_name(rule, meter, family),
+                    family.address_protocol()
+                );
+            }
+            None => out.push_str(stmt),
+        }
+    }
+
+    /// Formats port entries for text output (`22` or `{ 22, 8000-8080 }`)
+    fn ports_text(ports: &[PortEntry]) -> String {
+        if ports.len() == 1 {
+            ports[0].to_string()
+        } else {
+            let ports_str = ports
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{{ {ports_str} }}")
+        }
+    }
+
+    /// Formats interface names for text output (`"wlan*"` or `{ "eth0", "wlan0" }`)
+    fn interfaces_text(interfaces: &[String]) -> String {
+        let names = interfaces
+            .iter()
+            .map(|iface| format!("\"{iface}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if interfaces.len() == 1 {
+            names
+        } else {
+            format!("{{ {names} }}")
+        }
+    }
+
+    /// Writes the port-forward rules of the nat `prerouting` chain (`nat == true`)
+    /// or the matching forward-chain accepts. Mirrors `add_port_forward`.
+    fn write_port_forwards_text(
+        &self,
+        out: &mut String,
+        nat: bool,
+        now: chrono::DateTime<chrono::Utc>,
+    ) {
+        use std::fmt::Write;
+        let counters = self.advanced_security.rule_counters;
+
+        for rule in &self.rules {
+            let Some(forward) = rule.port_forward.filter(|_| self.is_rule_active(rule, now)) else {
+                continue;
+            };
+            let Some(refs) = RuleSetRefs::resolve(rule, &self.sets) else {
+                continue;
+            };
+            let Some(sources) = Self::port_forward_sources(rule, forward, &refs) else {
+                continue;
+            };
+            let family = forward.address_protocol();
+
+            let _ = write!(
+                out,
+                "        meta nfproto {} ",
+                if family == "ip6" { "ipv6" } else { "ipv4" }
+            );
+            let iif_op = Negation::text_op(rule.negate.interface);
+            let saddr_op = Negation::text_op(rule.negate.sources);
+            if !rule.interfaces.is_empty() {
+                let names = Self::interfaces_text(&rule.interfaces);
+                let _ = write!(out, "iifname {iif_op}{names} ");
+            }
+            if let Some(set) = refs.source {
+                let _ = write!(out, "{family} saddr {saddr_op}@{} ", set.name);
+            } else if sources.len() == 1 {
+                let _ = write!(out, "{family} saddr {saddr_op}{} ", sources[0]);
+            } else if !sources.is_empty() {
+                let addrs = sources
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let _ = write!(out, "{family} saddr {saddr_op}{{ {addrs} }} ");
+            }
+            let (sport, dport) = if rule.protocol == Protocol::TcpAndUdp {
+                let _ = write!(out, "meta l4proto {{ tcp, udp }} ");
+                ("th sport", "th dport")
+            } else {
+                let _ = write!(out, "meta l4proto {} ", rule.protocol);
+                if rule.protocol == Protocol::Tcp {
+                    ("tcp sport", "tcp dport")
+                } else {
+                    ("udp sport", "udp dport")
+                }
+            };
+            if !rule.source_ports.is_empty() {
+                let op = Negation::text_op(rule.negate.source_ports);
+                let _ = write!(out, "{sport} {op}{} ", Self::ports_text(&rule.source_ports));
+            }
+            let external_ports = if let Some(set) = refs.ports {
+                Some(format!("@{}", set.name))
+            } else if rule.ports.len() == 1 {
+                Some(rule.ports[0].to_string())
+            } else if rule.ports.is_empty() {
+                None
+            } else {
+                let ports_str = rule
+                    .ports
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("{{ {ports_str} }}"))
+            };
+
+            if nat {
+                if let Some(ports) = external_ports {
+                    let _ = write!(out, "{dport} {ports} ");
+                }
+                let _ = write!(out, "dnat {family} to {forward}");
+            } else {
+                let _ = write!(out, "{family} daddr {} ", forward.to_address);
+                if let Some(port) = forward.to_port {
+                    let _ = write!(out, "{dport} {port} ");
+                } else if let Some(ports) = external_ports {
+                    let _ = write!(out, "{dport} {ports} ");
+                }
+                let _ = write!(out, "ct status dnat ");
+                Self::write_rule_options_text(
+                    out,
+                    rule,
+                    Some(Self::address_family(forward.to_address.is_ipv6())),
+                    counters,
+                );
+                let _ = write!(out, "accept");
+            }
+            if let Some(comment) = Self::user_rule_comment(rule, counters) {
+                let _ = write!(out, " comment \"{comment}\"");
             }
             let _ = writeln!(out);
         }
-        let _ = writeln!(out);
     }
 }
@@ -0,0 +1,921 @@
+//! Country-based filtering from a local IP geolocation database
+//!
+//! Rules can match source addresses by country (`Rule::countries`, ISO 3166
+//! alpha-2 codes). Each distinct country selection becomes a pair of interval
+//! sets (`drfw_geo_{codes}_v4`/`_v6`) in the generated ruleset; like blocklist
+//! entries, the ranges are not part of the ruleset itself but appended to the
+//! apply payload.
+//!
+//! Ranges are read from the database file configured in `AppConfig`:
+//! - MMDB (`.mmdb`, e.g. `GeoLite2-Country` or DB-IP Lite), read with the
+//!   `maxminddb` crate
+//! - Country CSV with `cidr,CC` or `start,end,CC` lines (DB-IP style)
+//!
+//! The merged ranges of the referenced countries are cached in `geoip.json` in
+//! the state directory together with the database path and modification time,
+//! so applies only re-read the database when it changed. `drfw geoip rebuild`
+//! forces a rebuild and replaces the live set contents.
+
+use crate::core::blocklists::{
+    MergedRanges, interval_set_json, merge_address_ranges, range_elements,
+};
+use crate::core::error::{Error, Result};
+use crate::core::firewall::FirewallRuleset;
+use crate::utils::get_state_dir;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::path::{Path, PathBuf};
+
+/// Maximum size of a database file (country databases are a few dozen MB)
+const MAX_DATABASE_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Marks the start of the metadata section of an MMDB file
+const METADATA_MARKER: &[u8] = b"\xAB\xCD\xEFMaxMind.com";
+
+/// Address ranges per country code, read from a database
+type CountryRanges = BTreeMap<String, Vec<(IpAddr, IpAddr)>>;
+
+/// A country for the rule form picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Country {
+    pub code: &'static str,
+    pub name: &'static str,
+}
+
+impl Country {
+    const fn new(code: &'static str, name: &'static str) -> Self {
+        Self { code, name }
+    }
+}
+
+impl std::fmt::Display for Country {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.name, self.code)
+    }
+}
+
+/// Returns the display name of a country code, if it is a known ISO 3166 code
+pub fn country_name(code: &str) -> Option<&'static str> {
+    COUNTRIES.iter().find(|c| c.code == code).map(|c| c.name)
+}
+
+/// Normalizes a rule's country selection: uppercase, sorted, without duplicates
+pub fn normalize_countries(countries: &[String]) -> Vec<String> {
+    let codes: BTreeSet<String> = countries.iter().map(|c| c.to_ascii_uppercase()).collect();
+    codes.into_iter().collect()
+}
+
+/// Name of the interval set for a country selection (reserved `drfw_` prefix)
+///
+/// Rules selecting the same countries share a set, regardless of order.
+pub fn geo_set_name(countries: &[String], ipv6: bool) -> String {
+    let codes = normalize_countries(countries)
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    let suffix = if ipv6 { "v6" } else { "v4" };
+    format!("drfw_geo_{codes}_{suffix}")
+}
+
+/// Distinct (normalized) country selections of the rules active at `now`
+pub fn country_groups(ruleset: &FirewallRuleset, now: DateTime<Utc>) -> BTreeSet<Vec<String>> {
+    ruleset
+        .rules
+        .iter()
+        .filter(|r| !r.countries.is_empty() && ruleset.is_rule_active(r, now))
+        .map(|r| normalize_countries(&r.countries))
+        .collect()
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::Validation {
+        field: "geoip".to_string(),
+        message: message.into(),
+    }
+}
+
+// ============================================================================
+// Database readers
+// ============================================================================
+
+/// Returns an IPv4 network for a network of the IPv4 subtree of an IPv6
+/// database (`::192.0.2.0/120` becomes `192.0.2.0/24`)
+fn ipv4_subtree_network(net: ipnetwork::IpNetwork) -> ipnetwork::IpNetwork {
+    match net {
+        ipnetwork::IpNetwork::V6(v6) if v6.prefix() >= 96 => {
+            match u32::try_from(u128::from(v6.network())) {
+                Ok(bits) => {
+                    ipnetwork::IpNetwork::new(Ipv4Addr::from(bits).into(), v6.prefix() - 96)
+                        .unwrap_or(net)
+                }
+                Err(_) => net,
+            }
+        }
+        _ => net,
+    }
+}
+
+/// Reads the ranges of the wanted countries from an MMDB file
+fn parse_mmdb(bytes: &[u8], wanted: &BTreeSet<String>) -> Result<CountryRanges> {
+    let mmdb_error = |e: maxminddb::MaxMindDBError| invalid(format!("Invalid MaxMind DB: {e}"));
+
+    let reader = maxminddb::Reader::from_source(bytes).map_err(mmdb_error)?;
+    let all = if reader.metadata.ip_version == 4 {
+        "0.0.0.0/0"
+    } else {
+        "::/0"
+    };
+    let networks = reader
+        .within::<maxminddb::geoip2::Country>(
+            all.parse()
+                .map_err(|_| invalid("Invalid MaxMind DB network"))?,
+        )
+        .map_err(mmdb_error)?;
+
+    let mut out: CountryRanges = wanted.iter().map(|c| (c.clone(), Vec::new())).collect();
+    for item in networks {
+        let item = item.map_err(mmdb_error)?;
+        let Some(code) = item.info.country.and_then(|c| c.iso_code) else {
+            continue;
+        };
+        let Some(ranges) = out.get_mut(&code.to_ascii_uppercase()) else {
+            continue;
+        };
+        // The crate yields its own `IpNetwork` version, so convert through the parts
+        if let Ok(net) = ipnetwork::IpNetwork::new(item.ip_net.network(), item.ip_net.prefix()) {
+            ranges.push(network_range(ipv4_subtree_network(net)));
+        }
+    }
+    Ok(out)
+}
+
+/// Reads the ranges of the wanted countries from a country CSV file.
+///
+/// Accepts `cidr,CC` and `start,end,CC` lines (quoted or not); headers and
+/// other lines that don't parse are skipped.
+pub fn parse_csv(text: &str, wanted: &BTreeSet<String>) -> BTreeMap<String, Vec<(IpAddr, IpAddr)>> {
+    let mut out: CountryRanges = wanted.iter().map(|c| (c.clone(), Vec::new())).collect();
+
+    for line in text.lines() {
+        let fields: Vec<&str> = line
+            .split(',')
+            .map(|f| f.trim().trim_matches('"').trim())
+            .collect();
+        let parsed = match fields.as_slice() {
+            [start, end, country, ..] if start.parse::<IpAddr>().is_ok() => start
+                .parse::<IpAddr>()
+                .ok()
+                .zip(end.parse::<IpAddr>().ok())
+                .map(|range| (range, *country)),
+            [network, country, ..] => network
+                .parse::<ipnetwork::IpNetwork>()
+                .ok()
+                .map(|net| (network_range(net), *country)),
+            _ => None,
+        };
+        let Some((range, country)) = parsed else {
+            continue;
+        };
+        if let Some(ranges) = out.get_mut(&country.to_ascii_uppercase()) {
+            ranges.push(range);
+        }
+    }
+    out
+}
+
+fn network_range(net: ipnetwork::IpNetwork) -> (IpAddr, IpAddr) {
+    match net {
+        ipnetwork::IpNetwork::V4(net) => (IpAddr::V4(net.network()), IpAddr::V4(net.broadcast())),
+        ipnetwork::IpNetwork::V6(net) => {
+            let host_mask = u128::MAX.checked_shr(u32::from(net.prefix())).unwrap_or(0);
+            (
+                IpAddr::V6(net.network()),
+                IpAddr::V6(Ipv6Addr::from(u128::from(net.network()) | host_mask)),
+            )
+        }
+    }
+}
+
+/// Reads the merged ranges of the wanted countries from a database file's contents
+pub fn read_database(
+    bytes: &[u8],
+    wanted: &BTreeSet<String>,
+) -> Result<BTreeMap<String, MergedRanges>> {
+    let ranges = if bytes
+        .windows(METADATA_MARKER.len())
+        .any(|w| w == METADATA_MARKER)
+    {
+        parse_mmdb(bytes, wanted)?
+    } else {
+        parse_csv(&String::from_utf8_lossy(bytes), wanted)
+    };
+    Ok(ranges
+        .into_iter()
+        .map(|(country, ranges)| (country, merge_address_ranges(ranges)))
+        .collect())
+}
+
+// ============================================================================
+// Cache and set elements
+// ============================================================================
+
+/// Country ranges built from a database file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeoIpCache {
+    pub database: PathBuf,
+    /// Modification time of the database when the ranges were built
+    pub database_modified: Option<DateTime<Utc>>,
+    pub built_at: DateTime<Utc>,
+    pub countries: BTreeMap<String, MergedRanges>,
+}
+
+impl GeoIpCache {
+    /// Total number of ranges across all countries
+    pub fn range_count(&self) -> usize {
+        self.countries.values().map(MergedRanges::len).sum()
+    }
+
+    /// Creates `add element` commands for the sets of the given country selections
+    fn element_commands(&self, groups: &BTreeSet<Vec<String>>) -> Vec<Value> {
+        let mut commands = Vec::new();
+        for group in groups {
+            let ranges = group
+                .iter()
+                .filter_map(|country| self.countries.get(country))
+                .flat_map(|merged| {
+                    let v4 = merged
+                        .v4
+                        .iter()
+                        .map(|&(s, e)| (IpAddr::V4(s), IpAddr::V4(e)));
+                    let v6 = merged
+                        .v6
+                        .iter()
+                        .map(|&(s, e)| (IpAddr::V6(s), IpAddr::V6(e)));
+                    v4.chain(v6)
+                });
+            let merged = merge_address_ranges(ranges);
+            for (ipv6, elements) in [
+                (false, range_elements(&merged.v4)),
+                (true, range_elements(&merged.v6)),
+            ] {
+                if elements.is_empty() {
+                    continue;
+                }
+                commands.push(json!({
+                    "add": {
+                        "element": {
+                            "family": "inet",
+                            "table": "drfw",
+                            "name": geo_set_name(group, ipv6),
+                            "elem": elements
+                        }
+                    }
+                }));
+            }
+        }
+        commands
+    }
+}
+
+/// Returns the path of the country range cache
+fn cache_path() -> Result<PathBuf> {
+    let mut path = get_state_dir()
+        .ok_or_else(|| Error::Internal("Failed to get state directory".to_string()))?;
+    path.push("geoip.json");
+    Ok(path)
+}
+
+/// Loads the country range cache (`None` if nothing was built yet).
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+pub async fn load_cache() -> Result<Option<GeoIpCache>> {
+    let path = cache_path()?;
+    if !tokio::fs::try_exists(&path).await? {
+        return Ok(None);
+    }
+    let json = tokio::fs::read_to_string(&path).await?;
+    Ok(Some(serde_json::from_str(&json)?))
+}
+
+/// Saves the country range cache using the same atomic write pattern as the ban list.
+///
+/// # Async
+/// Uses `tokio::fs` for non-blocking file I/O.
+async fn save_cache(cache: &GeoIpCache) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let path = cache_path()?;
+    crate::utils::ensure_dirs()?;
+
+    let json = serde_json::to_string(cache)?;
+    let temp_path = path.with_extension("json.tmp");
+
+    #[cfg(unix)]
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .mode(0o600) // User read/write only
+        .open(&temp_path)
+        .await?;
+
+    #[cfg(not(unix))]
+    let mut file = tokio::fs::File::create(&temp_path).await?;
+
+    file.write_all(json.as_bytes()).await?;
+    file.sync_all().await?;
+
+    tokio::fs::rename(temp_path, path).await?;
+    Ok(())
+}
+
+/// Returns the modification time of the database file
+async fn database_modified(database: &Path) -> Result<Option<DateTime<Utc>>> {
+    let metadata = tokio::fs::metadata(database)
+        .await
+        .map_err(|e| invalid(format!("{}: {e}", database.display())))?;
+    if metadata.len() > MAX_DATABASE_BYTES {
+        return Err(invalid(format!(
+            "{}: file too large (max {} MB)",
+            database.display(),
+            MAX_DATABASE_BYTES / (1024 * 1024)
+        )));
+    }
+    Ok(metadata.modified().ok().map(DateTime::<Utc>::from))
+}
+
+/// Reads the ranges of `countries` from the database and replaces the cache.
+///
+/// Parsing runs on a blocking thread: a country database has around a million
+/// tree nodes.
+pub async fn build(database: &Path, countries: BTreeSet<String>) -> Result<GeoIpCache> {
+    let database_modified = database_modified(database).await?;
+    let bytes = tokio::fs::read(database)
+        .await
+        .map_err(|e| invalid(format!("{}: {e}", database.display())))?;
+    let countries = tokio::task::spawn_blocking(move || read_database(&bytes, &countries))
+        .await
+        .map_err(|e| Error::Internal(format!("GeoIP database reader failed: {e}")))??;
+
+    let cache = GeoIpCache {
+        database: database.to_path_buf(),
+        database_modified,
+        built_at: Utc::now(),
+        countries,
+    };
+    save_cache(&cache).await?;
+    Ok(cache)
+}
+
+/// Returns the cached ranges of `countries`, rebuilding them if the database
+/// path or modification time changed or a country was not built yet.
+pub async fn load_or_build(database: &Path, countries: &BTreeSet<String>) -> Result<GeoIpCache> {
+    let modified = database_modified(database).await?;
+    if let Ok(Some(cache)) = load_cache().await
+        && cache.database == database
+        && cache.database_modified == modified
+        && countries.iter().all(|c| cache.countries.contains_key(c))
+    {
+        return Ok(cache);
+    }
+    build(database, countries.clone()).await
+}
+
+/// Countries referenced by the given selections
+fn referenced_countries(groups: &BTreeSet<Vec<String>>) -> BTreeSet<String> {
+    groups.iter().flatten().cloned().collect()
+}
+
+/// Returns `flush set` commands for both country sets of each selection
+fn flush_commands(groups: &BTreeSet<Vec<String>>) -> Vec<Value> {
+    groups
+        .iter()
+        .flat_map(|group| [false, true].map(|ipv6| geo_set_name(group, ipv6)))
+        .map(|name| json!({ "flush": { "set": { "family": "inet", "table": "drfw", "name": name } } }))
+        .collect()
+}
+
+/// Appends the country ranges of the given selections to an apply payload.
+///
+/// Must be called on the output of `to_nftables_json()` (which creates the sets).
+/// Fails if countries are used without a configured database: applying empty
+/// sets would silently change what the rules match. `flush table` keeps set
+/// elements, so the sets are flushed first: ranges of removed countries or from
+/// an older database are dropped, and cannot overlap the new intervals.
+pub async fn append_geoip_elements(
+    json_payload: &mut Value,
+    groups: &BTreeSet<Vec<String>>,
+    database: Option<&Path>,
+) -> Result<()> {
+    if groups.is_empty() {
+        return Ok(());
+    }
+    let database = database.ok_or_else(|| {
+        invalid("Rules filter by country but no GeoIP database is configured (Settings)")
+    })?;
+    let cache = load_or_build(database, &referenced_countries(groups)).await?;
+    if let Some(nft_rules) = json_payload["nftables"].as_array_mut() {
+        nft_rules.extend(flush_commands(groups));
+        nft_rules.extend(cache.element_commands(groups));
+    }
+    Ok(())
+}
+
+/// Rebuilds the country ranges from the database and replaces the contents of
+/// the live country sets (no ruleset re-apply).
+pub async fn rebuild_live_sets(
+    groups: &BTreeSet<Vec<String>>,
+    database: &Path,
+) -> Result<GeoIpCache> {
+    let cache = build(database, referenced_countries(groups)).await?;
+
+    let mut batch = vec![json!({ "add": { "table": { "family": "inet", "name": "drfw" } } })];
+    for group in groups {
+        for (ipv6, set_type) in [(false, "ipv4_addr"), (true, "ipv6_addr")] {
+            batch.push(interval_set_json(&geo_set_name(group, ipv6), set_type));
+        }
+    }
+    batch.extend(flush_commands(groups));
+    batch.extend(cache.element_commands(groups));
+    crate::core::bans::run_element_batch(&json!({ "nftables": batch })).await?;
+    Ok(cache)
+}
+
+/// ISO 3166-1 alpha-2 countries for the rule form picker
+pub const COUNTRIES: &[Country] = &[
+    Country::new("AD", "Andorra"),
+    Country::new("AE", "United Arab Emirates"),
+    Country::new("AF", "Afghanistan"),
+    Country::new("AG", "Antigua and Barbuda"),
+    Country::new("AI", "Anguilla"),
+    Country::new("AL", "Albania"),
+    Country::new("AM", "Armenia"),
+    Country::new("AO", "Angola"),
+    Country::new("AQ", "Antarctica"),
+    Country::new("AR", "Argentina"),
+    Country::new("AS", "American Samoa"),
+    Country::new("AT", "Austria"),
+    Country::new("AU", "Australia"),
+    Country::new("AW", "Aruba"),
+    Country::new("AX", "Åland Islands"),
+    Country::new("AZ", "Azerbaijan"),
+    Country::new("BA", "Bosnia and Herzegovina"),
+    Country::new("BB", "Barbados"),
+    Country::new("BD", "Bangladesh"),
+    Country::new("BE", "Belgium"),
+    Country::new("BF", "Burkina Faso"),
+    Country::new("BG", "Bulgaria"),
+    Country::new("BH", "Bahrain"),
+    Country::new("BI", "Burundi"),
+    Country::new("BJ", "Benin"),
+    Country::new("BL", "Saint Barthélemy"),
+    Country::new("BM", "Bermuda"),
+    Country::new("BN", "Brunei"),
+    Country::new("BO", "Bolivia"),
+    Country::new("BQ", "Caribbean Netherlands"),
+    Country::new("BR", "Brazil"),
+    Country::new("BS", "Bahamas"),
+    Country::new("BT", "Bhutan"),
+    Country::new("BV", "Bouvet Island"),
+    Country::new("BW", "Botswana"),
+    Country::new("BY", "Belarus"),
+    Country::new("BZ", "Belize"),
+    Country::new("CA", "Canada"),
+    Country::new("CC", "Cocos (Keeling) Islands"),
+    Country::new("CD", "DR Congo"),
+    Country::new("CF", "Central African Republic"),
+    Country::new("CG", "Congo"),
+    Country::new("CH", "Switzerland"),
+    Country::new("CI", "Côte d'Ivoire"),
+    Country::new("CK", "Cook Islands"),
+    Country::new("CL", "Chile"),
+    Country::new("CM", "Cameroon"),
+    Country::new("CN", "China"),
+    Country::new("CO", "Colombia"),
+    Country::new("CR", "Costa Rica"),
+    Country::new("CU", "Cuba"),
+    Country::new("CV", "Cabo Verde"),
+    Country::new("CW", "Curaçao"),
+    Country::new("CX", "Christmas Island"),
+    Country::new("CY", "Cyprus"),
+    Country::new("CZ", "Czechia"),
+    Country::new("DE", "Germany"),
+    Country::new("DJ", "Djibouti"),
+    Country::new("DK", "Denmark"),
+    Country::new("DM", "Dominica"),
+    Country::new("DO", "Dominican Republic"),
+    Country::new("DZ", "Algeria"),
+    Country::new("EC", "Ecuador"),
+    Country::new("EE", "Estonia"),
+    Country::new("EG", "Egypt"),
+    Country::new("EH", "Western Sahara"),
+    Country::new("ER", "Eritrea"),
+    Country::new("ES", "Spain"),
+    Country::new("ET", "Ethiopia"),
+    Country::new("FI", "Finland"),
+    Country::new("FJ", "Fiji"),
+    Country::new("FK", "Falkland Islands"),
+    Country::new("FM", "Micronesia"),
+    Country::new("FO", "Faroe Islands"),
+    Country::new("FR", "France"),
+    Country::new("GA", "Gabon"),
+    Country::new("GB", "United Kingdom"),
+    Country::new("GD", "Grenada"),
+    Country::new("GE", "Georgia"),
+    Country::new("GF", "French Guiana"),
+    Country::new("GG", "Guernsey"),
+    Country::new("GH", "Ghana"),
+    Country::new("GI", "Gibraltar"),
+    Country::new("GL", "Greenland"),
+    Country::new("GM", "Gambia"),
+    Country::new("GN", "Guinea"),
+    Country::new("GP", "Guadeloupe"),
+    Country::new("GQ", "Equatorial Guinea"),
+    Country::new("GR", "Greece"),
+    Country::new("GS", "South Georgia and the South Sandwich Islands"),
+    Country::new("GT", "Guatemala"),
+    Country::new("GU", "Guam"),
+    Country::new("GW", "Guinea-Bissau"),
+    Country::new("GY", "Guyana"),
+    Country::new("HK", "Hong Kong"),
+    Country::new("HM", "Heard Island and McDonald Islands"),
+    Country::new("HN", "Honduras"),
+    Country::new("HR", "Croatia"),
+    Country::new("HT", "Haiti"),
+    Country::new("HU", "Hungary"),
+    Country::new("ID", "Indonesia"),
+    Country::new("IE", "Ireland"),
+    Country::new("IL", "Israel"),
+    Country::new("IM", "Isle of Man"),
+    Country::new("IN", "India"),
+    Country::new("IO", "British Indian Ocean Territory"),
+    Country::new("IQ", "Iraq"),
+    Country::new("IR", "Iran"),
+    Country::new("IS", "Iceland"),
+    Country::new("IT", "Italy"),
+    Country::new("JE", "Jersey"),
+    Country::new("JM", "Jamaica"),
+    Country::new("JO", "Jordan"),
+    Country::new("JP", "Japan"),
+    Country::new("KE", "Kenya"),
+    Country::new("KG", "Kyrgyzstan"),
+    Country::new("KH", "Cambodia"),
+    Country::new("KI", "Kiribati"),
+    Country::new("KM", "Comoros"),
+    Country::new("KN", "Saint Kitts and Nevis"),
+    Country::new("KP", "North Korea"),
+    Country::new("KR", "South Korea"),
+    Country::new("KW", "Kuwait"),
+    Country::new("KY", "Cayman Islands"),
+    Country::new("KZ", "Kazakhstan"),
+    Country::new("LA", "Laos"),
+    Country::new("LB", "Lebanon"),
+    Country::new("LC", "Saint Lucia"),
+    Country::new("LI", "Liechtenstein"),
+    Country::new("LK", "Sri Lanka"),
+    Country::new("LR", "Liberia"),
+    Country::new("LS", "Lesotho"),
+    Country::new("LT", "Lithuania"),
+    Country::new("LU", "Luxembourg"),
+    Country::new("LV", "Latvia"),
+    Country::new("LY", "Libya"),
+    Country::new("MA", "Morocco"),
+    Country::new("MC", "Monaco"),
+    Country::new("MD", "Moldova"),
+    Country::new("ME", "Montenegro"),
+    Country::new("MF", "Saint Martin"),
+    Country::new("MG", "Madagascar"),
+    Country::new("MH", "Marshall Islands"),
+    Country::new("MK", "North Macedonia"),
+    Country::new("ML", "Mali"),
+    Country::new("MM", "Myanmar"),
+    Country::new("MN", "Mongolia"),
+    Country::new("MO", "Macao"),
+    Country::new("MP", "Northern Mariana Islands"),
+    Country::new("MQ", "Martinique"),
+    Country::new("MR", "Mauritania"),
+    Country::new("MS", "Montserrat"),
+    Country::new("MT", "Malta"),
+    Country::new("MU", "Mauritius"),
+    Country::new("MV", "Maldives"),
+    Country::new("MW", "Malawi"),
+    Country::new("MX", "Mexico"),
+    Country::new("MY", "Malaysia"),
+    Country::new("MZ", "Mozambique"),
+    Country::new("NA", "Namibia"),
+    Country::new("NC", "New Caledonia"),
+    Country::new("NE", "Niger"),
+    Country::new("NF", "Norfolk Island"),
+    Country::new("NG", "Nigeria"),
+    Country::new("NI", "Nicaragua"),
+    Country::new("NL", "Netherlands"),
+    Country::new("NO", "Norway"),
+    Country::new("NP", "Nepal"),
+    Country::new("NR", "Nauru"),
+    Country::new("NU", "Niue"),
+    Country::new("NZ", "New Zealand"),
+    Country::new("OM", "Oman"),
+    Country::new("PA", "Panama"),
+    Country::new("PE", "Peru"),
+    Country::new("PF", "French Polynesia"),
+    Country::new("PG", "Papua New Guinea"),
+    Country::new("PH", "Philippines"),
+    Country::new("PK", "Pakistan"),
+    Country::new("PL", "Poland"),
+    Country::new("PM", "Saint Pierre and Miquelon"),
+    Country::new("PN", "Pitcairn"),
+    Country::new("PR", "Puerto Rico"),
+    Country::new("PS", "Palestine"),
+    Country::new("PT", "Portugal"),
+    Country::new("PW", "Palau"),
+    Country::new("PY", "Paraguay"),
+    Country::new("QA", "Qatar"),
+    Country::new("RE", "Réunion"),
+    Country::new("RO", "Romania"),
+    Country::new("RS", "Serbia"),
+    Country::new("RU", "Russia"),
+    Country::new("RW", "Rwanda"),
+    Country::new("SA", "Saudi Arabia"),
+    Country::new("SB", "Solomon Islands"),
+    Country::new("SC", "Seychelles"),
+    Country::new("SD", "Sudan"),
+    Country::new("SE", "Sweden"),
+    Country::new("SG", "Singapore"),
+    Country::new("SH", "Saint Helena"),
+    Country::new("SI", "Slovenia"),
+    Country::new("SJ", "Svalbard and Jan Mayen"),
+    Country::new("SK", "Slovakia"),
+    Country::new("SL", "Sierra Leone"),
+    Country::new("SM", "San Marino"),
+    Country::new("SN", "Senegal"),
+    Country::new("SO", "Somalia"),
+    Country::new("SR", "Suriname"),
+    Country::new("SS", "South Sudan"),
+    Country::new("ST", "São Tomé and Príncipe"),
+    Country::new("SV", "El Salvador"),
+    Country::new("SX", "Sint Maarten"),
+    Country::new("SY", "Syria"),
+    Country::new("SZ", "Eswatini"),
+    Country::new("TC", "Turks and Caicos Islands"),
+    Country::new("TD", "Chad"),
+    Country::new("TF", "French Southern Territories"),
+    Country::new("TG", "Togo"),
+    Country::new("TH", "Thailand"),
+    Country::new("TJ", "Tajikistan"),
+    Country::new("TK", "Tokelau"),
+    Country::new("TL", "Timor-Leste"),
+    Country::new("TM", "Turkmenistan"),
+    Country::new("TN", "Tunisia"),
+    Country::new("TO", "Tonga"),
+    Country::new("TR", "Türkiye"),
+    Country::new("TT", "Trinidad and Tobago"),
+    Country::new("TV", "Tuvalu"),
+    Country::new("TW", "Taiwan"),
+    Country::new("TZ", "Tanzania"),
+    Country::new("UA", "Ukraine"),
+    Country::new("UG", "Uganda"),
+    Country::new("UM", "U.S. Minor Outlying Islands"),
+    Country::new("US", "United States"),
+    Country::new("UY", "Uruguay"),
+    Country::new("UZ", "Uzbekistan"),
+    Country::new("VA", "Vatican City"),
+    Country::new("VC", "Saint Vincent and the Grenadines"),
+    Country::new("VE", "Venezuela"),
+    Country::new("VG", "British Virgin Islands"),
+    Country::new("VI", "U.S. Virgin Islands"),
+    Country::new("VN", "Vietnam"),
+    Country::new("VU", "Vanuatu"),
+    Country::new("WF", "Wallis and Futuna"),
+    Country::new("WS", "Samoa"),
+    Country::new("XK", "Kosovo"),
+    Country::new("YE", "Yemen"),
+    Country::new("YT", "Mayotte"),
+    Country::new("ZA", "South Africa"),
+    Country::new("ZM", "Zambia"),
+    Country::new("ZW", "Zimbabwe"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wanted(codes: &[&str]) -> BTreeSet<String> {
+        codes.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_geo_set_name_is_order_independent() {
+        let a = geo_set_name(&["FR".to_string(), "de".to_string()], false);
+        let b = geo_set_name(
+            &["DE".to_string(), "FR".to_string(), "FR".to_string()],
+            false,
+        );
+        assert_eq!(a, "drfw_geo_de_fr_v4");
+        assert_eq!(a, b);
+        assert!(geo_set_name(&["DE".to_string()], true).ends_with("_v6"));
+        assert_eq!(country_name("DE"), Some("Germany"));
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let text = "\
+network,country
+\"192.0.2.0/24\",\"DE\"
+198.51.100.0,198.51.100.127,de
+198.51.100.128,198.51.100.255,FR
+2001:db8::/32,DE
+garbage
+";
+        let ranges = parse_csv(text, &wanted(&["DE", "NL"]));
+        assert_eq!(ranges["DE"].len(), 3);
+        assert!(ranges["NL"].is_empty());
+        assert!(!ranges.contains_key("FR"));
+
+        let merged = read_database(text.as_bytes(), &wanted(&["DE"])).unwrap();
+        assert_eq!(merged["DE"].v4.len(), 2);
+        assert_eq!(merged["DE"].v6.len(), 1);
+    }
+
+    /// Encodes strings, unsigned integers, maps and arrays in the MMDB data format
+    fn mmdb_encode(value: &Value, out: &mut Vec<u8>) {
+        match value {
+            Value::String(text) => {
+                out.push(0x40 | u8::try_from(text.len()).unwrap());
+                out.extend(text.as_bytes());
+            }
+            Value::Number(number) => {
+                let number = number.as_u64().unwrap();
+                out.extend([0x08, 0x02]); // uint64 (extended type 9)
+                out.extend(number.to_be_bytes());
+            }
+            Value::Object(map) => {
+                out.push(0xE0 | u8::try_from(map.len()).unwrap());
+                for (key, value) in map {
+                    mmdb_encode(&Value::String(key.clone()), out);
+                    mmdb_encode(value, out);
+                }
+            }
+            Value::Array(items) => {
+                out.extend([u8::try_from(items.len()).unwrap(), 0x04]); // array (extended type 11)
+                for item in items {
+                    mmdb_encode(item, out);
+                }
+            }
+            _ => unreachable!("not used by the test databases"),
+        }
+    }
+
+    /// A search tree record of a test database
+    #[derive(Clone, Copy)]
+    enum Record {
+        Node(usize),
+        /// Data record with the country at this index
+        Country(usize),
+        /// No data
+        Empty,
+    }
+
+    /// Builds an MMDB file (24-bit records) from `(left, right)` records per node
+    /// and the country codes of its data section
+    fn mmdb_file(ip_version: u16, nodes: &[(Record, Record)], codes: &[&str]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut offsets = Vec::new();
+        for code in codes {
+            offsets.push(data.len());
+            mmdb_encode(&json!({ "country": { "iso_code": code } }), &mut data);
+        }
+        let value = |record: Record| match record {
+            Record::Node(node) => node,
+            Record::Country(index) => nodes.len() + 16 + offsets[index],
+            Record::Empty => nodes.len(),
+        };
+
+        let mut bytes = Vec::new();
+        for &(left, right) in nodes {
+            for record in [left, right] {
+                bytes.extend(&u32::try_from(value(record)).unwrap().to_be_bytes()[1..]);
+            }
+        }
+        bytes.extend([0u8; 16]);
+        bytes.extend(data);
+        bytes.extend(METADATA_MARKER);
+        mmdb_encode(
+            &json!({
+                "binary_format_major_version": 2,
+                "binary_format_minor_version": 0,
+                "build_epoch": 1_700_000_000,
+                "database_type": "Test-Country",
+                "description": { "en": "drfw test" },
+                "ip_version": ip_version,
+                "languages": ["en"],
+                "node_count": nodes.len(),
+                "record_size": 24,
+            }),
+            &mut bytes,
+        );
+        bytes
+    }
+
+    #[test]
+    fn test_parse_mmdb() {
+        use Record::{Country, Empty, Node};
+
+        // IPv4 database: 0.0.0.0/1 -> DE, 128.0.0.0/2 -> FR, 192.0.0.0/2 -> no data
+        let bytes = mmdb_file(
+            4,
+            &[(Country(0), Node(1)), (Country(1), Empty)],
+            &["DE", "FR"],
+        );
+        let merged = read_database(&bytes, &wanted(&["DE", "FR", "NL"])).unwrap();
+        assert_eq!(
+            merged["DE"].v4,
+            vec![(Ipv4Addr::UNSPECIFIED, Ipv4Addr::new(127, 255, 255, 255))]
+        );
+        assert_eq!(
+            merged["FR"].v4,
+            vec![(
+                Ipv4Addr::new(128, 0, 0, 0),
+                Ipv4Addr::new(191, 255, 255, 255)
+            )]
+        );
+        assert!(merged["NL"].is_empty());
+
+        // Truncated files are rejected
+        assert!(parse_mmdb(&bytes[..5], &wanted(&["DE"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_mmdb_ipv6() {
+        use Record::{Country, Empty, Node};
+
+        // IPv6 database: 8000::/1 -> NL; IPv4 lives below ::/96 with
+        // 0.0.0.0/1 -> DE and 128.0.0.0/2 -> FR
+        let mut nodes: Vec<_> = (1..=96).map(|next| (Node(next), Empty)).collect();
+        nodes[0].1 = Country(2);
+        nodes.push((Country(0), Node(97)));
+        nodes.push((Country(1), Empty));
+        let bytes = mmdb_file(6, &nodes, &["DE", "FR", "NL"]);
+
+        let merged = read_database(&bytes, &wanted(&["DE", "FR", "NL"])).unwrap();
+        assert_eq!(
+            merged["DE"].v4,
+            vec![(Ipv4Addr::UNSPECIFIED, Ipv4Addr::new(127, 255, 255, 255))]
+        );
+        assert!(merged["DE"].v6.is_empty());
+        assert_eq!(merged["FR"].v4.len(), 1);
+        assert_eq!(
+            merged["NL"].v6,
+            vec![(
+                "8000::".parse().unwrap(),
+                "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff".parse().unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_element_commands_union_countries() {
+        let cache = GeoIpCache {
+            database: PathBuf::from("/var/lib/GeoIP/countries.csv"),
+            database_modified: None,
+            built_at: Utc::now(),
+            countries: BTreeMap::from([
+                (
+                    "DE".to_string(),
+                    merge_address_ranges([(
+                        "192.0.2.0".parse().unwrap(),
+                        "192.0.2.127".parse().unwrap(),
+                    )]),
+                ),
+                (
+                    "AT".to_string(),
+                    merge_address_ranges([(
+                        "192.0.2.128".parse().unwrap(),
+                        "192.0.2.255".parse().unwrap(),
+                    )]),
+                ),
+            ]),
+        };
+        let groups = BTreeSet::from([vec!["AT".to_string(), "DE".to_string()]]);
+        let commands = cache.element_commands(&groups);
+        assert_eq!(commands.len(), 1, "Empty IPv6 set gets no element command");
+        // Both sets are still flushed, so ranges left from an earlier apply go away
+        let flushes: Vec<_> = flush_commands(&groups)
+            .into_iter()
+            .map(|c| c["flush"]["set"]["name"].clone())
+            .collect();
+        assert_eq!(
+            flushes,
+            [json!("drfw_geo_at_de_v4"), json!("drfw_geo_at_de_v6")]
+        );
+        assert_eq!(commands[0]["add"]["element"]["name"], "drfw_geo_at_de_v4");
+        assert_eq!(
+            commands[0]["add"]["element"]["elem"],
+            json!([{ "range": ["192.0.2.0", "192.0.2.255"] }])
+        );
+    }
+}
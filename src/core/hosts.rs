@@ -0,0 +1,202 @@
+//! Hostname resolution for address entries
+//!
+//! Rules and address sets may name hosts (`backup.lan`, `nas.local`) instead of
+//! hard-coding addresses that change. Hostnames are stored as
+//! [`AddressEntry::Host`] and resolved through the system resolver (which
+//! includes `/etc/hosts`) when the payload is generated for verify/apply.
+//!
+//! A ruleset is never rewritten in place: the generated ruleset is a copy with
+//! each hostname replaced by its addresses (see
+//! [`crate::core::subnets::expanded_ruleset`]), so profiles keep the names.
+//! A rule with a hostname that did not resolve is left out of the generated
+//! ruleset rather than widened, and [`resolution_warnings`] reports it, along
+//! with names whose addresses changed since the last apply.
+
+use crate::core::firewall::{AddressEntry, FirewallRuleset};
+use ipnetwork::IpNetwork;
+use std::collections::{BTreeMap, BTreeSet};
+use std::net::{IpAddr, ToSocketAddrs};
+use tracing::warn;
+
+/// Resolved addresses per hostname. A name that failed to resolve maps to an
+/// empty list; a name missing from the map has not been looked up yet.
+pub type HostAddresses = BTreeMap<String, Vec<IpAddr>>;
+
+/// Returns the hostnames used by the ruleset's rules and address sets
+pub fn hostnames(ruleset: &FirewallRuleset) -> BTreeSet<String> {
+    ruleset
+        .symbolic_addresses()
+        .filter_map(AddressEntry::hostname)
+        .map(String::from)
+        .collect()
+}
+
+/// Resolves a hostname through the system resolver (blocking).
+///
+/// Returns the sorted, deduplicated addresses, or an empty list on failure.
+pub fn resolve_blocking(name: &str) -> Vec<IpAddr> {
+    match (name, 0).to_socket_addrs() {
+        Ok(addrs) => {
+            let addrs: BTreeSet<IpAddr> = addrs.map(|a| a.ip()).collect();
+            addrs.into_iter().collect()
+        }
+        Err(e) => {
+            warn!("Failed to resolve {name}: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Resolves all hostnames used by a ruleset (blocking)
+pub fn resolve_all_blocking(names: &BTreeSet<String>) -> HostAddresses {
+    names
+        .iter()
+        .map(|name| (name.clone(), resolve_blocking(name)))
+        .collect()
+}
+
+/// Resolves hostnames on the blocking thread pool, for use from async tasks
+pub async fn resolve_all(names: BTreeSet<String>) -> HostAddresses {
+    tokio::task::spawn_blocking(move || resolve_all_blocking(&names))
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Hostname resolution task failed: {e}");
+            HostAddresses::new()
+        })
+}
+
+/// Returns the resolved addresses of a hostname entry as host networks
+/// (empty for other entries and names without addresses)
+pub fn host_networks(entry: &AddressEntry, resolved: &HostAddresses) -> Vec<IpNetwork> {
+    entry
+        .hostname()
+        .and_then(|name| resolved.get(name))
+        .map(|ips| ips.iter().map(|ip| IpNetwork::from(*ip)).collect())
+        .unwrap_or_default()
+}
+
+/// Formats a list of addresses for warnings (`10.0.0.5, fd00::5`)
+fn addresses_text(ips: &[IpAddr]) -> String {
+    ips.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns a warning per hostname that failed to resolve or whose addresses
+/// differ from `previous` (the resolution used for the last apply, if any)
+pub fn resolution_warnings(
+    names: &BTreeSet<String>,
+    resolved: &HostAddresses,
+    previous: Option<&HostAddresses>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for name in names {
+        let ips = resolved.get(name).map_or(&[][..], Vec::as_slice);
+        if ips.is_empty() {
+            warnings.push(format!(
+                "{name} did not resolve; rules using it are skipped"
+            ));
+            continue;
+        }
+        if let Some(old) = previous.and_then(|p| p.get(name))
+            && !old.is_empty()
+            && old.as_slice() != ips
+        {
+            warnings.push(format!(
+                "{name} changed since last apply: {} → {}",
+                addresses_text(old),
+                addresses_text(ips)
+            ));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::firewall::{NamedSet, SetType};
+    use crate::core::test_helpers::create_test_rule;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_hostnames() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Backup", Some(22));
+        rule.sources = vec![
+            "backup.lan".parse().unwrap(),
+            "10.0.0.0/8".parse().unwrap(),
+            "subnet:wlan0".parse().unwrap(),
+        ];
+        ruleset.rules.push(rule);
+        let mut set = NamedSet::new("servers", SetType::Ipv4Addr);
+        set.addresses = vec!["nas.local".parse().unwrap(), "backup.lan".parse().unwrap()];
+        ruleset.sets.push(set);
+
+        let names: Vec<_> = hostnames(&ruleset).into_iter().collect();
+        assert_eq!(names, vec!["backup.lan", "nas.local"]);
+    }
+
+    #[test]
+    fn test_host_networks() {
+        let resolved = HostAddresses::from([
+            ("nas.local".to_string(), vec![ip("10.0.0.5"), ip("fd00::5")]),
+            ("gone.lan".to_string(), Vec::new()),
+        ]);
+        let nas: AddressEntry = "nas.local".parse().unwrap();
+        assert_eq!(
+            host_networks(&nas, &resolved),
+            vec![
+                "10.0.0.5/32".parse::<IpNetwork>().unwrap(),
+                "fd00::5/128".parse().unwrap()
+            ]
+        );
+        let gone: AddressEntry = "gone.lan".parse().unwrap();
+        assert!(host_networks(&gone, &resolved).is_empty());
+        let unknown: AddressEntry = "other.lan".parse().unwrap();
+        assert!(host_networks(&unknown, &resolved).is_empty());
+        let network: AddressEntry = "10.0.0.5".parse().unwrap();
+        assert!(host_networks(&network, &resolved).is_empty());
+    }
+
+    #[test]
+    fn test_resolution_warnings() {
+        let names: BTreeSet<String> = ["gone.lan", "moved.lan", "nas.local", "new.lan"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let resolved = HostAddresses::from([
+            ("gone.lan".to_string(), Vec::new()),
+            ("moved.lan".to_string(), vec![ip("10.0.0.7"), ip("fd00::7")]),
+            ("nas.local".to_string(), vec![ip("10.0.0.5")]),
+            ("new.lan".to_string(), vec![ip("10.0.0.9")]),
+        ]);
+        let previous = HostAddresses::from([
+            ("moved.lan".to_string(), vec![ip("10.0.0.6")]),
+            ("nas.local".to_string(), vec![ip("10.0.0.5")]),
+            ("new.lan".to_string(), Vec::new()),
+        ]);
+
+        assert_eq!(
+            resolution_warnings(&names, &resolved, Some(&previous)),
+            vec![
+                "gone.lan did not resolve; rules using it are skipped".to_string(),
+                "moved.lan changed since last apply: 10.0.0.6 → 10.0.0.7, fd00::7".to_string(),
+            ]
+        );
+        // Without a previous apply only unresolved names are reported
+        assert_eq!(
+            resolution_warnings(&names, &resolved, None),
+            vec!["gone.lan did not resolve; rules using it are skipped".to_string()]
+        );
+        // A name missing from the resolution counts as unresolved
+        assert_eq!(
+            resolution_warnings(&names, &HostAddresses::new(), None).len(),
+            4
+        );
+    }
+}
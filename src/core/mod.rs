@@ -4,17 +4,29 @@
 //! It provides:
 //!
 //! - [`firewall`]: Data structures for representing firewall rules and rulesets
+//! - [`bans`]: Temporary IP bans via dynamic blocklist sets
+//! - [`blocklists`]: IP blocklists loaded from local files into interval sets
+//! - [`geoip`]: Country ranges from a local IP geolocation database
+//! - [`hosts`]: Hostname resolution for rule and set addresses at apply time
+//! - [`subnets`]: Interface subnet address entries expanded from local interfaces
+//! - [`counters`]: Per-rule packet/byte counters read back from the kernel
 //! - [`nft_json`]: JSON-based nftables rule application and snapshot management
 //! - [`verify`]: Ruleset validation and syntax checking
 //! - [`error`]: Error types for firewall operations
 //! - [`profiles`]: Firewall profile management
 //! - [`rule_constraints`]: Business rules for valid field combinations
 
+pub mod bans;
+pub mod blocklists;
+pub mod counters;
 pub mod error;
 pub mod firewall;
+pub mod geoip;
+pub mod hosts;
 pub mod nft_json;
 pub mod profiles;
 pub mod rule_constraints;
+pub mod subnets;
 pub mod verify;
 
 #[cfg(test)]
@@ -330,7 +330,7 @@ pub fn list_snapshots() -> Result<Vec<std::path::PathBuf>> {
         })
         .collect();
 
-    snapshots_with_time.sort_unstable_by(|a, b| b.1.cmp(&a.1)); // Newest first
+    snapshots_with_time.sort_unstable_by_key(|b| std::cmp::Reverse(b.1)); // Newest first
     let snapshots: Vec<_> = snapshots_with_time.into_iter().map(|(p, _)| p).collect();
 
     Ok(snapshots)
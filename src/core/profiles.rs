@@ -38,6 +38,21 @@ pub enum ProfileError {
 
     #[error("Rule limit exceeded: {current} rules (maximum: {limit})")]
     RuleLimitExceeded { current: usize, limit: usize },
+
+    #[error("Invalid named set '{name}': {reason}")]
+    InvalidSet { name: String, reason: &'static str },
+
+    #[error("Invalid port knock '{name}': {reason}")]
+    InvalidKnock { name: String, reason: &'static str },
+
+    #[error("Invalid blocklist '{path}': {reason}")]
+    InvalidBlocklist { path: String, reason: &'static str },
+
+    #[error("Invalid countries in rule '{rule}': {reason}")]
+    InvalidCountries { rule: String, reason: &'static str },
+
+    #[error("Invalid rule group '{name}': {reason}")]
+    InvalidGroup { name: String, reason: &'static str },
 }
 
 /// Validates a profile name for filesystem safety.
@@ -174,6 +189,89 @@ pub async fn load_profile(name: &str) -> Result<FirewallRuleset, ProfileError> {
         });
     }
 
+    // Validate named sets: names are interpolated into .nft text exports
+    for set in &ruleset.sets {
+        if let Err(reason) = crate::validators::validate_set_name(&set.name) {
+            return Err(ProfileError::InvalidSet {
+                name: set.name.clone(),
+                reason,
+            });
+        }
+        if set.element_count() > crate::validators::MAX_SET_ELEMENTS {
+            return Err(ProfileError::InvalidSet {
+                name: set.name.clone(),
+                reason: "Too many elements",
+            });
+        }
+    }
+
+    // Validate port knocks: names end up in set names and the .nft text export
+    let mut knock_names = std::collections::HashSet::new();
+    for knock in &ruleset.knocks {
+        let reason = crate::validators::validate_port_knock(knock)
+            .err()
+            .or_else(|| (!knock_names.insert(&knock.name)).then_some("Duplicate name"));
+        if let Some(reason) = reason {
+            return Err(ProfileError::InvalidKnock {
+                name: knock.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    // Validate country codes: they end up in generated set names
+    for rule in &ruleset.rules {
+        if let Err(reason) = crate::validators::validate_countries(&rule.countries) {
+            return Err(ProfileError::InvalidCountries {
+                rule: rule.label.clone(),
+                reason,
+            });
+        }
+    }
+
+    // Validate rule groups: names end up in jump comments and the .nft text export
+    for (index, group) in ruleset.groups.iter().enumerate() {
+        let reason = crate::validators::validate_group_name(&group.name)
+            .err()
+            .or_else(|| {
+                ruleset.groups[..index]
+                    .iter()
+                    .any(|g| g.id == group.id || g.name == group.name)
+                    .then_some("Duplicate group")
+            });
+        if let Some(reason) = reason {
+            return Err(ProfileError::InvalidGroup {
+                name: group.name.clone(),
+                reason,
+            });
+        }
+    }
+
+    // Validate blocklist files (read later by `drfw blocklist refresh` and applies)
+    if ruleset.blocklists.len() > crate::core::blocklists::MAX_BLOCKLISTS {
+        return Err(ProfileError::InvalidBlocklist {
+            path: ruleset.blocklists[crate::core::blocklists::MAX_BLOCKLISTS]
+                .display()
+                .to_string(),
+            reason: "Too many blocklists",
+        });
+    }
+    for (index, path) in ruleset.blocklists.iter().enumerate() {
+        let reason = crate::validators::validate_blocklist_path(path)
+            .err()
+            .or_else(|| {
+                ruleset.blocklists[..index]
+                    .contains(path)
+                    .then_some("Duplicate blocklist")
+            });
+        if let Some(reason) = reason {
+            return Err(ProfileError::InvalidBlocklist {
+                path: path.display().to_string(),
+                reason,
+            });
+        }
+    }
+
     // Rebuild caches for each rule to ensure performant UI rendering/filtering
     for rule in &mut ruleset.rules {
         rule.rebuild_caches();
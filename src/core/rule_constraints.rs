@@ -36,8 +36,10 @@
 // but not by the binary itself. These are intentionally exported for external use.
 #![allow(dead_code)]
 
-use super::firewall::{Chain, Protocol, RejectType};
-use ipnetwork::IpNetwork;
+use super::firewall::{
+    Action, AddressEntry, Chain, IcmpType, MatchField, PortEntry, Protocol, RejectType,
+};
+use std::net::IpAddr;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Protocol Constraints
@@ -109,12 +111,11 @@ pub fn protocol_is_icmp(protocol: Protocol) -> bool {
 /// # Examples
 ///
 /// ```
-/// use drfw::core::firewall::Protocol;
+/// use drfw::core::firewall::{AddressEntry, Protocol};
 /// use drfw::core::rule_constraints::ip_compatible_with_protocol;
-/// use ipnetwork::IpNetwork;
 ///
-/// let ipv4: IpNetwork = "192.168.1.0/24".parse().unwrap();
-/// let ipv6: IpNetwork = "2001:db8::/32".parse().unwrap();
+/// let ipv4: AddressEntry = "192.168.1.0/24".parse().unwrap();
+/// let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
 ///
 /// // ICMP (v4) only works with IPv4
 /// assert!(ip_compatible_with_protocol(&ipv4, Protocol::Icmp));
@@ -131,7 +132,10 @@ pub fn protocol_is_icmp(protocol: Protocol) -> bool {
 /// assert!(ip_compatible_with_protocol(&ipv6, Protocol::IcmpBoth));
 /// ```
 #[inline]
-pub fn ip_compatible_with_protocol(ip: &IpNetwork, protocol: Protocol) -> bool {
+pub fn ip_compatible_with_protocol(ip: &AddressEntry, protocol: Protocol) -> bool {
+    if ip.is_symbolic() {
+        return true; // Family is only known once expanded
+    }
     match protocol {
         Protocol::Icmp => ip.is_ipv4(),
         Protocol::Icmpv6 => ip.is_ipv6(),
@@ -239,6 +243,77 @@ pub fn available_reject_types_for_protocol(protocol: Protocol) -> Vec<RejectType
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// ICMP Type Constraints
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Returns `true` if the protocol supports ICMP type filtering.
+///
+/// Type names are family-specific (`icmp type` vs `icmpv6 type`), so only the
+/// single-family ICMP protocols support it, not `IcmpBoth`.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::Protocol;
+/// use drfw::core::rule_constraints::protocol_supports_icmp_types;
+///
+/// assert!(protocol_supports_icmp_types(Protocol::Icmp));
+/// assert!(protocol_supports_icmp_types(Protocol::Icmpv6));
+/// assert!(!protocol_supports_icmp_types(Protocol::IcmpBoth));
+/// assert!(!protocol_supports_icmp_types(Protocol::Tcp));
+/// ```
+#[inline]
+pub fn protocol_supports_icmp_types(protocol: Protocol) -> bool {
+    matches!(protocol, Protocol::Icmp | Protocol::Icmpv6)
+}
+
+/// Returns `true` if the ICMP type exists for the protocol's IP family.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::{IcmpType, Protocol};
+/// use drfw::core::rule_constraints::icmp_type_valid_for_protocol;
+///
+/// assert!(icmp_type_valid_for_protocol(IcmpType::EchoRequest, Protocol::Icmp));
+/// assert!(icmp_type_valid_for_protocol(IcmpType::EchoRequest, Protocol::Icmpv6));
+///
+/// // IPv4-only and ICMPv6-only types
+/// assert!(!icmp_type_valid_for_protocol(IcmpType::TimestampRequest, Protocol::Icmpv6));
+/// assert!(!icmp_type_valid_for_protocol(IcmpType::PacketTooBig, Protocol::Icmp));
+/// ```
+#[inline]
+pub fn icmp_type_valid_for_protocol(icmp_type: IcmpType, protocol: Protocol) -> bool {
+    match protocol {
+        Protocol::Icmp => icmp_type.icmp_name().is_some(),
+        Protocol::Icmpv6 => icmp_type.icmpv6_name().is_some(),
+        _ => false,
+    }
+}
+
+/// Returns the ICMP types that can be picked for a protocol (empty if the
+/// protocol does not support type filtering).
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::{IcmpType, Protocol};
+/// use drfw::core::rule_constraints::available_icmp_types_for_protocol;
+///
+/// let v6_types = available_icmp_types_for_protocol(Protocol::Icmpv6);
+/// assert!(v6_types.contains(&IcmpType::NeighborSolicitation));
+/// assert!(!v6_types.contains(&IcmpType::TimestampReply));
+/// assert!(available_icmp_types_for_protocol(Protocol::Udp).is_empty());
+/// ```
+pub fn available_icmp_types_for_protocol(protocol: Protocol) -> Vec<IcmpType> {
+    use strum::IntoEnumIterator;
+
+    IcmpType::iter()
+        .filter(|t| icmp_type_valid_for_protocol(*t, protocol))
+        .collect()
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Chain-Interface Constraints
 // ═══════════════════════════════════════════════════════════════════════════
@@ -250,6 +325,8 @@ pub enum InterfaceType {
     Input,
     /// Output interface (oif) - where packets exit
     Output,
+    /// Both interfaces - routed packets enter on one and exit on another
+    Both,
 }
 
 /// Returns `true` if the chain uses input interface (iif) matching.
@@ -257,6 +334,7 @@ pub enum InterfaceType {
 /// In nftables:
 /// - Input chain: packets arrive on an interface (iif is relevant)
 /// - Output chain: packets leave on an interface (oif is relevant)
+/// - Forward chain: packets are routed between interfaces (both are relevant)
 ///
 /// # Examples
 ///
@@ -265,11 +343,12 @@ pub enum InterfaceType {
 /// use drfw::core::rule_constraints::chain_uses_input_interface;
 ///
 /// assert!(chain_uses_input_interface(Chain::Input));
+/// assert!(chain_uses_input_interface(Chain::Forward));
 /// assert!(!chain_uses_input_interface(Chain::Output));
 /// ```
 #[inline]
 pub fn chain_uses_input_interface(chain: Chain) -> bool {
-    chain == Chain::Input
+    matches!(chain, Chain::Input | Chain::Forward)
 }
 
 /// Returns `true` if the chain uses output interface (oif) matching.
@@ -281,11 +360,12 @@ pub fn chain_uses_input_interface(chain: Chain) -> bool {
 /// use drfw::core::rule_constraints::chain_uses_output_interface;
 ///
 /// assert!(chain_uses_output_interface(Chain::Output));
+/// assert!(chain_uses_output_interface(Chain::Forward));
 /// assert!(!chain_uses_output_interface(Chain::Input));
 /// ```
 #[inline]
 pub fn chain_uses_output_interface(chain: Chain) -> bool {
-    chain == Chain::Output
+    matches!(chain, Chain::Output | Chain::Forward)
 }
 
 /// Returns which interface type is semantically appropriate for a chain.
@@ -301,15 +381,160 @@ pub fn chain_uses_output_interface(chain: Chain) -> bool {
 ///
 /// assert_eq!(chain_interface_type(Chain::Input), InterfaceType::Input);
 /// assert_eq!(chain_interface_type(Chain::Output), InterfaceType::Output);
+/// assert_eq!(chain_interface_type(Chain::Forward), InterfaceType::Both);
 /// ```
 #[inline]
 pub fn chain_interface_type(chain: Chain) -> InterfaceType {
     match chain {
         Chain::Input => InterfaceType::Input,
         Chain::Output => InterfaceType::Output,
+        Chain::Forward => InterfaceType::Both,
     }
 }
 
+/// Returns `true` if an interface list can be matched in a single rule.
+///
+/// Several interfaces are matched through an anonymous set, and not every nft
+/// version accepts wildcard names (`eth*`) inside one, so a wildcard must be the
+/// only interface of the list.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::rule_constraints::interfaces_valid_as_list;
+///
+/// let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+/// assert!(interfaces_valid_as_list(&names(&["eth*"])));
+/// assert!(interfaces_valid_as_list(&names(&["eth0", "wlan0"])));
+/// assert!(!interfaces_valid_as_list(&names(&["eth0", "wlan*"])));
+/// ```
+pub fn interfaces_valid_as_list(interfaces: &[String]) -> bool {
+    interfaces.len() < 2 || !interfaces.iter().any(|iface| iface.ends_with('*'))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Port Forward Constraints
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Returns `true` if the chain can carry a port forward.
+///
+/// Port forwards rewrite incoming traffic in the `prerouting` hook, so they
+/// only exist for the Input direction.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::Chain;
+/// use drfw::core::rule_constraints::chain_supports_port_forward;
+///
+/// assert!(chain_supports_port_forward(Chain::Input));
+/// assert!(!chain_supports_port_forward(Chain::Output));
+/// ```
+#[inline]
+pub fn chain_supports_port_forward(chain: Chain) -> bool {
+    chain == Chain::Input
+}
+
+/// Returns `true` if the external ports of a port forward are valid.
+///
+/// Exactly one port or range is required so the DNAT mapping is unambiguous.
+/// A range forwarded without an internal port keeps each original port.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::PortEntry;
+/// use drfw::core::rule_constraints::ports_valid_for_port_forward;
+///
+/// assert!(ports_valid_for_port_forward(&[PortEntry::Single(8080)]));
+/// assert!(ports_valid_for_port_forward(&[PortEntry::Range { start: 6000, end: 6010 }]));
+/// assert!(!ports_valid_for_port_forward(&[]));
+/// assert!(!ports_valid_for_port_forward(&[PortEntry::Single(80), PortEntry::Single(443)]));
+/// ```
+#[inline]
+pub fn ports_valid_for_port_forward(ports: &[PortEntry]) -> bool {
+    ports.len() == 1
+}
+
+/// Returns `true` if the action can be used with a port forward.
+///
+/// The forward-chain rule of a port forward always accepts; dropping forwarded
+/// traffic is expressed by not forwarding it in the first place.
+#[inline]
+pub fn action_valid_for_port_forward(action: Action) -> bool {
+    action == Action::Accept
+}
+
+/// Returns `true` if an address can be combined with a forward target.
+///
+/// DNAT cannot translate between IP families, so source filters must be in
+/// the same family as the internal host.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::rule_constraints::ip_compatible_with_forward_target;
+/// use drfw::core::firewall::AddressEntry;
+/// use std::net::IpAddr;
+///
+/// let lan: IpAddr = "192.168.1.10".parse().unwrap();
+/// let ipv4: AddressEntry = "203.0.113.0/24".parse().unwrap();
+/// let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
+///
+/// assert!(ip_compatible_with_forward_target(&ipv4, lan));
+/// assert!(!ip_compatible_with_forward_target(&ipv6, lan));
+/// ```
+#[inline]
+pub fn ip_compatible_with_forward_target(ip: &AddressEntry, target: IpAddr) -> bool {
+    ip.is_symbolic() || ip.is_ipv6() == target.is_ipv6()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Negation Constraints
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Returns `true` if an address can be part of a negated match.
+///
+/// A `/0` network (or a range spanning every address) covers its whole IP
+/// family, so "anything except" it would match nothing in that family.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::rule_constraints::ip_valid_for_negation;
+/// use drfw::core::firewall::AddressEntry;
+///
+/// let lan: AddressEntry = "10.0.0.0/8".parse().unwrap();
+/// let any: AddressEntry = "0.0.0.0/0".parse().unwrap();
+///
+/// assert!(ip_valid_for_negation(&lan));
+/// assert!(!ip_valid_for_negation(&any));
+/// ```
+#[inline]
+pub fn ip_valid_for_negation(ip: &AddressEntry) -> bool {
+    !ip.covers_family()
+}
+
+/// Returns `true` if a negated field can be combined with a port forward.
+///
+/// DNAT maps one external port or range to the internal host; "every port
+/// except 22" has no unambiguous mapping. Negated sources and interfaces are
+/// fine: they only narrow which connections get forwarded.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::MatchField;
+/// use drfw::core::rule_constraints::negation_valid_for_port_forward;
+///
+/// assert!(negation_valid_for_port_forward(MatchField::Sources));
+/// assert!(!negation_valid_for_port_forward(MatchField::Ports));
+/// ```
+#[inline]
+pub fn negation_valid_for_port_forward(field: MatchField) -> bool {
+    field != MatchField::Ports
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -428,16 +653,39 @@ mod tests {
         }
     }
 
+    // ICMP type tests
+    #[test]
+    fn test_icmp_type_constraints() {
+        assert!(protocol_supports_icmp_types(Protocol::Icmp));
+        assert!(!protocol_supports_icmp_types(Protocol::IcmpBoth));
+        assert!(!protocol_supports_icmp_types(Protocol::Any));
+
+        let v4_types = available_icmp_types_for_protocol(Protocol::Icmp);
+        assert!(v4_types.contains(&IcmpType::TimestampRequest));
+        assert!(!v4_types.contains(&IcmpType::PacketTooBig));
+        assert!(!v4_types.contains(&IcmpType::NeighborAdvertisement));
+
+        // Every type exists in at least one family
+        let v6_types = available_icmp_types_for_protocol(Protocol::Icmpv6);
+        assert!(
+            <IcmpType as strum::IntoEnumIterator>::iter()
+                .all(|t| v4_types.contains(&t) || v6_types.contains(&t))
+        );
+        assert!(available_icmp_types_for_protocol(Protocol::IcmpBoth).is_empty());
+    }
+
     // Chain-interface tests
     #[test]
     fn test_chain_uses_input_interface() {
         assert!(chain_uses_input_interface(Chain::Input));
+        assert!(chain_uses_input_interface(Chain::Forward));
         assert!(!chain_uses_input_interface(Chain::Output));
     }
 
     #[test]
     fn test_chain_uses_output_interface() {
         assert!(chain_uses_output_interface(Chain::Output));
+        assert!(chain_uses_output_interface(Chain::Forward));
         assert!(!chain_uses_output_interface(Chain::Input));
     }
 
@@ -445,13 +693,77 @@ mod tests {
     fn test_chain_interface_type() {
         assert_eq!(chain_interface_type(Chain::Input), InterfaceType::Input);
         assert_eq!(chain_interface_type(Chain::Output), InterfaceType::Output);
+        assert_eq!(chain_interface_type(Chain::Forward), InterfaceType::Both);
+    }
+
+    // Port forward tests
+    #[test]
+    fn test_port_forward_constraints() {
+        assert!(chain_supports_port_forward(Chain::Input));
+        assert!(!chain_supports_port_forward(Chain::Output));
+
+        assert!(ports_valid_for_port_forward(&[PortEntry::Single(8080)]));
+        assert!(!ports_valid_for_port_forward(&[]));
+        assert!(!ports_valid_for_port_forward(&[
+            PortEntry::Single(80),
+            PortEntry::Single(443)
+        ]));
+
+        assert!(action_valid_for_port_forward(Action::Accept));
+        assert!(!action_valid_for_port_forward(Action::Drop));
+        assert!(!action_valid_for_port_forward(Action::Reject));
+    }
+
+    #[test]
+    fn test_ip_compatible_with_forward_target() {
+        let v4_target: IpAddr = "192.168.1.10".parse().unwrap();
+        let v6_target: IpAddr = "fd00::10".parse().unwrap();
+        let ipv4: AddressEntry = "203.0.113.0/24".parse().unwrap();
+        let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
+
+        assert!(ip_compatible_with_forward_target(&ipv4, v4_target));
+        assert!(!ip_compatible_with_forward_target(&ipv6, v4_target));
+        assert!(ip_compatible_with_forward_target(&ipv6, v6_target));
+        assert!(!ip_compatible_with_forward_target(&ipv4, v6_target));
+    }
+
+    // Negation tests
+    #[test]
+    fn test_negation_constraints() {
+        for (ip, valid) in [
+            ("10.0.0.0/8", true),
+            ("192.168.1.5/32", true),
+            ("fd00::/8", true),
+            ("0.0.0.0/0", false),
+            ("::/0", false),
+            ("10.0.0.100-10.0.0.150", true),
+            ("0.0.0.0-255.255.255.255", false),
+        ] {
+            let ip: AddressEntry = ip.parse().unwrap();
+            assert_eq!(ip_valid_for_negation(&ip), valid, "{ip}");
+        }
+
+        assert!(negation_valid_for_port_forward(MatchField::Sources));
+        assert!(negation_valid_for_port_forward(MatchField::Interface));
+        assert!(!negation_valid_for_port_forward(MatchField::Ports));
+    }
+
+    // Interface list tests
+    #[test]
+    fn test_wildcard_interface_lists() {
+        let names = |list: &[&str]| list.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(interfaces_valid_as_list(&[]));
+        assert!(interfaces_valid_as_list(&names(&["docker*"])));
+        assert!(interfaces_valid_as_list(&names(&["eth0", "wg0"])));
+        assert!(!interfaces_valid_as_list(&names(&["eth*", "wg0"])));
+        assert!(!interfaces_valid_as_list(&names(&["eth*", "wlan*"])));
     }
 
     // ICMP Protocol / IP Version tests
     #[test]
     fn test_ip_compatible_with_icmp_v4() {
-        let ipv4: IpNetwork = "192.168.1.0/24".parse().unwrap();
-        let ipv6: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let ipv4: AddressEntry = "192.168.1.0/24".parse().unwrap();
+        let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
 
         // ICMP (v4) only works with IPv4
         assert!(ip_compatible_with_protocol(&ipv4, Protocol::Icmp));
@@ -460,8 +772,8 @@ mod tests {
 
     #[test]
     fn test_ip_compatible_with_icmpv6() {
-        let ipv4: IpNetwork = "192.168.1.0/24".parse().unwrap();
-        let ipv6: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let ipv4: AddressEntry = "192.168.1.0/24".parse().unwrap();
+        let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
 
         // ICMPv6 only works with IPv6
         assert!(ip_compatible_with_protocol(&ipv6, Protocol::Icmpv6));
@@ -470,8 +782,8 @@ mod tests {
 
     #[test]
     fn test_ip_compatible_with_other_protocols() {
-        let ipv4: IpNetwork = "192.168.1.0/24".parse().unwrap();
-        let ipv6: IpNetwork = "2001:db8::/32".parse().unwrap();
+        let ipv4: AddressEntry = "192.168.1.0/24".parse().unwrap();
+        let ipv6: AddressEntry = "2001:db8::/32".parse().unwrap();
 
         // All other protocols work with both IP versions
         for protocol in [
@@ -0,0 +1,166 @@
+//! Interface-relative address entries ("the subnet of wlan0")
+//!
+//! Rules like "allow Syncthing from the LAN" break whenever the machine moves
+//! between networks if the LAN's CIDR is hard-coded. An
+//! [`AddressEntry::InterfaceSubnet`] (`subnet:wlan0`, or `subnet:docker*` for
+//! any matching interface) stands for the networks currently configured on the
+//! interfaces. They are read with `network-interface` (as in
+//! `utils::list_interfaces`) and expanded whenever the preview or the
+//! verify/apply payload is built.
+//!
+//! An entry whose interfaces are missing or have no addresses stays unexpanded,
+//! so the rules using it are skipped rather than widened. `drfw reapply`
+//! re-expands the active profile after a network change, e.g. from a
+//! `NetworkManager` dispatcher script.
+
+use crate::core::firewall::{AddressEntry, FirewallRuleset};
+use crate::core::hosts::{HostAddresses, host_networks};
+use ipnetwork::IpNetwork;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+
+/// A network configured on a local interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalSubnet {
+    pub interface: String,
+    pub network: IpNetwork,
+}
+
+impl LocalSubnet {
+    /// Creates a subnet from an interface address and netmask, normalized to the
+    /// network address (`192.168.1.20/24` becomes `192.168.1.0/24`)
+    pub fn new(interface: impl Into<String>, ip: IpAddr, netmask: IpAddr) -> Option<Self> {
+        let net = IpNetwork::with_netmask(ip, netmask).ok()?;
+        let network = IpNetwork::new(net.network(), net.prefix()).ok()?;
+        Some(Self {
+            interface: interface.into(),
+            network,
+        })
+    }
+}
+
+/// Lists the subnets of the system's interfaces.
+///
+/// Loopback and IPv6 link-local networks are left out: they exist on every
+/// link, so they don't describe the network an interface is attached to.
+pub fn local_subnets() -> Vec<LocalSubnet> {
+    use network_interface::{NetworkInterface, NetworkInterfaceConfig};
+
+    let mut subnets: Vec<LocalSubnet> = NetworkInterface::show()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|iface| iface.name != "lo")
+        .flat_map(|iface| {
+            iface
+                .addr
+                .into_iter()
+                .filter_map(move |addr| LocalSubnet::new(&iface.name, addr.ip(), addr.netmask()?))
+        })
+        .filter(|subnet| match subnet.network.ip() {
+            IpAddr::V4(ip) => !ip.is_loopback(),
+            IpAddr::V6(ip) => !ip.is_loopback() && !ip.is_unicast_link_local(),
+        })
+        .collect();
+    subnets.sort_by(|a, b| {
+        (&a.interface, a.network.ip(), a.network.prefix()).cmp(&(
+            &b.interface,
+            b.network.ip(),
+            b.network.prefix(),
+        ))
+    });
+    subnets.dedup();
+    subnets
+}
+
+/// Returns `true` if an interface name matches a name or suffix wildcard (`docker*`)
+pub fn interface_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == pattern,
+    }
+}
+
+/// Returns the networks of an interface subnet entry (empty for other entries
+/// and interfaces without addresses)
+pub fn subnet_networks(entry: &AddressEntry, subnets: &[LocalSubnet]) -> Vec<IpNetwork> {
+    entry
+        .subnet_of()
+        .map(|pattern| networks_of(pattern, subnets))
+        .unwrap_or_default()
+}
+
+/// Returns the networks of the interfaces matching `pattern`
+fn networks_of(pattern: &str, subnets: &[LocalSubnet]) -> Vec<IpNetwork> {
+    subnets
+        .iter()
+        .filter(|s| interface_matches(pattern, &s.interface))
+        .map(|s| s.network)
+        .collect()
+}
+
+/// Formats networks for warnings (`192.168.1.0/24, fd00::/64`)
+fn networks_text(networks: &[IpNetwork]) -> String {
+    networks
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Returns a warning per interface subnet entry of the ruleset that matches no
+/// interface address, or whose networks differ from `previous` (the subnets used
+/// for the last apply, if any)
+pub fn expansion_warnings(
+    ruleset: &FirewallRuleset,
+    subnets: &[LocalSubnet],
+    previous: Option<&[LocalSubnet]>,
+) -> Vec<String> {
+    let patterns: BTreeSet<&str> = ruleset
+        .symbolic_addresses()
+        .filter_map(AddressEntry::subnet_of)
+        .collect();
+    let mut warnings = Vec::new();
+    for pattern in patterns {
+        let networks = networks_of(pattern, subnets);
+        if networks.is_empty() {
+            warnings.push(format!(
+                "subnet:{pattern} matches no interface address; rules using it are skipped"
+            ));
+            continue;
+        }
+        if let Some(old) = previous.map(|p| networks_of(pattern, p))
+            && !old.is_empty()
+            && old != networks
+        {
+            warnings.push(format!(
+                "subnet:{pattern} changed since last apply: {} → {}",
+                networks_text(&old),
+                networks_text(&networks)
+            ));
+        }
+    }
+    warnings
+}
+
+/// Returns `true` if the ruleset has interface subnet entries
+pub fn uses_subnets(ruleset: &FirewallRuleset) -> bool {
+    ruleset
+        .symbolic_addresses()
+        .any(|a| a.subnet_of().is_some())
+}
+
+/// Returns the ruleset with interface subnets and resolved hostnames replaced by
+/// their networks, as generated for the preview and the verify/apply payload
+/// (see [`FirewallRuleset::expand_addresses`])
+pub fn expanded_ruleset<'a>(
+    ruleset: &'a FirewallRuleset,
+    subnets: &[LocalSubnet],
+    hosts: &HostAddresses,
+) -> Cow<'a, FirewallRuleset> {
+    ruleset.expand_addresses(|entry| {
+        let mut networks = subnet_networks(entry, subnets);
+        networks.extend(host_networks(entry, hosts));
+        networks
+    })
+}
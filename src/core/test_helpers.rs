@@ -4,7 +4,7 @@
 //! This module is only compiled in test mode.
 
 use crate::core::firewall::{
-    Action, Chain, FirewallRuleset, PortEntry, Protocol, RejectType, Rule,
+    Action, Chain, FirewallRuleset, Negation, PortEntry, Protocol, RejectType, Rule,
 };
 use chrono::Utc;
 use std::sync::{Mutex, Once};
@@ -98,17 +98,36 @@ pub fn create_test_rule(label: &str, port: Option<u16>) -> Rule {
         ports: port.map(|p| vec![PortEntry::Single(p)]).unwrap_or_default(),
         sources: vec![],
         destinations: vec![],
-        interface: None,
-        output_interface: None,
+        interfaces: Vec::new(),
+        output_interfaces: Vec::new(),
         chain: Chain::Input,
         enabled: true,
         tags: Vec::new(),
         created_at: Utc::now(),
+        modified_at: None,
+        expires_at: None,
         action: Action::Accept,
         reject_type: RejectType::Default,
         rate_limit: None,
         connection_limit: 0,
         log_enabled: false,
+        source_set: None,
+        destination_set: None,
+        port_set: None,
+        port_forward: None,
+        source_ports: Vec::new(),
+        icmp_types: Vec::new(),
+        icmp_code: None,
+        ct_states: Vec::new(),
+        schedule: None,
+        quota: None,
+        per_source: None,
+        countries: Vec::new(),
+        group: None,
+        description: String::new(),
+        owner: String::new(),
+        reference: String::new(),
+        negate: Negation::default(),
         // Cached fields - will be populated by rebuild_caches()
         label_lowercase: String::new(),
         interface_lowercase: None,
@@ -124,6 +143,7 @@ pub fn create_test_rule(label: &str, port: Option<u16>) -> Rule {
         log_prefix: String::new(),
         tags_truncated: Vec::new(),
         badge_display: String::new(),
+        metadata_lowercase: String::new(),
     };
     rule.rebuild_caches();
     rule
@@ -155,17 +175,36 @@ pub fn create_full_test_rule(
             .map(|ip| vec![ip])
             .unwrap_or_default(),
         destinations: vec![],
-        interface: interface.map(String::from),
-        output_interface: None,
+        interfaces: interface.into_iter().map(String::from).collect(),
+        output_interfaces: Vec::new(),
         chain: Chain::Input,
         enabled: true,
         tags: Vec::new(),
         created_at: Utc::now(),
+        modified_at: None,
+        expires_at: None,
         action: Action::Accept,
         reject_type: RejectType::Default,
         rate_limit: None,
         connection_limit: 0,
         log_enabled: false,
+        source_set: None,
+        destination_set: None,
+        port_set: None,
+        port_forward: None,
+        source_ports: Vec::new(),
+        icmp_types: Vec::new(),
+        icmp_code: None,
+        ct_states: Vec::new(),
+        schedule: None,
+        quota: None,
+        per_source: None,
+        countries: Vec::new(),
+        group: None,
+        description: String::new(),
+        owner: String::new(),
+        reference: String::new(),
+        negate: Negation::default(),
         // Cached fields
         label_lowercase: String::new(),
         interface_lowercase: None,
@@ -181,6 +220,7 @@ pub fn create_full_test_rule(
         log_prefix: String::new(),
         tags_truncated: Vec::new(),
         badge_display: String::new(),
+        metadata_lowercase: String::new(),
     };
     rule.rebuild_caches();
     rule
@@ -197,6 +237,5 @@ pub fn is_nft_installed() -> bool {
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .status()
-        .map(|s| s.success())
-        .unwrap_or(false)
+        .is_ok_and(|s| s.success())
 }
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests_impl {
-    use crate::core::firewall::{Action, FirewallRuleset, PortEntry, Protocol, RejectType, Rule};
+    use crate::core::firewall::{
+        Action, FirewallRuleset, Negation, PortEntry, Protocol, RejectType, Rule,
+    };
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -13,17 +15,36 @@ mod tests_impl {
             ports,
             sources: vec![],
             destinations: vec![],
-            interface: None,
-            output_interface: None,
+            interfaces: Vec::new(),
+            output_interfaces: Vec::new(),
             chain: crate::core::firewall::Chain::Input,
             enabled: true,
             tags: Vec::new(),
             created_at: Utc::now(),
+            modified_at: None,
+            expires_at: None,
             action: Action::Accept,
             reject_type: RejectType::Default,
             rate_limit: None,
             connection_limit: 0,
             log_enabled: false,
+            source_set: None,
+            destination_set: None,
+            port_set: None,
+            port_forward: None,
+            source_ports: Vec::new(),
+            icmp_types: Vec::new(),
+            icmp_code: None,
+            ct_states: Vec::new(),
+            schedule: None,
+            quota: None,
+            per_source: None,
+            countries: Vec::new(),
+            group: None,
+            description: String::new(),
+            owner: String::new(),
+            reference: String::new(),
+            negate: Negation::default(),
             // Cached fields
             label_lowercase: String::new(),
             interface_lowercase: None,
@@ -39,6 +60,7 @@ mod tests_impl {
             log_prefix: String::new(),
             tags_truncated: Vec::new(),
             badge_display: String::new(),
+            metadata_lowercase: String::new(),
         }
     }
 
@@ -51,8 +73,8 @@ mod tests_impl {
         assert!(json["nftables"].is_array());
         let nft = json["nftables"].as_array().unwrap();
 
-        // table(1) + flush(1) + chains(3) + base(7: lo, invalid, ct, icmp_redir, icmpv6_redir, icmp, icmpv6) + rejection(1) + counter(1) = 14
-        assert_eq!(nft.len(), 14);
+        // table(1) + flush(1) + chains(3) + ban sets(2) + base(9: lo, ban4, ban6, invalid, ct, icmp_redir, icmpv6_redir, icmp, icmpv6) + rejection(1) + counter(1) = 18
+        assert_eq!(nft.len(), 18);
 
         assert_eq!(nft[0]["add"]["table"]["name"], "drfw");
         assert!(nft[1].get("flush").is_some());
@@ -69,10 +91,10 @@ mod tests_impl {
         let json = ruleset.to_nftables_json();
         let nft = json["nftables"].as_array().unwrap();
 
-        // 14 base + 1 user rule = 15
-        assert_eq!(nft.len(), 15);
+        // 18 base + 1 user rule = 19
+        assert_eq!(nft.len(), 19);
 
-        let user_rule = &nft[12]["add"]["rule"];
+        let user_rule = &nft[16]["add"]["rule"];
         assert_eq!(user_rule["chain"], "input");
         assert_eq!(user_rule["table"], "drfw");
         assert_eq!(user_rule["comment"], "Allow SSH");
@@ -112,10 +134,10 @@ mod tests_impl {
         let json = ruleset.to_nftables_json();
         let nft = json["nftables"].as_array().unwrap();
 
-        // 14 base + 1 user rule = 15
-        assert_eq!(nft.len(), 15);
+        // 18 base + 1 user rule = 19
+        assert_eq!(nft.len(), 19);
 
-        let user_rule = &nft[12]["add"]["rule"];
+        let user_rule = &nft[16]["add"]["rule"];
         let expr = user_rule["expr"].as_array().unwrap();
 
         // Should have source match (1) + accept (1) = 2
@@ -151,14 +173,16 @@ mod tests_impl {
             }
         }
 
-        // Verify correct ordering (loopback → invalid → established → drop redirects → icmp → icmpv6)
+        // Verify correct ordering (loopback → bans → invalid → established → drop redirects → icmp → icmpv6)
         assert_eq!(rule_comments[0], "allow from loopback");
-        assert_eq!(rule_comments[1], "early drop of invalid connections");
-        assert_eq!(rule_comments[2], "allow tracked connections");
-        assert_eq!(rule_comments[3], "drop icmp redirects");
-        assert_eq!(rule_comments[4], "drop icmpv6 redirects");
-        assert_eq!(rule_comments[5], "allow icmp");
-        assert_eq!(rule_comments[6], "allow icmp v6");
+        assert_eq!(rule_comments[1], "drop banned ipv4 addresses");
+        assert_eq!(rule_comments[2], "drop banned ipv6 addresses");
+        assert_eq!(rule_comments[3], "early drop of invalid connections");
+        assert_eq!(rule_comments[4], "allow tracked connections");
+        assert_eq!(rule_comments[5], "drop icmp redirects");
+        assert_eq!(rule_comments[6], "drop icmpv6 redirects");
+        assert_eq!(rule_comments[7], "allow icmp");
+        assert_eq!(rule_comments[8], "allow icmp v6");
     }
 
     #[test]
@@ -171,11 +195,13 @@ mod tests_impl {
         // Extract rule order from text
         assert!(text.contains("iifname \"lo\" accept comment \"allow from loopback\""));
         let lo_pos = text.find("allow from loopback").unwrap();
+        let ban_pos = text.find("drop banned ipv4 addresses").unwrap();
         let invalid_pos = text.find("early drop of invalid connections").unwrap();
         let est_pos = text.find("allow tracked connections").unwrap();
 
         // Verify order in text
-        assert!(lo_pos < invalid_pos);
+        assert!(lo_pos < ban_pos);
+        assert!(ban_pos < invalid_pos);
         assert!(invalid_pos < est_pos);
 
         // Extract from JSON
@@ -191,8 +217,9 @@ mod tests_impl {
 
         // Verify JSON has same order
         assert_eq!(comments[0], "allow from loopback");
-        assert_eq!(comments[1], "early drop of invalid connections");
-        assert_eq!(comments[2], "allow tracked connections");
+        assert_eq!(comments[1], "drop banned ipv4 addresses");
+        assert_eq!(comments[3], "early drop of invalid connections");
+        assert_eq!(comments[4], "allow tracked connections");
     }
 
     #[test]
@@ -216,7 +243,7 @@ mod tests_impl {
         );
         rule2.id = Uuid::new_v4();
         rule2.sources = vec!["0.0.0.0/0".parse().unwrap()];
-        rule2.interface = Some("eth0".to_string());
+        rule2.interfaces = vec!["eth0".to_string()];
         ruleset.rules.push(rule2);
 
         let mut rule3 = test_rule("DNS", Protocol::Udp, vec![PortEntry::Single(53)]);
@@ -254,12 +281,12 @@ mod tests_impl {
         // Verify all expected rules are present
         assert_eq!(
             json_comments.len(),
-            10,
-            "Should have 7 base rules + 3 user rules"
+            12,
+            "Should have 9 base rules + 3 user rules"
         );
-        assert_eq!(json_comments[7], "SSH Access");
-        assert_eq!(json_comments[8], "Web Server");
-        assert_eq!(json_comments[9], "DNS");
+        assert_eq!(json_comments[9], "SSH Access");
+        assert_eq!(json_comments[10], "Web Server");
+        assert_eq!(json_comments[11], "DNS");
     }
 
     #[test]
@@ -289,7 +316,7 @@ mod tests_impl {
         // Extract user rule comments from JSON (skip base rules)
         let nft = json["nftables"].as_array().unwrap();
         let mut json_user_rules = Vec::new();
-        for item in nft.iter().skip(12) {
+        for item in nft.iter().skip(16) {
             // Skip table, flush, chains, base rules
             if let Some(rule) = item.get("add").and_then(|a| a.get("rule"))
                 && let Some(comment) = rule.get("comment").and_then(|c| c.as_str())
@@ -418,7 +445,7 @@ mod tests_impl {
 #[cfg(test)]
 mod property_tests {
     use crate::core::firewall::{
-        Action, FirewallRuleset, PortEntry, PortRange, Protocol, RejectType, Rule,
+        Action, FirewallRuleset, Negation, PortEntry, PortRange, Protocol, RejectType, Rule,
     };
     use chrono::Utc;
     use proptest::prelude::*;
@@ -468,18 +495,37 @@ mod property_tests {
                 ports: port_entries.unwrap_or_default(),
                 sources: vec![],
                 destinations: vec![],
-                interface: None,
-                output_interface: None,
+                interfaces: Vec::new(),
+                output_interfaces: Vec::new(),
                 chain: crate::core::firewall::Chain::Input,
                 enabled: true,
                 tags: Vec::new(),
                 created_at: Utc::now(),
+                modified_at: None,
+                expires_at: None,
                 // Advanced options
                 action: Action::Accept,
                 reject_type: RejectType::Default,
                 rate_limit: None,
                 connection_limit: 0,
                 log_enabled: false,
+                source_set: None,
+                destination_set: None,
+                port_set: None,
+                port_forward: None,
+                source_ports: Vec::new(),
+                icmp_types: Vec::new(),
+                icmp_code: None,
+                ct_states: Vec::new(),
+                schedule: None,
+                quota: None,
+                per_source: None,
+                countries: Vec::new(),
+                group: None,
+                description: String::new(),
+                owner: String::new(),
+                reference: String::new(),
+                negate: Negation::default(),
                 // Cached fields
                 label_lowercase: String::new(),
                 interface_lowercase: None,
@@ -495,6 +541,7 @@ mod property_tests {
                 log_prefix: String::new(),
                 tags_truncated: Vec::new(),
                 badge_display: String::new(),
+                metadata_lowercase: String::new(),
             }
         }
     }
@@ -568,7 +615,7 @@ mod property_tests {
             use crate::core::firewall::{RateLimit, TimeUnit};
 
             let units = [TimeUnit::Second, TimeUnit::Minute, TimeUnit::Hour, TimeUnit::Day];
-            let rate_limit = RateLimit { count, unit: units[unit_idx], burst: None };
+            let rate_limit = RateLimit { count, unit: units[unit_idx], burst: None, over: false, bytes: None };
 
             // Test serialization
             let json = serde_json::to_string(&rate_limit).unwrap();
@@ -586,7 +633,7 @@ mod property_tests {
             use crate::core::firewall::{RateLimit, TimeUnit};
 
             let units = [TimeUnit::Second, TimeUnit::Minute, TimeUnit::Hour, TimeUnit::Day];
-            let rate_limit = RateLimit { count, unit: units[unit_idx], burst: None };
+            let rate_limit = RateLimit { count, unit: units[unit_idx], burst: None, over: false, bytes: None };
 
             let display = rate_limit.to_string();
 
@@ -609,7 +656,7 @@ mod integration_tests {
     //! Tests that exercise the verification flow should be in `tests/integration_tests.rs`
     //! to avoid duplication.
 
-    use crate::core::firewall::{FirewallRuleset, PortEntry, Protocol};
+    use crate::core::firewall::{AddressEntry, FirewallRuleset, PortEntry, Protocol};
     use crate::core::test_helpers::{create_test_rule, setup_mock_nft};
     use crate::core::verify;
 
@@ -678,8 +725,8 @@ mod integration_tests {
         let json = ruleset.to_nftables_json();
         let nft_array = json["nftables"].as_array().unwrap();
 
-        // Should have base rules (14) + 3 user rules
-        assert_eq!(nft_array.len(), 17);
+        // Should have base rules (18) + 3 user rules
+        assert_eq!(nft_array.len(), 21);
 
         // Verify JSON can be serialized
         let json_str = serde_json::to_string(&json);
@@ -1192,6 +1239,8 @@ mod integration_tests {
             count: 5,
             unit: crate::core::firewall::TimeUnit::Minute,
             burst: Some(10),
+            over: false,
+            bytes: None,
         });
         rule.rebuild_caches();
         ruleset.rules.push(rule);
@@ -1309,7 +1358,7 @@ mod integration_tests {
     fn test_output_interface_json() {
         let mut ruleset = FirewallRuleset::new();
         let mut rule = create_test_rule("Output Iface", Some(443));
-        rule.output_interface = Some("eth0".to_string());
+        rule.output_interfaces = vec!["eth0".to_string()];
         rule.rebuild_caches();
         ruleset.rules.push(rule);
 
@@ -1325,4 +1374,1647 @@ mod integration_tests {
             "Should match eth0 interface"
         );
     }
+
+    /// Tests named sets are emitted as `add set` and referenced via `@name`.
+    #[test]
+    fn test_named_set_json() {
+        use crate::core::firewall::{NamedSet, SetType};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut office = NamedSet::new("office", SetType::Ipv4Addr);
+        office.addresses = vec![
+            "10.0.0.0/8".parse().unwrap(),
+            "192.168.1.0/24".parse().unwrap(),
+        ];
+        let mut web = NamedSet::new("web", SetType::InetService);
+        web.ports = vec![PortEntry::Single(80), PortEntry::Single(443)];
+        ruleset.sets = vec![office, web];
+
+        let mut rule = create_test_rule("Office Web", None);
+        rule.source_set = Some("office".to_string());
+        rule.port_set = Some("web".to_string());
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+
+        let set_defs: Vec<_> = nft_array
+            .iter()
+            .filter_map(|obj| obj.get("add").and_then(|a| a.get("set")))
+            .collect();
+        assert_eq!(
+            set_defs.len(),
+            4,
+            "Each named set should be declared, followed by the two ban blocklists"
+        );
+        assert_eq!(set_defs[0]["name"], "office");
+        assert_eq!(set_defs[0]["type"], "ipv4_addr");
+        assert_eq!(set_defs[1]["type"], "inet_service");
+
+        // Each set is flushed before its elements are added, so removed entries
+        // do not linger in the kernel after `flush table`
+        let position =
+            |pred: &dyn Fn(&serde_json::Value) -> bool| nft_array.iter().position(pred).unwrap();
+        let declare = position(&|obj| obj["add"]["set"]["name"] == "office");
+        let flush = position(&|obj| obj["flush"]["set"]["name"] == "office");
+        let elements = position(&|obj| obj["add"]["element"]["name"] == "office");
+        assert!(declare < flush && flush < elements);
+        assert_eq!(
+            nft_array[elements]["add"]["element"]["elem"][1]["prefix"]["len"],
+            24
+        );
+        assert!(set_defs[0].get("elem").is_none());
+
+        let json_str = serde_json::to_string(&json).unwrap();
+        assert!(
+            json_str.contains(r#""right":"@office""#),
+            "Source should be a named set lookup"
+        );
+        assert!(
+            json_str.contains(r#""right":"@web""#),
+            "Ports should be a named set lookup"
+        );
+    }
+
+    /// Tests that a rule referencing a missing set is skipped rather than widened.
+    #[test]
+    fn test_dangling_set_reference_skips_rule() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Dangling", Some(22));
+        rule.source_set = Some("missing".to_string());
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let json_str = serde_json::to_string(&json).unwrap();
+        assert!(
+            !json_str.contains("Dangling"),
+            "Rule with unresolved set must not be emitted"
+        );
+        assert!(!ruleset.to_nft_text().contains("Dangling"));
+    }
+
+    /// Tests that an IPv6 address set pins the rule to the ip6 family.
+    #[test]
+    fn test_ipv6_set_pins_family() {
+        use crate::core::firewall::{NamedSet, SetType};
+
+        let mut ruleset = FirewallRuleset::new();
+        ruleset.sets = vec![NamedSet::new("v6hosts", SetType::Ipv6Addr)];
+
+        let mut rule = create_test_rule("V6 Only", Some(22));
+        rule.source_set = Some("v6hosts".to_string());
+        rule.destinations = vec!["192.168.1.1".parse().unwrap(), "fd00::1".parse().unwrap()];
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let user_rules: Vec<_> = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "V6 Only")
+            .collect();
+        assert_eq!(user_rules.len(), 1, "Set family should yield a single rule");
+
+        let rule_str = serde_json::to_string(user_rules[0]).unwrap();
+        assert!(rule_str.contains("fd00::1"));
+        assert!(
+            !rule_str.contains("192.168.1.1"),
+            "IPv4 destinations must be dropped for an IPv6 set"
+        );
+    }
+
+    /// Tests named sets render in the text preview.
+    #[test]
+    fn test_named_set_text_generation() {
+        use crate::core::firewall::{NamedSet, SetType};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut office = NamedSet::new("office", SetType::Ipv4Addr);
+        office.addresses = vec!["10.0.0.0/8".parse().unwrap()];
+        ruleset.sets = vec![office];
+
+        let mut rule = create_test_rule("Office SSH", Some(22));
+        rule.source_set = Some("office".to_string());
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("set office {"));
+        assert!(text.contains("type ipv4_addr"));
+        assert!(text.contains("elements = { 10.0.0.0/8 }"));
+        assert!(text.contains("ip saddr @office"));
+    }
+
+    #[test]
+    fn test_ban_blocklist_json() {
+        use crate::core::bans::{BAN_SET_V4, BAN_SET_V6, Ban, append_active_bans};
+
+        let ruleset = FirewallRuleset::new();
+        let mut json = ruleset.to_nftables_json();
+
+        let now = chrono::Utc::now();
+        let bans = vec![Ban {
+            ip: "203.0.113.7".parse().unwrap(),
+            created_at: now,
+            expires_at: now + chrono::Duration::hours(1),
+        }];
+        append_active_bans(&mut json, &bans, now);
+        let nft_array = json["nftables"].as_array().unwrap();
+
+        // Blocklists are timeout sets declared before any rule uses them
+        let set_pos = nft_array
+            .iter()
+            .position(|obj| obj["add"]["set"]["name"] == BAN_SET_V4)
+            .expect("ipv4 blocklist should be declared");
+        assert_eq!(nft_array[set_pos]["add"]["set"]["flags"][0], "timeout");
+        assert_eq!(nft_array[set_pos + 1]["add"]["set"]["name"], BAN_SET_V6);
+
+        let drop_pos = nft_array
+            .iter()
+            .position(|obj| obj["add"]["rule"]["comment"] == "drop banned ipv4 addresses")
+            .expect("ban drop rule should exist");
+        let tracked_pos = nft_array
+            .iter()
+            .position(|obj| obj["add"]["rule"]["comment"] == "allow tracked connections")
+            .unwrap();
+        assert!(set_pos < drop_pos);
+        assert!(
+            drop_pos < tracked_pos,
+            "Bans must apply before established connections are accepted"
+        );
+
+        // Active bans are re-added with their remaining timeout
+        let last = nft_array.last().unwrap();
+        assert_eq!(last["add"]["element"]["name"], BAN_SET_V4);
+        assert_eq!(
+            last["add"]["element"]["elem"][0]["elem"]["val"],
+            "203.0.113.7"
+        );
+        assert_eq!(last["add"]["element"]["elem"][0]["elem"]["timeout"], 3600);
+    }
+
+    #[test]
+    fn test_ct_state_json() {
+        use crate::core::firewall::CtState;
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("New SSH", Some(22));
+        rule.ct_states = vec![CtState::New];
+        rule.log_enabled = true;
+        rule.rebuild_caches();
+        assert_eq!(rule.badge_display, "TCP: 22 [NEW]");
+        ruleset.rules.push(rule);
+        let mut rule = create_test_rule("Replies", None);
+        rule.ct_states = vec![CtState::Established, CtState::Related];
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let expr = json["nftables"][16]["add"]["rule"]["expr"]
+            .as_array()
+            .unwrap();
+        let ct_pos = expr
+            .iter()
+            .position(|e| e["match"]["left"]["ct"]["key"] == "state")
+            .expect("ct state match");
+        assert_eq!(expr[ct_pos]["match"]["right"], "new");
+        // The match must come before the log statement
+        assert!(ct_pos < expr.iter().position(|e| e.get("log").is_some()).unwrap());
+        assert_eq!(
+            json["nftables"][17]["add"]["rule"]["expr"][1]["match"]["right"]["set"],
+            serde_json::json!(["established", "related"])
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("tcp dport 22 ct state new log prefix"));
+        assert!(text.contains("ct state { established, related } accept"));
+    }
+
+    #[test]
+    fn test_icmp_type_json() {
+        use crate::core::firewall::IcmpType;
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Too big", None);
+        rule.protocol = Protocol::Icmpv6;
+        rule.icmp_types = vec![IcmpType::PacketTooBig];
+        rule.icmp_code = Some(0);
+        ruleset.rules.push(rule);
+        let mut rule = create_test_rule("Ping", None);
+        rule.protocol = Protocol::Icmp;
+        rule.icmp_types = vec![IcmpType::EchoRequest, IcmpType::RouterAdvertisement];
+        ruleset.rules.push(rule);
+        // Only ICMPv6-only types on ICMP (v4): rule cannot match and is skipped
+        let mut rule = create_test_rule("Invalid", None);
+        rule.protocol = Protocol::Icmp;
+        rule.icmp_types = vec![IcmpType::NeighborSolicitation];
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        let v6_expr = nft_array[16]["add"]["rule"]["expr"].as_array().unwrap();
+        assert_eq!(v6_expr[1]["match"]["left"]["payload"]["protocol"], "icmpv6");
+        assert_eq!(v6_expr[1]["match"]["right"], "packet-too-big");
+        assert_eq!(v6_expr[2]["match"]["left"]["payload"]["field"], "code");
+        let v4_expr = nft_array[17]["add"]["rule"]["expr"].as_array().unwrap();
+        assert_eq!(
+            v4_expr[1]["match"]["right"]["set"],
+            serde_json::json!(["echo-request", "router-advertisement"])
+        );
+        assert!(
+            !nft_array
+                .iter()
+                .any(|obj| obj["add"]["rule"]["comment"] == "Invalid")
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("icmpv6 type packet-too-big icmpv6 code 0 accept"));
+        assert!(text.contains("icmp type { echo-request, router-advertisement } accept"));
+        assert!(!text.contains("Invalid"));
+    }
+
+    #[test]
+    fn test_source_port_json() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("NTP replies", None);
+        rule.protocol = Protocol::Udp;
+        rule.source_ports = vec![PortEntry::Single(123)];
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let expr = &json["nftables"][16]["add"]["rule"]["expr"];
+        let sport = expr
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["match"]["left"]["payload"]["field"] == "sport")
+            .expect("sport match");
+        assert_eq!(sport["match"]["left"]["payload"]["protocol"], "udp");
+        assert_eq!(sport["match"]["right"], 123);
+        assert!(
+            !expr
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|e| e["match"]["left"]["payload"]["field"] == "dport")
+        );
+
+        assert!(
+            ruleset
+                .to_nft_text()
+                .contains("udp sport 123 accept comment \"NTP replies\"")
+        );
+    }
+
+    #[test]
+    fn test_port_forward_json() {
+        use crate::core::firewall::PortForward;
+
+        let mut ruleset = FirewallRuleset::new();
+        let chain_count = |json: &serde_json::Value| {
+            json["nftables"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|obj| obj["add"]["chain"].is_object())
+                .count()
+        };
+        // No nat chain without port forwards
+        assert_eq!(chain_count(&ruleset.to_nftables_json()), 3);
+
+        let mut rule = create_test_rule("Web VM", Some(8080));
+        rule.interfaces = vec!["eth0".to_string()];
+        rule.sources = vec![
+            "203.0.113.0/24".parse().unwrap(),
+            "2001:db8::/32".parse().unwrap(),
+        ];
+        rule.port_forward = Some(PortForward {
+            to_address: "192.168.122.10".parse().unwrap(),
+            to_port: Some(80),
+        });
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        assert_eq!(chain_count(&json), 4);
+        let nft_array = json["nftables"].as_array().unwrap();
+
+        let nat_chain = nft_array
+            .iter()
+            .find(|obj| obj["add"]["chain"]["name"] == "prerouting")
+            .expect("nat chain should exist");
+        assert_eq!(nat_chain["add"]["chain"]["type"], "nat");
+        assert_eq!(nat_chain["add"]["chain"]["prio"], -100);
+
+        let rules_in = |chain: &str| -> Vec<&serde_json::Value> {
+            nft_array
+                .iter()
+                .filter(|obj| obj["add"]["rule"]["chain"] == chain)
+                .collect()
+        };
+
+        // DNAT: IPv4 only (the IPv6 source cannot reach an IPv4 target)
+        let dnat_rules = rules_in("prerouting");
+        assert_eq!(dnat_rules.len(), 1);
+        let dnat_expr = dnat_rules[0]["add"]["rule"]["expr"].as_array().unwrap();
+        let dnat = &dnat_expr.last().unwrap()["dnat"];
+        assert_eq!(dnat["family"], "ip");
+        assert_eq!(dnat["addr"], "192.168.122.10");
+        assert_eq!(dnat["port"], 80);
+        assert!(dnat_expr.iter().any(|e| e["match"]["right"] == 8080));
+        assert!(
+            dnat_expr
+                .iter()
+                .any(|e| e["match"]["right"]["prefix"]["addr"] == "203.0.113.0")
+        );
+        assert!(
+            !dnat_expr
+                .iter()
+                .any(|e| e["match"]["right"]["prefix"]["addr"] == "2001:db8::")
+        );
+
+        // Forward chain: tracked replies, then the DNATed connection to the internal port
+        let forward_rules = rules_in("forward");
+        assert_eq!(forward_rules.len(), 3);
+        assert_eq!(
+            forward_rules[1]["add"]["rule"]["comment"],
+            "allow tracked forwarded connections"
+        );
+        let accept_expr = forward_rules[2]["add"]["rule"]["expr"].as_array().unwrap();
+        assert!(
+            accept_expr
+                .iter()
+                .any(|e| e["match"]["right"] == "192.168.122.10")
+        );
+        assert!(accept_expr.iter().any(|e| e["match"]["right"] == 80));
+        assert!(accept_expr.iter().any(|e| e["match"]["right"] == "dnat"));
+        assert!(accept_expr.last().unwrap().get("accept").is_some());
+
+        // Not emitted as an input rule
+        assert!(
+            !rules_in("input")
+                .iter()
+                .any(|r| r["add"]["rule"]["comment"] == "Web VM")
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("type nat hook prerouting priority -100"));
+        assert!(text.contains("tcp dport 8080 dnat ip to 192.168.122.10:80"));
+        assert!(text.contains("ip daddr 192.168.122.10 tcp dport 80 ct status dnat accept"));
+    }
+
+    #[test]
+    fn test_internet_sharing_json() {
+        use crate::core::firewall::{EgressProfile, SharingSettings};
+
+        let mut ruleset = FirewallRuleset::new();
+        ruleset.advanced_security.sharing = SharingSettings {
+            enabled: true,
+            upstream_interface: "wlan0".to_string(),
+            downstream_interface: "eth0".to_string(),
+            allow_dhcp_dns: true,
+        };
+        ruleset.advanced_security.egress_profile = EgressProfile::Server;
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        let comments_in = |chain: &str| -> Vec<&str> {
+            nft_array
+                .iter()
+                .filter(|obj| obj["add"]["rule"]["chain"] == chain)
+                .filter_map(|obj| obj["add"]["rule"]["comment"].as_str())
+                .collect()
+        };
+
+        let nat_chain = nft_array
+            .iter()
+            .find(|obj| obj["add"]["chain"]["name"] == "postrouting")
+            .expect("postrouting nat chain should exist");
+        assert_eq!(nat_chain["add"]["chain"]["type"], "nat");
+        assert_eq!(comments_in("postrouting"), ["masquerade shared connection"]);
+        assert_eq!(
+            comments_in("forward"),
+            [
+                "drop invalid forwarded connections",
+                "allow tracked forwarded connections",
+                "allow shared connection from downstream",
+            ]
+        );
+        let input = comments_in("input");
+        assert!(input.contains(&"allow dhcp from downstream"));
+        assert!(input.contains(&"allow dns from downstream"));
+        assert_eq!(
+            comments_in("output"),
+            ["allow dhcp and dns replies to downstream"]
+        );
+
+        // Incomplete configuration emits nothing
+        ruleset.advanced_security.sharing.downstream_interface = "wlan0".to_string();
+        let json = ruleset.to_nftables_json();
+        assert!(
+            !json["nftables"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|obj| obj["add"]["chain"]["name"] == "postrouting")
+        );
+    }
+
+    #[test]
+    fn test_forward_chain_rule() {
+        use crate::core::firewall::Chain;
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Bridge to LAN", Some(8080));
+        rule.chain = Chain::Forward;
+        rule.interfaces = vec!["br0".to_string()];
+        rule.output_interfaces = vec!["eth0".to_string()];
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        let forward: Vec<_> = nft_array
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["chain"] == "forward")
+            .collect();
+        // Conntrack base rules come first so replies to forwarded traffic pass
+        assert_eq!(
+            forward[1]["add"]["rule"]["comment"],
+            "allow tracked forwarded connections"
+        );
+        let user = forward.last().unwrap();
+        assert_eq!(user["add"]["rule"]["comment"], "Bridge to LAN");
+        let expr = user["add"]["rule"]["expr"].to_string();
+        assert!(expr.contains("\"iifname\""));
+        assert!(expr.contains("\"oifname\""));
+
+        let text = ruleset.to_nft_text();
+        let forward_chain = text.split("chain forward").nth(1).unwrap();
+        let forward_chain = forward_chain.split("chain output").next().unwrap();
+        assert!(forward_chain.contains("iifname \"br0\" oifname \"eth0\" tcp dport 8080 accept"));
+        let input_chain = text.split("chain forward").next().unwrap();
+        assert!(!input_chain.contains("Bridge to LAN"));
+    }
+
+    #[test]
+    fn test_schedule_json() {
+        use crate::core::firewall::{Schedule, Weekday};
+        use chrono::NaiveTime;
+
+        let time = |s| NaiveTime::parse_from_str(s, "%H:%M").unwrap();
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Evening games", Some(27015));
+        rule.schedule = Some(Schedule {
+            start: time("18:00"),
+            end: time("23:00"),
+            days: vec![Weekday::Saturday, Weekday::Sunday],
+        });
+        ruleset.rules.push(rule);
+        let mut rule = create_test_rule("Night backup", Some(873));
+        rule.schedule = Some(Schedule {
+            start: time("22:00"),
+            end: time("06:00"),
+            days: vec![Weekday::Monday],
+        });
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let expr = json["nftables"][16]["add"]["rule"]["expr"]
+            .as_array()
+            .unwrap();
+        let hour = expr
+            .iter()
+            .find(|e| e["match"]["left"]["meta"]["key"] == "hour")
+            .expect("hour match");
+        assert_eq!(hour["match"]["op"], "==");
+        assert_eq!(
+            hour["match"]["right"]["range"],
+            serde_json::json!(["18:00", "23:00"])
+        );
+        let day = expr
+            .iter()
+            .find(|e| e["match"]["left"]["meta"]["key"] == "day")
+            .expect("day match");
+        assert_eq!(
+            day["match"]["right"]["set"],
+            serde_json::json!(["Saturday", "Sunday"])
+        );
+
+        // A window spanning midnight is matched as "outside end-start"
+        let expr = json["nftables"][17]["add"]["rule"]["expr"]
+            .as_array()
+            .unwrap();
+        let hour = expr
+            .iter()
+            .find(|e| e["match"]["left"]["meta"]["key"] == "hour")
+            .expect("hour match");
+        assert_eq!(hour["match"]["op"], "!=");
+        assert_eq!(
+            hour["match"]["right"]["range"],
+            serde_json::json!(["06:00", "22:00"])
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains(
+            r#"tcp dport 27015 meta hour "18:00"-"23:00" meta day { "Saturday", "Sunday" } accept"#
+        ));
+        assert!(
+            text.contains(r#"tcp dport 873 meta hour != "06:00"-"22:00" meta day "Monday" accept"#)
+        );
+    }
+
+    #[test]
+    fn test_rule_counters_json() {
+        use crate::core::counters::rule_id_from_comment;
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Allow SSH", Some(22));
+        rule.log_enabled = true;
+        let id = rule.id;
+        ruleset.rules.push(rule);
+
+        // Disabled by default: plain label comment, no counter
+        let json = ruleset.to_nftables_json();
+        let user_rule = &json["nftables"][16]["add"]["rule"];
+        assert_eq!(user_rule["comment"], "Allow SSH");
+        assert!(
+            !user_rule["expr"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|e| e.get("counter").is_some())
+        );
+
+        ruleset.advanced_security.rule_counters = true;
+        let json = ruleset.to_nftables_json();
+        let user_rule = &json["nftables"][16]["add"]["rule"];
+        assert_eq!(
+            rule_id_from_comment(user_rule["comment"].as_str().unwrap()),
+            Some(id)
+        );
+        let expr = user_rule["expr"].as_array().unwrap();
+        // Counter sits right before the verdict, after the log statement
+        assert!(expr[expr.len() - 2].get("counter").is_some());
+        assert!(expr[expr.len() - 3].get("log").is_some());
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains(&format!(
+            "level info counter accept comment \"Allow SSH [{id}]\""
+        )));
+    }
+
+    #[test]
+    fn test_quota_json() {
+        use crate::core::firewall::{ByteUnit, Quota, QuotaMode};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Metered SSH", Some(22));
+        rule.quota = Some(Quota {
+            amount: 5,
+            unit: ByteUnit::GBytes,
+            mode: QuotaMode::Until,
+        });
+        rule.rate_limit = Some(crate::core::firewall::RateLimit {
+            count: 10,
+            unit: crate::core::firewall::TimeUnit::Second,
+            burst: None,
+            over: false,
+            bytes: None,
+        });
+        ruleset.rules.push(rule);
+        let mut rule = create_test_rule("Cut off", Some(80));
+        rule.action = crate::core::firewall::Action::Drop;
+        rule.quota = Some(Quota {
+            amount: 500,
+            unit: ByteUnit::MBytes,
+            mode: QuotaMode::Over,
+        });
+        ruleset.rules.push(rule);
+
+        let metered = format!("drfw_quota_{}", ruleset.rules[0].id.simple());
+        let cut_off = format!("drfw_quota_{}", ruleset.rules[1].id.simple());
+        let json = ruleset.to_nftables_json();
+        let items = json["nftables"].as_array().unwrap();
+        let quota_object = |name: &str| {
+            items
+                .iter()
+                .find(|obj| obj["add"]["quota"]["name"] == name)
+                .map(|obj| obj["add"]["quota"].clone())
+                .expect("quota object")
+        };
+        assert_eq!(quota_object(&metered)["bytes"], 5_u64 << 30);
+        assert_eq!(quota_object(&metered)["inv"], false);
+        assert_eq!(quota_object(&cut_off)["bytes"], 500_u64 << 20);
+        assert_eq!(quota_object(&cut_off)["inv"], true);
+
+        // Rules reference their quota object by name, next to the limit
+        let rule_expr = |label: &str| {
+            items
+                .iter()
+                .find(|obj| obj["add"]["rule"]["comment"] == label)
+                .and_then(|obj| obj["add"]["rule"]["expr"].as_array())
+                .expect("rule")
+                .clone()
+        };
+        let expr = rule_expr("Metered SSH");
+        let quota_pos = expr
+            .iter()
+            .position(|e| e["quota"] == metered.as_str())
+            .expect("quota reference");
+        assert!(expr[quota_pos - 1].get("limit").is_some());
+        assert!(
+            rule_expr("Cut off")
+                .iter()
+                .any(|e| e["quota"] == cut_off.as_str())
+        );
+
+        // nft has no gbytes unit: 5 GB is written as 5120 mbytes
+        let text = ruleset.to_nft_text();
+        assert!(text.contains(&format!(
+            "    quota {metered} {{\n        until 5120 mbytes\n"
+        )));
+        assert!(text.contains(&format!(
+            "    quota {cut_off} {{\n        over 500 mbytes\n"
+        )));
+        assert!(text.contains(&format!(
+            "limit rate 10/second quota name \"{metered}\" accept"
+        )));
+        assert!(text.contains(&format!("tcp dport 80 quota name \"{cut_off}\" drop")));
+    }
+
+    /// Tests that the IPv4 and IPv6 variants of a rule share one quota object.
+    #[test]
+    fn test_quota_shared_by_variants() {
+        use crate::core::firewall::{ByteUnit, Quota, QuotaMode};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Metered web", Some(443));
+        rule.sources = vec![
+            "192.168.1.0/24".parse().unwrap(),
+            "fd00::/64".parse().unwrap(),
+        ];
+        rule.quota = Some(Quota {
+            amount: 5,
+            unit: ByteUnit::GBytes,
+            mode: QuotaMode::Until,
+        });
+        rule.rebuild_caches();
+        let name = format!("drfw_quota_{}", rule.id.simple());
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let items = json["nftables"].as_array().unwrap();
+        let objects = items
+            .iter()
+            .filter(|obj| obj["add"].get("quota").is_some())
+            .count();
+        assert_eq!(objects, 1);
+        let variants: Vec<_> = items
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "Metered web")
+            .collect();
+        assert_eq!(variants.len(), 2);
+        for variant in variants {
+            let expr = variant["add"]["rule"]["expr"].as_array().unwrap();
+            assert!(expr.iter().any(|e| e["quota"] == name.as_str()));
+        }
+
+        let text = ruleset.to_nft_text();
+        assert_eq!(text.matches(&format!("quota {name} {{")).count(), 1);
+        assert!(text.contains(&format!("quota name \"{name}\" accept")));
+    }
+
+    /// Tests `limit rate over` and byte-based rate limits in both generators.
+    #[test]
+    fn test_rate_limit_over_and_bytes() {
+        use crate::core::firewall::{Action, ByteUnit, RateLimit, TimeUnit};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("SSH flood", Some(22));
+        rule.action = Action::Drop;
+        rule.rate_limit = Some(RateLimit {
+            count: 5,
+            unit: TimeUnit::Minute,
+            burst: Some(10),
+            over: true,
+            bytes: None,
+        });
+        rule.rebuild_caches();
+        assert_eq!(rule.rate_limit_display.as_deref(), Some(">5/m b10"));
+        ruleset.rules.push(rule);
+        let mut rule = create_test_rule("Downloads", Some(443));
+        rule.rate_limit = Some(RateLimit {
+            count: 2,
+            unit: TimeUnit::Second,
+            burst: Some(4),
+            over: false,
+            bytes: Some(ByteUnit::GBytes),
+        });
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let limit = |index: usize| {
+            json["nftables"][index]["add"]["rule"]["expr"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find_map(|e| e.get("limit"))
+                .cloned()
+                .expect("limit statement")
+        };
+        assert_eq!(
+            limit(16),
+            serde_json::json!({ "rate": 5, "per": "minute", "burst": 10, "inv": true })
+        );
+        // nft has no gbytes unit: rate and burst are given in mbytes
+        assert_eq!(
+            limit(17),
+            serde_json::json!({
+                "rate": 2048,
+                "rate_unit": "mbytes",
+                "per": "second",
+                "burst": 4096,
+                "burst_unit": "mbytes"
+            })
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("limit rate over 5/minute burst 10 packets drop"));
+        assert!(text.contains("limit rate 2048 mbytes/second burst 4096 mbytes accept"));
+    }
+
+    /// Tests per-source limits: dynamic sets per family and `update`/`add` set statements.
+    #[test]
+    fn test_per_source_limits_json() {
+        use crate::core::firewall::{PerSourceLimit, RateLimit, TimeUnit};
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("SSH", Some(22));
+        rule.rate_limit = Some(RateLimit {
+            count: 3,
+            unit: TimeUnit::Minute,
+            burst: None,
+            over: false,
+            bytes: None,
+        });
+        rule.connection_limit = 4;
+        rule.per_source = Some(PerSourceLimit {
+            size: 1024,
+            timeout_secs: 120,
+        });
+        rule.rebuild_caches();
+        assert_eq!(rule.rate_limit_display.as_deref(), Some("3/m per IP"));
+        let id = rule.id.simple().to_string();
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let items = json["nftables"].as_array().unwrap();
+        let sets: Vec<_> = items
+            .iter()
+            .filter_map(|i| i["add"]["set"].as_object())
+            .filter(|s| s["name"].as_str().unwrap().starts_with("drfw_meter_"))
+            .collect();
+        assert_eq!(sets.len(), 4, "rate + conn set for each family");
+        let rate_v4 = sets
+            .iter()
+            .find(|s| s["name"] == format!("drfw_meter_rate_v4_{id}"))
+            .unwrap();
+        assert_eq!(rate_v4["flags"], serde_json::json!(["dynamic", "timeout"]));
+        assert_eq!(rate_v4["timeout"], 120);
+        assert_eq!(rate_v4["size"], 1024);
+        // ct count can't live in a timeout set
+        let conn_v6 = sets
+            .iter()
+            .find(|s| s["name"] == format!("drfw_meter_conn_v6_{id}"))
+            .unwrap();
+        assert_eq!(conn_v6["flags"], serde_json::json!(["dynamic"]));
+        assert!(conn_v6.get("timeout").is_none());
+
+        // A rule without addresses is split into an IPv4 and an IPv6 variant
+        let rules: Vec<_> = items
+            .iter()
+            .filter(|i| i["add"]["rule"]["comment"] == "SSH")
+            .collect();
+        assert_eq!(rules.len(), 2);
+        let expr = rules[1]["add"]["rule"]["expr"].as_array().unwrap();
+        assert_eq!(expr[0]["match"]["right"], "ipv6");
+        let update = expr
+            .iter()
+            .find_map(|e| e.get("set").filter(|s| s["op"] == "update"))
+            .expect("rate limit set update");
+        assert_eq!(update["set"], format!("@drfw_meter_rate_v6_{id}"));
+        assert_eq!(update["elem"]["payload"]["protocol"], "ip6");
+        assert_eq!(update["stmt"][0]["limit"]["rate"], 3);
+        assert!(
+            expr.iter()
+                .any(|e| e["set"]["op"] == "add" && e["set"]["stmt"][0]["ct count"]["val"] == 4)
+        );
+        assert!(!expr.iter().any(|e| e.get("limit").is_some()));
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains(&format!("set drfw_meter_rate_v4_{id} {{")));
+        assert!(text.contains("flags dynamic,timeout"));
+        assert!(text.contains(&format!(
+            "meta nfproto ipv4 tcp dport 22 update @drfw_meter_rate_v4_{id} {{ ip saddr limit rate 3/minute }} add @drfw_meter_conn_v4_{id} {{ ip saddr ct count 4 }} accept"
+        )));
+        assert!(text.contains(&format!(
+            "update @drfw_meter_rate_v6_{id} {{ ip6 saddr limit rate 3/minute }}"
+        )));
+    }
+
+    /// Tests the stage sets and rules generated for a port-knocking sequence.
+    #[test]
+    fn test_port_knock_json_and_text() {
+        use crate::core::firewall::PortKnock;
+
+        let mut ruleset = FirewallRuleset::new();
+        ruleset.knocks.push(PortKnock {
+            name: "ssh".to_string(),
+            knock_protocol: Protocol::Tcp,
+            sequence: vec![7000, 8000, 9000],
+            protocol: Protocol::Tcp,
+            port: 22,
+            timeout_secs: 15,
+        });
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        let knock_sets: Vec<_> = nft_array
+            .iter()
+            .filter_map(|obj| obj["add"].get("set"))
+            .filter(|set| set["name"].as_str().unwrap().starts_with("drfw_knock_"))
+            .collect();
+        assert_eq!(knock_sets.len(), 6, "One set per stage and family");
+        assert_eq!(knock_sets[0]["name"], "drfw_knock_ssh_1_v4");
+        assert_eq!(knock_sets[0]["timeout"], 15);
+        assert_eq!(knock_sets[5]["type"], "ipv6_addr");
+
+        let rules: Vec<_> = nft_array
+            .iter()
+            .filter_map(|obj| obj["add"].get("rule"))
+            .filter(|rule| {
+                rule["comment"]
+                    .as_str()
+                    .is_some_and(|c| c.starts_with("knock ssh"))
+            })
+            .collect();
+        assert_eq!(rules.len(), 8, "Three stages and the accept, per family");
+
+        let first = rules[0]["expr"].as_array().unwrap();
+        assert_eq!(rules[0]["comment"], "knock ssh: stage 1/3");
+        assert!(
+            !first
+                .iter()
+                .any(|e| e["match"]["right"] == "@drfw_knock_ssh_1_v4")
+        );
+        assert_eq!(first.last().unwrap()["set"]["set"], "@drfw_knock_ssh_1_v4");
+
+        let second = rules[1]["expr"].as_array().unwrap();
+        assert!(
+            second
+                .iter()
+                .any(|e| e["match"]["right"] == "@drfw_knock_ssh_1_v4")
+        );
+        assert_eq!(second.last().unwrap()["set"]["set"], "@drfw_knock_ssh_2_v4");
+
+        let open = rules[7]["expr"].as_array().unwrap();
+        assert_eq!(rules[7]["comment"], "knock ssh: open tcp/22");
+        assert!(
+            open.iter()
+                .any(|e| e["match"]["right"] == "@drfw_knock_ssh_3_v6")
+        );
+        assert!(open.last().unwrap().get("accept").is_some());
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("set drfw_knock_ssh_3_v6 {"));
+        assert!(text.contains("timeout 15s"));
+        assert!(text.contains("# --- Port Knocking ---"));
+        assert!(text.contains(
+            "meta nfproto ipv4 tcp dport 8000 ip saddr @drfw_knock_ssh_1_v4 update @drfw_knock_ssh_2_v4 { ip saddr } comment \"knock ssh: stage 2/3\""
+        ));
+        assert!(text.contains(
+            "meta nfproto ipv6 tcp dport 22 ip6 saddr @drfw_knock_ssh_3_v6 accept comment \"knock ssh: open tcp/22\""
+        ));
+    }
+
+    /// Tests that attached blocklists add interval sets and early drop rules.
+    #[test]
+    fn test_blocklist_sets_and_rules() {
+        use crate::core::blocklists::{
+            BLOCKLIST_SET_V4, BLOCKLIST_SET_V6, LoadedBlocklist, append_blocklist_elements,
+        };
+
+        let mut ruleset = FirewallRuleset::new();
+        let json = ruleset.to_nftables_json();
+        assert!(
+            !serde_json::to_string(&json)
+                .unwrap()
+                .contains(BLOCKLIST_SET_V4),
+            "No blocklist sets without attached files"
+        );
+
+        let path = std::path::PathBuf::from("/etc/drfw/drop.txt");
+        ruleset.blocklists.push(path.clone());
+        let mut json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+
+        let set = nft_array
+            .iter()
+            .find(|obj| obj["add"]["set"]["name"] == BLOCKLIST_SET_V4)
+            .expect("blocklist set");
+        assert_eq!(set["add"]["set"]["flags"][0], "interval");
+
+        let comments: Vec<&str> = nft_array
+            .iter()
+            .filter_map(|obj| obj["add"]["rule"]["comment"].as_str())
+            .collect();
+        let blocklist_drop = comments
+            .iter()
+            .position(|c| *c == "drop blocklisted ipv6 addresses")
+            .expect("blocklist drop rule");
+        let ban_drop = comments
+            .iter()
+            .position(|c| *c == "drop banned ipv6 addresses")
+            .unwrap();
+        let established = comments
+            .iter()
+            .position(|c| *c == "allow tracked connections")
+            .unwrap();
+        assert!(ban_drop < blocklist_drop && blocklist_drop < established);
+
+        let loaded = vec![LoadedBlocklist {
+            path: path.clone(),
+            loaded_at: chrono::Utc::now(),
+            entries: vec![
+                "192.0.2.0/25".parse().unwrap(),
+                "192.0.2.128/25".parse().unwrap(),
+                "2001:db8::1".parse().unwrap(),
+            ],
+            invalid_lines: 0,
+        }];
+        append_blocklist_elements(&mut json, &ruleset.blocklists, &loaded);
+        let nft_array = json["nftables"].as_array().unwrap();
+        // Both sets are emptied before the merged ranges are added
+        let flushes: Vec<_> = nft_array[nft_array.len() - 4..nft_array.len() - 2]
+            .iter()
+            .map(|obj| &obj["flush"]["set"]["name"])
+            .collect();
+        assert_eq!(flushes, [BLOCKLIST_SET_V4, BLOCKLIST_SET_V6]);
+        let v4 = &nft_array[nft_array.len() - 2]["add"]["element"];
+        assert_eq!(v4["name"], BLOCKLIST_SET_V4);
+        assert_eq!(v4["elem"][0]["range"][0], "192.0.2.0");
+        assert_eq!(v4["elem"][0]["range"][1], "192.0.2.255");
+        let v6 = &nft_array[nft_array.len() - 1]["add"]["element"];
+        assert_eq!(v6["name"], BLOCKLIST_SET_V6);
+        assert_eq!(v6["elem"][0], "2001:db8::1");
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("set drfw_blocklist_v4 {"));
+        assert!(text.contains("# from /etc/drfw/drop.txt"));
+        assert!(text.contains(
+            "ip saddr @drfw_blocklist_v4 drop comment \"drop blocklisted ipv4 addresses\""
+        ));
+    }
+
+    #[test]
+    fn test_geoip_country_sets() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Web from DACH", Some(443));
+        rule.countries = vec!["DE".to_string(), "AT".to_string(), "CH".to_string()];
+        rule.rebuild_caches();
+        assert_eq!(rule.sources_display, "@geo DE, AT, CH");
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        for name in ["drfw_geo_at_ch_de_v4", "drfw_geo_at_ch_de_v6"] {
+            let set = nft_array
+                .iter()
+                .find(|obj| obj["add"]["set"]["name"] == name)
+                .expect("geo set");
+            assert_eq!(set["add"]["set"]["flags"][0], "interval");
+        }
+
+        // One rule per family, each looking up its own set
+        let lookups: Vec<String> = nft_array
+            .iter()
+            .filter(|obj| {
+                obj["add"]["rule"]["comment"]
+                    .as_str()
+                    .is_some_and(|c| c.contains("Web from DACH"))
+            })
+            .map(|obj| obj["add"]["rule"]["expr"].to_string())
+            .collect();
+        assert_eq!(lookups.len(), 2);
+        assert!(lookups[0].contains("@drfw_geo_at_ch_de_v4"));
+        assert!(lookups[1].contains("@drfw_geo_at_ch_de_v6"));
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("set drfw_geo_at_ch_de_v4 {"));
+        assert!(text.contains("# GeoIP ranges of AT, CH, DE"));
+        assert!(text.contains("ip saddr @drfw_geo_at_ch_de_v4 "));
+        assert!(text.contains("ip6 saddr @drfw_geo_at_ch_de_v6 "));
+    }
+
+    #[test]
+    fn test_rule_group_chains() {
+        use crate::core::firewall::RuleGroup;
+
+        let mut ruleset = FirewallRuleset::new();
+        let group = RuleGroup::new("Media server".to_string());
+        let chain = group.chain_name(crate::core::firewall::Chain::Input);
+        for (label, port) in [("Jellyfin", 8096), ("Plex", 32400)] {
+            let mut rule = create_test_rule(label, Some(port));
+            rule.group = Some(group.id);
+            ruleset.rules.push(rule);
+        }
+        ruleset.rules.push(create_test_rule("SSH", Some(22)));
+        ruleset.groups.push(group);
+
+        let json = ruleset.to_nftables_json();
+        let nft_array = json["nftables"].as_array().unwrap();
+        let group_chain = nft_array
+            .iter()
+            .find(|obj| obj["add"]["chain"]["name"] == chain.as_str())
+            .expect("group chain");
+        assert!(group_chain["add"]["chain"].get("hook").is_none());
+
+        // A single jump from the input chain, placed before the ungrouped rule
+        let jumps: Vec<usize> = nft_array
+            .iter()
+            .enumerate()
+            .filter(|(_, obj)| obj["add"]["rule"]["comment"] == "group: Media server")
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(jumps.len(), 1);
+        let jump = &nft_array[jumps[0]]["add"]["rule"];
+        assert_eq!(jump["chain"], "input");
+        assert_eq!(jump["expr"][0]["jump"]["target"], chain.as_str());
+
+        // Members live in the group chain, ungrouped rules stay in input
+        for obj in nft_array {
+            let Some(comment) = obj["add"]["rule"]["comment"].as_str() else {
+                continue;
+            };
+            if comment.contains("Jellyfin") || comment.contains("Plex") {
+                assert_eq!(obj["add"]["rule"]["chain"], chain.as_str());
+            } else if comment.contains("SSH") {
+                assert_eq!(obj["add"]["rule"]["chain"], "input");
+            }
+        }
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains(&format!("jump {chain} comment \"group: Media server\"")));
+        assert!(text.contains(&format!("chain {chain} {{")));
+
+        // Disabling the group removes its chain and jump
+        ruleset.groups[0].enabled = false;
+        let json = ruleset.to_nftables_json();
+        let dump = json.to_string();
+        assert!(!dump.contains(&chain));
+        assert!(!dump.contains("Jellyfin"));
+        assert!(dump.contains("SSH"));
+    }
+
+    #[test]
+    fn test_rule_metadata_not_compiled() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("SSH", Some(22));
+        rule.description = "Admin access \"only\"\nSee runbook".to_string();
+        rule.owner = "ops\u{7}-team".to_string();
+        rule.reference = "OPS-1234".to_string();
+        rule.rebuild_caches();
+        assert_eq!(rule.owner, "ops-team");
+        assert!(rule.metadata_lowercase.contains("see runbook"));
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json().to_string();
+        let text = ruleset.to_nft_text();
+        for needle in ["runbook", "ops-team", "OPS-1234"] {
+            assert!(!json.contains(needle));
+            assert!(!text.contains(needle));
+        }
+    }
+
+    #[test]
+    fn test_unicode_label_comment_and_log_prefix() {
+        let mut ruleset = FirewallRuleset::new();
+        ruleset.advanced_security.rule_counters = true;
+        let mut rule = create_test_rule(&"Zugang für Büro 🖨 ".repeat(4), Some(631));
+        rule.log_enabled = true;
+        rule.rebuild_caches();
+        assert_eq!(
+            rule.label.chars().count(),
+            crate::validators::MAX_LABEL_LENGTH
+        );
+        assert!(rule.label.starts_with("Zugang für Büro 🖨"));
+        assert!(rule.log_prefix.starts_with("DRFW-ZugangfuerBuero"));
+        assert!(rule.log_prefix.is_ascii());
+        let id = rule.id;
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let comment = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find_map(|obj| {
+                obj["add"]["rule"]["comment"]
+                    .as_str()
+                    .filter(|c| c.starts_with("Zugang"))
+            })
+            .expect("user rule comment");
+        assert!(comment.len() <= crate::validators::MAX_COMMENT_BYTES);
+        assert_eq!(
+            crate::core::counters::rule_id_from_comment(comment),
+            Some(id)
+        );
+    }
+
+    /// Tests that tags are shortened by characters, not bytes, so a multibyte
+    /// character straddling the cut does not panic.
+    #[test]
+    fn test_unicode_tag_truncation() {
+        let mut rule = create_test_rule("Printer", Some(631));
+        rule.tags = vec!["ü".repeat(9), "ü".repeat(20), "büro".to_string()];
+        rule.rebuild_caches();
+        assert_eq!(
+            rule.tags_truncated,
+            vec![
+                "ü".repeat(9),
+                format!("{}…", "ü".repeat(15)),
+                "büro".to_string()
+            ]
+        );
+        assert_eq!(
+            crate::core::firewall::Rule::truncate_tag(&"ü".repeat(16)),
+            "ü".repeat(16)
+        );
+    }
+
+    #[test]
+    fn test_expired_rules_skipped() {
+        let now = chrono::Utc::now();
+        let mut ruleset = FirewallRuleset::new();
+        let mut expired = create_test_rule("Contractor", Some(2201));
+        expired.expires_at = Some(now - chrono::Duration::minutes(5));
+        let mut future = create_test_rule("LAN party", Some(2202));
+        future.expires_at = Some(now + chrono::Duration::days(2));
+        ruleset.rules.push(expired);
+        ruleset.rules.push(future);
+
+        let expired: Vec<_> = ruleset.expired_rules(now).map(|r| &r.label).collect();
+        assert_eq!(expired, ["Contractor"]);
+
+        let json = ruleset.to_nftables_json().to_string();
+        let text = ruleset.to_nft_text();
+        assert!(!json.contains("2201"));
+        assert!(!text.contains("2201"));
+        assert!(json.contains("2202"));
+        assert!(text.contains("2202"));
+
+        // One generation pass agrees on expiry: the group chain, its jump and the
+        // quota object come and go together with the expiring rule
+        let group = crate::core::firewall::RuleGroup::new("Contractors".to_string());
+        let chain = group.chain_name(crate::core::firewall::Chain::Input);
+        let expiry = now + chrono::Duration::minutes(5);
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Contractor", Some(2201));
+        rule.expires_at = Some(expiry);
+        rule.group = Some(group.id);
+        rule.quota = Some(crate::core::firewall::Quota {
+            amount: 1,
+            unit: crate::core::firewall::ByteUnit::GBytes,
+            mode: crate::core::firewall::QuotaMode::Until,
+        });
+        ruleset.groups.push(group);
+        ruleset.rules.push(rule);
+        let before = ruleset
+            .to_nftables_json_at(expiry - chrono::Duration::seconds(1))
+            .to_string();
+        let after = ruleset.to_nftables_json_at(expiry).to_string();
+        assert!(before.contains("2201") && before.contains(&chain));
+        assert!(before.contains("drfw_quota_"));
+        assert!(!after.contains("2201") && !after.contains(&chain));
+        assert!(!after.contains("drfw_quota_"));
+    }
+
+    #[test]
+    fn test_negated_matches() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Not LAN", Some(22));
+        rule.sources = vec!["10.0.0.0/8".parse().unwrap()];
+        rule.interfaces = vec!["wg0".to_string()];
+        rule.negate.sources = true;
+        rule.negate.ports = true;
+        rule.negate.interface = true;
+        rule.rebuild_caches();
+        assert_eq!(rule.interface_display, "not @wg0");
+        assert_eq!(rule.sources_display, "not 10.0.0.0/8");
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let user_rules: Vec<_> = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "Not LAN")
+            .collect();
+        // The IPv4 rule excludes the network; IPv6 traffic is left unfiltered
+        assert_eq!(user_rules.len(), 2);
+        let ops = |rule: &serde_json::Value| -> Vec<(String, String)> {
+            rule["add"]["rule"]["expr"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter_map(|e| {
+                    let m = &e["match"];
+                    let left = m["left"]["payload"]["field"]
+                        .as_str()
+                        .or(m["left"]["meta"]["key"].as_str())?;
+                    Some((left.to_string(), m["op"].as_str()?.to_string()))
+                })
+                .collect()
+        };
+        let v4 = ops(user_rules[0]);
+        assert!(v4.contains(&("saddr".into(), "!=".into())));
+        assert!(v4.contains(&("iifname".into(), "!=".into())));
+        assert!(v4.contains(&("dport".into(), "!=".into())));
+        let v6 = ops(user_rules[1]);
+        assert!(v6.contains(&("nfproto".into(), "==".into())));
+        assert!(!v6.iter().any(|(field, _)| field == "saddr"));
+        assert!(v6.contains(&("dport".into(), "!=".into())));
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("ip saddr != 10.0.0.0/8 iifname != \"wg0\" tcp dport != 22"));
+        assert!(text.contains("meta nfproto ipv6 iifname != \"wg0\" tcp dport != 22"));
+    }
+
+    /// Tests that source ports negate independently of destination ports.
+    #[test]
+    fn test_negated_source_ports() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Not NTP", Some(123));
+        rule.protocol = Protocol::Udp;
+        rule.source_ports = vec![PortEntry::Single(123)];
+        rule.negate.source_ports = true;
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let user_rule = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|obj| obj["add"]["rule"]["comment"] == "Not NTP")
+            .unwrap();
+        let op = |field: &str| {
+            user_rule["add"]["rule"]["expr"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|e| e["match"]["left"]["payload"]["field"] == field)
+                .map(|e| e["match"]["op"].clone())
+        };
+        assert_eq!(op("sport"), Some(serde_json::json!("!=")));
+        assert_eq!(op("dport"), Some(serde_json::json!("==")));
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("udp sport != 123 dport 123"));
+    }
+
+    /// Tests that address ranges compile to nft `range` expressions, both as a
+    /// single match and inside an anonymous set, and split per IP family.
+    #[test]
+    fn test_address_ranges() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("DHCP pool", Some(22));
+        rule.sources = vec![
+            "192.168.1.100-192.168.1.150".parse().unwrap(),
+            "10.0.0.0/8".parse().unwrap(),
+            "fd00::10-fd00::20".parse().unwrap(),
+        ];
+        rule.destinations = vec!["192.168.1.1-192.168.1.3".parse().unwrap()];
+        rule.rebuild_caches();
+        assert_eq!(
+            rule.sources_display, "3 addresses",
+            "Ranges count as one entry each"
+        );
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let user_rules: Vec<_> = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "DHCP pool")
+            .collect();
+        // IPv6 sources have no IPv6 destination to pair with
+        assert_eq!(user_rules.len(), 1);
+
+        let right = |field: &str| {
+            user_rules[0]["add"]["rule"]["expr"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|e| e["match"]["left"]["payload"]["field"] == field)
+                .map(|e| e["match"]["right"].clone())
+                .unwrap()
+        };
+        assert_eq!(
+            right("saddr"),
+            serde_json::json!({ "set": [
+                { "range": ["192.168.1.100", "192.168.1.150"] },
+                { "prefix": { "addr": "10.0.0.0", "len": 8 } },
+            ] })
+        );
+        assert_eq!(
+            right("daddr"),
+            serde_json::json!({ "range": ["192.168.1.1", "192.168.1.3"] })
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("ip saddr { 192.168.1.100-192.168.1.150, 10.0.0.0/8 }"));
+        assert!(text.contains("ip daddr 192.168.1.1-192.168.1.3"));
+
+        // Without destinations, each family gets its own rule
+        ruleset.rules[0].destinations.clear();
+        let json = ruleset.to_nftables_json();
+        let v6_range = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "DHCP pool")
+            .flat_map(|obj| obj["add"]["rule"]["expr"].as_array().unwrap())
+            .any(|e| {
+                e["match"]["right"] == serde_json::json!({ "range": ["fd00::10", "fd00::20"] })
+            });
+        assert!(v6_range);
+
+        // Existing profiles store plain CIDR strings; ranges round-trip as objects
+        let saved = serde_json::to_string(&ruleset.rules[0].sources).unwrap();
+        let loaded: Vec<AddressEntry> = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded, ruleset.rules[0].sources);
+        let legacy: Vec<AddressEntry> = serde_json::from_str(r#"["10.0.0.0/8"]"#).unwrap();
+        assert_eq!(legacy, vec!["10.0.0.0/8".parse().unwrap()]);
+    }
+
+    /// Tests that several interfaces compile to an `iifname` set and that profiles
+    /// storing a single interface name still load.
+    #[test]
+    fn test_multiple_interfaces() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Jellyfin", Some(8096));
+        rule.interfaces = vec!["wlan0".to_string(), "eth0".to_string()];
+        rule.rebuild_caches();
+        assert_eq!(rule.interface_display, "@wlan0 +1");
+        assert_eq!(rule.interface_lowercase.as_deref(), Some("wlan0 eth0"));
+        ruleset.rules.push(rule);
+
+        let json = ruleset.to_nftables_json();
+        let iifname = json["nftables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|obj| obj["add"]["rule"]["comment"] == "Jellyfin")
+            .flat_map(|obj| obj["add"]["rule"]["expr"].as_array().unwrap())
+            .find(|e| e["match"]["left"]["meta"]["key"] == "iifname")
+            .expect("Rule should match iifname");
+        assert_eq!(
+            iifname["match"]["right"],
+            serde_json::json!({ "set": ["wlan0", "eth0"] })
+        );
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("iifname { \"wlan0\", \"eth0\" } tcp dport 8096"));
+
+        // Older profiles stored a single name (or null) under `interface`
+        let mut legacy = serde_json::to_value(&ruleset.rules[0]).unwrap();
+        let fields = legacy.as_object_mut().unwrap();
+        fields.remove("interfaces");
+        fields.insert("interface".to_string(), serde_json::json!("eth0"));
+        fields.insert("output_interface".to_string(), serde_json::Value::Null);
+        fields.remove("output_interfaces");
+        let loaded: crate::core::firewall::Rule = serde_json::from_value(legacy).unwrap();
+        assert_eq!(loaded.interfaces, vec!["eth0".to_string()]);
+        assert!(loaded.output_interfaces.is_empty());
+    }
+
+    /// Tests that a wildcard interface is matched on its own, and that a rule
+    /// mixing a wildcard into an interface list is skipped rather than emitted as
+    /// an anonymous set some nft versions reject.
+    #[test]
+    fn test_wildcard_interfaces() {
+        let mut ruleset = FirewallRuleset::new();
+        let mut single = create_test_rule("Docker", Some(9000));
+        single.interfaces = vec!["docker*".to_string()];
+        single.rebuild_caches();
+        let mut mixed = create_test_rule("Mixed", Some(9001));
+        mixed.interfaces = vec!["wlan0".to_string(), "eth*".to_string()];
+        mixed.rebuild_caches();
+        assert!(single.interface_lists_valid());
+        assert!(!mixed.interface_lists_valid());
+        ruleset.rules = vec![single, mixed];
+
+        let json = ruleset.to_nftables_json();
+        let rules_for = |label: &str| -> Vec<serde_json::Value> {
+            json["nftables"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|obj| obj["add"]["rule"]["comment"] == label)
+                .cloned()
+                .collect()
+        };
+        let docker = rules_for("Docker");
+        assert!(!docker.is_empty());
+        let iifname = docker[0]["add"]["rule"]["expr"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["match"]["left"]["meta"]["key"] == "iifname")
+            .expect("Rule should match iifname");
+        assert_eq!(iifname["match"]["right"], "docker*");
+        assert!(rules_for("Mixed").is_empty());
+
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("iifname \"docker*\" tcp dport 9000"));
+        assert!(text.contains("# skipped \"Mixed\": wildcard interface in a list"));
+        assert!(!text.contains("dport 9001"));
+    }
+
+    #[test]
+    fn test_hostname_addresses() {
+        use crate::core::firewall::{NamedSet, SetType};
+        use crate::core::hosts::{self, HostAddresses};
+        use crate::core::subnets;
+
+        let nas: AddressEntry = "NAS.local".parse().unwrap();
+        assert_eq!(nas, AddressEntry::Host("nas.local".to_string()));
+        let json = serde_json::to_value(&nas).unwrap();
+        assert_eq!(json, serde_json::json!("nas.local"));
+        assert_eq!(serde_json::from_value::<AddressEntry>(json).unwrap(), nas);
+        // Strings that are neither addresses nor hostnames fail to load
+        assert!(serde_json::from_str::<AddressEntry>(r#""192.168.1""#).is_err());
+        assert!(serde_json::from_str::<AddressEntry>(r#""not a host!""#).is_err());
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Backup", Some(22));
+        rule.sources = vec![nas.clone(), "10.0.0.0/8".parse().unwrap()];
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+        let mut set = NamedSet::new("servers", SetType::Ipv4Addr);
+        set.addresses = vec!["192.168.1.1".parse().unwrap(), nas];
+        ruleset.sets.push(set);
+        assert_eq!(
+            hosts::hostnames(&ruleset).into_iter().collect::<Vec<_>>(),
+            vec!["nas.local".to_string()]
+        );
+
+        let backup_rules = |json: &serde_json::Value| -> Vec<serde_json::Value> {
+            json["nftables"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|obj| obj["add"]["rule"]["comment"] == "Backup")
+                .cloned()
+                .collect()
+        };
+
+        // Unresolved: the rule is skipped rather than widened to 10.0.0.0/8 only
+        assert!(backup_rules(&ruleset.to_nftables_json()).is_empty());
+        let text = ruleset.to_nft_text();
+        assert!(text.contains("# skipped \"Backup\": unresolved nas.local"));
+        assert!(text.contains("elements = { 192.168.1.1/32 }"));
+
+        let resolved: HostAddresses = [(
+            "nas.local".to_string(),
+            vec!["192.168.1.20".parse().unwrap(), "fd00::20".parse().unwrap()],
+        )]
+        .into();
+        let resolved_ruleset = subnets::expanded_ruleset(&ruleset, &[], &resolved);
+        let rules = backup_rules(&resolved_ruleset.to_nftables_json());
+        assert_eq!(rules.len(), 2, "One rule per resolved address family");
+        let text = resolved_ruleset.to_nft_text();
+        assert!(text.contains("ip saddr { 192.168.1.20/32, 10.0.0.0/8 }"));
+        assert!(text.contains("ip6 saddr fd00::20/128"));
+        // The IPv4 set only takes the IPv4 address
+        assert!(text.contains("elements = { 192.168.1.1/32, 192.168.1.20/32 }"));
+        // The ruleset itself keeps the name
+        assert_eq!(ruleset.rules[0].sources[0].to_string(), "nas.local");
+
+        // A failed lookup keeps the rule skipped and is reported
+        let failed: HostAddresses = [("nas.local".to_string(), Vec::new())].into();
+        let names = hosts::hostnames(&ruleset);
+        assert!(
+            backup_rules(&subnets::expanded_ruleset(&ruleset, &[], &failed).to_nftables_json())
+                .is_empty()
+        );
+        assert_eq!(
+            hosts::resolution_warnings(&names, &failed, None),
+            vec!["nas.local did not resolve; rules using it are skipped".to_string()]
+        );
+
+        // Changes are reported against the addresses of the last apply
+        let moved: HostAddresses = [(
+            "nas.local".to_string(),
+            vec!["192.168.1.21".parse().unwrap()],
+        )]
+        .into();
+        assert!(hosts::resolution_warnings(&names, &resolved, Some(&resolved)).is_empty());
+        assert_eq!(
+            hosts::resolution_warnings(&names, &moved, Some(&resolved)),
+            vec![
+                "nas.local changed since last apply: 192.168.1.20, fd00::20 → 192.168.1.21"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interface_subnets() {
+        use crate::core::firewall::{NamedSet, SetType};
+        use crate::core::hosts::HostAddresses;
+        use crate::core::subnets::{self, LocalSubnet};
+
+        let lan: AddressEntry = "subnet:wlan0".parse().unwrap();
+        assert_eq!(lan.to_string(), "subnet:wlan0");
+        assert!("subnet:".parse::<AddressEntry>().is_err());
+        assert!("subnet:eth0; drop".parse::<AddressEntry>().is_err());
+        let json = serde_json::to_value(&lan).unwrap();
+        assert_eq!(json, serde_json::json!({ "subnet_of": "wlan0" }));
+        assert_eq!(serde_json::from_value::<AddressEntry>(json).unwrap(), lan);
+
+        let mut ruleset = FirewallRuleset::new();
+        let mut rule = create_test_rule("Syncthing", Some(22000));
+        rule.sources = vec![lan];
+        rule.rebuild_caches();
+        ruleset.rules.push(rule);
+        let mut set = NamedSet::new("containers", SetType::Ipv4Addr);
+        set.addresses = vec!["subnet:docker*".parse().unwrap()];
+        ruleset.sets.push(set);
+        assert!(subnets::uses_subnets(&ruleset));
+
+        let syncthing_rules = |json: &serde_json::Value| {
+            json["nftables"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|obj| obj["add"]["rule"]["comment"] == "Syncthing")
+                .count()
+        };
+
+        // Unexpanded: skipped rather than matching any source
+        assert_eq!(syncthing_rules(&ruleset.to_nftables_json()), 0);
+        assert!(
+            ruleset
+                .to_nft_text()
+                .contains("# skipped \"Syncthing\": unresolved subnet:wlan0")
+        );
+
+        let local = |interface: &str, ip: &str, mask: &str| {
+            LocalSubnet::new(interface, ip.parse().unwrap(), mask.parse().unwrap()).unwrap()
+        };
+        let home = vec![
+            local("wlan0", "192.168.1.20", "255.255.255.0"),
+            local("wlan0", "fd00:1::20", "ffff:ffff:ffff:ffff::"),
+            local("docker0", "172.17.0.1", "255.255.0.0"),
+            local("docker1", "fd00:d::1", "ffff:ffff:ffff:ffff::"),
+            local("eth0", "10.0.0.5", "255.0.0.0"),
+        ];
+        let expanded = subnets::expanded_ruleset(&ruleset, &home, &HostAddresses::new());
+        assert_eq!(syncthing_rules(&expanded.to_nftables_json()), 2);
+        let text = expanded.to_nft_text();
+        assert!(text.contains("ip saddr 192.168.1.0/24"));
+        assert!(text.contains("ip6 saddr fd00:1::/64"));
+        // The wildcard matches both bridges, the IPv4 set only takes IPv4 networks
+        assert!(text.contains("elements = { 172.17.0.0/16 }"));
+        assert!(!text.contains("10.0.0.0/8"));
+
+        // Warnings for entries without networks and for networks that moved
+        assert_eq!(
+            subnets::expansion_warnings(&ruleset, &[], None),
+            vec![
+                "subnet:docker* matches no interface address; rules using it are skipped"
+                    .to_string(),
+                "subnet:wlan0 matches no interface address; rules using it are skipped".to_string(),
+            ]
+        );
+        let office = vec![
+            local("wlan0", "10.20.0.7", "255.255.252.0"),
+            local("docker0", "172.17.0.1", "255.255.0.0"),
+        ];
+        assert_eq!(
+            subnets::expansion_warnings(&ruleset, &office, Some(&home)),
+            vec![
+                "subnet:docker* changed since last apply: 172.17.0.0/16, fd00:d::/64 → 172.17.0.0/16"
+                    .to_string(),
+                "subnet:wlan0 changed since last apply: 192.168.1.0/24, fd00:1::/64 → 10.20.0.0/22"
+                    .to_string(),
+            ]
+        );
+        assert!(subnets::interface_matches("docker*", "docker1"));
+        assert!(!subnets::interface_matches("wlan0", "wlan01"));
+    }
 }
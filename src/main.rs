@@ -43,6 +43,13 @@
 //! drfw apply my-profile --confirm    # Apply with 15s auto-revert
 //! drfw apply my-profile --confirm 60 # Apply with 60s auto-revert
 //! drfw export my-profile --format nft  # Export as nftables config
+//! drfw ban 203.0.113.7 --for 24h     # Temporarily ban an address
+//! drfw unban 203.0.113.7             # Lift a ban
+//! drfw bans                          # List active bans
+//! drfw blocklist refresh             # Re-read blocklist files into the live sets
+//! drfw geoip rebuild                 # Rebuild country sets from the GeoIP database
+//! drfw expired                       # List expired rules in the active profile
+//! drfw prune-expired --apply         # Remove them and re-apply (e.g. from a systemd timer)
 //! ```
 
 mod app;
@@ -210,6 +217,69 @@ enum Commands {
         #[arg(short, long, default_value = "nft")]
         format: String,
     },
+    /// Temporarily ban an address (drops all incoming traffic from it)
+    ///
+    /// Updates the live blocklist without re-applying the ruleset. Bans are
+    /// kept across applies until they expire.
+    Ban {
+        /// IPv4 or IPv6 address to ban
+        ip: std::net::IpAddr,
+        /// Ban duration (e.g. 30m, 1h, 24h, 7d; max 365d)
+        #[arg(long = "for", value_name = "DURATION", default_value = "1h")]
+        duration: String,
+    },
+    /// Lift a temporary ban
+    Unban {
+        /// IPv4 or IPv6 address to unban
+        ip: std::net::IpAddr,
+    },
+    /// List active temporary bans
+    Bans,
+    /// Manage the IP blocklist files attached to the active profile
+    Blocklist {
+        #[command(subcommand)]
+        action: BlocklistAction,
+    },
+    /// Manage the country sets built from the country database
+    Geoip {
+        #[command(subcommand)]
+        action: GeoipAction,
+    },
+    /// List expired rules in the active profile
+    Expired,
+    /// Remove expired rules from the active profile
+    ///
+    /// Expired rules are skipped when a ruleset is built but stay in the kernel
+    /// until the next apply. Run with --apply from a timer to enforce expiry.
+    PruneExpired {
+        /// Re-apply the active profile after pruning (no auto-revert)
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Re-apply the active profile with its interface subnets and hostnames expanded again
+    ///
+    /// Meant for network manager dispatcher scripts: subnet:wlan0 entries follow
+    /// the network the machine is on. Applies without auto-revert, and does nothing
+    /// if the profile has no such entries.
+    Reapply,
+}
+
+#[derive(Subcommand)]
+enum BlocklistAction {
+    /// Re-read the blocklist files and replace the live blocklist sets
+    ///
+    /// Does not re-apply the ruleset; the drop rules exist once a profile with
+    /// blocklists has been applied.
+    Refresh,
+}
+
+#[derive(Subcommand)]
+enum GeoipAction {
+    /// Rebuild the country sets from the configured country database
+    ///
+    /// Run after the database file changes. Does not re-apply the ruleset;
+    /// the country rules exist once the active profile has been applied.
+    Rebuild,
 }
 
 fn main() -> ExitCode {
@@ -265,23 +335,11 @@ async fn handle_cli(command: Commands) -> Result<(), Box<dyn std::error::Error>>
                 }
                 Err(e) => return Err(e.into()),
             };
-            let nft_json = ruleset.to_nftables_json();
+            let nft_json = build_apply_json(&ruleset).await?;
 
             // Verify first
             println!("Verifying profile '{name}'...");
-            let verify_result = core::verify::verify_ruleset(nft_json.clone()).await?;
-            if !verify_result.success {
-                let _ = stdout().execute(SetForegroundColor(Color::Red));
-                eprint!("✗");
-                let _ = stdout().execute(ResetColor);
-                eprintln!(" Verification failed:");
-                for error in &verify_result.errors {
-                    let _ = stdout().execute(SetForegroundColor(Color::Red));
-                    eprintln!("  {error}");
-                    let _ = stdout().execute(ResetColor);
-                }
-                return Err("Verification failed".into());
-            }
+            verify_or_report(&nft_json).await?;
 
             println!();
             println!("Applying ruleset...");
@@ -363,6 +421,343 @@ async fn handle_cli(command: Commands) -> Result<(), Box<dyn std::error::Error>>
                 _ => return Err("Invalid format. Use 'nft' or 'json'.".into()),
             }
         }
+        Commands::Ban { ip, duration } => {
+            let duration = validators::parse_ban_duration(&duration)?;
+            let config = config::load_config().await;
+            match core::bans::ban(ip, duration).await {
+                Ok(ban) => {
+                    audit::log_ip_banned(
+                        config.enable_event_log,
+                        &ip.to_string(),
+                        duration.as_secs(),
+                        true,
+                        None,
+                    )
+                    .await;
+                    let _ = stdout().execute(SetForegroundColor(Color::Green));
+                    print!("✓");
+                    let _ = stdout().execute(ResetColor);
+                    println!(
+                        " Banned {ip} until {}",
+                        ban.expires_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+                Err(e) => {
+                    audit::log_ip_banned(
+                        config.enable_event_log,
+                        &ip.to_string(),
+                        duration.as_secs(),
+                        false,
+                        Some(e.to_string()),
+                    )
+                    .await;
+                    return Err(e.into());
+                }
+            }
+        }
+        Commands::Unban { ip } => {
+            let config = config::load_config().await;
+            match core::bans::unban(ip).await {
+                Ok(was_banned) => {
+                    audit::log_ip_unbanned(config.enable_event_log, &ip.to_string(), true, None)
+                        .await;
+                    let _ = stdout().execute(SetForegroundColor(Color::Green));
+                    print!("✓");
+                    let _ = stdout().execute(ResetColor);
+                    if was_banned {
+                        println!(" Unbanned {ip}");
+                    } else {
+                        println!(" {ip} was not banned");
+                    }
+                }
+                Err(e) => {
+                    audit::log_ip_unbanned(
+                        config.enable_event_log,
+                        &ip.to_string(),
+                        false,
+                        Some(e.to_string()),
+                    )
+                    .await;
+                    return Err(e.into());
+                }
+            }
+        }
+        Commands::Blocklist {
+            action: BlocklistAction::Refresh,
+        } => {
+            let config = config::load_config().await;
+            let ruleset = core::profiles::load_profile(&config.active_profile).await?;
+            if ruleset.blocklists.is_empty() {
+                println!(
+                    "Profile '{}' has no blocklists attached.",
+                    config.active_profile
+                );
+                return Ok(());
+            }
+
+            let result = async {
+                let lists = core::blocklists::refresh(&ruleset.blocklists).await?;
+                core::blocklists::update_live_sets(&ruleset.blocklists, &lists).await?;
+                Ok::<_, core::error::Error>(lists)
+            }
+            .await;
+            let entries = result
+                .as_ref()
+                .map_or(0, |lists| lists.iter().map(|l| l.entries.len()).sum());
+            audit::log_blocklists_refreshed(
+                config.enable_event_log,
+                ruleset.blocklists.len(),
+                entries,
+                result.is_ok(),
+                result.as_ref().err().map(ToString::to_string),
+            )
+            .await;
+
+            for list in result? {
+                println!(
+                    "  {:<50} {:>8} entries  ({} invalid lines)",
+                    list.path.display(),
+                    list.entries.len(),
+                    list.invalid_lines
+                );
+            }
+            let _ = stdout().execute(SetForegroundColor(Color::Green));
+            print!("✓");
+            let _ = stdout().execute(ResetColor);
+            println!(" Blocklist sets updated ({entries} entries)");
+        }
+        Commands::Geoip {
+            action: GeoipAction::Rebuild,
+        } => {
+            let config = config::load_config().await;
+            let Some(database) = config.geoip_database else {
+                return Err("No GeoIP database configured (set one in the GUI settings)".into());
+            };
+            let ruleset = core::profiles::load_profile(&config.active_profile).await?;
+            let groups = core::geoip::country_groups(&ruleset, chrono::Utc::now());
+            if groups.is_empty() {
+                println!(
+                    "Profile '{}' has no enabled rules filtering by country.",
+                    config.active_profile
+                );
+                return Ok(());
+            }
+
+            let result = core::geoip::rebuild_live_sets(&groups, &database).await;
+            audit::log_geoip_rebuilt(
+                config.enable_event_log,
+                result.as_ref().map_or(0, |cache| cache.countries.len()),
+                result
+                    .as_ref()
+                    .map_or(0, core::geoip::GeoIpCache::range_count),
+                result.is_ok(),
+                result.as_ref().err().map(ToString::to_string),
+            )
+            .await;
+
+            let cache = result?;
+            for (country, ranges) in &cache.countries {
+                let name = core::geoip::country_name(country).unwrap_or("Unknown");
+                if ranges.is_empty() {
+                    println!("  {country}  {name:<40} not found in the database");
+                } else {
+                    println!(
+                        "  {country}  {name:<40} {:>7} IPv4 {:>7} IPv6 ranges",
+                        ranges.v4.len(),
+                        ranges.v6.len()
+                    );
+                }
+            }
+            let _ = stdout().execute(SetForegroundColor(Color::Green));
+            print!("✓");
+            let _ = stdout().execute(ResetColor);
+            println!(
+                " GeoIP sets updated from {} ({} ranges)",
+                database.display(),
+                cache.range_count()
+            );
+        }
+        Commands::Expired => {
+            let config = config::load_config().await;
+            let ruleset = core::profiles::load_profile(&config.active_profile).await?;
+            let mut expired = ruleset.expired_rules(chrono::Utc::now()).peekable();
+            if expired.peek().is_none() {
+                println!("No expired rules in '{}'.", config.active_profile);
+            } else {
+                println!("Expired rules in '{}':", config.active_profile);
+                for rule in expired {
+                    println!(
+                        "  {:<40} (expired {})",
+                        rule.label,
+                        rule.expires_at
+                            .unwrap_or_default()
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                    );
+                }
+            }
+        }
+        Commands::PruneExpired { apply } => {
+            let config = config::load_config().await;
+            let mut ruleset = core::profiles::load_profile(&config.active_profile).await?;
+            let now = chrono::Utc::now();
+            let labels: Vec<String> = ruleset
+                .expired_rules(now)
+                .map(|r| r.label.clone())
+                .collect();
+            if labels.is_empty() {
+                println!("No expired rules in '{}'.", config.active_profile);
+                return Ok(());
+            }
+
+            ruleset.rules.retain(|r| !r.is_expired(now));
+            if apply {
+                let nft_json = build_apply_json(&ruleset).await?;
+                verify_or_report(&nft_json).await?;
+                let result = core::nft_json::apply_with_snapshot(nft_json).await;
+                audit::log_apply(
+                    config.enable_event_log,
+                    ruleset.rules.len(),
+                    ruleset.rules.iter().filter(|r| r.enabled).count(),
+                    result.is_ok(),
+                    result.as_ref().err().map(ToString::to_string),
+                )
+                .await;
+                let _ = core::nft_json::save_snapshot_to_disk(&result?);
+            }
+
+            // Saved only once the pruned ruleset is live, so a failed verify or
+            // apply leaves the profile matching the running ruleset
+            core::profiles::save_profile(&config.active_profile, &ruleset).await?;
+            audit::log_expired_rules_removed(config.enable_event_log, &labels).await;
+            for label in &labels {
+                println!("  - {label}");
+            }
+            let _ = stdout().execute(SetForegroundColor(Color::Green));
+            print!("✓");
+            let _ = stdout().execute(ResetColor);
+            println!(
+                " Removed {} expired rule(s) from '{}'",
+                labels.len(),
+                config.active_profile
+            );
+            if apply {
+                let _ = stdout().execute(SetForegroundColor(Color::Green));
+                print!("✓");
+                let _ = stdout().execute(ResetColor);
+                println!(" Re-applied '{}'.", config.active_profile);
+            }
+        }
+        Commands::Reapply => {
+            let config = config::load_config().await;
+            let ruleset = core::profiles::load_profile(&config.active_profile).await?;
+            if ruleset.symbolic_addresses().next().is_none() {
+                println!(
+                    "No interface subnets or hostnames in '{}'.",
+                    config.active_profile
+                );
+                return Ok(());
+            }
+
+            let nft_json = build_apply_json(&ruleset).await?;
+            verify_or_report(&nft_json).await?;
+            let result = core::nft_json::apply_with_snapshot(nft_json).await;
+            audit::log_apply(
+                config.enable_event_log,
+                ruleset.rules.len(),
+                ruleset.rules.iter().filter(|r| r.enabled).count(),
+                result.is_ok(),
+                result.as_ref().err().map(ToString::to_string),
+            )
+            .await;
+            let _ = core::nft_json::save_snapshot_to_disk(&result?);
+            let _ = stdout().execute(SetForegroundColor(Color::Green));
+            print!("✓");
+            let _ = stdout().execute(ResetColor);
+            println!(" Re-applied '{}'.", config.active_profile);
+        }
+        Commands::Bans => {
+            let bans = core::bans::load_bans().await?;
+            if bans.is_empty() {
+                println!("No active bans.");
+            } else {
+                let now = chrono::Utc::now();
+                println!("Active bans:");
+                for ban in bans {
+                    let remaining = ban.remaining(now).unwrap_or_default();
+                    println!(
+                        "  {:<40} {:>8} left  (until {})",
+                        ban.ip.to_string(),
+                        core::bans::format_remaining(remaining),
+                        ban.expires_at
+                            .with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Builds the JSON applied for a profile: the ruleset with its interface subnets
+/// expanded and hostnames resolved, plus active bans, cached blocklist entries and
+/// country ranges
+async fn build_apply_json(
+    ruleset: &core::firewall::FirewallRuleset,
+) -> Result<serde_json::Value, Box<dyn std::error::Error>> {
+    let subnets = if core::subnets::uses_subnets(ruleset) {
+        core::subnets::local_subnets()
+    } else {
+        Vec::new()
+    };
+    let hostnames = core::hosts::hostnames(ruleset);
+    let addresses = core::hosts::resolve_all(hostnames.clone()).await;
+    let warnings = core::subnets::expansion_warnings(ruleset, &subnets, None)
+        .into_iter()
+        .chain(core::hosts::resolution_warnings(
+            &hostnames, &addresses, None,
+        ));
+    for warning in warnings {
+        let _ = stdout().execute(SetForegroundColor(Color::Yellow));
+        eprint!("!");
+        let _ = stdout().execute(ResetColor);
+        eprintln!(" {warning}");
+    }
+    // Country sets are generated for the rules active at `now`, so fill the same ones
+    let now = chrono::Utc::now();
+    let mut nft_json =
+        core::subnets::expanded_ruleset(ruleset, &subnets, &addresses).to_nftables_json_at(now);
+    let bans = core::bans::load_bans().await.unwrap_or_default();
+    core::bans::append_active_bans(&mut nft_json, &bans, now);
+    let blocklists = core::blocklists::load_cache().await.unwrap_or_default();
+    core::blocklists::append_blocklist_elements(&mut nft_json, &ruleset.blocklists, &blocklists);
+    let config = config::load_config().await;
+    core::geoip::append_geoip_elements(
+        &mut nft_json,
+        &core::geoip::country_groups(ruleset, now),
+        config.geoip_database.as_deref(),
+    )
+    .await?;
+    Ok(nft_json)
+}
+
+/// Verifies the JSON with `nft --check`, printing any errors
+async fn verify_or_report(nft_json: &serde_json::Value) -> Result<(), Box<dyn std::error::Error>> {
+    let verify_result = core::verify::verify_ruleset(nft_json.clone()).await?;
+    if !verify_result.success {
+        let _ = stdout().execute(SetForegroundColor(Color::Red));
+        eprint!("✗");
+        let _ = stdout().execute(ResetColor);
+        eprintln!(" Verification failed:");
+        for error in &verify_result.errors {
+            let _ = stdout().execute(SetForegroundColor(Color::Red));
+            eprintln!("  {error}");
+            let _ = stdout().execute(ResetColor);
+        }
+        return Err("Verification failed".into());
     }
     Ok(())
 }
@@ -16,9 +16,12 @@
 // Validation Constants
 // ═══════════════════════════════════════════════════════════════════════════
 
-/// Maximum length for rule labels (nftables comment limit)
+/// Maximum length for rule labels, in characters (labels may contain Unicode)
 pub const MAX_LABEL_LENGTH: usize = 64;
 
+/// Maximum nftables comment length in bytes (the kernel's 128 including the NUL)
+pub const MAX_COMMENT_BYTES: usize = 127;
+
 /// Maximum connection limit (kernel conntrack limit)
 pub const MAX_CONNECTION_LIMIT: u32 = 65_535;
 
@@ -34,6 +37,18 @@ pub const MAX_RATE_LIMIT_PER_HOUR: u32 = 1_000_000;
 /// Maximum rate limit per day
 pub const MAX_RATE_LIMIT_PER_DAY: u32 = 10_000_000;
 
+/// Maximum byte rate limit in bytes per second (16 GiB/s, beyond 100 Gbit/s)
+pub const MAX_BYTE_RATE_PER_SECOND: u64 = 1 << 34;
+
+/// Maximum number of addresses tracked by a per-source limit set
+pub const MAX_PER_SOURCE_SIZE: u32 = 1 << 20;
+
+/// Maximum idle timeout of per-source rate limit entries (1 day)
+pub const MAX_PER_SOURCE_TIMEOUT_SECS: u32 = 86_400;
+
+/// Maximum byte quota (1 PiB)
+pub const MAX_QUOTA_BYTES: u64 = 1 << 50;
+
 /// Maximum log rate per minute (prevents log flooding)
 pub const MAX_LOG_RATE_PER_MINUTE: u32 = 1_000;
 
@@ -43,31 +58,33 @@ pub const MAX_INTERFACE_LENGTH: usize = 15;
 /// Maximum interface name length with wildcard suffix
 pub const MAX_INTERFACE_LENGTH_WITH_WILDCARD: usize = 16;
 
-/// Sanitizes a label for safe use in nftables comments.
-///
-/// Removes control characters, quotes, and shell metacharacters.
-/// Limits length to 64 bytes (ASCII characters only) as per specification.
-///
-/// This correctly filters out zero-width characters (U+200B, U+200D, etc.)
-/// and combining characters since they are not ASCII.
-///
-/// SECURITY: Uses `is_ascii_alphanumeric()` to prevent Unicode-based bypasses
-/// and ensure labels stay within system limits (64 bytes max).
-///
-/// # Examples
-///
-/// ```
-/// use drfw::validators::sanitize_label;
-///
-/// let safe = sanitize_label("Normal Label");
-/// assert_eq!(safe, "Normal Label");
-///
-/// let unsafe_label = "Test\nNewline\"Quote";
-/// let safe = sanitize_label(unsafe_label);
-/// assert!(!safe.contains('\n'));
-/// assert!(!safe.contains('"'));
-/// ```
-///
+/// Maximum named set name length (kept short for sidebar/preview display)
+pub const MAX_SET_NAME_LENGTH: usize = 32;
+
+/// Maximum elements per named set (prevents memory exhaustion from malformed profiles)
+pub const MAX_SET_ELEMENTS: usize = 4096;
+
+/// Maximum number of ports in a port-knocking sequence
+pub const MAX_KNOCK_PORTS: usize = 8;
+
+/// Maximum time between knocks (and how long a knocked port stays open)
+pub const MAX_KNOCK_TIMEOUT_SECS: u32 = 3600;
+
+/// Maximum countries selected by a single rule (each selection becomes its own set)
+pub const MAX_COUNTRIES_PER_RULE: usize = 16;
+
+/// Prefix reserved for sets managed by DRFW itself (e.g. the ban blocklists)
+pub const RESERVED_SET_PREFIX: &str = "drfw_";
+
+/// Maximum temporary ban duration (365 days)
+pub const MAX_BAN_DURATION_SECS: u64 = 365 * 24 * 60 * 60;
+
+/// Maximum characters in a rule description (stored in the profile only)
+pub const MAX_DESCRIPTION_LENGTH: usize = 2000;
+
+/// Maximum characters in a rule's owner and reference fields
+pub const MAX_METADATA_LENGTH: usize = 256;
+
 /// Generic validator for labeled strings (labels, log prefixes, etc.)
 /// Extracted to avoid duplication between label and log prefix validation
 fn validate_labeled_string(
@@ -102,18 +119,216 @@ fn validate_labeled_string(
     Ok(sanitized)
 }
 
+/// Sanitizes a rule label (also used for tags and group names).
+///
+/// Labels are stored as Unicode so the GUI shows them as written (German,
+/// Japanese, emoji, ...). ASCII is limited to letters, digits and ` -_.:`,
+/// which removes control characters, quotes, and shell metacharacters.
+/// Non-ASCII whitespace becomes a space. Limits length to 64 characters.
+///
+/// This filters out invisible characters (zero-width spaces and joiners,
+/// bidi overrides, soft hyphens) so a label can't hide or reorder text.
+///
+/// The label is never written to nftables as-is: [`sanitize_comment`] and
+/// [`transliterate`] derive the kernel comment and log prefix from it.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::sanitize_label;
+///
+/// let safe = sanitize_label("Normal Label");
+/// assert_eq!(safe, "Normal Label");
+///
+/// let unsafe_label = "Test\nNewline\"Quote";
+/// let safe = sanitize_label(unsafe_label);
+/// assert!(!safe.contains('\n'));
+/// assert!(!safe.contains('"'));
+///
+/// assert_eq!(sanitize_label("Büro 🖨 Drucker"), "Büro 🖨 Drucker");
+/// ```
 pub fn sanitize_label(input: &str) -> String {
     // Note: sanitize_label has different semantics than validators - it never errors, just truncates
     input
         .chars()
-        .filter(|c| {
-            // SECURITY: Use ASCII-only to prevent Unicode bypasses and multi-byte issues
-            c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.' | ':')
+        .filter_map(|c| {
+            if c.is_ascii() {
+                (c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.' | ':')).then_some(c)
+            } else if c.is_control() || is_invisible(c) {
+                None
+            } else if c.is_whitespace() {
+                Some(' ')
+            } else {
+                Some(c)
+            }
         })
         .take(MAX_LABEL_LENGTH)
         .collect()
 }
 
+/// Zero-width, bidi control and other invisible formatting characters
+fn is_invisible(c: char) -> bool {
+    matches!(c,
+        '\u{00AD}' | '\u{034F}' | '\u{061C}' | '\u{180E}'
+        | '\u{200B}'..='\u{200F}'
+        | '\u{202A}'..='\u{202E}'
+        | '\u{2060}'..='\u{206F}'
+        | '\u{FEFF}'
+    )
+}
+
+/// Makes text safe for an nftables comment of at most `max_bytes` bytes.
+///
+/// Comments keep UTF-8 (the kernel stores them as opaque bytes), but double
+/// quotes, backslashes and control characters are dropped so the text form
+/// (`comment "..."`) stays parseable. Truncation never splits a character.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::sanitize_comment;
+///
+/// assert_eq!(sanitize_comment("Say \"hi\"", 64), "Say hi");
+/// assert_eq!(sanitize_comment("日本語", 7), "日本");
+/// ```
+pub fn sanitize_comment(input: &str, max_bytes: usize) -> String {
+    let mut out = String::with_capacity(input.len().min(max_bytes));
+    for c in input
+        .chars()
+        .filter(|c| !c.is_control() && !matches!(c, '"' | '\\'))
+    {
+        if out.len() + c.len_utf8() > max_bytes {
+            break;
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Transliterates text to ASCII for places that only take ASCII (log prefixes).
+///
+/// Latin letters with diacritics map to their base letters (`ü` → `ue`,
+/// `é` → `e`, `ß` → `ss`); characters without a Latin spelling (CJK, emoji)
+/// are dropped.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::transliterate;
+///
+/// assert_eq!(transliterate("Zugang für Büro"), "Zugang fuer Buero");
+/// assert_eq!(transliterate("Café ñandú"), "Cafe nandu");
+/// assert_eq!(transliterate("東京 VPN"), " VPN");
+/// ```
+pub fn transliterate(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for c in input.chars() {
+        if c.is_ascii() {
+            out.push(c);
+            continue;
+        }
+        let ascii = match c {
+            'ä' | 'æ' => "ae",
+            'Ä' | 'Æ' => "Ae",
+            'ö' | 'œ' => "oe",
+            'Ö' | 'Œ' => "Oe",
+            'ü' => "ue",
+            'Ü' => "Ue",
+            'ß' => "ss",
+            'þ' => "th",
+            'Þ' => "Th",
+            'à' | 'á' | 'â' | 'ã' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'À' | 'Á' | 'Â' | 'Ã' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+            'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+            'ð' | 'ď' | 'đ' => "d",
+            'Ð' | 'Ď' | 'Đ' => "D",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+            'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+            'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+            'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+            'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+            'ł' | 'ĺ' | 'ļ' | 'ľ' => "l",
+            'Ł' | 'Ĺ' | 'Ļ' | 'Ľ' => "L",
+            'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+            'Ñ' | 'Ń' | 'Ņ' | 'Ň' => "N",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+            'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+            'ŕ' | 'ŗ' | 'ř' => "r",
+            'Ŕ' | 'Ŗ' | 'Ř' => "R",
+            'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+            'Ś' | 'Ŝ' | 'Ş' | 'Š' | 'Ș' => "S",
+            'ţ' | 'ť' | 'ț' => "t",
+            'Ţ' | 'Ť' | 'Ț' => "T",
+            'ù' | 'ú' | 'û' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+            'Ù' | 'Ú' | 'Û' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+            'ý' | 'ÿ' => "y",
+            'Ý' | 'Ÿ' => "Y",
+            'ź' | 'ż' | 'ž' => "z",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            _ => "",
+        };
+        out.push_str(ascii);
+    }
+    out
+}
+
+/// Sanitizes a free-text rule description.
+///
+/// Descriptions are never written to nftables, so any printable character is
+/// kept (including Unicode). Control characters other than newlines are
+/// dropped, trailing whitespace is trimmed and the length is limited to
+/// [`MAX_DESCRIPTION_LENGTH`] characters.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::sanitize_description;
+///
+/// assert_eq!(sanitize_description("Jellyfin\nAsked for by Ana\t\n"), "Jellyfin\nAsked for by Ana");
+/// ```
+pub fn sanitize_description(input: &str) -> String {
+    let kept: String = input
+        .chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .take(MAX_DESCRIPTION_LENGTH)
+        .collect();
+    kept.trim_end().to_string()
+}
+
+/// Sanitizes a single-line metadata field (owner, reference).
+///
+/// Like [`sanitize_description`] but also drops newlines, trims both ends and
+/// limits the length to [`MAX_METADATA_LENGTH`] characters.
+pub fn sanitize_metadata(input: &str) -> String {
+    let kept: String = input
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_METADATA_LENGTH)
+        .collect();
+    kept.trim().to_string()
+}
+
+/// Validates a rule reference: a ticket ID (`OPS-1234`) or an http(s) URL.
+///
+/// # Errors
+///
+/// Returns `Err` if the reference contains whitespace or uses a URL scheme
+/// other than `http`/`https`.
+pub fn validate_reference(reference: &str) -> Result<(), &'static str> {
+    if reference.chars().any(char::is_whitespace) {
+        return Err("Reference must be a ticket ID or URL without spaces");
+    }
+    if let Some((scheme, _)) = reference.split_once("://")
+        && !scheme.eq_ignore_ascii_case("http")
+        && !scheme.eq_ignore_ascii_case("https")
+    {
+        return Err("Only http and https links are supported");
+    }
+    Ok(())
+}
+
 /// Validates a single port number.
 ///
 /// # Errors
@@ -294,9 +509,421 @@ pub fn validate_interface(name: &str) -> Result<String, &'static str> {
     Ok(name.to_string())
 }
 
+/// Checks that a string is a valid DNS hostname (RFC 1123).
+///
+/// Used to tell hostnames apart from mistyped addresses in address helpers.
+///
+/// # Constraints
+///
+/// - At most 253 characters, labels of 1 to 63 characters
+/// - ASCII alphanumeric and dash only, no dash at either end of a label
+/// - The last label must not be all digits (`192.168.1` is not a hostname)
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::is_valid_hostname;
+///
+/// assert!(is_valid_hostname("backup.lan"));
+/// assert!(is_valid_hostname("nas"));
+/// assert!(!is_valid_hostname("192.168.1"));
+/// assert!(!is_valid_hostname("-nas.local"));
+/// ```
+pub fn is_valid_hostname(name: &str) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if name.is_empty() || name.len() > 253 {
+        return false;
+    }
+    let labels_valid = name.split('.').all(|label| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    });
+    labels_valid
+        && !name
+            .rsplit('.')
+            .next()
+            .is_some_and(|tld| tld.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Validates a named set name.
+///
+/// Set names appear verbatim in nftables lookups (`ip saddr @office`), so they are
+/// restricted to identifiers nft accepts without quoting.
+///
+/// # Constraints
+///
+/// - 1 to 32 characters
+/// - Must start with an ASCII letter
+/// - ASCII alphanumeric and underscore only
+/// - Must not use the reserved `drfw_` prefix
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::validate_set_name;
+///
+/// assert!(validate_set_name("office_nets").is_ok());
+/// assert!(validate_set_name("9lives").is_err());
+/// assert!(validate_set_name("bad-name").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the name is empty, too long, or contains invalid characters.
+pub fn validate_set_name(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        return Err("Set name cannot be empty");
+    }
+
+    if name.len() > MAX_SET_NAME_LENGTH {
+        return Err("Set name too long (max 32 characters)");
+    }
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err("Set name must start with a letter");
+    }
+
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err("Set name may only contain letters, digits and underscores");
+    }
+
+    if name.starts_with(RESERVED_SET_PREFIX) {
+        return Err("Set names starting with 'drfw_' are reserved");
+    }
+
+    Ok(())
+}
+
+/// Validates a port-knocking sequence.
+///
+/// The name follows the named set rules since it becomes part of the stage set names.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::core::firewall::{PortKnock, Protocol};
+/// use drfw::validators::validate_port_knock;
+///
+/// let mut knock = PortKnock {
+///     name: "ssh".to_string(),
+///     knock_protocol: Protocol::Tcp,
+///     sequence: vec![7000, 8000, 9000],
+///     protocol: Protocol::Tcp,
+///     port: 22,
+///     timeout_secs: 30,
+/// };
+/// assert!(validate_port_knock(&knock).is_ok());
+///
+/// knock.sequence = vec![7000, 8000, 7000];
+/// assert!(validate_port_knock(&knock).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the name is invalid, the sequence is too short or too long,
+/// repeats a port or includes the protected port, a protocol is not
+/// TCP or UDP, or the timeout is out of range.
+pub fn validate_port_knock(knock: &crate::core::firewall::PortKnock) -> Result<(), &'static str> {
+    use crate::core::firewall::Protocol;
+
+    validate_set_name(&knock.name).map_err(
+        |_| "Name must start with a letter and use only letters, digits and underscores",
+    )?;
+
+    if knock.sequence.len() < 2 || knock.sequence.len() > MAX_KNOCK_PORTS {
+        return Err("Knock sequence must have between 2 and 8 ports");
+    }
+    if knock.sequence.contains(&0) || knock.port == 0 {
+        return Err("Ports must be between 1 and 65535");
+    }
+    // A repeated port would match two stages at once, letting a client skip ahead
+    if (1..knock.sequence.len()).any(|i| knock.sequence[..i].contains(&knock.sequence[i])) {
+        return Err("Knock sequence cannot repeat a port");
+    }
+    if knock.knock_protocol == knock.protocol && knock.sequence.contains(&knock.port) {
+        return Err("Knock sequence cannot include the protected port");
+    }
+    if ![knock.knock_protocol, knock.protocol]
+        .iter()
+        .all(|p| matches!(p, Protocol::Tcp | Protocol::Udp))
+    {
+        return Err("Port knocking supports TCP or UDP only");
+    }
+    if knock.timeout_secs == 0 || knock.timeout_secs > MAX_KNOCK_TIMEOUT_SECS {
+        return Err("Timeout must be between 1 and 3600 seconds");
+    }
+    Ok(())
+}
+
+/// Validates the path of a blocklist file attached to a ruleset.
+///
+/// Paths must be absolute: the ruleset may be applied from the CLI, the GUI or
+/// at boot, each with a different working directory.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::validate_blocklist_path;
+/// use std::path::Path;
+///
+/// assert!(validate_blocklist_path(Path::new("/etc/drfw/spamhaus_drop.txt")).is_ok());
+/// assert!(validate_blocklist_path(Path::new("lists/drop.txt")).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the path is relative, names a directory, or is not valid UTF-8.
+pub fn validate_blocklist_path(path: &std::path::Path) -> Result<(), &'static str> {
+    if !path.is_absolute() {
+        return Err("Blocklist path must be absolute");
+    }
+    if path.file_name().is_none() {
+        return Err("Blocklist path must name a file");
+    }
+    if path.to_str().is_none() {
+        return Err("Blocklist path must be valid UTF-8");
+    }
+    Ok(())
+}
+
+/// Validates a rule's country selection (ISO 3166 alpha-2 codes).
+///
+/// Codes end up in generated set names, so only two uppercase ASCII letters are
+/// accepted. Codes are not checked against a country list: the database
+/// decides which codes exist.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::validate_countries;
+///
+/// assert!(validate_countries(&["DE".to_string(), "AT".to_string()]).is_ok());
+/// assert!(validate_countries(&["de".to_string()]).is_err());
+/// assert!(validate_countries(&["DEU".to_string()]).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if a code is malformed or repeated, or too many are selected.
+pub fn validate_countries(countries: &[String]) -> Result<(), &'static str> {
+    if countries.len() > MAX_COUNTRIES_PER_RULE {
+        return Err("Too many countries (max 16)");
+    }
+    for (index, code) in countries.iter().enumerate() {
+        if code.len() != 2 || !code.bytes().all(|b| b.is_ascii_uppercase()) {
+            return Err("Country codes must be two uppercase letters (ISO 3166)");
+        }
+        if countries[..index].contains(code) {
+            return Err("Duplicate country");
+        }
+    }
+    Ok(())
+}
+
+/// Validates a rule group name.
+///
+/// Group names are written into the jump rule comments and `.nft` exports, so
+/// they follow the rule label character set.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::validate_group_name;
+///
+/// assert!(validate_group_name("Media server").is_ok());
+/// assert!(validate_group_name("  ").is_err());
+/// assert!(validate_group_name("Dev \"tools\"").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the name is blank, too long, or contains characters a label can't.
+pub fn validate_group_name(name: &str) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        return Err("Group name cannot be empty");
+    }
+    if name.chars().count() > MAX_LABEL_LENGTH {
+        return Err("Group name too long (max 64 characters)");
+    }
+    if sanitize_label(name) != name {
+        return Err("Group name may not contain quotes, symbols or control characters");
+    }
+    Ok(())
+}
+
+/// Parses a ban duration such as `90s`, `30m`, `1h`, `24h` or `7d`.
+///
+/// A bare number is interpreted as seconds.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::parse_ban_duration;
+///
+/// assert_eq!(parse_ban_duration("1h").unwrap().as_secs(), 3600);
+/// assert_eq!(parse_ban_duration("7d").unwrap().as_secs(), 604_800);
+/// assert!(parse_ban_duration("0m").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the input is malformed, zero, or longer than 365 days.
+pub fn parse_ban_duration(input: &str) -> Result<std::time::Duration, &'static str> {
+    let input = input.trim();
+    let (digits, multiplier) = match input.chars().last() {
+        Some('s') => (&input[..input.len() - 1], 1),
+        Some('m') => (&input[..input.len() - 1], 60),
+        Some('h') => (&input[..input.len() - 1], 60 * 60),
+        Some('d') => (&input[..input.len() - 1], 24 * 60 * 60),
+        Some(c) if c.is_ascii_digit() => (input, 1),
+        _ => return Err("Invalid duration (use e.g. 30m, 1h, 24h, 7d)"),
+    };
+
+    let value: u64 = digits
+        .parse()
+        .map_err(|_| "Invalid duration (use e.g. 30m, 1h, 24h, 7d)")?;
+
+    if value == 0 {
+        return Err("Duration must be greater than zero");
+    }
+
+    let secs = value
+        .checked_mul(multiplier)
+        .filter(|secs| *secs <= MAX_BAN_DURATION_SECS)
+        .ok_or("Duration too long (max 365 days)")?;
+
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Parses a rule expiry: a local date and time (`2026-10-19 18:00`), a date
+/// (expires at the end of that day) or a duration from `now` (`36h`, `7d`).
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::parse_expiry;
+///
+/// let now = chrono::Utc::now();
+/// assert_eq!(parse_expiry("1h", now).unwrap(), now + chrono::Duration::hours(1));
+/// assert!(parse_expiry("2001-01-01", now).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the input is malformed, doesn't exist in local time, or is
+/// not after `now`.
+pub fn parse_expiry(
+    input: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<chrono::DateTime<chrono::Utc>, &'static str> {
+    use chrono::{NaiveDate, NaiveDateTime, TimeZone};
+
+    const FORMAT_HELP: &str = "Use YYYY-MM-DD HH:MM, YYYY-MM-DD or a duration like 7d";
+
+    let input = input.trim();
+    let expiry = if let Ok(naive) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        chrono::Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or("That time doesn't exist in the local time zone")?
+            .with_timezone(&chrono::Utc)
+    } else if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        let midnight = date
+            .succ_opt()
+            .and_then(|next| next.and_hms_opt(0, 0, 0))
+            .ok_or(FORMAT_HELP)?;
+        chrono::Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .ok_or("That time doesn't exist in the local time zone")?
+            .with_timezone(&chrono::Utc)
+    } else {
+        let duration = parse_ban_duration(input).map_err(|_| FORMAT_HELP)?;
+        now + chrono::Duration::from_std(duration).map_err(|_| FORMAT_HELP)?
+    };
+
+    if expiry <= now {
+        return Err("Expiry must be in the future");
+    }
+    Ok(expiry)
+}
+
+/// Validates an address for a temporary ban.
+///
+/// Loopback, unspecified and multicast addresses are rejected: banning them
+/// either has no effect or breaks local networking.
+///
+/// # Errors
+///
+/// Returns `Err` if the address cannot be banned.
+pub fn validate_ban_address(ip: std::net::IpAddr) -> Result<std::net::IpAddr, &'static str> {
+    if ip.is_loopback() {
+        return Err("Cannot ban a loopback address");
+    }
+    if ip.is_unspecified() {
+        return Err("Cannot ban the unspecified address");
+    }
+    if ip.is_multicast() {
+        return Err("Cannot ban a multicast address");
+    }
+    Ok(ip)
+}
+
+/// Validates the internal host of a port forward.
+///
+/// Loopback, unspecified, multicast and broadcast targets are rejected: the
+/// kernel does not route translated traffic to them.
+///
+/// # Errors
+///
+/// Returns `Err` if traffic cannot be forwarded to the address.
+pub fn validate_forward_address(ip: std::net::IpAddr) -> Result<std::net::IpAddr, &'static str> {
+    if ip.is_loopback() {
+        return Err("Cannot forward to a loopback address");
+    }
+    if ip.is_unspecified() {
+        return Err("Cannot forward to the unspecified address");
+    }
+    if ip.is_multicast() {
+        return Err("Cannot forward to a multicast address");
+    }
+    if let std::net::IpAddr::V4(v4) = ip
+        && v4.is_broadcast()
+    {
+        return Err("Cannot forward to the broadcast address");
+    }
+    Ok(ip)
+}
+
+/// Parses a schedule time of day in 24-hour `HH:MM` form.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::parse_schedule_time;
+///
+/// assert_eq!(parse_schedule_time("08:30").unwrap().to_string(), "08:30:00");
+/// assert!(parse_schedule_time("24:00").is_err());
+/// assert!(parse_schedule_time("8am").is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the input is not a valid `HH:MM` time.
+pub fn parse_schedule_time(input: &str) -> Result<chrono::NaiveTime, &'static str> {
+    chrono::NaiveTime::parse_from_str(input.trim(), "%H:%M")
+        .map_err(|_| "Enter times as HH:MM (24-hour)")
+}
+
 /// Validates a rate limit value.
 ///
-/// Returns Ok(Some(warning)) for high but acceptable values.
+/// `bytes` is the byte unit of a byte-based limit, or `None` for packets.
+///
+/// Returns Ok(Some(warning)) for high but acceptable values, or byte rates so
+/// low that almost all traffic exceeds them.
 /// Returns Err for values that exceed kernel/system limits.
 ///
 /// # Errors
@@ -305,9 +932,27 @@ pub fn validate_interface(name: &str) -> Result<String, &'static str> {
 pub fn validate_rate_limit(
     count: u32,
     unit: crate::core::firewall::TimeUnit,
+    bytes: Option<crate::core::firewall::ByteUnit>,
 ) -> Result<Option<String>, String> {
     use crate::core::firewall::TimeUnit;
 
+    if let Some(bytes) = bytes {
+        if count == 0 {
+            return Err("Byte rate must be greater than 0".to_string());
+        }
+        let per_second = u64::from(count).saturating_mul(bytes.bytes()) / unit.seconds();
+        if per_second > MAX_BYTE_RATE_PER_SECOND {
+            return Err("Byte rate exceeds max 16 GB/second".to_string());
+        }
+        if per_second < 1024 {
+            return Ok(Some(format!(
+                "Very low rate ({count} {bytes}/{}) - under 1 KB/second",
+                unit.as_str()
+            )));
+        }
+        return Ok(None);
+    }
+
     let (max, warn) = match unit {
         TimeUnit::Second => (MAX_RATE_LIMIT_PER_SECOND, MAX_RATE_LIMIT_PER_SECOND / 10),
         TimeUnit::Minute => (MAX_RATE_LIMIT_PER_MINUTE, MAX_RATE_LIMIT_PER_MINUTE / 10),
@@ -331,6 +976,35 @@ pub fn validate_rate_limit(
     Ok(None)
 }
 
+/// Validates a byte quota.
+///
+/// Returns Ok(Some(warning)) for quotas small enough to run out almost immediately.
+/// Returns Err for zero quotas or quotas above [`MAX_QUOTA_BYTES`].
+///
+/// # Errors
+///
+/// Returns `Err` if the quota is zero or too large.
+pub fn validate_quota(
+    amount: u64,
+    unit: crate::core::firewall::ByteUnit,
+) -> Result<Option<String>, String> {
+    if amount == 0 {
+        return Err("Quota must be greater than zero".to_string());
+    }
+    let bytes = amount
+        .checked_mul(unit.bytes())
+        .filter(|b| *b <= MAX_QUOTA_BYTES)
+        .ok_or_else(|| "Quota exceeds max 1048576 GB".to_string())?;
+
+    if bytes < 1024 * 1024 {
+        return Ok(Some(format!(
+            "Small quota ({amount} {unit}) - will be used up almost immediately"
+        )));
+    }
+
+    Ok(None)
+}
+
 /// Validates connection limit.
 ///
 /// Returns Ok(Some(warning)) for high but acceptable values.
@@ -359,6 +1033,35 @@ pub fn validate_connection_limit(limit: u32) -> Result<Option<String>, String> {
     Ok(None)
 }
 
+/// Validates the set size and idle timeout of per-source limit tracking.
+///
+/// # Examples
+///
+/// ```
+/// use drfw::validators::validate_per_source;
+///
+/// assert!(validate_per_source(65_535, 60).is_ok());
+/// assert!(validate_per_source(0, 60).is_err());
+/// assert!(validate_per_source(1024, 0).is_err());
+/// ```
+///
+/// # Errors
+///
+/// Returns `Err` if the size or timeout is zero or above its maximum.
+pub fn validate_per_source(size: u32, timeout_secs: u32) -> Result<(), String> {
+    if size == 0 || size > MAX_PER_SOURCE_SIZE {
+        return Err(format!(
+            "Tracked addresses must be between 1 and {MAX_PER_SOURCE_SIZE}"
+        ));
+    }
+    if timeout_secs == 0 || timeout_secs > MAX_PER_SOURCE_TIMEOUT_SECS {
+        return Err(format!(
+            "Timeout must be between 1 and {MAX_PER_SOURCE_TIMEOUT_SECS} seconds"
+        ));
+    }
+    Ok(())
+}
+
 /// Validates log rate per minute.
 ///
 /// High log rates can flood system logs and impact performance.
@@ -449,9 +1152,84 @@ mod tests {
 
     #[test]
     fn test_sanitize_label_unicode() {
-        // Unicode should be removed (not alphanumeric ASCII)
-        assert_eq!(sanitize_label("Test😀Emoji"), "TestEmoji");
-        assert_eq!(sanitize_label("Test™Symbol"), "TestSymbol");
+        // Unicode is kept so labels display as written
+        assert_eq!(sanitize_label("Test😀Emoji"), "Test😀Emoji");
+        assert_eq!(sanitize_label("Zugang für Büro"), "Zugang für Büro");
+        assert_eq!(sanitize_label("社内\u{3000}VPN"), "社内 VPN");
+        // Invisible and bidi control characters are removed
+        assert_eq!(sanitize_label("Te\u{200B}st\u{202E}"), "Test");
+        assert_eq!(sanitize_label("Test\u{85}Next"), "TestNext");
+        // Length is counted in characters, not bytes
+        assert_eq!(sanitize_label(&"ü".repeat(100)).chars().count(), 64);
+    }
+
+    #[test]
+    fn test_sanitize_comment() {
+        assert_eq!(sanitize_comment("a\\b\"c\nd", 64), "abcd");
+        assert_eq!(sanitize_comment("Zugang für Büro", 64), "Zugang für Büro");
+        // Truncation respects character boundaries
+        let comment = sanitize_comment(&"😀".repeat(40), MAX_COMMENT_BYTES);
+        assert_eq!(comment.len(), 124);
+        assert!(comment.chars().all(|c| c == '😀'));
+    }
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate("Straße Ærø"), "Strasse Aero");
+        assert_eq!(transliterate("Łódź"), "Lodz");
+        assert_eq!(transliterate("🔥 Web"), " Web");
+    }
+
+    #[test]
+    fn test_sanitize_metadata() {
+        assert_eq!(
+            sanitize_description("Line one\r\nLine two \u{7}\n\n"),
+            "Line one\nLine two"
+        );
+        assert_eq!(sanitize_description("Für Ana 😀"), "Für Ana 😀");
+        assert_eq!(
+            sanitize_description(&"d".repeat(MAX_DESCRIPTION_LENGTH + 10)).len(),
+            MAX_DESCRIPTION_LENGTH
+        );
+        assert_eq!(sanitize_metadata("  ops-team\n "), "ops-team");
+
+        assert!(validate_reference("OPS-1234").is_ok());
+        assert!(validate_reference("https://tickets.example.com/OPS-1234").is_ok());
+        assert!(validate_reference("OPS 1234").is_err());
+        assert!(validate_reference("javascript://alert").is_err());
+    }
+
+    #[test]
+    fn test_parse_expiry() {
+        use chrono::TimeZone;
+
+        let now = chrono::Utc
+            .with_ymd_and_hms(2026, 10, 17, 12, 0, 0)
+            .unwrap();
+        assert_eq!(
+            parse_expiry("7d", now).unwrap(),
+            now + chrono::Duration::days(7)
+        );
+        let local = |s: &str| {
+            chrono::Local
+                .from_local_datetime(
+                    &chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap(),
+                )
+                .unwrap()
+                .with_timezone(&chrono::Utc)
+        };
+        assert_eq!(
+            parse_expiry("2026-10-19 18:30", now).unwrap(),
+            local("2026-10-19 18:30")
+        );
+        // A bare date lasts until the end of that day
+        assert_eq!(
+            parse_expiry("2026-10-19", now).unwrap(),
+            local("2026-10-20 00:00")
+        );
+        assert!(parse_expiry("2026-10-01", now).is_err());
+        assert!(parse_expiry("next week", now).is_err());
+        assert!(parse_expiry("0d", now).is_err());
     }
 
     #[test]
@@ -490,6 +1268,21 @@ mod tests {
         assert!(validate_interface("enp3s0").is_ok());
     }
 
+    #[test]
+    fn test_is_valid_hostname() {
+        assert!(is_valid_hostname("backup.lan"));
+        assert!(is_valid_hostname("nas-01.home.arpa"));
+        assert!(is_valid_hostname("printer"));
+        assert!(is_valid_hostname("nas.local."));
+        assert!(!is_valid_hostname(""));
+        assert!(!is_valid_hostname("10.0.0.1"));
+        assert!(!is_valid_hostname("10.0.0.1-10.0.0.5"));
+        assert!(!is_valid_hostname("nas..local"));
+        assert!(!is_valid_hostname("nas-.local"));
+        assert!(!is_valid_hostname("nas_box.lan"));
+        assert!(!is_valid_hostname(&"a".repeat(64)));
+    }
+
     #[test]
     fn test_validate_interface_empty() {
         assert!(validate_interface("").is_ok());
@@ -553,16 +1346,28 @@ mod tests {
     fn test_validate_rate_limit_normal() {
         use crate::core::firewall::TimeUnit;
 
-        assert!(validate_rate_limit(10, TimeUnit::Second).unwrap().is_none());
-        assert!(validate_rate_limit(50, TimeUnit::Minute).unwrap().is_none());
-        assert!(validate_rate_limit(100, TimeUnit::Hour).unwrap().is_none());
+        assert!(
+            validate_rate_limit(10, TimeUnit::Second, None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            validate_rate_limit(50, TimeUnit::Minute, None)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            validate_rate_limit(100, TimeUnit::Hour, None)
+                .unwrap()
+                .is_none()
+        );
     }
 
     #[test]
     fn test_validate_rate_limit_warning() {
         use crate::core::firewall::TimeUnit;
 
-        let result = validate_rate_limit(5000, TimeUnit::Second).unwrap();
+        let result = validate_rate_limit(5000, TimeUnit::Second, None).unwrap();
         assert!(result.is_some());
         assert!(result.unwrap().contains("High rate"));
     }
@@ -571,8 +1376,101 @@ mod tests {
     fn test_validate_rate_limit_exceeds_max() {
         use crate::core::firewall::TimeUnit;
 
-        assert!(validate_rate_limit(99_999, TimeUnit::Second).is_err());
-        assert!(validate_rate_limit(999_999, TimeUnit::Minute).is_err());
+        assert!(validate_rate_limit(99_999, TimeUnit::Second, None).is_err());
+        assert!(validate_rate_limit(999_999, TimeUnit::Minute, None).is_err());
+    }
+
+    #[test]
+    fn test_validate_byte_rate_limit() {
+        use crate::core::firewall::{ByteUnit, TimeUnit};
+
+        // Packet maximums don't apply to byte rates
+        let mbytes = Some(ByteUnit::MBytes);
+        assert!(
+            validate_rate_limit(99_999, TimeUnit::Minute, mbytes)
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            validate_rate_limit(10, TimeUnit::Day, Some(ByteUnit::KBytes))
+                .unwrap()
+                .is_some()
+        );
+        assert!(validate_rate_limit(0, TimeUnit::Second, mbytes).is_err());
+        assert!(validate_rate_limit(17, TimeUnit::Second, Some(ByteUnit::GBytes)).is_err());
+    }
+
+    #[test]
+    fn test_validate_quota() {
+        use crate::core::firewall::ByteUnit;
+
+        assert!(validate_quota(5, ByteUnit::GBytes).unwrap().is_none());
+        assert!(validate_quota(100, ByteUnit::KBytes).unwrap().is_some());
+        assert!(validate_quota(0, ByteUnit::MBytes).is_err());
+        assert!(validate_quota(2_000_000, ByteUnit::GBytes).is_err());
+        assert!(validate_quota(u64::MAX, ByteUnit::KBytes).is_err());
+    }
+
+    #[test]
+    fn test_validate_per_source() {
+        assert!(validate_per_source(1, 1).is_ok());
+        assert!(validate_per_source(MAX_PER_SOURCE_SIZE, MAX_PER_SOURCE_TIMEOUT_SECS).is_ok());
+        assert!(validate_per_source(MAX_PER_SOURCE_SIZE + 1, 60).is_err());
+        assert!(validate_per_source(1024, MAX_PER_SOURCE_TIMEOUT_SECS + 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_port_knock() {
+        use crate::core::firewall::{PortKnock, Protocol};
+
+        let knock = PortKnock {
+            name: "ssh".to_string(),
+            knock_protocol: Protocol::Udp,
+            sequence: vec![7000, 8000, 22],
+            protocol: Protocol::Tcp,
+            port: 22,
+            timeout_secs: 30,
+        };
+        assert!(validate_port_knock(&knock).is_ok());
+
+        let with = |f: fn(&mut PortKnock)| {
+            let mut k = knock.clone();
+            f(&mut k);
+            validate_port_knock(&k)
+        };
+        assert!(with(|k| k.knock_protocol = Protocol::Tcp).is_err());
+        assert!(with(|k| k.sequence.truncate(1)).is_err());
+        assert!(with(|k| k.sequence = vec![1; 9]).is_err());
+        assert!(with(|k| k.sequence[1] = 0).is_err());
+        assert!(with(|k| k.sequence[1] = 7000).is_err());
+        assert!(with(|k| k.sequence = vec![1000, 2000, 1000]).is_err());
+        assert!(with(|k| k.protocol = Protocol::Any).is_err());
+        assert!(with(|k| k.timeout_secs = 0).is_err());
+        assert!(with(|k| k.timeout_secs = 3601).is_err());
+        assert!(with(|k| k.name = "drfw_ssh".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_validate_countries() {
+        let codes = |c: &[&str]| c.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert!(validate_countries(&[]).is_ok());
+        assert!(validate_countries(&codes(&["DE", "XK"])).is_ok());
+        assert!(validate_countries(&codes(&["DE", "DE"])).is_err());
+        assert!(validate_countries(&codes(&["D1"])).is_err());
+        assert!(validate_countries(&codes(&["de_at"])).is_err());
+        let many: Vec<String> = (b'A'..=b'Q')
+            .map(|c| format!("A{}", char::from(c)))
+            .collect();
+        assert!(validate_countries(&many).is_err());
+    }
+
+    #[test]
+    fn test_validate_group_name() {
+        assert!(validate_group_name("Dev tools").is_ok());
+        assert!(validate_group_name("media-server_2").is_ok());
+        assert!(validate_group_name("").is_err());
+        assert!(validate_group_name("Dev{tools}").is_err());
+        assert!(validate_group_name(&"g".repeat(MAX_LABEL_LENGTH + 1)).is_err());
     }
 
     // Connection limit tests
@@ -656,6 +1554,65 @@ mod tests {
         assert!(validate_log_prefix("$$$").is_err());
     }
 
+    #[test]
+    fn test_validate_set_name_valid() {
+        assert!(validate_set_name("office").is_ok());
+        assert!(validate_set_name("office_nets_2").is_ok());
+        assert!(validate_set_name(&"a".repeat(32)).is_ok());
+    }
+
+    #[test]
+    fn test_validate_set_name_invalid() {
+        assert!(validate_set_name("").is_err());
+        assert!(validate_set_name("_office").is_err());
+        assert!(validate_set_name("1office").is_err());
+        assert!(validate_set_name("office nets").is_err());
+        assert!(validate_set_name("office@home").is_err());
+        assert!(validate_set_name(&"a".repeat(33)).is_err());
+        assert!(validate_set_name("drfw_banned_v4").is_err());
+    }
+
+    #[test]
+    fn test_parse_ban_duration() {
+        assert_eq!(parse_ban_duration("90s").unwrap().as_secs(), 90);
+        assert_eq!(parse_ban_duration("30m").unwrap().as_secs(), 1800);
+        assert_eq!(parse_ban_duration("24h").unwrap().as_secs(), 86_400);
+        assert_eq!(parse_ban_duration(" 2d ").unwrap().as_secs(), 172_800);
+        assert_eq!(parse_ban_duration("600").unwrap().as_secs(), 600);
+        assert_eq!(
+            parse_ban_duration("365d").unwrap().as_secs(),
+            MAX_BAN_DURATION_SECS
+        );
+
+        assert!(parse_ban_duration("").is_err());
+        assert!(parse_ban_duration("h").is_err());
+        assert!(parse_ban_duration("0h").is_err());
+        assert!(parse_ban_duration("1w").is_err());
+        assert!(parse_ban_duration("-1h").is_err());
+        assert!(parse_ban_duration("366d").is_err());
+        assert!(parse_ban_duration("99999999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_validate_ban_address() {
+        assert!(validate_ban_address("203.0.113.7".parse().unwrap()).is_ok());
+        assert!(validate_ban_address("2001:db8::1".parse().unwrap()).is_ok());
+        assert!(validate_ban_address("127.0.0.1".parse().unwrap()).is_err());
+        assert!(validate_ban_address("::1".parse().unwrap()).is_err());
+        assert!(validate_ban_address("0.0.0.0".parse().unwrap()).is_err());
+        assert!(validate_ban_address("224.0.0.1".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_validate_forward_address() {
+        assert!(validate_forward_address("192.168.1.10".parse().unwrap()).is_ok());
+        assert!(validate_forward_address("fd00::10".parse().unwrap()).is_ok());
+        assert!(validate_forward_address("127.0.0.1".parse().unwrap()).is_err());
+        assert!(validate_forward_address("::".parse().unwrap()).is_err());
+        assert!(validate_forward_address("ff02::1".parse().unwrap()).is_err());
+        assert!(validate_forward_address("255.255.255.255".parse().unwrap()).is_err());
+    }
+
     // Well-known port tests
 }
 
@@ -668,7 +1625,15 @@ mod property_tests {
         #[test]
         fn test_sanitize_label_never_exceeds_64_chars(input in "\\PC*") {
             let sanitized = sanitize_label(&input);
-            prop_assert!(sanitized.len() <= 64);
+            prop_assert!(sanitized.chars().count() <= 64);
+        }
+
+        #[test]
+        fn test_sanitize_comment_fits_kernel_limit(input in "\\PC*") {
+            let comment = sanitize_comment(&sanitize_label(&input), MAX_COMMENT_BYTES);
+            prop_assert!(comment.len() <= MAX_COMMENT_BYTES);
+            prop_assert!(!comment.contains('"'));
+            prop_assert!(!comment.contains('\\'));
         }
 
         #[test]
@@ -803,28 +1768,26 @@ mod property_tests {
             prop_assert!(result.is_err(), "Should reject RTL marker U+{:04X}", rtl_marker as u32);
         }
 
-        /// Property test: sanitize_label must remove all Unicode characters.
+        /// Property test: sanitize_label keeps non-Latin letters.
         ///
-        /// Labels are used in nftables comments and UI display. Unicode could
-        /// cause rendering issues or bypass length checks.
+        /// Labels are stored and displayed as written; only the derived nft
+        /// comment and log prefix are escaped or transliterated.
         #[test]
-        fn test_sanitize_label_removes_unicode(
+        fn test_sanitize_label_keeps_unicode(
             ascii_part in "[a-zA-Z0-9 _-]{1,20}",
             unicode_char in "[\\p{L}&&[^a-zA-Z]]"
         ) {
             let label_with_unicode = format!("{ascii_part}{unicode_char}test");
             let sanitized = sanitize_label(&label_with_unicode);
-            // Unicode should be removed, leaving only ASCII parts
-            prop_assert!(!sanitized.contains(&unicode_char as &str));
-            prop_assert!(sanitized.is_ascii());
+            prop_assert!(sanitized.contains(&unicode_char as &str));
+            prop_assert!(transliterate(&sanitized).is_ascii());
         }
 
-        /// Property test: sanitize_label must remove emoji.
+        /// Property test: emoji survive in labels but never reach the log prefix.
         ///
-        /// Emoji are multi-byte Unicode characters that could bypass length
-        /// checks or cause display issues in terminals and logs.
+        /// Kernel log lines (syslog) should stay ASCII-safe.
         #[test]
-        fn test_sanitize_label_removes_emoji(
+        fn test_sanitize_label_keeps_emoji(
             ascii_part in "[a-zA-Z0-9 ]{1,20}",
             emoji in prop::sample::select(vec![
                 "😀", "🔥", "🚀", "⚠️", "✅", "❌", "🎉", "💀",
@@ -833,16 +1796,19 @@ mod property_tests {
         ) {
             let label_with_emoji = format!("{ascii_part}{emoji}");
             let sanitized = sanitize_label(&label_with_emoji);
-            prop_assert!(!sanitized.contains(emoji), "Should remove emoji: {}", emoji);
-            prop_assert!(sanitized.is_ascii());
+            prop_assert!(sanitized.contains(emoji), "Should keep emoji: {}", emoji);
+            let ascii = transliterate(&sanitized);
+            prop_assert!(ascii.is_ascii());
+            prop_assert!(!ascii.contains(emoji));
         }
 
-        /// Property test: sanitize_label must handle homoglyphs.
+        /// Property test: homoglyphs can't reach ASCII-only kernel strings.
         ///
         /// Homoglyphs are characters from different scripts that look identical
-        /// (e.g., Cyrillic 'а' vs Latin 'a'). They should be removed.
+        /// (e.g., Cyrillic 'а' vs Latin 'a'). Labels keep them (they are valid
+        /// Cyrillic text), but transliteration must drop rather than map them.
         #[test]
-        fn test_sanitize_label_removes_homoglyphs(
+        fn test_transliterate_drops_homoglyphs(
             prefix in "[a-zA-Z]{1,10}",
             homoglyph in prop::sample::select(vec![
                 'а',  // Cyrillic 'a' (U+0430)
@@ -855,9 +1821,8 @@ mod property_tests {
         ) {
             let label_with_homoglyph = format!("{prefix}{homoglyph}");
             let sanitized = sanitize_label(&label_with_homoglyph);
-            // Homoglyph should be removed (not ASCII alphanumeric)
-            prop_assert!(!sanitized.contains(homoglyph));
-            prop_assert!(sanitized.is_ascii());
+            prop_assert!(sanitized.contains(homoglyph));
+            prop_assert_eq!(transliterate(&sanitized), prefix);
         }
 
         /// Property test: validate_log_prefix must reject Unicode.
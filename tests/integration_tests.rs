@@ -28,7 +28,9 @@
 
 #![allow(clippy::uninlined_format_args)]
 
-use drfw::core::firewall::{Action, FirewallRuleset, PortEntry, Protocol, RejectType, Rule};
+use drfw::core::firewall::{
+    Action, FirewallRuleset, Negation, PortEntry, Protocol, RejectType, Rule,
+};
 use drfw::core::nft_json;
 use drfw::core::verify;
 use std::env;
@@ -89,17 +91,36 @@ fn create_test_rule(label: &str, port: Option<u16>) -> Rule {
         ports: port.map(|p| vec![PortEntry::Single(p)]).unwrap_or_default(),
         sources: vec![],
         destinations: vec![],
-        interface: None,
-        output_interface: None,
+        interfaces: Vec::new(),
+        output_interfaces: Vec::new(),
         chain: drfw::core::firewall::Chain::Input,
         enabled: true,
         tags: vec![],
         created_at: chrono::Utc::now(),
+        modified_at: None,
+        expires_at: None,
         action: Action::Accept,
         reject_type: RejectType::Default,
         rate_limit: None,
         connection_limit: 0,
         log_enabled: false,
+        source_set: None,
+        destination_set: None,
+        port_set: None,
+        port_forward: None,
+        source_ports: Vec::new(),
+        icmp_types: Vec::new(),
+        icmp_code: None,
+        ct_states: Vec::new(),
+        schedule: None,
+        quota: None,
+        per_source: None,
+        countries: Vec::new(),
+        group: None,
+        description: String::new(),
+        owner: String::new(),
+        reference: String::new(),
+        negate: Negation::default(),
         // Cached fields
         label_lowercase: String::new(),
         interface_lowercase: None,
@@ -115,6 +136,7 @@ fn create_test_rule(label: &str, port: Option<u16>) -> Rule {
         log_prefix: String::new(),
         tags_truncated: Vec::new(),
         badge_display: String::new(),
+        metadata_lowercase: String::new(),
     };
     rule.rebuild_caches();
     rule
@@ -138,17 +160,36 @@ fn create_full_test_rule(
             .map(|ip| vec![ip])
             .unwrap_or_default(),
         destinations: vec![],
-        interface: interface.map(String::from),
-        output_interface: None,
+        interfaces: interface.into_iter().map(String::from).collect(),
+        output_interfaces: Vec::new(),
         chain: drfw::core::firewall::Chain::Input,
         enabled: true,
         tags: vec![],
         created_at: chrono::Utc::now(),
+        modified_at: None,
+        expires_at: None,
         action: Action::Accept,
         reject_type: RejectType::Default,
         rate_limit: None,
         connection_limit: 0,
         log_enabled: false,
+        source_set: None,
+        destination_set: None,
+        port_set: None,
+        port_forward: None,
+        source_ports: Vec::new(),
+        icmp_types: Vec::new(),
+        icmp_code: None,
+        ct_states: Vec::new(),
+        schedule: None,
+        quota: None,
+        per_source: None,
+        countries: Vec::new(),
+        group: None,
+        description: String::new(),
+        owner: String::new(),
+        reference: String::new(),
+        negate: Negation::default(),
         // Cached fields
         label_lowercase: String::new(),
         interface_lowercase: None,
@@ -164,6 +205,7 @@ fn create_full_test_rule(
         log_prefix: String::new(),
         tags_truncated: Vec::new(),
         badge_display: String::new(),
+        metadata_lowercase: String::new(),
     };
     rule.rebuild_caches();
     rule
@@ -36,8 +36,8 @@ use std::process::Command;
 use chrono::{TimeZone, Utc};
 use clap::{Parser, ValueEnum};
 use drfw::core::firewall::{
-    Action, AdvancedSecuritySettings, Chain, EgressProfile, FirewallRuleset, PortEntry, Protocol,
-    RateLimit, RejectType, Rule, TimeUnit,
+    Action, AddressEntry, AdvancedSecuritySettings, Chain, EgressProfile, FirewallRuleset,
+    Negation, PortEntry, Protocol, RateLimit, RejectType, Rule, TimeUnit,
 };
 use drfw::core::rule_constraints::{
     available_reject_types_for_protocol, chain_uses_input_interface, protocol_requires_ipv4,
@@ -175,6 +175,7 @@ impl CoverageTracker {
             .entry(match rule.chain {
                 Chain::Input => "input",
                 Chain::Output => "output",
+                Chain::Forward => "forward",
             })
             .or_insert(0) += 1;
 
@@ -210,10 +211,10 @@ impl CoverageTracker {
         if !rule.destinations.is_empty() {
             self.with_destinations += 1;
         }
-        if rule.interface.is_some() {
+        if !rule.interfaces.is_empty() {
             self.with_interface += 1;
         }
-        if rule.output_interface.is_some() {
+        if !rule.output_interfaces.is_empty() {
             self.with_output_interface += 1;
         }
         if !rule.tags.is_empty() {
@@ -281,6 +282,9 @@ impl CoverageTracker {
         if !self.chains.contains_key("output") {
             missing.push("Chain::Output".to_string());
         }
+        if !self.chains.contains_key("forward") {
+            missing.push("Chain::Forward".to_string());
+        }
 
         // Check reject types (only if we have Reject actions)
         if self.actions.contains_key("reject") {
@@ -321,7 +325,7 @@ const PROTOCOLS: [Protocol; 7] = [
 
 const ACTIONS: [Action; 3] = [Action::Accept, Action::Drop, Action::Reject];
 
-const CHAINS: [Chain; 2] = [Chain::Input, Chain::Output];
+const CHAINS: [Chain; 3] = [Chain::Input, Chain::Output, Chain::Forward];
 
 const REJECT_TYPES: [RejectType; 5] = [
     RejectType::Default,
@@ -407,10 +411,10 @@ struct RuleConfig {
     reject_type: RejectType,
     label: String,
     ports: Vec<PortEntry>,
-    sources: Vec<IpNetwork>,
-    destinations: Vec<IpNetwork>,
-    interface: Option<String>,
-    output_interface: Option<String>,
+    sources: Vec<AddressEntry>,
+    destinations: Vec<AddressEntry>,
+    interfaces: Vec<String>,
+    output_interfaces: Vec<String>,
     rate_limit: Option<RateLimit>,
     connection_limit: u32,
     log_enabled: bool,
@@ -427,11 +431,13 @@ impl RuleConfig {
             protocol: self.protocol,
             ports: self.ports,
             sources: self.sources,
-            interface: self.interface,
-            output_interface: self.output_interface,
+            interfaces: self.interfaces,
+            output_interfaces: self.output_interfaces,
             chain: self.chain,
             enabled: self.enabled,
             created_at: self.timestamp,
+            modified_at: None,
+            expires_at: None,
             tags: self.tags,
             destinations: self.destinations,
             action: self.action,
@@ -439,6 +445,23 @@ impl RuleConfig {
             rate_limit: self.rate_limit,
             connection_limit: self.connection_limit,
             log_enabled: self.log_enabled,
+            source_set: None,
+            destination_set: None,
+            port_set: None,
+            port_forward: None,
+            source_ports: Vec::new(),
+            icmp_types: Vec::new(),
+            icmp_code: None,
+            ct_states: Vec::new(),
+            schedule: None,
+            quota: None,
+            per_source: None,
+            countries: Vec::new(),
+            group: None,
+            description: String::new(),
+            owner: String::new(),
+            reference: String::new(),
+            negate: Negation::default(),
             // Cached fields (populated by rebuild_caches())
             label_lowercase: String::new(),
             interface_lowercase: None,
@@ -454,6 +477,7 @@ impl RuleConfig {
             log_prefix: String::new(),
             tags_truncated: Vec::new(),
             badge_display: String::new(),
+            metadata_lowercase: String::new(),
         };
         rule.rebuild_caches();
         rule
@@ -483,10 +507,10 @@ fn random_reject_type(rng: &mut impl Rng, protocol: Protocol) -> RejectType {
 
 fn random_chain(rng: &mut impl Rng) -> Chain {
     // Weight towards Input (more common)
-    if rng.random_bool(0.7) {
-        Chain::Input
-    } else {
-        Chain::Output
+    match rng.random_range(0..10) {
+        0..7 => Chain::Input,
+        7..9 => Chain::Output,
+        _ => Chain::Forward,
     }
 }
 
@@ -551,7 +575,7 @@ fn random_ipv6(rng: &mut impl Rng) -> IpNetwork {
     IpNetwork::new(std::net::IpAddr::V6(addr), prefix_len).unwrap()
 }
 
-fn random_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
+fn random_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<AddressEntry> {
     if rng.random_bool(0.4) {
         return Vec::new(); // No source filter
     }
@@ -573,13 +597,13 @@ fn random_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
         } else {
             random_ipv6(rng)
         };
-        sources.push(ip);
+        sources.push(AddressEntry::from(ip));
     }
 
     sources
 }
 
-fn random_destinations(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
+fn random_destinations(rng: &mut impl Rng, protocol: Protocol) -> Vec<AddressEntry> {
     if rng.random_bool(0.6) {
         return Vec::new(); // Less common to have destination filters
     }
@@ -601,18 +625,22 @@ fn random_destinations(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork>
         } else {
             random_ipv6(rng)
         };
-        dests.push(ip);
+        dests.push(AddressEntry::from(ip));
     }
 
     dests
 }
 
-fn random_interface(rng: &mut impl Rng) -> Option<String> {
+fn random_interfaces(rng: &mut impl Rng) -> Vec<String> {
     if rng.random_bool(0.7) {
-        None
-    } else {
-        Some(INTERFACE_NAMES.choose(rng).unwrap().to_string())
+        return Vec::new();
     }
+
+    let count = rng.random_range(1..=2);
+    INTERFACE_NAMES
+        .choose_multiple(rng, count)
+        .map(ToString::to_string)
+        .collect()
 }
 
 fn random_rate_limit(rng: &mut impl Rng) -> Option<RateLimit> {
@@ -635,7 +663,13 @@ fn random_rate_limit(rng: &mut impl Rng) -> Option<RateLimit> {
         None
     };
 
-    Some(RateLimit { count, unit, burst })
+    Some(RateLimit {
+        count,
+        unit,
+        burst,
+        over: false,
+        bytes: None,
+    })
 }
 
 fn random_connection_limit(rng: &mut impl Rng) -> u32 {
@@ -774,9 +808,9 @@ fn edge_case_ports(rng: &mut impl Rng) -> Vec<PortEntry> {
     cases.choose(rng).unwrap().clone()
 }
 
-fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
+fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<AddressEntry> {
     // Use centralized constraint for IP version compatibility
-    let ipv4_cases: Vec<Vec<IpNetwork>> = vec![
+    let ipv4_cases: Vec<Vec<AddressEntry>> = vec![
         // Any IPv4 (0.0.0.0/0)
         vec!["0.0.0.0/0".parse().unwrap()],
         // Single host IPv4 (/32)
@@ -790,9 +824,11 @@ fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
             "10.0.0.0/8".parse().unwrap(),
             "192.168.1.0/24".parse().unwrap(),
         ],
+        // Address range (e.g., a DHCP pool)
+        vec!["192.168.1.100-192.168.1.150".parse().unwrap()],
     ];
 
-    let ipv6_cases: Vec<Vec<IpNetwork>> = vec![
+    let ipv6_cases: Vec<Vec<AddressEntry>> = vec![
         // Any IPv6 (::/0)
         vec!["::/0".parse().unwrap()],
         // Single host IPv6 (/128)
@@ -801,6 +837,8 @@ fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
         vec!["fe80::/10".parse().unwrap()],
         // IPv6 loopback
         vec!["::1/128".parse().unwrap()],
+        // IPv6 address range
+        vec!["fd00::10-fd00::20".parse().unwrap()],
     ];
 
     // Select appropriate cases based on protocol constraints
@@ -810,7 +848,7 @@ fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
         ipv6_cases.choose(rng).unwrap().clone()
     } else {
         // Other protocols: mix of both (can include mixed cases)
-        let mixed_cases: Vec<Vec<IpNetwork>> = vec![
+        let mixed_cases: Vec<Vec<AddressEntry>> = vec![
             vec!["0.0.0.0/0".parse().unwrap()],
             vec!["::/0".parse().unwrap()],
             vec!["192.168.1.1/32".parse().unwrap()],
@@ -825,17 +863,19 @@ fn edge_case_sources(rng: &mut impl Rng, protocol: Protocol) -> Vec<IpNetwork> {
     }
 }
 
-fn edge_case_interface(rng: &mut impl Rng) -> Option<String> {
+fn edge_case_interfaces(rng: &mut impl Rng) -> Vec<String> {
     let cases = [
         // Normal interfaces
-        Some("eth0".to_string()),
-        Some("lo".to_string()),
+        vec!["eth0".to_string()],
+        vec!["lo".to_string()],
         // Wildcards
-        Some(INTERFACE_WILDCARDS.choose(rng).unwrap().to_string()),
+        vec![INTERFACE_WILDCARDS.choose(rng).unwrap().to_string()],
         // Long interface name (max 15 chars for Linux)
-        Some("abcdefghijklmno".to_string()),
+        vec!["abcdefghijklmno".to_string()],
+        // Several names in one set (wildcards must stand alone)
+        vec!["wlan0".to_string(), "eth0".to_string()],
         // None
-        None,
+        Vec::new(),
     ];
 
     cases.choose(rng).unwrap().clone()
@@ -870,24 +910,32 @@ fn edge_case_rate_limit(rng: &mut impl Rng) -> Option<RateLimit> {
             count: MAX_RATE_LIMIT_PER_SECOND,
             unit: TimeUnit::Second,
             burst: None,
+            over: false,
+            bytes: None,
         }),
         // Very low rate
         Some(RateLimit {
             count: 1,
             unit: TimeUnit::Day,
             burst: None,
+            over: false,
+            bytes: None,
         }),
         // Large burst
         Some(RateLimit {
             count: 10,
             unit: TimeUnit::Minute,
             burst: Some(1000),
+            over: false,
+            bytes: None,
         }),
         // High minute rate at validator limit
         Some(RateLimit {
             count: MAX_RATE_LIMIT_PER_MINUTE,
             unit: TimeUnit::Minute,
             burst: None,
+            over: false,
+            bytes: None,
         }),
         // None
         None,
@@ -929,20 +977,20 @@ fn generate_edge_case_rule(rng: &mut impl Rng, index: usize) -> Rule {
     let chain = random_chain(rng);
 
     // Intentionally create some semantic mismatches for testing
-    let (interface, output_interface) = if rng.random_bool(0.3) {
+    let (interfaces, output_interfaces) = if rng.random_bool(0.3) {
         // Semantic mismatch: opposite interface for chain (tests display/handling)
         if chain_uses_input_interface(chain) {
-            (None, edge_case_interface(rng))
+            (Vec::new(), edge_case_interfaces(rng))
         } else {
             // OUTPUT chain with input interface
-            (edge_case_interface(rng), None)
+            (edge_case_interfaces(rng), Vec::new())
         }
     } else {
         // Normal: appropriate interface for chain
         if chain_uses_input_interface(chain) {
-            (edge_case_interface(rng), None)
+            (edge_case_interfaces(rng), Vec::new())
         } else {
-            (None, edge_case_interface(rng))
+            (Vec::new(), edge_case_interfaces(rng))
         }
     };
 
@@ -981,8 +1029,8 @@ fn generate_edge_case_rule(rng: &mut impl Rng, index: usize) -> Rule {
         } else {
             random_destinations(rng, protocol)
         },
-        interface,
-        output_interface,
+        interfaces,
+        output_interfaces,
         rate_limit: edge_case_rate_limit(rng),
         connection_limit: edge_case_connection_limit(rng),
         log_enabled: rng.random_bool(0.3),
@@ -1003,10 +1051,10 @@ fn generate_rule(rng: &mut impl Rng, index: usize, vary_timestamps: bool) -> Rul
     let chain = random_chain(rng);
 
     // Use centralized constraint logic for interface-chain relationship
-    let (interface, output_interface) = if chain_uses_input_interface(chain) {
-        (random_interface(rng), None)
+    let (interfaces, output_interfaces) = if chain_uses_input_interface(chain) {
+        (random_interfaces(rng), Vec::new())
     } else {
-        (None, random_interface(rng))
+        (Vec::new(), random_interfaces(rng))
     };
 
     RuleConfig {
@@ -1022,8 +1070,8 @@ fn generate_rule(rng: &mut impl Rng, index: usize, vary_timestamps: bool) -> Rul
         ports: random_ports(rng, protocol),
         sources: random_sources(rng, protocol),
         destinations: random_destinations(rng, protocol),
-        interface,
-        output_interface,
+        interfaces,
+        output_interfaces,
         rate_limit: random_rate_limit(rng),
         connection_limit: random_connection_limit(rng),
         log_enabled: rng.random_bool(0.1),
@@ -1045,10 +1093,10 @@ fn generate_coverage_rule(
     time_unit: Option<TimeUnit>,
 ) -> Rule {
     // Use centralized constraint logic for interface-chain relationship
-    let (interface, output_interface) = if chain_uses_input_interface(chain) {
-        (random_interface(rng), None)
+    let (interfaces, output_interfaces) = if chain_uses_input_interface(chain) {
+        (random_interfaces(rng), Vec::new())
     } else {
-        (None, random_interface(rng))
+        (Vec::new(), random_interfaces(rng))
     };
 
     // Use centralized constraint logic for port support
@@ -1075,6 +1123,8 @@ fn generate_coverage_rule(
             } else {
                 None
             },
+            over: false,
+            bytes: None,
         })
     } else {
         random_rate_limit(rng)
@@ -1089,8 +1139,8 @@ fn generate_coverage_rule(
         ports,
         sources: random_sources(rng, protocol),
         destinations: random_destinations(rng, protocol),
-        interface,
-        output_interface,
+        interfaces,
+        output_interfaces,
         rate_limit,
         connection_limit: random_connection_limit(rng),
         log_enabled: rng.random_bool(0.1),
@@ -1247,12 +1297,14 @@ fn generate_ruleset(
         } else {
             EgressProfile::Server
         },
+        ..AdvancedSecuritySettings::default()
     };
 
     (
         FirewallRuleset {
             rules,
             advanced_security,
+            ..FirewallRuleset::default()
         },
         tracker,
     )